@@ -0,0 +1,205 @@
+#pragma clang diagnostic ignored "-Wmissing-prototypes"
+#pragma clang diagnostic ignored "-Wmissing-braces"
+
+#include <metal_stdlib>
+#include <simd/simd.h>
+
+using namespace metal;
+
+template<typename T, size_t Num>
+struct spvUnsafeArray
+{
+    T elements[Num ? Num : 1];
+    
+    thread T& operator [] (size_t pos) thread
+    {
+        return elements[pos];
+    }
+    constexpr const thread T& operator [] (size_t pos) const thread
+    {
+        return elements[pos];
+    }
+    
+    device T& operator [] (size_t pos) device
+    {
+        return elements[pos];
+    }
+    constexpr const device T& operator [] (size_t pos) const device
+    {
+        return elements[pos];
+    }
+    
+    constexpr const constant T& operator [] (size_t pos) const constant
+    {
+        return elements[pos];
+    }
+    
+    threadgroup T& operator [] (size_t pos) threadgroup
+    {
+        return elements[pos];
+    }
+    constexpr const threadgroup T& operator [] (size_t pos) const threadgroup
+    {
+        return elements[pos];
+    }
+};
+
+struct SourceVertices
+{
+    float data[1];
+};
+
+struct MorphTargetDeltas
+{
+    float data[1];
+};
+
+struct DeformedVertices
+{
+    float data[1];
+};
+
+struct DeformationArgs
+{
+    uint vertex_count;
+    uint is_skinned;
+    uint morph_target_count;
+};
+
+struct MorphTargetWeights
+{
+    float data[1];
+};
+
+struct JointWeights
+{
+    spvUnsafeArray<uint, 4> joints;
+    spvUnsafeArray<float, 4> weights;
+};
+
+struct JointWeights_1
+{
+    uint joints[4];
+    float weights[4];
+};
+
+struct MeshJointWeights
+{
+    JointWeights_1 data[1];
+};
+
+struct JointMatrices
+{
+    float4x4 data[1];
+};
+
+constant uint3 gl_WorkGroupSize [[maybe_unused]] = uint3(64u, 1u, 1u);
+
+struct spvDescriptorSetBuffer0
+{
+    const device SourceVertices* source_vertices [[id(0)]];
+    const device MorphTargetDeltas* morph_target_deltas [[id(1)]];
+    device DeformedVertices* deformed_vertices [[id(2)]];
+    constant DeformationArgs* deformation_args [[id(3)]];
+    const device MorphTargetWeights* morph_target_weights [[id(4)]];
+    const device MeshJointWeights* joint_weights [[id(5)]];
+    const device JointMatrices* joint_matrices [[id(6)]];
+};
+
+static inline __attribute__((always_inline))
+float3 read_vec3(thread const uint& offset, const device SourceVertices& source_vertices)
+{
+    return float3(source_vertices.data[offset], source_vertices.data[offset + 1u], source_vertices.data[offset + 2u]);
+}
+
+static inline __attribute__((always_inline))
+float3 read_morph_target_delta(thread const uint& offset, const device MorphTargetDeltas& morph_target_deltas)
+{
+    return float3(morph_target_deltas.data[offset], morph_target_deltas.data[offset + 1u], morph_target_deltas.data[offset + 2u]);
+}
+
+static inline __attribute__((always_inline))
+void write_vec3(thread const uint& offset, thread const float3& value, device DeformedVertices& deformed_vertices)
+{
+    deformed_vertices.data[offset] = value.x;
+    deformed_vertices.data[offset + 1u] = value.y;
+    deformed_vertices.data[offset + 2u] = value.z;
+}
+
+kernel void main0(constant spvDescriptorSetBuffer0& spvDescriptorSet0 [[buffer(0)]], uint3 gl_GlobalInvocationID [[thread_position_in_grid]])
+{
+    uint vertex_index = gl_GlobalInvocationID.x;
+    if (vertex_index >= (*spvDescriptorSet0.deformation_args).vertex_count)
+    {
+        return;
+    }
+    uint offset = vertex_index * 12u;
+    uint param = offset;
+    float3 position = read_vec3(param, (*spvDescriptorSet0.source_vertices));
+    uint param_1 = offset + 3u;
+    float3 normal = read_vec3(param_1, (*spvDescriptorSet0.source_vertices));
+    uint param_2 = offset + 6u;
+    float3 tangent = read_vec3(param_2, (*spvDescriptorSet0.source_vertices));
+    float tangent_sign = (*spvDescriptorSet0.source_vertices).data[offset + 9u];
+    float2 tex_coord = float2((*spvDescriptorSet0.source_vertices).data[offset + 10u], (*spvDescriptorSet0.source_vertices).data[offset + 11u]);
+    for (uint i = 0u; i < (*spvDescriptorSet0.deformation_args).morph_target_count; i++)
+    {
+        float weight = (*spvDescriptorSet0.morph_target_weights).data[i];
+        if (weight != 0.0)
+        {
+            uint delta_offset = ((i * (*spvDescriptorSet0.deformation_args).vertex_count) + vertex_index) * 9u;
+            uint param_3 = delta_offset;
+            position += (read_morph_target_delta(param_3, (*spvDescriptorSet0.morph_target_deltas)) * weight);
+            uint param_4 = delta_offset + 3u;
+            normal += (read_morph_target_delta(param_4, (*spvDescriptorSet0.morph_target_deltas)) * weight);
+            uint param_5 = delta_offset + 6u;
+            tangent += (read_morph_target_delta(param_5, (*spvDescriptorSet0.morph_target_deltas)) * weight);
+        }
+    }
+    if ((*spvDescriptorSet0.deformation_args).is_skinned != 0u)
+    {
+        JointWeights vertex_weights;
+        vertex_weights.joints[0] = (*spvDescriptorSet0.joint_weights).data[vertex_index].joints[0];
+        vertex_weights.joints[1] = (*spvDescriptorSet0.joint_weights).data[vertex_index].joints[1];
+        vertex_weights.joints[2] = (*spvDescriptorSet0.joint_weights).data[vertex_index].joints[2];
+        vertex_weights.joints[3] = (*spvDescriptorSet0.joint_weights).data[vertex_index].joints[3];
+        vertex_weights.weights[0] = (*spvDescriptorSet0.joint_weights).data[vertex_index].weights[0];
+        vertex_weights.weights[1] = (*spvDescriptorSet0.joint_weights).data[vertex_index].weights[1];
+        vertex_weights.weights[2] = (*spvDescriptorSet0.joint_weights).data[vertex_index].weights[2];
+        vertex_weights.weights[3] = (*spvDescriptorSet0.joint_weights).data[vertex_index].weights[3];
+        float4x4 skinning_matrix = float4x4(float4(0.0), float4(0.0), float4(0.0), float4(0.0));
+        float total_weight = 0.0;
+        for (int i_1 = 0; i_1 < 4; i_1++)
+        {
+            float weight_1 = vertex_weights.weights[i_1];
+            if (weight_1 > 0.0)
+            {
+                float4x4 _282 = (*spvDescriptorSet0.joint_matrices).data[vertex_weights.joints[i_1]] * weight_1;
+                skinning_matrix = float4x4(skinning_matrix[0] + _282[0], skinning_matrix[1] + _282[1], skinning_matrix[2] + _282[2], skinning_matrix[3] + _282[3]);
+                total_weight += weight_1;
+            }
+        }
+        if (total_weight > 0.0)
+        {
+            skinning_matrix = skinning_matrix * (1.0 / total_weight);
+            position = (skinning_matrix * float4(position, 1.0)).xyz;
+            normal = float3x3(skinning_matrix[0].xyz, skinning_matrix[1].xyz, skinning_matrix[2].xyz) * normal;
+            tangent = float3x3(skinning_matrix[0].xyz, skinning_matrix[1].xyz, skinning_matrix[2].xyz) * tangent;
+        }
+    }
+    normal = normalize(normal);
+    tangent = normalize(tangent);
+    uint param_6 = offset;
+    float3 param_7 = position;
+    write_vec3(param_6, param_7, (*spvDescriptorSet0.deformed_vertices));
+    uint param_8 = offset + 3u;
+    float3 param_9 = normal;
+    write_vec3(param_8, param_9, (*spvDescriptorSet0.deformed_vertices));
+    uint param_10 = offset + 6u;
+    float3 param_11 = tangent;
+    write_vec3(param_10, param_11, (*spvDescriptorSet0.deformed_vertices));
+    (*spvDescriptorSet0.deformed_vertices).data[offset + 9u] = tangent_sign;
+    (*spvDescriptorSet0.deformed_vertices).data[offset + 10u] = tex_coord.x;
+    (*spvDescriptorSet0.deformed_vertices).data[offset + 11u] = tex_coord.y;
+}
+
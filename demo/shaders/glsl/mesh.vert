@@ -2,9 +2,12 @@
 #extension GL_ARB_separate_shader_objects : enable
 #extension GL_ARB_shading_language_420pack : enable
 
-#define PBR_TEXTURES
+#pragma rafx_keywords PBR_TEXTURES
+
 #include "mesh_pbr_uniform.glsl"
+#ifdef PBR_TEXTURES
 #include "mesh_pbr_textures.glsl"
+#endif
 #include "mesh_pbr_vert.glsl"
 
 void main() {
@@ -10,6 +10,9 @@ use rafx_framework::{
     ResourceArc,
 };
 
+#[allow(unused_imports)]
+use rafx_framework::{RafxCommandBuffer, RafxRootSignature};
+
 pub const TEX_DESCRIPTOR_SET_INDEX: usize = 0;
 pub const TEX_DESCRIPTOR_BINDING_INDEX: usize = 0;
 pub const SMP_DESCRIPTOR_SET_INDEX: usize = 0;
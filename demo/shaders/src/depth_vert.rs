@@ -10,6 +10,9 @@ use rafx_framework::{
     ResourceArc,
 };
 
+#[allow(unused_imports)]
+use rafx_framework::{RafxCommandBuffer, RafxRootSignature};
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)]
 pub struct PerViewDataStd140 {
@@ -11,10 +11,9 @@ pub mod egui_frag;
 pub mod egui_vert;
 pub mod imgui_frag;
 pub mod imgui_vert;
-pub mod mesh_textured_frag;
-pub mod mesh_textured_vert;
-pub mod mesh_untextured_frag;
-pub mod mesh_untextured_vert;
+pub mod mesh_deformation_comp;
+pub mod mesh_frag;
+pub mod mesh_vert;
 pub mod mesh_wireframe_frag;
 pub mod mesh_wireframe_vert;
 pub mod postprocess_vert;
@@ -0,0 +1,156 @@
+// This code is auto-generated by the shader processor.
+
+#[allow(unused_imports)]
+use rafx_framework::RafxResult;
+
+#[allow(unused_imports)]
+use rafx_framework::{
+    DescriptorSetAllocator, DescriptorSetArc, DescriptorSetBindings, DescriptorSetInitializer,
+    DescriptorSetWriter, DescriptorSetWriterContext, DynDescriptorSet, ImageViewResource,
+    ResourceArc,
+};
+
+#[allow(unused_imports)]
+use rafx_framework::{RafxCommandBuffer, RafxRootSignature};
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct DeformationArgsStd140 {
+    pub vertex_count: u32,       // +0 (size: 4)
+    pub is_skinned: u32,         // +4 (size: 4)
+    pub morph_target_count: u32, // +8 (size: 4)
+    pub _padding0: [u8; 4],      // +12 (size: 4)
+} // 16 bytes
+
+impl Default for DeformationArgsStd140 {
+    fn default() -> Self {
+        DeformationArgsStd140 {
+            vertex_count: <u32>::default(),
+            is_skinned: <u32>::default(),
+            morph_target_count: <u32>::default(),
+            _padding0: [u8::default(); 4],
+        }
+    }
+}
+
+pub type DeformationArgsUniform = DeformationArgsStd140;
+
+pub const DEFORMATION_ARGS_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const DEFORMATION_ARGS_DESCRIPTOR_BINDING_INDEX: usize = 0;
+pub const SOURCE_VERTICES_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const SOURCE_VERTICES_DESCRIPTOR_BINDING_INDEX: usize = 1;
+pub const JOINT_WEIGHTS_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const JOINT_WEIGHTS_DESCRIPTOR_BINDING_INDEX: usize = 2;
+pub const JOINT_MATRICES_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const JOINT_MATRICES_DESCRIPTOR_BINDING_INDEX: usize = 3;
+pub const DEFORMED_VERTICES_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const DEFORMED_VERTICES_DESCRIPTOR_BINDING_INDEX: usize = 4;
+pub const MORPH_TARGET_DELTAS_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const MORPH_TARGET_DELTAS_DESCRIPTOR_BINDING_INDEX: usize = 5;
+pub const MORPH_TARGET_WEIGHTS_DESCRIPTOR_SET_INDEX: usize = 0;
+pub const MORPH_TARGET_WEIGHTS_DESCRIPTOR_BINDING_INDEX: usize = 6;
+
+pub struct DescriptorSet0Args<'a> {
+    pub deformation_args: &'a DeformationArgsUniform,
+}
+
+impl<'a> DescriptorSetInitializer<'a> for DescriptorSet0Args<'a> {
+    type Output = DescriptorSet0;
+
+    fn create_dyn_descriptor_set(
+        descriptor_set: DynDescriptorSet,
+        args: Self,
+    ) -> Self::Output {
+        let mut descriptor = DescriptorSet0(descriptor_set);
+        descriptor.set_args(args);
+        descriptor
+    }
+
+    fn create_descriptor_set(
+        descriptor_set_allocator: &mut DescriptorSetAllocator,
+        descriptor_set: DynDescriptorSet,
+        args: Self,
+    ) -> RafxResult<DescriptorSetArc> {
+        let mut descriptor = Self::create_dyn_descriptor_set(descriptor_set, args);
+        descriptor.0.flush(descriptor_set_allocator)?;
+        Ok(descriptor.0.descriptor_set().clone())
+    }
+}
+
+impl<'a> DescriptorSetWriter<'a> for DescriptorSet0Args<'a> {
+    fn write_to(
+        descriptor_set: &mut DescriptorSetWriterContext,
+        args: Self,
+    ) {
+        descriptor_set.set_buffer_data(
+            DEFORMATION_ARGS_DESCRIPTOR_BINDING_INDEX as u32,
+            args.deformation_args,
+        );
+    }
+}
+
+pub struct DescriptorSet0(pub DynDescriptorSet);
+
+impl DescriptorSet0 {
+    pub fn set_args_static(
+        descriptor_set: &mut DynDescriptorSet,
+        args: DescriptorSet0Args,
+    ) {
+        descriptor_set.set_buffer_data(
+            DEFORMATION_ARGS_DESCRIPTOR_BINDING_INDEX as u32,
+            args.deformation_args,
+        );
+    }
+
+    pub fn set_args(
+        &mut self,
+        args: DescriptorSet0Args,
+    ) {
+        self.set_deformation_args(args.deformation_args);
+    }
+
+    pub fn set_deformation_args(
+        &mut self,
+        deformation_args: &DeformationArgsUniform,
+    ) {
+        self.0.set_buffer_data(
+            DEFORMATION_ARGS_DESCRIPTOR_BINDING_INDEX as u32,
+            deformation_args,
+        );
+    }
+
+    pub fn flush(
+        &mut self,
+        descriptor_set_allocator: &mut DescriptorSetAllocator,
+    ) -> RafxResult<()> {
+        self.0.flush(descriptor_set_allocator)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_struct_deformation_args_std140() {
+        assert_eq!(std::mem::size_of::<DeformationArgsStd140>(), 16);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(DeformationArgsStd140, vertex_count),
+            0
+        );
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(memoffset::offset_of!(DeformationArgsStd140, is_skinned), 4);
+        assert_eq!(std::mem::size_of::<u32>(), 4);
+        assert_eq!(std::mem::align_of::<u32>(), 4);
+        assert_eq!(
+            memoffset::offset_of!(DeformationArgsStd140, morph_target_count),
+            8
+        );
+        assert_eq!(std::mem::size_of::<[u8; 4]>(), 4);
+        assert_eq!(std::mem::align_of::<[u8; 4]>(), 1);
+        assert_eq!(memoffset::offset_of!(DeformationArgsStd140, _padding0), 12);
+    }
+}
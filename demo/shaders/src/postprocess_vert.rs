@@ -9,3 +9,6 @@ use rafx_framework::{
     DescriptorSetWriter, DescriptorSetWriterContext, DynDescriptorSet, ImageViewResource,
     ResourceArc,
 };
+
+#[allow(unused_imports)]
+use rafx_framework::{RafxCommandBuffer, RafxRootSignature};
@@ -0,0 +1,483 @@
+use glam::{Mat4, Quat, Vec3, Vec4};
+use rafx::api::RafxResult;
+use rafx::assets::{AssetManager, DefaultAssetTypeHandler, DefaultAssetTypeLoadHandler};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use type_uuid::*;
+
+//
+// Skins
+//
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct SkinJointAssetData {
+    pub name: Option<String>,
+    // Index of the node in the glTF file. Animation channels target joints by node index
+    pub node_index: usize,
+    // Index into SkinAssetData::joints
+    pub parent: Option<usize>,
+    pub inverse_bind_matrix: [[f32; 4]; 4],
+    // Rest pose, used for any property that the playing animation does not animate
+    pub translation: [f32; 3],
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "d343465a-0260-46d7-8549-93026086ac91"]
+pub struct SkinAssetData {
+    // Order matches the joint indices in the JOINTS_0 vertex attribute
+    pub joints: Vec<SkinJointAssetData>,
+    // Joint indices ordered so that parents come before their children
+    pub evaluation_order: Vec<usize>,
+    // Transform of the nodes above the root joints (for example an armature node)
+    pub root_transform: [[f32; 4]; 4],
+}
+
+#[derive(TypeUuid, Clone)]
+#[uuid = "e2260223-bda3-4039-b081-e3b37268a193"]
+pub struct SkinAsset {
+    pub inner: Arc<SkinAssetData>,
+}
+
+impl SkinAsset {
+    pub fn find_joint_by_node_index(
+        &self,
+        node_index: usize,
+    ) -> Option<usize> {
+        self.inner
+            .joints
+            .iter()
+            .position(|x| x.node_index == node_index)
+    }
+
+    // Returns a matrix per joint that transforms a vertex from bind pose to the posed skeleton. If
+    // a clip is given, it is sampled at the given time, otherwise the rest pose is used.
+    pub fn joint_matrices(
+        &self,
+        clip: Option<&AnimationClipAsset>,
+        time: f32,
+    ) -> Vec<Mat4> {
+        let joints = &self.inner.joints;
+
+        let mut translations: Vec<_> = joints.iter().map(|x| Vec3::from(x.translation)).collect();
+        let mut rotations: Vec<_> = joints.iter().map(|x| quat_from_array(x.rotation)).collect();
+        let mut scales: Vec<_> = joints.iter().map(|x| Vec3::from(x.scale)).collect();
+
+        if let Some(clip) = clip {
+            for channel in &clip.inner.channels {
+                let joint_index = match self.find_joint_by_node_index(channel.node_index) {
+                    Some(joint_index) => joint_index,
+                    None => continue,
+                };
+
+                let value = match channel.sample(time) {
+                    Some(value) => value,
+                    None => continue,
+                };
+
+                match channel.property {
+                    AnimationProperty::Translation => {
+                        translations[joint_index] = Vec4::from(value).truncate()
+                    }
+                    AnimationProperty::Rotation => rotations[joint_index] = quat_from_array(value),
+                    AnimationProperty::Scale => scales[joint_index] = Vec4::from(value).truncate(),
+                }
+            }
+        }
+
+        let root_transform = Mat4::from_cols_array_2d(&self.inner.root_transform);
+        let mut global_transforms = vec![Mat4::IDENTITY; joints.len()];
+        for &joint_index in &self.inner.evaluation_order {
+            let local_transform = Mat4::from_scale_rotation_translation(
+                scales[joint_index],
+                rotations[joint_index],
+                translations[joint_index],
+            );
+
+            let parent_transform = joints[joint_index]
+                .parent
+                .map(|parent| global_transforms[parent])
+                .unwrap_or(root_transform);
+
+            global_transforms[joint_index] = parent_transform * local_transform;
+        }
+
+        global_transforms
+            .iter()
+            .zip(joints)
+            .map(|(global_transform, joint)| {
+                *global_transform * Mat4::from_cols_array_2d(&joint.inverse_bind_matrix)
+            })
+            .collect()
+    }
+}
+
+pub struct SkinLoadHandler;
+
+impl DefaultAssetTypeLoadHandler<SkinAssetData, SkinAsset> for SkinLoadHandler {
+    #[profiling::function]
+    fn load(
+        _asset_manager: &mut AssetManager,
+        skin_asset: SkinAssetData,
+    ) -> RafxResult<SkinAsset> {
+        Ok(SkinAsset {
+            inner: Arc::new(skin_asset),
+        })
+    }
+}
+
+pub type SkinAssetType = DefaultAssetTypeHandler<SkinAssetData, SkinAsset, SkinLoadHandler>;
+
+//
+// Animation clips
+//
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum AnimationProperty {
+    Translation,
+    // Quaternion, xyzw
+    Rotation,
+    Scale,
+}
+
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum AnimationInterpolation {
+    Linear,
+    Step,
+    // Each keyframe stores an in-tangent, a value, and an out-tangent
+    CubicSpline,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct AnimationChannelAssetData {
+    // Index of the animated node in the glTF file
+    pub node_index: usize,
+    pub property: AnimationProperty,
+    pub interpolation: AnimationInterpolation,
+    pub times: Vec<f32>,
+    // Translation and scale leave w unused
+    pub values: Vec<[f32; 4]>,
+}
+
+impl AnimationChannelAssetData {
+    fn keyframe_value(
+        &self,
+        keyframe: usize,
+    ) -> Vec4 {
+        match self.interpolation {
+            AnimationInterpolation::CubicSpline => Vec4::from(self.values[keyframe * 3 + 1]),
+            _ => Vec4::from(self.values[keyframe]),
+        }
+    }
+
+    // Time is clamped to the range of the channel's keyframes, and NaN is treated as the start of
+    // the channel. Returns None if the channel has no keyframes or is missing values, in which case
+    // the property should be left as it is.
+    pub fn sample(
+        &self,
+        time: f32,
+    ) -> Option<[f32; 4]> {
+        let keyframe_count = self.times.len();
+        let values_per_keyframe = match self.interpolation {
+            AnimationInterpolation::CubicSpline => 3,
+            _ => 1,
+        };
+
+        if keyframe_count == 0 || self.values.len() != keyframe_count * values_per_keyframe {
+            return None;
+        }
+
+        if time.is_nan() || time <= self.times[0] {
+            return Some(self.keyframe_value(0).into());
+        }
+
+        if time >= self.times[keyframe_count - 1] {
+            return Some(self.keyframe_value(keyframe_count - 1).into());
+        }
+
+        // The keyframe before the given time
+        let k0 = match self.times.binary_search_by(|x| x.total_cmp(&time)) {
+            Ok(k) => k,
+            Err(k) => k - 1,
+        };
+        let k1 = k0 + 1;
+
+        let delta_time = self.times[k1] - self.times[k0];
+        let s = if delta_time > 0.0 {
+            (time - self.times[k0]) / delta_time
+        } else {
+            0.0
+        };
+
+        let value = match self.interpolation {
+            AnimationInterpolation::Step => self.keyframe_value(k0),
+            AnimationInterpolation::Linear => {
+                let v0 = self.keyframe_value(k0);
+                let v1 = self.keyframe_value(k1);
+                if self.property == AnimationProperty::Rotation {
+                    let q0 = quat_from_array(v0.into());
+                    let q1 = quat_from_array(shortest_path(v0, v1));
+                    Vec4::from(q0.slerp(q1, s).normalize())
+                } else {
+                    v0 + (v1 - v0) * s
+                }
+            }
+            AnimationInterpolation::CubicSpline => {
+                // Hermite spline, tangents are scaled by the time between keyframes
+                let p0 = self.keyframe_value(k0);
+                let m0 = Vec4::from(self.values[k0 * 3 + 2]) * delta_time;
+                let p1 = self.keyframe_value(k1);
+                let m1 = Vec4::from(self.values[k1 * 3]) * delta_time;
+
+                let s2 = s * s;
+                let s3 = s2 * s;
+                let value = p0 * (2.0 * s3 - 3.0 * s2 + 1.0)
+                    + m0 * (s3 - 2.0 * s2 + s)
+                    + p1 * (-2.0 * s3 + 3.0 * s2)
+                    + m1 * (s3 - s2);
+
+                if self.property == AnimationProperty::Rotation {
+                    value.normalize()
+                } else {
+                    value
+                }
+            }
+        };
+
+        Some(value.into())
+    }
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "05d33686-7da3-47b1-b138-a1ab9c615406"]
+pub struct AnimationClipAssetData {
+    pub name: Option<String>,
+    // Time of the last keyframe of any channel, in seconds
+    pub duration: f32,
+    pub channels: Vec<AnimationChannelAssetData>,
+}
+
+#[derive(TypeUuid, Clone)]
+#[uuid = "5b1f53e6-d2c1-4fe4-90c2-3edd8e4e6939"]
+pub struct AnimationClipAsset {
+    pub inner: Arc<AnimationClipAssetData>,
+}
+
+pub struct AnimationClipLoadHandler;
+
+impl DefaultAssetTypeLoadHandler<AnimationClipAssetData, AnimationClipAsset>
+    for AnimationClipLoadHandler
+{
+    #[profiling::function]
+    fn load(
+        _asset_manager: &mut AssetManager,
+        animation_clip_asset: AnimationClipAssetData,
+    ) -> RafxResult<AnimationClipAsset> {
+        Ok(AnimationClipAsset {
+            inner: Arc::new(animation_clip_asset),
+        })
+    }
+}
+
+pub type AnimationClipAssetType =
+    DefaultAssetTypeHandler<AnimationClipAssetData, AnimationClipAsset, AnimationClipLoadHandler>;
+
+fn quat_from_array(value: [f32; 4]) -> Quat {
+    Quat::from_xyzw(value[0], value[1], value[2], value[3])
+}
+
+// q and -q are the same rotation. Flip the second quaternion if needed so that interpolation takes
+// the short way around
+fn shortest_path(
+    q0: Vec4,
+    q1: Vec4,
+) -> [f32; 4] {
+    if q0.dot(q1) < 0.0 {
+        (-q1).into()
+    } else {
+        q1.into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn channel(
+        property: AnimationProperty,
+        interpolation: AnimationInterpolation,
+        times: Vec<f32>,
+        values: Vec<[f32; 4]>,
+    ) -> AnimationChannelAssetData {
+        AnimationChannelAssetData {
+            node_index: 0,
+            property,
+            interpolation,
+            times,
+            values,
+        }
+    }
+
+    fn assert_close(
+        actual: Option<[f32; 4]>,
+        expected: [f32; 4],
+    ) {
+        let actual = actual.unwrap();
+        assert!(
+            (Vec4::from(actual) - Vec4::from(expected)).length() < 1e-5,
+            "expected {:?} got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_linear_translation() {
+        let channel = channel(
+            AnimationProperty::Translation,
+            AnimationInterpolation::Linear,
+            vec![1.0, 2.0, 4.0],
+            vec![
+                [0.0, 0.0, 0.0, 0.0],
+                [2.0, 4.0, 6.0, 0.0],
+                [4.0, 4.0, 4.0, 0.0],
+            ],
+        );
+
+        assert_close(channel.sample(1.5), [1.0, 2.0, 3.0, 0.0]);
+        assert_close(channel.sample(2.0), [2.0, 4.0, 6.0, 0.0]);
+        assert_close(channel.sample(3.0), [3.0, 4.0, 5.0, 0.0]);
+
+        // Time is clamped to the first and last keyframes
+        assert_close(channel.sample(0.0), [0.0, 0.0, 0.0, 0.0]);
+        assert_close(channel.sample(10.0), [4.0, 4.0, 4.0, 0.0]);
+    }
+
+    #[test]
+    fn test_linear_rotation() {
+        let q0 = Quat::IDENTITY;
+        let q1 = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let expected = Quat::from_rotation_y(std::f32::consts::FRAC_PI_4);
+
+        let channel = channel(
+            AnimationProperty::Rotation,
+            AnimationInterpolation::Linear,
+            vec![0.0, 1.0],
+            vec![Vec4::from(q0).into(), Vec4::from(q1).into()],
+        );
+        assert_close(channel.sample(0.5), Vec4::from(expected).into());
+
+        // -q1 is the same rotation as q1, so interpolation still takes the short way around
+        let flipped_channel = AnimationChannelAssetData {
+            values: vec![Vec4::from(q0).into(), (-Vec4::from(q1)).into()],
+            ..channel
+        };
+        assert_close(flipped_channel.sample(0.5), Vec4::from(expected).into());
+    }
+
+    #[test]
+    fn test_step() {
+        let channel = channel(
+            AnimationProperty::Scale,
+            AnimationInterpolation::Step,
+            vec![0.0, 1.0, 2.0],
+            vec![
+                [1.0, 1.0, 1.0, 0.0],
+                [2.0, 2.0, 2.0, 0.0],
+                [3.0, 3.0, 3.0, 0.0],
+            ],
+        );
+
+        // Each keyframe's value is held until the next keyframe
+        assert_close(channel.sample(0.0), [1.0, 1.0, 1.0, 0.0]);
+        assert_close(channel.sample(0.99), [1.0, 1.0, 1.0, 0.0]);
+        assert_close(channel.sample(1.0), [2.0, 2.0, 2.0, 0.0]);
+        assert_close(channel.sample(1.5), [2.0, 2.0, 2.0, 0.0]);
+        assert_close(channel.sample(5.0), [3.0, 3.0, 3.0, 0.0]);
+    }
+
+    #[test]
+    fn test_cubic_spline() {
+        // Each keyframe is an in-tangent, a value, and an out-tangent
+        let keyframes = |out_tangent: f32| {
+            vec![
+                [0.0, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+                [out_tangent, 0.0, 0.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+                [1.0, 2.0, 3.0, 0.0],
+                [0.0, 0.0, 0.0, 0.0],
+            ]
+        };
+
+        // With flat tangents the spline eases between the values and passes through the midpoint
+        let flat = channel(
+            AnimationProperty::Translation,
+            AnimationInterpolation::CubicSpline,
+            vec![0.0, 2.0],
+            keyframes(0.0),
+        );
+        assert_close(flat.sample(0.0), [0.0, 0.0, 0.0, 0.0]);
+        assert_close(flat.sample(1.0), [0.5, 1.0, 1.5, 0.0]);
+        assert_close(flat.sample(0.5), [0.15625, 0.3125, 0.46875, 0.0]);
+        assert_close(flat.sample(2.0), [1.0, 2.0, 3.0, 0.0]);
+        assert_close(flat.sample(3.0), [1.0, 2.0, 3.0, 0.0]);
+
+        // Tangents are per second, so they are scaled by the 2 seconds between the keyframes.
+        // At s = 0.5 the out-tangent's basis function is 0.125, adding 1 * 2 * 0.125
+        let sloped = channel(
+            AnimationProperty::Translation,
+            AnimationInterpolation::CubicSpline,
+            vec![0.0, 2.0],
+            keyframes(1.0),
+        );
+        assert_close(sloped.sample(1.0), [0.75, 1.0, 1.5, 0.0]);
+    }
+
+    #[test]
+    fn test_cubic_spline_rotation_is_normalized() {
+        let q0 = Vec4::from(Quat::IDENTITY);
+        let q1 = Vec4::from(Quat::from_rotation_z(std::f32::consts::FRAC_PI_2));
+        let zero = [0.0; 4];
+
+        let channel = channel(
+            AnimationProperty::Rotation,
+            AnimationInterpolation::CubicSpline,
+            vec![0.0, 1.0],
+            vec![zero, q0.into(), zero, zero, q1.into(), zero],
+        );
+
+        let value = Vec4::from(channel.sample(0.5).unwrap());
+        assert!((value.length() - 1.0).abs() < 1e-5);
+        assert_close(Some(value.into()), (q0 + q1).normalize().into());
+    }
+
+    #[test]
+    fn test_sample_invalid_time_or_channel() {
+        let channel = channel(
+            AnimationProperty::Translation,
+            AnimationInterpolation::Linear,
+            vec![0.0, 1.0],
+            vec![[1.0, 0.0, 0.0, 0.0], [2.0, 0.0, 0.0, 0.0]],
+        );
+
+        assert_close(channel.sample(f32::NAN), [1.0, 0.0, 0.0, 0.0]);
+        assert_close(channel.sample(f32::NEG_INFINITY), [1.0, 0.0, 0.0, 0.0]);
+        assert_close(channel.sample(f32::INFINITY), [2.0, 0.0, 0.0, 0.0]);
+
+        // Empty channels and channels with the wrong number of values leave the property alone
+        let empty = AnimationChannelAssetData {
+            times: vec![],
+            values: vec![],
+            ..channel.clone()
+        };
+        assert!(empty.sample(0.5).is_none());
+
+        let missing_values = AnimationChannelAssetData {
+            interpolation: AnimationInterpolation::CubicSpline,
+            ..channel
+        };
+        assert!(missing_values.sample(0.5).is_none());
+    }
+}
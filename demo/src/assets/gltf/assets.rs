@@ -1,7 +1,8 @@
-use crate::features::mesh::MeshUntexturedRenderFeatureFlag;
+use super::SkinAsset;
+use crate::features::mesh::{MeshUntexturedRenderFeatureFlag, MeshVertex};
 use crate::phases::{DepthPrepassRenderPhase, OpaqueRenderPhase, WireframeRenderPhase};
 use distill::loader::handle::Handle;
-use rafx::api::RafxResult;
+use rafx::api::{RafxBufferDef, RafxIndexType, RafxMemoryUsage, RafxResourceType, RafxResult};
 use rafx::assets::MaterialInstanceAsset;
 use rafx::assets::{
     AssetManager, BufferAsset, DefaultAssetTypeHandler, DefaultAssetTypeLoadHandler,
@@ -10,17 +11,16 @@ use rafx::framework::render_features::{RenderPhase, RenderPhaseIndex, RenderView
 use rafx::framework::{BufferResource, DescriptorSetArc, MaterialPassResource, ResourceArc};
 use rafx::rafx_visibility::VisibleBounds;
 use serde::{Deserialize, Serialize};
-use shaders::mesh_textured_frag::MaterialDataStd140;
+use shaders::mesh_frag::MaterialDataStd140;
 use std::sync::Arc;
 use type_uuid::*;
 
-//TODO: These are extensions that might be interesting to try supporting. In particular, lights,
-// LOD, and clearcoat
+//TODO: These are extensions that might be interesting to try supporting. In particular, LOD and
+// clearcoat
 // Good explanations of upcoming extensions here: https://medium.com/@babylonjs/gltf-extensions-in-babylon-js-b3fa56de5483
 //KHR_materials_clearcoat: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Khronos/KHR_materials_clearcoat/README.md
 //KHR_materials_pbrSpecularGlossiness: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Khronos/KHR_materials_pbrSpecularGlossiness/README.md
 //KHR_materials_unlit: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Khronos/KHR_materials_unlit/README.md
-//KHR_lights_punctual (directional, point, spot): https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Khronos/KHR_lights_punctual/README.md
 //EXT_lights_image_based: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Vendor/EXT_lights_image_based/README.md
 //MSFT_lod: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Vendor/MSFT_lod/README.md
 //MSFT_packing_normalRoughnessMetallic: https://github.com/KhronosGroup/glTF/blob/master/extensions/2.0/Vendor/MSFT_packing_normalRoughnessMetallic/README.md
@@ -95,16 +95,52 @@ pub struct MeshPartAssetData {
     pub vertex_buffer_size_in_bytes: u32,
     pub index_buffer_offset_in_bytes: u32,
     pub index_buffer_size_in_bytes: u32,
+    pub index_type: RafxIndexType,
     pub material_instance: Handle<MaterialInstanceAsset>,
 }
 
+// Joints index into the skin's joints. Weights of vertices that aren't skinned are all zero
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default)]
+pub struct MeshVertexJointWeights {
+    pub joints: [u16; 4],
+    pub weights: [f32; 4],
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MeshSkinAssetData {
+    pub skin: Handle<SkinAsset>,
+    // One per vertex in bind_pose_vertices
+    pub joint_weights: Vec<MeshVertexJointWeights>,
+}
+
+// Each delta list has one entry per vertex in bind_pose_vertices
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct MeshMorphTargetAssetData {
+    pub position_deltas: Vec<[f32; 3]>,
+    pub normal_deltas: Vec<[f32; 3]>,
+    pub tangent_deltas: Vec<[f32; 3]>,
+}
+
+// Deformable meshes keep a copy of the vertex buffer data, which is uploaded to storage buffers
+// that the mesh deformation compute shader reads from
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MeshDeformationAssetData {
+    // Same layout as the mesh's vertex buffer
+    pub bind_pose_vertices: Vec<MeshVertex>,
+    pub skin: Option<MeshSkinAssetData>,
+    pub morph_targets: Vec<MeshMorphTargetAssetData>,
+    // Used when the entity does not have a MorphTargetWeightsComponent
+    pub default_morph_target_weights: Vec<f32>,
+}
+
 #[derive(TypeUuid, Serialize, Deserialize, Clone)]
 #[uuid = "cf232526-3757-4d94-98d1-c2f7e27c979f"]
 pub struct MeshAssetData {
     pub mesh_parts: Vec<MeshPartAssetData>,
     pub vertex_buffer: Handle<BufferAsset>, //Vec<MeshVertex>,
-    pub index_buffer: Handle<BufferAsset>,  //Vec<u16>,
+    pub index_buffer: Handle<BufferAsset>, //Vec<u16> or Vec<u32>, see MeshPartAssetData::index_type
     pub visible_bounds: VisibleBounds,
+    pub deformation: Option<MeshDeformationAssetData>,
 }
 
 pub struct MeshAssetPart {
@@ -116,6 +152,7 @@ pub struct MeshAssetPart {
     pub vertex_buffer_size_in_bytes: u32,
     pub index_buffer_offset_in_bytes: u32,
     pub index_buffer_size_in_bytes: u32,
+    pub index_type: RafxIndexType,
 }
 
 pub const PER_MATERIAL_DESCRIPTOR_SET_LAYOUT_INDEX: usize = 1;
@@ -166,10 +203,108 @@ impl MeshAssetPart {
     }
 }
 
+// Same layout as JointWeights in mesh_deformation.comp
+#[derive(Copy, Clone, Default)]
+#[repr(C)]
+struct MeshDeformationJointWeights {
+    joints: [u32; 4],
+    weights: [f32; 4],
+}
+
+// Storage buffers read by the mesh deformation compute shader
+pub struct MeshDeformationBuffers {
+    pub vertex_count: u32,
+    pub bind_pose_vertices: ResourceArc<BufferResource>,
+    // Set if the mesh is skinned, one per vertex
+    pub joint_weights: Option<ResourceArc<BufferResource>>,
+    pub morph_target_count: u32,
+    // Set if the mesh has morph targets, see MorphTargetDeltas in mesh_deformation.comp
+    pub morph_target_deltas: Option<ResourceArc<BufferResource>>,
+}
+
+impl MeshDeformationBuffers {
+    fn new(
+        asset_manager: &AssetManager,
+        deformation: &MeshDeformationAssetData,
+    ) -> RafxResult<Self> {
+        let bind_pose_vertices =
+            create_storage_buffer(asset_manager, &deformation.bind_pose_vertices)?;
+
+        let joint_weights = if let Some(skin) = &deformation.skin {
+            let joint_weights: Vec<_> = skin
+                .joint_weights
+                .iter()
+                .map(|joint_weights| MeshDeformationJointWeights {
+                    joints: [
+                        joint_weights.joints[0] as u32,
+                        joint_weights.joints[1] as u32,
+                        joint_weights.joints[2] as u32,
+                        joint_weights.joints[3] as u32,
+                    ],
+                    weights: joint_weights.weights,
+                })
+                .collect();
+
+            Some(create_storage_buffer(asset_manager, &joint_weights)?)
+        } else {
+            None
+        };
+
+        let morph_target_deltas = if !deformation.morph_targets.is_empty() {
+            let mut morph_target_deltas = Vec::with_capacity(
+                deformation.morph_targets.len() * deformation.bind_pose_vertices.len(),
+            );
+            for morph_target in &deformation.morph_targets {
+                for ((position_delta, normal_delta), tangent_delta) in morph_target
+                    .position_deltas
+                    .iter()
+                    .zip(&morph_target.normal_deltas)
+                    .zip(&morph_target.tangent_deltas)
+                {
+                    morph_target_deltas.push([*position_delta, *normal_delta, *tangent_delta]);
+                }
+            }
+
+            Some(create_storage_buffer(asset_manager, &morph_target_deltas)?)
+        } else {
+            None
+        };
+
+        Ok(MeshDeformationBuffers {
+            vertex_count: deformation.bind_pose_vertices.len() as u32,
+            bind_pose_vertices,
+            joint_weights,
+            morph_target_count: deformation.morph_targets.len() as u32,
+            morph_target_deltas,
+        })
+    }
+}
+
+fn create_storage_buffer<T: Copy>(
+    asset_manager: &AssetManager,
+    data: &[T],
+) -> RafxResult<ResourceArc<BufferResource>> {
+    // Zero-sized buffers can't be created
+    let size = std::mem::size_of_val(data).max(std::mem::size_of::<T>()) as u64;
+    let buffer = asset_manager
+        .device_context()
+        .create_buffer(&RafxBufferDef {
+            size,
+            memory_usage: RafxMemoryUsage::CpuToGpu,
+            resource_type: RafxResourceType::BUFFER,
+            ..Default::default()
+        })?;
+
+    buffer.copy_to_host_visible_buffer(data)?;
+    Ok(asset_manager.resources().insert_buffer(buffer))
+}
+
 pub struct MeshAssetInner {
     pub mesh_parts: Vec<Option<MeshAssetPart>>,
     pub vertex_buffer: ResourceArc<BufferResource>,
     pub index_buffer: ResourceArc<BufferResource>,
+    // Set if the mesh is skinned or has morph targets
+    pub deformation_buffers: Option<MeshDeformationBuffers>,
     pub asset_data: MeshAssetData,
 }
 
@@ -252,13 +387,21 @@ impl DefaultAssetTypeLoadHandler<MeshAssetData, MeshAsset> for MeshLoadHandler {
                     vertex_buffer_size_in_bytes: mesh_part.vertex_buffer_size_in_bytes,
                     index_buffer_offset_in_bytes: mesh_part.index_buffer_offset_in_bytes,
                     index_buffer_size_in_bytes: mesh_part.index_buffer_size_in_bytes,
+                    index_type: mesh_part.index_type,
                 })
             })
             .collect();
 
+        let deformation_buffers = mesh_asset
+            .deformation
+            .as_ref()
+            .map(|deformation| MeshDeformationBuffers::new(asset_manager, deformation))
+            .transpose()?;
+
         let inner = MeshAssetInner {
             vertex_buffer,
             index_buffer,
+            deformation_buffers,
             asset_data: mesh_asset,
             mesh_parts,
         };
@@ -1,7 +1,12 @@
+use super::mesh_generation::{build_primitive_vertices, PrimitiveAttributes, PrimitiveVertices};
 use crate::assets::gltf::{
-    GltfMaterialData, GltfMaterialDataShaderParam, MeshAssetData, MeshPartAssetData,
+    AnimationChannelAssetData, AnimationClipAssetData, AnimationInterpolation, AnimationProperty,
+    GltfMaterialData, GltfMaterialDataShaderParam, MeshAsset, MeshAssetData,
+    MeshDeformationAssetData, MeshMorphTargetAssetData, MeshPartAssetData, MeshSkinAssetData,
+    MeshVertexJointWeights, PrefabAssetData, PrefabCameraAssetData, PrefabCameraProjection,
+    PrefabLightAssetData, PrefabLightKind, PrefabNodeAssetData, SkinAsset, SkinAssetData,
+    SkinJointAssetData,
 };
-use crate::features::mesh::MeshVertex;
 use distill::core::AssetUuid;
 use distill::importer::{Error, ImportOp, ImportedAsset, Importer, ImporterValue};
 use distill::loader::handle::Handle;
@@ -11,7 +16,7 @@ use glam::Vec3;
 use gltf::buffer::Data as GltfBufferData;
 use gltf::image::Data as GltfImageData;
 use itertools::Itertools;
-use rafx::api::RafxResourceType;
+use rafx::api::{RafxIndexType, RafxResourceType};
 use rafx::assets::push_buffer::PushBuffer;
 use rafx::assets::BufferAssetData;
 use rafx::assets::ImageAsset;
@@ -20,7 +25,6 @@ use rafx::assets::{ImageAssetColorSpace, ImageAssetData};
 use rafx::assets::{MaterialInstanceAssetData, MaterialInstanceSlotAssignment};
 use rafx::rafx_visibility::{PolygonSoup, PolygonSoupIndex, VisibleBounds};
 use serde::{Deserialize, Serialize};
-use std::convert::TryInto;
 use std::io::Read;
 use type_uuid::*;
 
@@ -91,6 +95,21 @@ struct BufferToImport {
     asset: BufferAssetData,
 }
 
+struct SkinToImport {
+    id: GltfObjectId,
+    asset: SkinAssetData,
+}
+
+struct AnimationToImport {
+    id: GltfObjectId,
+    asset: AnimationClipAssetData,
+}
+
+struct PrefabToImport {
+    id: GltfObjectId,
+    asset: PrefabAssetData,
+}
+
 // fn get_or_create_uuid(option_uuid: &mut Option<AssetUuid>) -> AssetUuid {
 //     let uuid = option_uuid.unwrap_or_else(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
 //
@@ -109,6 +128,12 @@ pub struct GltfImporterStateStable {
     material_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
     material_instance_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
     mesh_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
+    #[serde(default)]
+    skin_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
+    #[serde(default)]
+    animation_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
+    #[serde(default)]
+    prefab_asset_uuids: Vec<(GltfObjectId, AssetUuid)>,
 }
 
 impl From<GltfImporterStateUnstable> for GltfImporterStateStable {
@@ -139,6 +164,21 @@ impl From<GltfImporterStateUnstable> for GltfImporterStateStable {
             .into_iter()
             .sorted_by_key(|(id, _uuid)| id.clone())
             .collect();
+        stable.skin_asset_uuids = other
+            .skin_asset_uuids
+            .into_iter()
+            .sorted_by_key(|(id, _uuid)| id.clone())
+            .collect();
+        stable.animation_asset_uuids = other
+            .animation_asset_uuids
+            .into_iter()
+            .sorted_by_key(|(id, _uuid)| id.clone())
+            .collect();
+        stable.prefab_asset_uuids = other
+            .prefab_asset_uuids
+            .into_iter()
+            .sorted_by_key(|(id, _uuid)| id.clone())
+            .collect();
         stable
     }
 }
@@ -153,6 +193,9 @@ pub struct GltfImporterStateUnstable {
     material_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
     material_instance_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
     mesh_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
+    skin_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
+    animation_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
+    prefab_asset_uuids: FnvHashMap<GltfObjectId, AssetUuid>,
 }
 
 impl From<GltfImporterStateStable> for GltfImporterStateUnstable {
@@ -164,10 +207,37 @@ impl From<GltfImporterStateStable> for GltfImporterStateUnstable {
         unstable.material_instance_asset_uuids =
             other.material_instance_asset_uuids.into_iter().collect();
         unstable.mesh_asset_uuids = other.mesh_asset_uuids.into_iter().collect();
+        unstable.skin_asset_uuids = other.skin_asset_uuids.into_iter().collect();
+        unstable.animation_asset_uuids = other.animation_asset_uuids.into_iter().collect();
+        unstable.prefab_asset_uuids = other.prefab_asset_uuids.into_iter().collect();
         unstable
     }
 }
 
+// How normals are generated for mesh primitives that don't include them
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum GltfNormalGeneration {
+    // Triangles sharing a vertex are blended, weighted by area
+    Smooth,
+    // Every triangle gets its own face normal
+    Flat,
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "efbb433e-626c-425a-95a5-c91a3af93e08"]
+#[serde(default)]
+pub struct GltfImporterOptions {
+    pub normal_generation: GltfNormalGeneration,
+}
+
+impl Default for GltfImporterOptions {
+    fn default() -> Self {
+        GltfImporterOptions {
+            normal_generation: GltfNormalGeneration::Smooth,
+        }
+    }
+}
+
 #[derive(TypeUuid)]
 #[uuid = "fc9ae812-110d-4daf-9223-e87b40966c6b"]
 pub struct GltfImporter;
@@ -176,14 +246,14 @@ impl Importer for GltfImporter {
     where
         Self: Sized,
     {
-        26
+        32
     }
 
     fn version(&self) -> u32 {
         Self::version_static()
     }
 
-    type Options = ();
+    type Options = GltfImporterOptions;
 
     type State = GltfImporterStateStable;
 
@@ -192,7 +262,7 @@ impl Importer for GltfImporter {
         &self,
         op: &mut ImportOp,
         source: &mut dyn Read,
-        _options: &Self::Options,
+        options: &Self::Options,
         stable_state: &mut Self::State,
     ) -> distill::importer::Result<ImporterValue> {
         let mut unstable_state: GltfImporterStateUnstable = stable_state.clone().into();
@@ -290,10 +360,12 @@ impl Importer for GltfImporter {
                 slot_name: "per_material_data".to_string(),
                 array_index: 0,
                 image: None,
+                render_target: None,
                 sampler: None,
                 buffer_data: Some(
                     rafx::base::memory::any_as_bytes(&material_data_shader_param).into(),
                 ),
+                buffer_members: Default::default(),
             });
 
             fn push_image_slot_assignment(
@@ -311,8 +383,10 @@ impl Importer for GltfImporter {
                     } else {
                         Some(default_image.clone())
                     },
+                    render_target: None,
                     sampler: None,
                     buffer_data: None,
+                    buffer_members: Default::default(),
                 });
             }
 
@@ -373,6 +447,63 @@ impl Importer for GltfImporter {
             });
         }
 
+        //
+        // Skins
+        //
+        let skins_to_import = extract_skins_to_import(&doc, &buffers);
+        let mut skin_index_to_handle = vec![];
+        for skin_to_import in skins_to_import {
+            let skin_uuid = *unstable_state
+                .skin_asset_uuids
+                .entry(skin_to_import.id.clone())
+                .or_insert_with(|| op.new_asset_uuid());
+
+            skin_index_to_handle.push(make_handle::<SkinAsset>(skin_uuid));
+
+            let mut search_tags: Vec<(String, Option<String>)> = vec![];
+            if let GltfObjectId::Name(name) = &skin_to_import.id {
+                search_tags.push(("name".to_string(), Some(name.clone())));
+            }
+
+            log::debug!("Importing skin uuid {:?}", skin_uuid);
+
+            imported_assets.push(ImportedAsset {
+                id: skin_uuid,
+                search_tags,
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(skin_to_import.asset),
+            });
+        }
+
+        //
+        // Animations
+        //
+        let animations_to_import = extract_animations_to_import(&doc, &buffers);
+        for animation_to_import in animations_to_import {
+            let animation_uuid = *unstable_state
+                .animation_asset_uuids
+                .entry(animation_to_import.id.clone())
+                .or_insert_with(|| op.new_asset_uuid());
+
+            let mut search_tags: Vec<(String, Option<String>)> = vec![];
+            if let GltfObjectId::Name(name) = &animation_to_import.id {
+                search_tags.push(("name".to_string(), Some(name.clone())));
+            }
+
+            log::debug!("Importing animation uuid {:?}", animation_uuid);
+
+            imported_assets.push(ImportedAsset {
+                id: animation_uuid,
+                search_tags,
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(animation_to_import.asset),
+            });
+        }
+
         //
         // Meshes
         //
@@ -382,6 +513,8 @@ impl Importer for GltfImporter {
             &doc,
             &buffers,
             &material_instance_index_to_handle,
+            &skin_index_to_handle,
+            options,
         )?;
 
         let mut buffer_index_to_handle = vec![];
@@ -410,7 +543,7 @@ impl Importer for GltfImporter {
             });
         }
 
-        //let mut mesh_index_to_uuid_lookup = vec![];
+        let mut mesh_index_to_handle = vec![];
         for mesh_to_import in meshes_to_import {
             // Find the UUID associated with this image or create a new one
             let mesh_uuid = *unstable_state
@@ -418,8 +551,8 @@ impl Importer for GltfImporter {
                 .entry(mesh_to_import.id.clone())
                 .or_insert_with(|| op.new_asset_uuid());
 
-            // Push the UUID into the list so that we have an O(1) lookup for image index to UUID
-            //mesh_index_to_uuid_lookup.push(mesh_uuid.clone());
+            // Push the UUID into the list so that we have an O(1) lookup for mesh index to UUID
+            mesh_index_to_handle.push(make_handle::<MeshAsset>(mesh_uuid));
 
             let mut search_tags: Vec<(String, Option<String>)> = vec![];
             if let GltfObjectId::Name(name) = &mesh_to_import.id {
@@ -463,6 +596,33 @@ impl Importer for GltfImporter {
             });
         }
 
+        //
+        // Prefabs
+        //
+        let prefabs_to_import = extract_prefabs_to_import(&doc, &mesh_index_to_handle);
+        for prefab_to_import in prefabs_to_import {
+            let prefab_uuid = *unstable_state
+                .prefab_asset_uuids
+                .entry(prefab_to_import.id.clone())
+                .or_insert_with(|| op.new_asset_uuid());
+
+            let mut search_tags: Vec<(String, Option<String>)> = vec![];
+            if let GltfObjectId::Name(name) = &prefab_to_import.id {
+                search_tags.push(("name".to_string(), Some(name.clone())));
+            }
+
+            log::debug!("Importing prefab uuid {:?}", prefab_uuid);
+
+            imported_assets.push(ImportedAsset {
+                id: prefab_uuid,
+                search_tags,
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(prefab_to_import.asset),
+            });
+        }
+
         *stable_state = unstable_state.into();
 
         Ok(ImporterValue {
@@ -736,30 +896,46 @@ fn extract_materials_to_import(
     materials_to_import
 }
 
-//TODO: This feels kind of dumb..
-fn convert_to_u16_indices(
-    read_indices: gltf::mesh::util::ReadIndices
-) -> Result<Vec<u16>, std::num::TryFromIntError> {
-    let indices_u32: Vec<u32> = read_indices.into_u32().collect();
-    let mut indices_u16: Vec<u16> = Vec::with_capacity(indices_u32.len());
-    for index in indices_u32 {
-        indices_u16.push(index.try_into()?);
-    }
-
-    Ok(indices_u16)
-}
-
 fn extract_meshes_to_import(
     op: &mut ImportOp,
     state: &mut GltfImporterStateUnstable,
     doc: &gltf::Document,
     buffers: &[GltfBufferData],
     material_instance_index_to_handle: &[Handle<MaterialInstanceAsset>],
+    skin_index_to_handle: &[Handle<SkinAsset>],
+    options: &GltfImporterOptions,
 ) -> distill::importer::Result<(Vec<MeshToImport>, Vec<BufferToImport>)> {
     let mut meshes_to_import = Vec::with_capacity(doc.meshes().len());
     let mut buffers_to_import = Vec::with_capacity(doc.meshes().len() * 2);
 
+    // The skin is assigned by the node that references the mesh. If several nodes use the mesh
+    // with different skins, the first one is used.
+    let mut mesh_index_to_skin_index = FnvHashMap::default();
+    for node in doc.nodes() {
+        if let (Some(mesh), Some(skin)) = (node.mesh(), node.skin()) {
+            mesh_index_to_skin_index
+                .entry(mesh.index())
+                .or_insert_with(|| skin.index());
+        }
+    }
+
     for mesh in doc.meshes() {
+        let skin_index = mesh_index_to_skin_index.get(&mesh.index()).copied();
+        let skin_joint_count =
+            skin_index.map(|skin_index| doc.skins().nth(skin_index).unwrap().joints().count());
+
+        // glTF requires every primitive in a mesh to have the same number of morph targets
+        let morph_target_count = mesh
+            .primitives()
+            .map(|x| x.morph_targets().len())
+            .max()
+            .unwrap_or(0);
+        let is_deformable = skin_index.is_some() || morph_target_count > 0;
+
+        let mut all_bind_pose_vertices = Vec::new();
+        let mut all_joint_weights = Vec::new();
+        let mut all_morph_targets = vec![MeshMorphTargetAssetData::default(); morph_target_count];
+
         let mut all_positions = Vec::with_capacity(1024);
         let mut all_position_indices = Vec::with_capacity(8192);
 
@@ -782,83 +958,162 @@ fn extract_meshes_to_import(
                 //let colors = reader.read_colors();
                 let tex_coords = reader.read_tex_coords(0);
                 let indices = reader.read_indices();
+                let joints = reader.read_joints(0);
+                let weights = reader.read_weights(0);
 
-                if let (
-                    Some(indices),
-                    Some(positions),
-                    Some(normals),
-                    Some(tangents),
-                    Some(tex_coords),
-                ) = (indices, positions, normals, tangents, tex_coords)
-                {
-                    let part_indices = convert_to_u16_indices(indices);
-
-                    if let Ok(part_indices) = part_indices {
-                        //TODO: Consider computing binormal (bitangent) here
-                        let positions: Vec<_> = positions.collect();
-                        let normals: Vec<_> = normals.collect();
-                        let tangents: Vec<_> = tangents.collect();
-                        let tex_coords: Vec<_> = tex_coords.into_f32().collect();
-
-                        let vertex_offset = all_vertices.len();
-                        let indices_offset = all_indices.len();
-
-                        for i in 0..positions.len() {
-                            all_positions.push(Vec3::new(
-                                positions[i][0],
-                                positions[i][1],
-                                positions[i][2],
-                            ));
-                            all_vertices.push(
-                                &[MeshVertex {
-                                    position: positions[i],
-                                    normal: normals[i],
-                                    tangent: tangents[i],
-                                    tex_coord: tex_coords[i],
-                                }],
-                                1,
-                            );
-                        }
+                let positions = if let Some(positions) = positions {
+                    positions.collect()
+                } else {
+                    log::error!("Mesh primitives must specify positions");
+                    return Err(distill::importer::Error::Boxed(Box::new(
+                        GltfImportError::new("Mesh primitives must specify positions"),
+                    )));
+                };
 
-                        all_indices.push(&part_indices, 1);
-                        all_position_indices.extend_from_slice(&part_indices);
+                let attributes = PrimitiveAttributes {
+                    positions,
+                    normals: normals.map(|x| x.collect()),
+                    tangents: tangents.map(|x| x.collect()),
+                    tex_coords: tex_coords.map(|x| x.into_f32().collect()),
+                    indices: indices.map(|x| x.into_u32().collect()),
+                };
 
-                        let vertex_size = all_vertices.len() - vertex_offset;
-                        let indices_size = all_indices.len() - indices_offset;
+                if attributes.requires_generated_attributes()
+                    && primitive.mode() != gltf::mesh::Mode::Triangles
+                {
+                    log::error!(
+                        "Mesh primitives without normals or tangents must be triangle lists"
+                    );
+                    return Err(distill::importer::Error::Boxed(Box::new(
+                        GltfImportError::new(
+                            "Mesh primitives without normals or tangents must be triangle lists",
+                        ),
+                    )));
+                }
 
-                        let material_instance = if let Some(material_index) =
-                            primitive.material().index()
-                        {
-                            material_instance_index_to_handle[material_index].clone()
-                        } else {
+                let vertex_count = attributes.positions.len();
+
+                //TODO: Consider computing binormal (bitangent) here
+                let PrimitiveVertices {
+                    vertices,
+                    vertex_sources,
+                    indices: part_indices,
+                } = build_primitive_vertices(attributes, options.normal_generation).map_err(
+                    |error| {
+                        log::error!("{}", error);
+                        distill::importer::Error::Boxed(Box::new(GltfImportError::new(&error)))
+                    },
+                )?;
+
+                if let Some(skin_joint_count) = skin_joint_count {
+                    let joint_weights = if let (Some(joints), Some(weights)) = (joints, weights) {
+                        let joints: Vec<_> = joints.into_u16().collect();
+                        let weights: Vec<_> = weights.into_f32().collect();
+                        if let Err(error) = validate_joint_weights(
+                            &joints,
+                            &weights,
+                            vertex_count,
+                            skin_joint_count,
+                        ) {
+                            log::error!("{}", error);
                             return Err(distill::importer::Error::Boxed(Box::new(
-                                GltfImportError::new("A mesh primitive did not have a material"),
+                                GltfImportError::new(&error),
                             )));
-                        };
-
-                        Some(MeshPartAssetData {
-                            //material,
-                            material_instance,
-                            vertex_buffer_offset_in_bytes: vertex_offset as u32,
-                            vertex_buffer_size_in_bytes: vertex_size as u32,
-                            index_buffer_offset_in_bytes: indices_offset as u32,
-                            index_buffer_size_in_bytes: indices_size as u32,
-                        })
+                        }
+
+                        vertex_sources
+                            .iter()
+                            .map(|&source| MeshVertexJointWeights {
+                                joints: joints[source as usize],
+                                weights: weights[source as usize],
+                            })
+                            .collect()
                     } else {
-                        log::error!("indices must fit in u16");
-                        return Err(distill::importer::Error::Boxed(Box::new(
-                            GltfImportError::new("indices must fit in u16"),
-                        )));
+                        // Primitives without weights are left in the bind pose
+                        vec![MeshVertexJointWeights::default(); vertices.len()]
+                    };
+
+                    all_joint_weights.extend(joint_weights);
+                }
+
+                if morph_target_count > 0 {
+                    let mut morph_targets: Vec<_> = reader.read_morph_targets().collect();
+                    morph_targets.resize_with(morph_target_count, || (None, None, None));
+
+                    // Primitives that don't provide some deltas get zeros so that the deltas line
+                    // up with the vertex buffer
+                    let remap = |deltas: Option<Vec<[f32; 3]>>| -> Vec<[f32; 3]> {
+                        match deltas {
+                            Some(deltas) => vertex_sources
+                                .iter()
+                                .map(|&source| deltas[source as usize])
+                                .collect(),
+                            None => vec![[0.0; 3]; vertices.len()],
+                        }
+                    };
+
+                    for (morph_target, (position_deltas, normal_deltas, tangent_deltas)) in
+                        all_morph_targets.iter_mut().zip(morph_targets)
+                    {
+                        morph_target
+                            .position_deltas
+                            .extend(remap(position_deltas.map(|x| x.collect())));
+                        morph_target
+                            .normal_deltas
+                            .extend(remap(normal_deltas.map(|x| x.collect())));
+                        morph_target
+                            .tangent_deltas
+                            .extend(remap(tangent_deltas.map(|x| x.collect())));
                     }
+                }
+
+                if is_deformable {
+                    all_bind_pose_vertices.extend_from_slice(&vertices);
+                }
+
+                let vertex_offset = all_vertices.len();
+
+                // Indices in the index buffer are relative to the part, but the polygon soup holds
+                // positions for all parts
+                let first_position_index = all_positions.len() as u32;
+                for vertex in &vertices {
+                    all_positions.push(Vec3::from(vertex.position));
+                }
+                all_vertices.push(&vertices, 1);
+                all_position_indices.extend(part_indices.iter().map(|x| x + first_position_index));
+
+                let vertex_size = all_vertices.len() - vertex_offset;
+
+                // Prefer 16-bit indices, but large meshes (scans, photogrammetry) need 32-bit
+                let (index_type, index_result) = if vertices.len() <= u16::MAX as usize + 1 {
+                    let part_indices: Vec<u16> =
+                        part_indices.into_iter().map(|x| x as u16).collect();
+                    let index_type = RafxIndexType::Uint16;
+                    let result = all_indices.push(&part_indices, index_type.size_in_bytes());
+                    (index_type, result)
                 } else {
-                    log::error!(
-                        "Mesh primitives must specify indices, positions, normals, tangents, and tex_coords"
-                    );
+                    let index_type = RafxIndexType::Uint32;
+                    let result = all_indices.push(&part_indices, index_type.size_in_bytes());
+                    (index_type, result)
+                };
 
+                let material_instance = if let Some(material_index) = primitive.material().index() {
+                    material_instance_index_to_handle[material_index].clone()
+                } else {
                     return Err(distill::importer::Error::Boxed(Box::new(
-                        GltfImportError::new("Mesh primitives must specify indices, positions, normals, tangents, and tex_coords"),
+                        GltfImportError::new("A mesh primitive did not have a material"),
                     )));
-                }
+                };
+
+                Some(MeshPartAssetData {
+                    //material,
+                    material_instance,
+                    vertex_buffer_offset_in_bytes: vertex_offset as u32,
+                    vertex_buffer_size_in_bytes: vertex_size as u32,
+                    index_buffer_offset_in_bytes: index_result.offset() as u32,
+                    index_buffer_size_in_bytes: index_result.size() as u32,
+                    index_type,
+                })
             };
 
             if let Some(mesh_part) = mesh_part {
@@ -912,7 +1167,7 @@ fn extract_meshes_to_import(
 
         let mesh_data = PolygonSoup {
             vertex_positions: all_positions,
-            index: PolygonSoupIndex::Indexed16(all_position_indices),
+            index: PolygonSoupIndex::Indexed32(all_position_indices),
         };
 
         let asset = MeshAssetData {
@@ -920,6 +1175,22 @@ fn extract_meshes_to_import(
             vertex_buffer: vertex_buffer_handle,
             index_buffer: index_buffer_handle,
             visible_bounds: VisibleBounds::from(mesh_data),
+            deformation: if is_deformable {
+                Some(MeshDeformationAssetData {
+                    bind_pose_vertices: all_bind_pose_vertices,
+                    skin: skin_index.map(|skin_index| MeshSkinAssetData {
+                        skin: skin_index_to_handle[skin_index].clone(),
+                        joint_weights: all_joint_weights,
+                    }),
+                    morph_targets: all_morph_targets,
+                    default_morph_target_weights: mesh
+                        .weights()
+                        .map(|x| x.to_vec())
+                        .unwrap_or_else(|| vec![0.0; morph_target_count]),
+                })
+            } else {
+                None
+            },
         };
 
         let mesh_id = mesh
@@ -943,3 +1214,369 @@ fn extract_meshes_to_import(
 
     Ok((meshes_to_import, buffers_to_import))
 }
+
+// JOINTS_0 and WEIGHTS_0 must have an element per vertex, and every joint index must refer to a
+// joint in the mesh's skin
+fn validate_joint_weights(
+    joints: &[[u16; 4]],
+    weights: &[[f32; 4]],
+    vertex_count: usize,
+    skin_joint_count: usize,
+) -> Result<(), String> {
+    if joints.len() != vertex_count || weights.len() != vertex_count {
+        return Err(format!(
+            "Mesh primitive has {} positions but {} joints and {} weights",
+            vertex_count,
+            joints.len(),
+            weights.len()
+        ));
+    }
+
+    if let Some(&joint) = joints
+        .iter()
+        .flatten()
+        .find(|&&x| x as usize >= skin_joint_count)
+    {
+        return Err(format!(
+            "Mesh primitive references joint {} but its skin only has {} joints",
+            joint, skin_joint_count
+        ));
+    }
+
+    Ok(())
+}
+
+fn extract_skins_to_import(
+    doc: &gltf::Document,
+    buffers: &[GltfBufferData],
+) -> Vec<SkinToImport> {
+    let mut node_parents = FnvHashMap::default();
+    for node in doc.nodes() {
+        for child in node.children() {
+            node_parents.insert(child.index(), node.index());
+        }
+    }
+
+    let nodes: Vec<_> = doc.nodes().collect();
+
+    let mut skins_to_import = Vec::with_capacity(doc.skins().len());
+    for skin in doc.skins() {
+        let reader = skin.reader(|buffer| buffers.get(buffer.index()).map(|x| &**x));
+        let inverse_bind_matrices: Vec<_> = reader
+            .read_inverse_bind_matrices()
+            .map(|x| x.collect())
+            .unwrap_or_default();
+
+        let joint_nodes: Vec<_> = skin.joints().collect();
+        let node_to_joint: FnvHashMap<_, _> = joint_nodes
+            .iter()
+            .enumerate()
+            .map(|(joint_index, node)| (node.index(), joint_index))
+            .collect();
+
+        // The nearest ancestor of each joint that is also a joint
+        let joint_parent = |node_index: usize| {
+            let mut current = node_parents.get(&node_index);
+            while let Some(&parent) = current {
+                if let Some(&joint_index) = node_to_joint.get(&parent) {
+                    return Some(joint_index);
+                }
+                current = node_parents.get(&parent);
+            }
+            None
+        };
+
+        let mut joints = Vec::with_capacity(joint_nodes.len());
+        let mut joint_depths = Vec::with_capacity(joint_nodes.len());
+        for (joint_index, node) in joint_nodes.iter().enumerate() {
+            let (translation, rotation, scale) = node.transform().decomposed();
+            let parent = joint_parent(node.index());
+
+            let mut depth = 0;
+            let mut ancestor = parent;
+            while let Some(ancestor_index) = ancestor {
+                depth += 1;
+                ancestor = joint_parent(joint_nodes[ancestor_index].index());
+            }
+            joint_depths.push(depth);
+
+            joints.push(SkinJointAssetData {
+                name: node.name().map(|x| x.to_string()),
+                node_index: node.index(),
+                parent,
+                inverse_bind_matrix: inverse_bind_matrices
+                    .get(joint_index)
+                    .copied()
+                    .unwrap_or_else(|| glam::Mat4::IDENTITY.to_cols_array_2d()),
+                translation,
+                rotation,
+                scale,
+            });
+        }
+
+        let evaluation_order = (0..joints.len())
+            .sorted_by_key(|&joint_index| joint_depths[joint_index])
+            .collect();
+
+        // Accumulate the transforms of the non-joint nodes above the first root joint
+        let mut root_transform = glam::Mat4::IDENTITY;
+        if let Some(root_joint) = joints.iter().find(|x| x.parent.is_none()) {
+            let mut current = node_parents.get(&root_joint.node_index);
+            while let Some(&parent) = current {
+                let parent_transform = nodes[parent].transform().matrix();
+                root_transform = glam::Mat4::from_cols_array_2d(&parent_transform) * root_transform;
+                current = node_parents.get(&parent);
+            }
+        }
+
+        let skin_id = skin
+            .name()
+            .map(|s| GltfObjectId::Name(s.to_string()))
+            .unwrap_or_else(|| GltfObjectId::Index(skin.index()));
+
+        log::debug!(
+            "Importing skin name: {:?} index: {} joint count: {}",
+            skin.name(),
+            skin.index(),
+            joints.len()
+        );
+
+        skins_to_import.push(SkinToImport {
+            id: skin_id,
+            asset: SkinAssetData {
+                joints,
+                evaluation_order,
+                root_transform: root_transform.to_cols_array_2d(),
+            },
+        });
+    }
+
+    skins_to_import
+}
+
+fn extract_animations_to_import(
+    doc: &gltf::Document,
+    buffers: &[GltfBufferData],
+) -> Vec<AnimationToImport> {
+    let mut animations_to_import = Vec::with_capacity(doc.animations().len());
+    for animation in doc.animations() {
+        let mut channels = Vec::new();
+        let mut duration = 0.0f32;
+
+        for channel in animation.channels() {
+            let reader = channel.reader(|buffer| buffers.get(buffer.index()).map(|x| &**x));
+
+            let times: Vec<f32> = match reader.read_inputs() {
+                Some(inputs) => inputs.collect(),
+                None => continue,
+            };
+
+            if times.is_empty() || times.iter().any(|x| !x.is_finite()) {
+                log::warn!(
+                    "Animation {:?} has a channel with no keyframes or a non-finite keyframe time, it will be ignored",
+                    animation.name()
+                );
+                continue;
+            }
+
+            let (property, values): (_, Vec<[f32; 4]>) = match reader.read_outputs() {
+                Some(gltf::animation::util::ReadOutputs::Translations(translations)) => (
+                    AnimationProperty::Translation,
+                    translations.map(|x| [x[0], x[1], x[2], 0.0]).collect(),
+                ),
+                Some(gltf::animation::util::ReadOutputs::Rotations(rotations)) => {
+                    (AnimationProperty::Rotation, rotations.into_f32().collect())
+                }
+                Some(gltf::animation::util::ReadOutputs::Scales(scales)) => (
+                    AnimationProperty::Scale,
+                    scales.map(|x| [x[0], x[1], x[2], 0.0]).collect(),
+                ),
+                _ => {
+                    log::warn!(
+                        "Animation {:?} has a channel for an unsupported property, it will be ignored",
+                        animation.name()
+                    );
+                    continue;
+                }
+            };
+
+            let interpolation = match channel.sampler().interpolation() {
+                gltf::animation::Interpolation::Linear => AnimationInterpolation::Linear,
+                gltf::animation::Interpolation::Step => AnimationInterpolation::Step,
+                gltf::animation::Interpolation::CubicSpline => AnimationInterpolation::CubicSpline,
+            };
+
+            if let Some(last_time) = times.last() {
+                duration = duration.max(*last_time);
+            }
+
+            channels.push(AnimationChannelAssetData {
+                node_index: channel.target().node().index(),
+                property,
+                interpolation,
+                times,
+                values,
+            });
+        }
+
+        let animation_id = animation
+            .name()
+            .map(|s| GltfObjectId::Name(s.to_string()))
+            .unwrap_or_else(|| GltfObjectId::Index(animation.index()));
+
+        log::debug!(
+            "Importing animation name: {:?} index: {} channel count: {}",
+            animation.name(),
+            animation.index(),
+            channels.len()
+        );
+
+        animations_to_import.push(AnimationToImport {
+            id: animation_id,
+            asset: AnimationClipAssetData {
+                name: animation.name().map(|x| x.to_string()),
+                duration,
+                channels,
+            },
+        });
+    }
+
+    animations_to_import
+}
+
+fn extract_prefabs_to_import(
+    doc: &gltf::Document,
+    mesh_index_to_handle: &[Handle<MeshAsset>],
+) -> Vec<PrefabToImport> {
+    let mut prefabs_to_import = Vec::with_capacity(doc.scenes().len());
+    for scene in doc.scenes() {
+        let mut nodes = Vec::new();
+
+        // Depth-first walk from the scene's root nodes so that parents are always pushed before
+        // their children
+        let mut nodes_to_visit: Vec<_> = scene.nodes().map(|node| (node, None)).collect();
+        nodes_to_visit.reverse();
+        while let Some((node, parent)) = nodes_to_visit.pop() {
+            let node_index = nodes.len();
+            let (translation, rotation, scale) = node.transform().decomposed();
+
+            let light = node.light().map(|light| PrefabLightAssetData {
+                name: light.name().map(|x| x.to_string()),
+                kind: match light.kind() {
+                    gltf::khr_lights_punctual::Kind::Directional => PrefabLightKind::Directional,
+                    gltf::khr_lights_punctual::Kind::Point => PrefabLightKind::Point,
+                    gltf::khr_lights_punctual::Kind::Spot {
+                        inner_cone_angle,
+                        outer_cone_angle,
+                    } => PrefabLightKind::Spot {
+                        inner_cone_angle,
+                        outer_cone_angle,
+                    },
+                },
+                color: light.color(),
+                intensity: light.intensity(),
+                range: light.range(),
+            });
+
+            let camera = node.camera().map(|camera| PrefabCameraAssetData {
+                name: camera.name().map(|x| x.to_string()),
+                projection: match camera.projection() {
+                    gltf::camera::Projection::Perspective(perspective) => {
+                        PrefabCameraProjection::Perspective {
+                            y_fov: perspective.yfov(),
+                            aspect_ratio: perspective.aspect_ratio(),
+                            z_near: perspective.znear(),
+                            z_far: perspective.zfar(),
+                        }
+                    }
+                    gltf::camera::Projection::Orthographic(orthographic) => {
+                        PrefabCameraProjection::Orthographic {
+                            x_mag: orthographic.xmag(),
+                            y_mag: orthographic.ymag(),
+                            z_near: orthographic.znear(),
+                            z_far: orthographic.zfar(),
+                        }
+                    }
+                },
+            });
+
+            nodes.push(PrefabNodeAssetData {
+                name: node.name().map(|x| x.to_string()),
+                parent,
+                translation,
+                rotation,
+                scale,
+                mesh: node
+                    .mesh()
+                    .map(|mesh| mesh_index_to_handle[mesh.index()].clone()),
+                morph_target_weights: node.weights().map(|x| x.to_vec()).unwrap_or_default(),
+                light,
+                camera,
+            });
+
+            let children: Vec<_> = node.children().collect();
+            for child in children.into_iter().rev() {
+                nodes_to_visit.push((child, Some(node_index)));
+            }
+        }
+
+        let prefab_id = scene
+            .name()
+            .map(|s| GltfObjectId::Name(s.to_string()))
+            .unwrap_or_else(|| GltfObjectId::Index(scene.index()));
+
+        log::debug!(
+            "Importing prefab name: {:?} index: {} node count: {}",
+            scene.name(),
+            scene.index(),
+            nodes.len()
+        );
+
+        prefabs_to_import.push(PrefabToImport {
+            id: prefab_id,
+            asset: PrefabAssetData {
+                name: scene.name().map(|x| x.to_string()),
+                nodes,
+            },
+        });
+    }
+
+    prefabs_to_import
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_prefab_parents_are_before_children() {
+        // The nodes are declared out of order: root -> (a -> a_child), b
+        let json = r#"{
+            "asset": { "version": "2.0" },
+            "scene": 0,
+            "scenes": [ { "nodes": [2] } ],
+            "nodes": [
+                { "name": "a", "children": [3] },
+                { "name": "b" },
+                { "name": "root", "children": [0, 1] },
+                { "name": "a_child" }
+            ]
+        }"#;
+        let doc = gltf::Gltf::from_slice(json.as_bytes()).unwrap().document;
+
+        let prefabs = extract_prefabs_to_import(&doc, &[]);
+        assert_eq!(prefabs.len(), 1);
+
+        let nodes = &prefabs[0].asset.nodes;
+        let names: Vec<_> = nodes.iter().map(|x| x.name.as_deref().unwrap()).collect();
+        let parents: Vec<_> = nodes.iter().map(|x| x.parent).collect();
+        assert_eq!(names, ["root", "a", "a_child", "b"]);
+        assert_eq!(parents, [None, Some(0), Some(1), Some(0)]);
+
+        for (index, node) in nodes.iter().enumerate() {
+            if let Some(parent) = node.parent {
+                assert!(parent < index);
+            }
+        }
+    }
+}
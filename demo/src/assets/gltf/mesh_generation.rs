@@ -0,0 +1,516 @@
+use super::GltfNormalGeneration;
+use crate::features::mesh::MeshVertex;
+use fnv::FnvHashMap;
+use glam::Vec3;
+
+// Vertex attributes read from a single glTF primitive. Only positions are required, anything else
+// that is missing gets generated
+pub(super) struct PrimitiveAttributes {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Option<Vec<[f32; 3]>>,
+    pub tangents: Option<Vec<[f32; 4]>>,
+    pub tex_coords: Option<Vec<[f32; 2]>>,
+    pub indices: Option<Vec<u32>>,
+}
+
+impl PrimitiveAttributes {
+    pub fn requires_generated_attributes(&self) -> bool {
+        self.normals.is_none() || self.tangents.is_none()
+    }
+
+    // Every attribute must have one element per position, and every index must refer to a position
+    pub fn validate(&self) -> Result<(), String> {
+        let vertex_count = self.positions.len();
+        let attribute_lengths = [
+            ("normals", self.normals.as_ref().map(|x| x.len())),
+            ("tangents", self.tangents.as_ref().map(|x| x.len())),
+            ("tex coords", self.tex_coords.as_ref().map(|x| x.len())),
+        ];
+
+        for (name, length) in &attribute_lengths {
+            if let Some(length) = length {
+                if *length != vertex_count {
+                    return Err(format!(
+                        "Mesh primitive has {} positions but {} {}",
+                        vertex_count, length, name
+                    ));
+                }
+            }
+        }
+
+        if let Some(indices) = &self.indices {
+            if let Some(&index) = indices.iter().find(|&&x| x as usize >= vertex_count) {
+                return Err(format!(
+                    "Mesh primitive has an index {} but only {} vertices",
+                    index, vertex_count
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(super) struct PrimitiveVertices {
+    pub vertices: Vec<MeshVertex>,
+    // The index of the vertex in the glTF primitive that each vertex was built from. Used to look
+    // up other per-vertex data (like skinning weights) that isn't part of MeshVertex
+    pub vertex_sources: Vec<u32>,
+    pub indices: Vec<u32>,
+}
+
+// Produces the vertex and index buffer data for a primitive. If normals or tangents are missing,
+// the primitive must be a triangle list.
+pub(super) fn build_primitive_vertices(
+    attributes: PrimitiveAttributes,
+    normal_generation: GltfNormalGeneration,
+) -> Result<PrimitiveVertices, String> {
+    attributes.validate()?;
+
+    let PrimitiveAttributes {
+        positions,
+        normals,
+        tangents,
+        tex_coords,
+        indices,
+    } = attributes;
+
+    let indices = indices.unwrap_or_else(|| (0..positions.len() as u32).collect());
+    let tex_coord = |i: usize| tex_coords.as_ref().map(|x| x[i]).unwrap_or([0.0, 0.0]);
+
+    if let (Some(normals), Some(tangents)) = (&normals, &tangents) {
+        let vertices = (0..positions.len())
+            .map(|i| MeshVertex {
+                position: positions[i],
+                normal: normals[i],
+                tangent: tangents[i],
+                tex_coord: tex_coord(i),
+            })
+            .collect();
+
+        return Ok(PrimitiveVertices {
+            vertices,
+            vertex_sources: (0..positions.len() as u32).collect(),
+            indices,
+        });
+    }
+
+    if indices.len() % 3 != 0 {
+        return Err(format!(
+            "Mesh primitive is a triangle list but has {} indices, which is not a multiple of 3",
+            indices.len()
+        ));
+    }
+
+    let normals = match (normals, normal_generation) {
+        (Some(normals), _) => Some(normals),
+        (None, GltfNormalGeneration::Smooth) => Some(smooth_normals(&positions, &indices)),
+        // Computed per triangle below
+        (None, GltfNormalGeneration::Flat) => None,
+    };
+
+    //
+    // Expand to one vertex per triangle corner. Generated flat normals and tangents can differ for
+    // each triangle that shares a vertex, so they can't be computed on the indexed data.
+    //
+    let mut corners = Vec::with_capacity(indices.len());
+    let mut corner_sources = Vec::with_capacity(indices.len());
+    for triangle in indices.chunks_exact(3) {
+        let face_normal = triangle_normal(
+            positions[triangle[0] as usize],
+            positions[triangle[1] as usize],
+            positions[triangle[2] as usize],
+        );
+
+        for &index in triangle {
+            let i = index as usize;
+            corners.push(MeshVertex {
+                position: positions[i],
+                normal: normals.as_ref().map(|x| x[i]).unwrap_or(face_normal),
+                tangent: tangents.as_ref().map(|x| x[i]).unwrap_or([0.0; 4]),
+                tex_coord: tex_coord(i),
+            });
+            corner_sources.push(index);
+        }
+    }
+
+    if tangents.is_none() {
+        let generated = tex_coords.is_some() && {
+            let mut geometry = MikktspaceGeometry {
+                corners: &mut corners,
+            };
+            mikktspace::generate_tangents(&mut geometry)
+        };
+
+        if !generated {
+            // Without UVs there is no meaningful tangent space, but normal mapped materials still
+            // need an orthonormal basis
+            for corner in &mut corners {
+                corner.tangent = fallback_tangent(corner.normal);
+            }
+        }
+    }
+
+    Ok(weld_vertices(corners, corner_sources))
+}
+
+fn triangle_normal(
+    p0: [f32; 3],
+    p1: [f32; 3],
+    p2: [f32; 3],
+) -> [f32; 3] {
+    let p0 = Vec3::from(p0);
+    let normal = (Vec3::from(p1) - p0).cross(Vec3::from(p2) - p0);
+    normalize_or(normal, Vec3::Y).into()
+}
+
+fn normalize_or(
+    v: Vec3,
+    fallback: Vec3,
+) -> Vec3 {
+    let length = v.length();
+    if length > 0.0 && length.is_finite() {
+        v / length
+    } else {
+        fallback
+    }
+}
+
+// Each vertex normal is the sum of the normals of adjoining triangles, weighted by triangle area
+fn smooth_normals(
+    positions: &[[f32; 3]],
+    indices: &[u32],
+) -> Vec<[f32; 3]> {
+    let mut accumulated = vec![Vec3::ZERO; positions.len()];
+    for triangle in indices.chunks_exact(3) {
+        let p0 = Vec3::from(positions[triangle[0] as usize]);
+        let p1 = Vec3::from(positions[triangle[1] as usize]);
+        let p2 = Vec3::from(positions[triangle[2] as usize]);
+
+        // Length of the cross product is twice the triangle's area
+        let normal = (p1 - p0).cross(p2 - p0);
+        for &index in triangle {
+            accumulated[index as usize] += normal;
+        }
+    }
+
+    accumulated
+        .into_iter()
+        .map(|x| normalize_or(x, Vec3::Y).into())
+        .collect()
+}
+
+fn fallback_tangent(normal: [f32; 3]) -> [f32; 4] {
+    let normal = Vec3::from(normal);
+    let axis = if normal.x.abs() < 0.9 {
+        Vec3::X
+    } else {
+        Vec3::Y
+    };
+
+    let tangent = normalize_or(axis - normal * normal.dot(axis), Vec3::X);
+    [tangent.x, tangent.y, tangent.z, 1.0]
+}
+
+// Merges corners that came from the same source vertex and ended up with identical attributes back
+// into shared vertices
+fn weld_vertices(
+    corners: Vec<MeshVertex>,
+    corner_sources: Vec<u32>,
+) -> PrimitiveVertices {
+    let mut vertices = Vec::with_capacity(corners.len());
+    let mut vertex_sources = Vec::with_capacity(corners.len());
+    let mut indices = Vec::with_capacity(corners.len());
+    let mut vertex_lookup = FnvHashMap::<[u32; 13], u32>::default();
+
+    for (corner, source) in corners.into_iter().zip(corner_sources) {
+        let mut key = [0; 13];
+        key[12] = source;
+        let values = corner
+            .position
+            .iter()
+            .chain(&corner.normal)
+            .chain(&corner.tangent)
+            .chain(&corner.tex_coord);
+        for (k, v) in key.iter_mut().zip(values) {
+            *k = v.to_bits();
+        }
+
+        let index = *vertex_lookup.entry(key).or_insert_with(|| {
+            vertices.push(corner);
+            vertex_sources.push(source);
+            (vertices.len() - 1) as u32
+        });
+        indices.push(index);
+    }
+
+    PrimitiveVertices {
+        vertices,
+        vertex_sources,
+        indices,
+    }
+}
+
+struct MikktspaceGeometry<'a> {
+    // Triangle list, 3 corners per face
+    corners: &'a mut [MeshVertex],
+}
+
+impl<'a> mikktspace::Geometry for MikktspaceGeometry<'a> {
+    fn num_faces(&self) -> usize {
+        self.corners.len() / 3
+    }
+
+    fn num_vertices_of_face(
+        &self,
+        _face: usize,
+    ) -> usize {
+        3
+    }
+
+    fn position(
+        &self,
+        face: usize,
+        vert: usize,
+    ) -> [f32; 3] {
+        self.corners[face * 3 + vert].position
+    }
+
+    fn normal(
+        &self,
+        face: usize,
+        vert: usize,
+    ) -> [f32; 3] {
+        self.corners[face * 3 + vert].normal
+    }
+
+    fn tex_coord(
+        &self,
+        face: usize,
+        vert: usize,
+    ) -> [f32; 2] {
+        self.corners[face * 3 + vert].tex_coord
+    }
+
+    // The encoded w component is the bitangent sign, which matches the glTF convention
+    fn set_tangent_encoded(
+        &mut self,
+        tangent: [f32; 4],
+        face: usize,
+        vert: usize,
+    ) {
+        self.corners[face * 3 + vert].tangent = tangent;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Two triangles in the XY plane facing +Z, without normals, tangents or indices
+    fn quad() -> PrimitiveAttributes {
+        PrimitiveAttributes {
+            positions: vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 0.0, 0.0],
+                [1.0, 1.0, 0.0],
+                [0.0, 1.0, 0.0],
+            ],
+            normals: None,
+            tangents: None,
+            tex_coords: None,
+            indices: None,
+        }
+    }
+
+    // Two triangles that share the edge along +X, one facing +Z and the other facing +Y
+    fn folded_quad() -> PrimitiveAttributes {
+        PrimitiveAttributes {
+            positions: vec![
+                [0.0, 0.0, 0.0],
+                [1.0, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [0.0, 0.0, 1.0],
+            ],
+            normals: None,
+            tangents: None,
+            tex_coords: None,
+            indices: Some(vec![0, 1, 2, 1, 0, 3]),
+        }
+    }
+
+    fn assert_close(
+        actual: [f32; 3],
+        expected: [f32; 3],
+    ) {
+        assert!(
+            (Vec3::from(actual) - Vec3::from(expected)).length() < 1e-5,
+            "expected {:?} got {:?}",
+            expected,
+            actual
+        );
+    }
+
+    fn assert_tangents_orthonormal(vertices: &[MeshVertex]) {
+        for vertex in vertices {
+            let normal = Vec3::from(vertex.normal);
+            let tangent = Vec3::new(vertex.tangent[0], vertex.tangent[1], vertex.tangent[2]);
+            assert!((normal.length() - 1.0).abs() < 1e-5);
+            assert!((tangent.length() - 1.0).abs() < 1e-5);
+            assert!(normal.dot(tangent).abs() < 1e-5);
+            assert_eq!(vertex.tangent[3].abs(), 1.0);
+        }
+    }
+
+    // Each triangle of the output as its three vertices
+    fn triangles(primitive: &PrimitiveVertices) -> Vec<[&MeshVertex; 3]> {
+        primitive
+            .indices
+            .chunks_exact(3)
+            .map(|x| {
+                [
+                    &primitive.vertices[x[0] as usize],
+                    &primitive.vertices[x[1] as usize],
+                    &primitive.vertices[x[2] as usize],
+                ]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quad_without_normals_or_indices() {
+        for &normal_generation in &[GltfNormalGeneration::Smooth, GltfNormalGeneration::Flat] {
+            let primitive = build_primitive_vertices(quad(), normal_generation).unwrap();
+
+            // Without indices every corner is a separate source vertex, so nothing is welded
+            assert_eq!(primitive.vertices.len(), 6);
+            assert_eq!(primitive.indices.len(), 6);
+            assert_eq!(primitive.vertex_sources, vec![0, 1, 2, 3, 4, 5]);
+
+            for (vertex, &source) in primitive.vertices.iter().zip(&primitive.vertex_sources) {
+                assert_eq!(vertex.position, quad().positions[source as usize]);
+                assert_close(vertex.normal, [0.0, 0.0, 1.0]);
+            }
+
+            // Winding is preserved
+            for triangle in triangles(&primitive) {
+                let normal = triangle_normal(
+                    triangle[0].position,
+                    triangle[1].position,
+                    triangle[2].position,
+                );
+                assert_close(normal, [0.0, 0.0, 1.0]);
+            }
+
+            assert_tangents_orthonormal(&primitive.vertices);
+        }
+    }
+
+    #[test]
+    fn test_smooth_normals_are_shared() {
+        let primitive =
+            build_primitive_vertices(folded_quad(), GltfNormalGeneration::Smooth).unwrap();
+
+        // The vertices on the fold are shared by both triangles, and their normals are blended
+        assert_eq!(primitive.vertices.len(), 4);
+        let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+        for (vertex, &source) in primitive.vertices.iter().zip(&primitive.vertex_sources) {
+            let expected = match source {
+                0 | 1 => [0.0, diagonal, diagonal],
+                2 => [0.0, 0.0, 1.0],
+                3 => [0.0, 1.0, 0.0],
+                _ => unreachable!(),
+            };
+            assert_close(vertex.normal, expected);
+        }
+
+        assert_tangents_orthonormal(&primitive.vertices);
+    }
+
+    #[test]
+    fn test_flat_normals_are_split() {
+        let primitive =
+            build_primitive_vertices(folded_quad(), GltfNormalGeneration::Flat).unwrap();
+
+        // The vertices on the fold are split so that each triangle gets its face normal
+        assert_eq!(primitive.vertices.len(), 6);
+        let triangles = triangles(&primitive);
+        assert_eq!(triangles.len(), 2);
+        for (triangle, expected) in triangles.iter().zip(&[[0.0, 0.0, 1.0], [0.0, 1.0, 0.0]]) {
+            for vertex in triangle {
+                assert_close(vertex.normal, *expected);
+            }
+        }
+
+        assert_tangents_orthonormal(&primitive.vertices);
+    }
+
+    #[test]
+    fn test_generated_tangents_follow_tex_coords() {
+        let mut attributes = quad();
+        attributes.tex_coords = Some(vec![
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [1.0, 1.0],
+            [0.0, 0.0],
+            [1.0, 1.0],
+            [0.0, 1.0],
+        ]);
+
+        // U increases along +X
+        let primitive = build_primitive_vertices(attributes, GltfNormalGeneration::Smooth).unwrap();
+        for vertex in &primitive.vertices {
+            assert_close(
+                [vertex.tangent[0], vertex.tangent[1], vertex.tangent[2]],
+                [1.0, 0.0, 0.0],
+            );
+        }
+
+        assert_tangents_orthonormal(&primitive.vertices);
+    }
+
+    #[test]
+    fn test_provided_attributes_are_kept() {
+        let attributes = PrimitiveAttributes {
+            positions: vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            normals: Some(vec![[0.0, 0.0, -1.0]; 3]),
+            tangents: Some(vec![[0.0, 1.0, 0.0, -1.0]; 3]),
+            tex_coords: None,
+            indices: Some(vec![2, 1, 0]),
+        };
+        assert!(!attributes.requires_generated_attributes());
+
+        let primitive = build_primitive_vertices(attributes, GltfNormalGeneration::Flat).unwrap();
+        assert_eq!(primitive.vertices.len(), 3);
+        assert_eq!(primitive.indices, vec![2, 1, 0]);
+        for vertex in &primitive.vertices {
+            assert_eq!(vertex.normal, [0.0, 0.0, -1.0]);
+            assert_eq!(vertex.tangent, [0.0, 1.0, 0.0, -1.0]);
+        }
+    }
+
+    #[test]
+    fn test_malformed_primitives_are_rejected() {
+        // Index out of range
+        let mut attributes = folded_quad();
+        attributes.indices = Some(vec![0, 1, 4]);
+        assert!(build_primitive_vertices(attributes, GltfNormalGeneration::Smooth).is_err());
+
+        // Attribute length doesn't match the number of positions
+        let mut attributes = folded_quad();
+        attributes.tex_coords = Some(vec![[0.0, 0.0]; 3]);
+        assert!(build_primitive_vertices(attributes, GltfNormalGeneration::Smooth).is_err());
+
+        // Index count isn't a multiple of 3
+        let mut attributes = folded_quad();
+        attributes.indices = Some(vec![0, 1, 2, 1]);
+        assert!(build_primitive_vertices(attributes, GltfNormalGeneration::Flat).is_err());
+
+        // Vertex count isn't a multiple of 3 and there are no indices
+        let mut attributes = quad();
+        attributes.positions.pop();
+        assert!(build_primitive_vertices(attributes, GltfNormalGeneration::Flat).is_err());
+    }
+}
@@ -1,8 +1,16 @@
+mod animation;
+pub use animation::*;
+
 mod assets;
 pub use assets::*;
 
 mod importer;
 pub use importer::*;
 
+mod mesh_generation;
+
 mod plugin;
 pub use plugin::*;
+
+mod prefab;
+pub use prefab::*;
@@ -1,4 +1,4 @@
-use crate::assets::gltf::MeshAssetType;
+use crate::assets::gltf::{AnimationClipAssetType, MeshAssetType, PrefabAssetType, SkinAssetType};
 use rafx::assets::distill_impl::AssetResource;
 use rafx::assets::AssetManager;
 use rafx::distill::daemon::AssetDaemon;
@@ -22,6 +22,9 @@ impl RendererAssetPlugin for GltfAssetTypeRendererPlugin {
         asset_manager: &mut AssetManager,
         asset_resource: &mut AssetResource,
     ) {
+        asset_manager.register_asset_type::<SkinAssetType>(asset_resource);
+        asset_manager.register_asset_type::<AnimationClipAssetType>(asset_resource);
         asset_manager.register_asset_type::<MeshAssetType>(asset_resource);
+        asset_manager.register_asset_type::<PrefabAssetType>(asset_resource);
     }
 }
@@ -0,0 +1,140 @@
+use super::MeshAsset;
+use distill::loader::handle::Handle;
+use glam::{Mat4, Quat, Vec3};
+use rafx::api::RafxResult;
+use rafx::assets::{AssetManager, DefaultAssetTypeHandler, DefaultAssetTypeLoadHandler};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use type_uuid::*;
+
+// Light types from KHR_lights_punctual. Spot and directional lights shine down the node's local -Z
+// axis.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, PartialEq)]
+pub enum PrefabLightKind {
+    Directional,
+    Point,
+    // Angles are in radians, measured from the center of the cone
+    Spot {
+        inner_cone_angle: f32,
+        outer_cone_angle: f32,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrefabLightAssetData {
+    pub name: Option<String>,
+    pub kind: PrefabLightKind,
+    pub color: [f32; 3],
+    // Candela for point and spot lights, lux for directional lights
+    pub intensity: f32,
+    // None means the light has no range limit
+    pub range: Option<f32>,
+}
+
+// Cameras look down the node's local -Z axis with +Y up
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum PrefabCameraProjection {
+    Perspective {
+        y_fov: f32,
+        // None means the aspect ratio of the viewport should be used
+        aspect_ratio: Option<f32>,
+        z_near: f32,
+        // None means an infinite far plane
+        z_far: Option<f32>,
+    },
+    Orthographic {
+        x_mag: f32,
+        y_mag: f32,
+        z_near: f32,
+        z_far: f32,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrefabCameraAssetData {
+    pub name: Option<String>,
+    pub projection: PrefabCameraProjection,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PrefabNodeAssetData {
+    pub name: Option<String>,
+    // Index into PrefabAssetData::nodes
+    pub parent: Option<usize>,
+    pub translation: [f32; 3],
+    // Quaternion, xyzw
+    pub rotation: [f32; 4],
+    pub scale: [f32; 3],
+    pub mesh: Option<Handle<MeshAsset>>,
+    // Overrides the default morph target weights of the mesh if not empty
+    pub morph_target_weights: Vec<f32>,
+    pub light: Option<PrefabLightAssetData>,
+    pub camera: Option<PrefabCameraAssetData>,
+}
+
+impl PrefabNodeAssetData {
+    pub fn local_transform(&self) -> Mat4 {
+        Mat4::from_scale_rotation_translation(
+            Vec3::from(self.scale),
+            Quat::from_xyzw(
+                self.rotation[0],
+                self.rotation[1],
+                self.rotation[2],
+                self.rotation[3],
+            ),
+            Vec3::from(self.translation),
+        )
+    }
+}
+
+// One of these is imported for each scene in a glTF file
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "31de2fed-117b-4d09-ae30-901e751b4e66"]
+pub struct PrefabAssetData {
+    pub name: Option<String>,
+    // Parents always come before their children
+    pub nodes: Vec<PrefabNodeAssetData>,
+}
+
+#[derive(TypeUuid, Clone)]
+#[uuid = "8204e031-348c-431c-b03c-c6b42ce4c073"]
+pub struct PrefabAsset {
+    pub inner: Arc<PrefabAssetData>,
+}
+
+impl PrefabAsset {
+    // Returns the transform of every node relative to the given root transform
+    pub fn world_transforms(
+        &self,
+        root_transform: Mat4,
+    ) -> Vec<Mat4> {
+        let nodes = &self.inner.nodes;
+        let mut world_transforms = Vec::with_capacity(nodes.len());
+        for node in nodes {
+            let parent_transform = node
+                .parent
+                .map(|parent| world_transforms[parent])
+                .unwrap_or(root_transform);
+
+            world_transforms.push(parent_transform * node.local_transform());
+        }
+
+        world_transforms
+    }
+}
+
+pub struct PrefabLoadHandler;
+
+impl DefaultAssetTypeLoadHandler<PrefabAssetData, PrefabAsset> for PrefabLoadHandler {
+    #[profiling::function]
+    fn load(
+        _asset_manager: &mut AssetManager,
+        prefab_asset: PrefabAssetData,
+    ) -> RafxResult<PrefabAsset> {
+        Ok(PrefabAsset {
+            inner: Arc::new(prefab_asset),
+        })
+    }
+}
+
+pub type PrefabAssetType = DefaultAssetTypeHandler<PrefabAssetData, PrefabAsset, PrefabLoadHandler>;
@@ -160,8 +160,10 @@ impl Importer for LdtkImporter {
                 slot_name: "tilemap_texture".to_string(),
                 array_index: 0,
                 image: Some(image_handle.clone()),
+                render_target: None,
                 sampler: None,
                 buffer_data: None,
+                buffer_members: Default::default(),
             });
 
             let material_instance = MaterialInstanceAssetData {
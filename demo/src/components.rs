@@ -1,6 +1,11 @@
+use crate::assets::gltf::{AnimationClipAsset, PrefabCameraProjection};
+use distill::loader::handle::Handle;
 use glam::f32::Vec3;
 use glam::Quat;
 use rafx::framework::visibility::VisibilityObjectArc;
+use rafx::rafx_visibility::{
+    DepthRange, OrthographicParameters, PerspectiveParameters, Projection,
+};
 use rafx::render_features::RenderObjectHandle;
 use rafx::visibility::ViewFrustumArc;
 
@@ -9,6 +14,40 @@ pub struct MeshComponent {
     pub render_object_handle: RenderObjectHandle,
 }
 
+// Plays an animation clip on the skin of the entity's mesh. Skinned meshes without this component
+// are drawn in their rest pose.
+#[derive(Clone)]
+pub struct AnimationComponent {
+    pub clip: Handle<AnimationClipAsset>,
+    // TimeState::total_time() when the clip started playing, in seconds
+    pub start_time: f32,
+    pub speed: f32,
+    pub looping: bool,
+}
+
+// Overrides the default weights of the morph targets of the entity's mesh. Missing weights are
+// treated as 0.
+#[derive(Clone)]
+pub struct MorphTargetWeightsComponent {
+    pub weights: Vec<f32>,
+}
+
+impl AnimationComponent {
+    // Returns the time within the clip
+    pub fn clip_time(
+        &self,
+        total_time: f32,
+        duration: f32,
+    ) -> f32 {
+        let time = (total_time - self.start_time) * self.speed;
+        if self.looping && duration > 0.0 {
+            time.rem_euclid(duration)
+        } else {
+            time.max(0.0).min(duration)
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SpriteComponent {
     pub render_object_handle: RenderObjectHandle,
@@ -61,3 +100,58 @@ pub struct SpotLightComponent {
     pub intensity: f32,
     pub view_frustum: ViewFrustumArc,
 }
+
+// Far plane used for the view frustum of perspective cameras that have no far plane. The projection
+// matrix still uses an infinite far plane.
+const CAMERA_INFINITE_FAR_DISTANCE: f32 = 10000.0;
+
+// A camera imported from a prefab. The entity's TransformComponent places the camera, which looks
+// down its local -Z axis with +Y up.
+#[derive(Clone)]
+pub struct CameraComponent {
+    pub projection: PrefabCameraProjection,
+}
+
+impl CameraComponent {
+    // viewport_aspect_ratio is used if the camera does not specify an aspect ratio
+    pub fn projection(
+        &self,
+        viewport_aspect_ratio: f32,
+    ) -> Projection {
+        match self.projection {
+            PrefabCameraProjection::Perspective {
+                y_fov,
+                aspect_ratio,
+                z_near,
+                z_far,
+            } => {
+                let (far_distance, depth_range) = match z_far {
+                    Some(z_far) => (z_far, DepthRange::Reverse),
+                    None => (CAMERA_INFINITE_FAR_DISTANCE, DepthRange::InfiniteReverse),
+                };
+
+                Projection::Perspective(PerspectiveParameters::new(
+                    y_fov,
+                    aspect_ratio.unwrap_or(viewport_aspect_ratio),
+                    z_near,
+                    far_distance,
+                    depth_range,
+                ))
+            }
+            PrefabCameraProjection::Orthographic {
+                x_mag,
+                y_mag,
+                z_near,
+                z_far,
+            } => Projection::Orthographic(OrthographicParameters::new(
+                -x_mag,
+                x_mag,
+                -y_mag,
+                y_mag,
+                z_near,
+                z_far,
+                DepthRange::Reverse,
+            )),
+        }
+    }
+}
@@ -3,6 +3,7 @@ use crate::features::sprite::SpriteRenderFeature;
 use bevy_tasks::prelude::*;
 use bevy_tasks::{TaskPool, TaskPoolBuilder};
 use crossbeam_channel::{bounded, unbounded};
+use rafx::api::RafxResult;
 use rafx::framework::render_features::render_features_prelude::*;
 use rafx::render_feature_renderer_prelude::RenderFeaturePlugin;
 use rafx::renderer::{RenderFrameJob, Renderer, RendererThreadPool};
@@ -344,11 +345,37 @@ impl RendererThreadPool for DemoRendererThreadPool {
             .collect()
     }
 
+    fn write_thread_pool(&self) -> Option<&dyn RenderJobWriteThreadPool> {
+        Some(self)
+    }
+
     fn clone_to_box(&mut self) -> Box<dyn RendererThreadPool> {
         Box::new(self.clone())
     }
 }
 
+impl RenderJobWriteThreadPool for DemoRendererThreadPool {
+    fn submit_node_chunk_size(&self) -> usize {
+        // Each chunk is written into its own secondary command buffer, so keep them large enough
+        // that the overhead of beginning/executing them is small compared to the draws
+        512
+    }
+
+    fn run_write_chunks(
+        &self,
+        num_chunks: usize,
+        write_chunk: &(dyn Fn(usize) -> RafxResult<()> + Sync),
+    ) -> RafxResult<()> {
+        let results = self.task_pool.scope(|scope| {
+            for chunk_index in 0..num_chunks {
+                scope.spawn(async move { write_chunk(chunk_index) });
+            }
+        });
+
+        results.into_iter().collect()
+    }
+}
+
 #[derive(Default)]
 struct ParallelChunkSizes {
     extract_chunk_size: Option<usize>,
@@ -3,10 +3,11 @@ use crate::assets::gltf::MeshAsset;
 use crate::components::{
     DirectionalLightComponent, PointLightComponent, SpotLightComponent, TransformComponent,
 };
-use glam::{Quat, Vec3};
+use glam::{Mat4, Quat, Vec3};
 use rafx::framework::render_features::render_features_prelude::*;
 use rafx::framework::{
-    BufferResource, DescriptorSetArc, ImageViewResource, MaterialPassResource, ResourceArc,
+    BufferResource, ComputePipelineResource, DescriptorSetArc, ImageViewResource,
+    MaterialPassResource, ResourceArc,
 };
 
 pub struct MeshRenderFeatureTypes;
@@ -19,6 +20,7 @@ pub type MeshRenderObjectStaticData = MeshRenderObject;
 
 pub struct MeshPerFrameData {
     pub depth_material_pass: Option<ResourceArc<MaterialPassResource>>,
+    pub deformation_pipeline: ResourceArc<ComputePipelineResource>,
 }
 
 pub struct MeshRenderObjectInstanceData {
@@ -26,6 +28,10 @@ pub struct MeshRenderObjectInstanceData {
     pub translation: Vec3,
     pub rotation: Quat,
     pub scale: Vec3,
+    // Set if the mesh is skinned
+    pub joint_matrices: Option<Vec<Mat4>>,
+    // Set if the entity overrides the mesh's default morph target weights
+    pub morph_target_weights: Option<Vec<f32>>,
 }
 
 #[derive(Default)]
@@ -81,19 +87,29 @@ pub struct MeshPartMaterialDescriptorSetPair {
 
 pub struct MeshPerFrameSubmitData {
     pub num_shadow_map_2d: usize,
-    pub shadow_map_2d_data:
-        [shaders::mesh_textured_frag::ShadowMap2DDataStd140; MAX_SHADOW_MAPS_2D],
+    pub shadow_map_2d_data: [shaders::mesh_frag::ShadowMap2DDataStd140; MAX_SHADOW_MAPS_2D],
     pub shadow_map_2d_image_views: [Option<ResourceArc<ImageViewResource>>; MAX_SHADOW_MAPS_2D],
     pub num_shadow_map_cube: usize,
-    pub shadow_map_cube_data:
-        [shaders::mesh_textured_frag::ShadowMapCubeDataStd140; MAX_SHADOW_MAPS_CUBE],
+    pub shadow_map_cube_data: [shaders::mesh_frag::ShadowMapCubeDataStd140; MAX_SHADOW_MAPS_CUBE],
     pub shadow_map_cube_image_views: [Option<ResourceArc<ImageViewResource>>; MAX_SHADOW_MAPS_CUBE],
     pub shadow_map_image_index_remap: [Option<usize>; MAX_SHADOW_MAPS_2D + MAX_SHADOW_MAPS_CUBE],
     pub model_matrix_buffer: TrustCell<Option<ResourceArc<BufferResource>>>,
+    pub deformation_dispatches: TrustCell<Vec<MeshDeformationDispatch>>,
+}
+
+// Deforms one render object instance's vertices with the mesh deformation compute shader before
+// any render phase is drawn
+#[derive(Clone)]
+pub struct MeshDeformationDispatch {
+    pub descriptor_set: DescriptorSetArc,
+    pub deformed_vertex_buffer: ResourceArc<BufferResource>,
+    pub vertex_count: u32,
 }
 
 pub struct MeshRenderObjectInstanceSubmitData {
     pub model_matrix_offset: usize,
+    // Replaces the mesh's vertex buffer when the mesh is skinned or has morph targets
+    pub deformed_vertex_buffer: Option<ResourceArc<BufferResource>>,
 }
 
 impl SubmitPacketData for MeshRenderFeatureTypes {
@@ -124,4 +140,5 @@ pub struct MeshDrawCall {
     pub per_material_descriptor_set: Option<DescriptorSetArc>,
     pub mesh_part_index: usize,
     pub model_matrix_offset: usize,
+    pub deformed_vertex_buffer: Option<ResourceArc<BufferResource>>,
 }
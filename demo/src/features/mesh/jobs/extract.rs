@@ -1,19 +1,25 @@
 use rafx::render_feature_extract_job_predule::*;
 
 use super::*;
+use crate::assets::gltf::MeshAsset;
 use crate::components::{
-    DirectionalLightComponent, PointLightComponent, SpotLightComponent, TransformComponent,
+    AnimationComponent, DirectionalLightComponent, MorphTargetWeightsComponent,
+    PointLightComponent, SpotLightComponent, TransformComponent,
 };
+use crate::time::TimeState;
+use glam::Mat4;
 use legion::{Entity, EntityStore, IntoQuery, Read, World};
-use rafx::assets::{AssetManagerRenderResource, MaterialAsset};
+use rafx::assets::{AssetManagerRenderResource, ComputePipelineAsset, MaterialAsset};
 use rafx::base::resource_map::ReadBorrow;
 use rafx::base::resource_ref_map::ResourceRefBorrow;
 use rafx::distill::loader::handle::Handle;
 
 pub struct MeshExtractJob<'extract> {
     world: ResourceRefBorrow<'extract, World>,
+    time_state: ResourceRefBorrow<'extract, TimeState>,
     asset_manager: ReadBorrow<'extract, AssetManagerRenderResource>,
     depth_material: Handle<MaterialAsset>,
+    deformation_pipeline: Handle<ComputePipelineAsset>,
     render_objects: MeshRenderObjectSet,
 }
 
@@ -22,20 +28,54 @@ impl<'extract> MeshExtractJob<'extract> {
         extract_context: &RenderJobExtractContext<'extract>,
         frame_packet: Box<MeshFramePacket>,
         depth_material: Handle<MaterialAsset>,
+        deformation_pipeline: Handle<ComputePipelineAsset>,
         render_objects: MeshRenderObjectSet,
     ) -> Arc<dyn RenderFeatureExtractJob<'extract> + 'extract> {
         Arc::new(ExtractJob::new(
             Self {
                 world: extract_context.extract_resources.fetch::<World>(),
+                time_state: extract_context.extract_resources.fetch::<TimeState>(),
                 asset_manager: extract_context
                     .render_resources
                     .fetch::<AssetManagerRenderResource>(),
                 depth_material,
+                deformation_pipeline,
                 render_objects,
             },
             frame_packet,
         ))
     }
+
+    // Poses the mesh's skin using the entity's animation, if it has one
+    fn extract_joint_matrices(
+        &self,
+        mesh_asset: &MeshAsset,
+        animation_component: Option<&AnimationComponent>,
+    ) -> Option<Vec<Mat4>> {
+        let skin_data = mesh_asset
+            .inner
+            .asset_data
+            .deformation
+            .as_ref()?
+            .skin
+            .as_ref()?;
+        let skin = self.asset_manager.committed_asset(&skin_data.skin)?;
+
+        let clip = animation_component.and_then(|animation_component| {
+            self.asset_manager
+                .committed_asset(&animation_component.clip)
+                .map(|clip| (animation_component, clip))
+        });
+
+        Some(match clip {
+            Some((animation_component, clip)) => {
+                let total_time = self.time_state.total_time().as_secs_f32();
+                let time = animation_component.clip_time(total_time, clip.inner.duration);
+                skin.joint_matrices(Some(clip), time)
+            }
+            None => skin.joint_matrices(None, 0.0),
+        })
+    }
 }
 
 impl<'extract> ExtractJobEntryPoints<'extract> for MeshExtractJob<'extract> {
@@ -53,6 +93,12 @@ impl<'extract> ExtractJobEntryPoints<'extract> for MeshExtractJob<'extract> {
                     .unwrap()
                     .get_single_material_pass()
                     .ok(),
+                deformation_pipeline: self
+                    .asset_manager
+                    .committed_asset(&self.deformation_pipeline)
+                    .unwrap()
+                    .compute_pipeline
+                    .clone(),
             });
     }
 
@@ -72,11 +118,16 @@ impl<'extract> ExtractJobEntryPoints<'extract> for MeshExtractJob<'extract> {
         context.set_render_object_instance_data(mesh_asset.and_then(|mesh_asset| {
             let entry = self.world.entry_ref(context.object_id().into()).unwrap();
             let transform_component = entry.get_component::<TransformComponent>().unwrap();
+            let animation_component = entry.get_component::<AnimationComponent>().ok();
+            let morph_target_weights_component =
+                entry.get_component::<MorphTargetWeightsComponent>().ok();
             Some(MeshRenderObjectInstanceData {
                 mesh_asset: mesh_asset.clone(),
                 translation: transform_component.translation,
                 rotation: transform_component.rotation,
                 scale: transform_component.scale,
+                joint_matrices: self.extract_joint_matrices(mesh_asset, animation_component),
+                morph_target_weights: morph_target_weights_component.map(|x| x.weights.clone()),
             })
         }));
     }
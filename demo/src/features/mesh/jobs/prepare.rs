@@ -1,6 +1,7 @@
 use rafx::render_feature_prepare_job_predule::*;
 
 use super::*;
+use crate::assets::gltf::{MeshDeformationAssetData, MeshDeformationBuffers};
 use crate::components::{
     DirectionalLightComponent, PointLightComponent, SpotLightComponent, TransformComponent,
 };
@@ -8,18 +9,24 @@ use crate::phases::{
     DepthPrepassRenderPhase, OpaqueRenderPhase, ShadowMapRenderPhase, WireframeRenderPhase,
 };
 use rafx::base::resource_map::ReadBorrow;
-use rafx::framework::{MaterialPassResource, ResourceArc, ResourceContext};
+use rafx::framework::{
+    BufferResource, ComputePipelineResource, DescriptorSetBindings, DynResourceAllocatorSet,
+    MaterialPassResource, ResourceArc, ResourceContext,
+};
 
 use glam::Mat4;
 use rafx::api::{RafxBufferDef, RafxDeviceContext, RafxMemoryUsage, RafxResourceType};
 use rafx::renderer::InvalidResources;
 use shaders::depth_vert::PerViewDataUniform as ShadowPerViewShaderParam;
-use shaders::mesh_textured_frag::PerViewDataUniform as MeshPerViewFragmentShaderParam;
+use shaders::mesh_deformation_comp;
+use shaders::mesh_frag::PerViewDataUniform as MeshPerViewFragmentShaderParam;
 
 const PER_VIEW_DESCRIPTOR_SET_INDEX: u32 =
-    shaders::mesh_textured_frag::PER_VIEW_DATA_DESCRIPTOR_SET_INDEX as u32;
+    shaders::mesh_frag::PER_VIEW_DATA_DESCRIPTOR_SET_INDEX as u32;
 const PER_MATERIAL_DESCRIPTOR_SET_INDEX: u32 =
-    shaders::mesh_textured_frag::PER_MATERIAL_DATA_DESCRIPTOR_SET_INDEX as u32;
+    shaders::mesh_frag::PER_MATERIAL_DATA_DESCRIPTOR_SET_INDEX as u32;
+const DEFORMATION_DESCRIPTOR_SET_INDEX: usize =
+    mesh_deformation_comp::DEFORMATION_ARGS_DESCRIPTOR_SET_INDEX;
 
 struct PreparedDirectionalLight<'a> {
     light: &'a DirectionalLightComponent,
@@ -47,6 +54,8 @@ pub struct MeshPrepareJob<'prepare> {
     shadow_map_data: ReadBorrow<'prepare, ShadowMapResource>,
     invalid_resources: ReadBorrow<'prepare, InvalidResources>,
     render_object_instance_transforms: Arc<AtomicOnceCellStack<[[f32; 4]; 4]>>,
+    deformation_pipeline: ResourceArc<ComputePipelineResource>,
+    deformation_dispatches: Arc<AtomicOnceCellStack<MeshDeformationDispatch>>,
     render_objects: MeshRenderObjectSet,
 }
 
@@ -81,6 +90,11 @@ impl<'prepare> MeshPrepareJob<'prepare> {
                         frame_packet.render_object_instances().len(),
                     ))
                 },
+                deformation_dispatches: {
+                    Arc::new(AtomicOnceCellStack::with_capacity(
+                        frame_packet.render_object_instances().len(),
+                    ))
+                },
                 depth_material_pass: {
                     frame_packet
                         .per_frame_data()
@@ -88,6 +102,13 @@ impl<'prepare> MeshPrepareJob<'prepare> {
                         .depth_material_pass
                         .clone()
                 },
+                deformation_pipeline: {
+                    frame_packet
+                        .per_frame_data()
+                        .get()
+                        .deformation_pipeline
+                        .clone()
+                },
                 shadow_map_data: {
                     prepare_context
                         .render_resources
@@ -102,6 +123,153 @@ impl<'prepare> MeshPrepareJob<'prepare> {
             submit_packet,
         ))
     }
+
+    // Creates the buffer that the render object instance's deformed vertices will be written to
+    // and queues the compute dispatch that writes them
+    fn prepare_deformation(
+        &self,
+        deformation: &MeshDeformationAssetData,
+        deformation_buffers: &MeshDeformationBuffers,
+        extracted_data: &MeshRenderObjectInstanceData,
+    ) -> ResourceArc<BufferResource> {
+        let dyn_resource_allocator_set = self.resource_context.create_dyn_resource_allocator_set();
+
+        // Entities that don't set weights use the mesh's default weights
+        let morph_target_weights = extracted_data
+            .morph_target_weights
+            .as_ref()
+            .unwrap_or(&deformation.default_morph_target_weights);
+
+        let source_vertices = &deformation_buffers.bind_pose_vertices;
+
+        let morph_targets = match &deformation_buffers.morph_target_deltas {
+            Some(morph_target_deltas)
+                if morph_target_weights.iter().any(|weight| *weight != 0.0) =>
+            {
+                // Missing weights are treated as 0
+                let mut morph_target_weights = morph_target_weights.clone();
+                morph_target_weights.resize(deformation_buffers.morph_target_count as usize, 0.0);
+
+                Some((
+                    morph_target_deltas,
+                    self.create_storage_buffer(&dyn_resource_allocator_set, &morph_target_weights),
+                ))
+            }
+            _ => None,
+        };
+
+        let joint_matrices = match (
+            &deformation_buffers.joint_weights,
+            &extracted_data.joint_matrices,
+        ) {
+            (Some(joint_weights), Some(joint_matrices)) => Some((
+                joint_weights,
+                self.create_storage_buffer(&dyn_resource_allocator_set, joint_matrices),
+            )),
+            _ => None,
+        };
+
+        let deformed_vertex_buffer = dyn_resource_allocator_set.insert_buffer(
+            self.device_context
+                .create_buffer(&RafxBufferDef {
+                    size: deformation_buffers.vertex_count as u64
+                        * std::mem::size_of::<MeshVertex>() as u64,
+                    memory_usage: RafxMemoryUsage::GpuOnly,
+                    resource_type: RafxResourceType::VERTEX_BUFFER
+                        | RafxResourceType::BUFFER_READ_WRITE,
+                    ..Default::default()
+                })
+                .unwrap(),
+        );
+
+        let mut descriptor_set_allocator = self.resource_context.create_descriptor_set_allocator();
+        let mut descriptor_set = descriptor_set_allocator
+            .create_dyn_descriptor_set_uninitialized(
+                &self.deformation_pipeline.get_raw().descriptor_set_layouts
+                    [DEFORMATION_DESCRIPTOR_SET_INDEX],
+            )
+            .unwrap();
+
+        descriptor_set.set_buffer_data(
+            mesh_deformation_comp::DEFORMATION_ARGS_DESCRIPTOR_BINDING_INDEX as u32,
+            &mesh_deformation_comp::DeformationArgsUniform {
+                vertex_count: deformation_buffers.vertex_count,
+                is_skinned: joint_matrices.is_some() as u32,
+                morph_target_count: if morph_targets.is_some() {
+                    deformation_buffers.morph_target_count
+                } else {
+                    0
+                },
+                ..Default::default()
+            },
+        );
+        descriptor_set.set_buffer(
+            mesh_deformation_comp::SOURCE_VERTICES_DESCRIPTOR_BINDING_INDEX as u32,
+            source_vertices,
+        );
+
+        // Every binding must be written even though the shader only reads the joint and morph
+        // target buffers when they are used, so the source vertices stand in for them
+        let (joint_weights, joint_matrices) = match &joint_matrices {
+            Some((joint_weights, joint_matrices)) => (*joint_weights, joint_matrices),
+            None => (source_vertices, source_vertices),
+        };
+        descriptor_set.set_buffer(
+            mesh_deformation_comp::JOINT_WEIGHTS_DESCRIPTOR_BINDING_INDEX as u32,
+            joint_weights,
+        );
+        descriptor_set.set_buffer(
+            mesh_deformation_comp::JOINT_MATRICES_DESCRIPTOR_BINDING_INDEX as u32,
+            joint_matrices,
+        );
+        descriptor_set.set_buffer(
+            mesh_deformation_comp::DEFORMED_VERTICES_DESCRIPTOR_BINDING_INDEX as u32,
+            &deformed_vertex_buffer,
+        );
+
+        let (morph_target_deltas, morph_target_weights) = match &morph_targets {
+            Some((morph_target_deltas, morph_target_weights)) => {
+                (*morph_target_deltas, morph_target_weights)
+            }
+            None => (source_vertices, source_vertices),
+        };
+        descriptor_set.set_buffer(
+            mesh_deformation_comp::MORPH_TARGET_DELTAS_DESCRIPTOR_BINDING_INDEX as u32,
+            morph_target_deltas,
+        );
+        descriptor_set.set_buffer(
+            mesh_deformation_comp::MORPH_TARGET_WEIGHTS_DESCRIPTOR_BINDING_INDEX as u32,
+            morph_target_weights,
+        );
+        descriptor_set.flush(&mut descriptor_set_allocator).unwrap();
+
+        self.deformation_dispatches.push(MeshDeformationDispatch {
+            descriptor_set: descriptor_set.descriptor_set().clone(),
+            deformed_vertex_buffer: deformed_vertex_buffer.clone(),
+            vertex_count: deformation_buffers.vertex_count,
+        });
+
+        deformed_vertex_buffer
+    }
+
+    fn create_storage_buffer<T: Copy>(
+        &self,
+        dyn_resource_allocator_set: &DynResourceAllocatorSet,
+        data: &[T],
+    ) -> ResourceArc<BufferResource> {
+        let buffer = self
+            .device_context
+            .create_buffer(&RafxBufferDef {
+                size: std::mem::size_of_val(data) as u64,
+                memory_usage: RafxMemoryUsage::CpuToGpu,
+                resource_type: RafxResourceType::BUFFER,
+                ..Default::default()
+            })
+            .unwrap();
+
+        buffer.copy_to_host_visible_buffer(data).unwrap();
+        dyn_resource_allocator_set.insert_buffer(buffer)
+    }
 }
 
 impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
@@ -118,6 +286,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
             shadow_map_cube_image_views: Default::default(),
             shadow_map_image_index_remap: [None; MAX_SHADOW_MAPS_CUBE + MAX_SHADOW_MAPS_2D],
             model_matrix_buffer: Default::default(),
+            deformation_dispatches: Default::default(),
         });
 
         let shadow_map_data = &self.shadow_map_data;
@@ -151,7 +320,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
                         }
 
                         per_frame_submit_data.shadow_map_2d_data[num_shadow_map_2d] =
-                            shaders::mesh_textured_frag::ShadowMap2DDataStd140 {
+                            shaders::mesh_frag::ShadowMap2DDataStd140 {
                                 shadow_map_view_proj: shadow_view.view_proj().to_cols_array_2d(),
                                 shadow_map_light_dir: shadow_view.view_dir().into(),
                                 ..Default::default()
@@ -178,7 +347,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
                             .unwrap();
 
                         per_frame_submit_data.shadow_map_cube_data[num_shadow_map_cube] =
-                            shaders::mesh_textured_frag::ShadowMapCubeDataStd140 {
+                            shaders::mesh_frag::ShadowMapCubeDataStd140 {
                                 cube_map_projection_near_z: near,
                                 cube_map_projection_far_z: far,
                                 ..Default::default()
@@ -230,8 +399,25 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
         let model = world_transform.to_cols_array_2d();
         let model_matrix_offset = self.render_object_instance_transforms.push(model);
 
+        let deformation = extracted_data
+            .mesh_asset
+            .inner
+            .asset_data
+            .deformation
+            .as_ref();
+        let deformed_vertex_buffer = match (
+            deformation,
+            &extracted_data.mesh_asset.inner.deformation_buffers,
+        ) {
+            (Some(deformation), Some(deformation_buffers)) => {
+                Some(self.prepare_deformation(deformation, deformation_buffers, extracted_data))
+            }
+            _ => None,
+        };
+
         context.set_render_object_instance_submit_data(MeshRenderObjectInstanceSubmitData {
             model_matrix_offset,
+            deformed_vertex_buffer,
         });
     }
 
@@ -246,9 +432,9 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
             let distance = (view.eye_position() - extracted_data.translation).length_squared();
             let render_object_instance_id = context.render_object_instance_id();
 
-            let model_matrix_offset = context
-                .render_object_instance_submit_data()
-                .model_matrix_offset;
+            let render_object_instance_submit_data = context.render_object_instance_submit_data();
+            let model_matrix_offset = render_object_instance_submit_data.model_matrix_offset;
+            let deformed_vertex_buffer = &render_object_instance_submit_data.deformed_vertex_buffer;
 
             for (mesh_part_index, mesh_part) in extracted_data
                 .mesh_asset
@@ -273,6 +459,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
                             per_material_descriptor_set: None,
                             mesh_part_index,
                             model_matrix_offset,
+                            deformed_vertex_buffer: deformed_vertex_buffer.clone(),
                         },
                         0,
                         distance,
@@ -287,6 +474,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
                             per_material_descriptor_set: None,
                             mesh_part_index,
                             model_matrix_offset,
+                            deformed_vertex_buffer: deformed_vertex_buffer.clone(),
                         },
                         0,
                         distance,
@@ -314,6 +502,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
                             per_material_descriptor_set,
                             mesh_part_index,
                             model_matrix_offset,
+                            deformed_vertex_buffer: deformed_vertex_buffer.clone(),
                         },
                         0,
                         distance,
@@ -346,6 +535,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
                             per_material_descriptor_set,
                             mesh_part_index,
                             model_matrix_offset,
+                            deformed_vertex_buffer: deformed_vertex_buffer.clone(),
                         },
                         0,
                         distance,
@@ -563,7 +753,7 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
                     descriptor_set_allocator
                         .create_descriptor_set(
                             &per_view_descriptor_set_layout,
-                            shaders::mesh_textured_frag::DescriptorSet0Args {
+                            shaders::mesh_frag::DescriptorSet0Args {
                                 shadow_map_images,
                                 shadow_map_images_cube,
                                 per_view_data: &per_view_frag_data,
@@ -612,6 +802,11 @@ impl<'prepare> PrepareJobEntryPoints<'prepare> for MeshPrepareJob<'prepare> {
         &self,
         context: &PreparePerFrameContext<'prepare, '_, Self>,
     ) {
+        *context
+            .per_frame_submit_data()
+            .deformation_dispatches
+            .borrow_mut() = self.deformation_dispatches.iter().cloned().collect();
+
         let mut model_matrix_buffer = context
             .per_frame_submit_data()
             .model_matrix_buffer
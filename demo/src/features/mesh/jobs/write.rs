@@ -3,9 +3,8 @@ use rafx::render_feature_write_job_prelude::*;
 use super::*;
 use crate::phases::{DepthPrepassRenderPhase, ShadowMapRenderPhase, WireframeRenderPhase};
 use rafx::api::RafxPrimitiveTopology;
-use rafx::api::{
-    RafxIndexBufferBinding, RafxIndexType, RafxVertexAttributeRate, RafxVertexBufferBinding,
-};
+use rafx::api::{RafxBarrierQueueTransition, RafxBufferBarrier, RafxResourceState};
+use rafx::api::{RafxIndexBufferBinding, RafxVertexAttributeRate, RafxVertexBufferBinding};
 use rafx::framework::{MaterialPassResource, ResourceArc};
 use rafx::framework::{VertexDataLayout, VertexDataSetLayout};
 use serde::{Deserialize, Serialize};
@@ -51,6 +50,9 @@ lazy_static::lazy_static! {
     };
 }
 
+// Must match local_size_x in mesh_deformation.comp
+const DEFORMATION_WORKGROUP_SIZE: u32 = 64;
+
 pub struct MeshWriteJob<'write> {
     depth_material_pass: Option<ResourceArc<MaterialPassResource>>,
     depth_prepass_index: RenderPhaseIndex,
@@ -90,6 +92,71 @@ impl<'write> MeshWriteJob<'write> {
 }
 
 impl<'write> RenderFeatureWriteJob<'write> for MeshWriteJob<'write> {
+    fn on_begin_execute_graph(
+        &self,
+        begin_execute_graph_context: &mut RenderJobBeginExecuteGraphContext,
+    ) -> RafxResult<()> {
+        profiling::scope!(super::render_feature_debug_constants().on_begin_execute_graph);
+
+        // This is only called for the first graph executed with this frame's render data (see
+        // PreparedRenderGraph::execute_graph), so the deformation compute work is dispatched once
+        // per frame even if several graphs draw the meshes. The deformed vertex buffers are
+        // allocated for this frame, so nothing reads them before the dispatches.
+        let deformation_dispatches = self
+            .submit_packet
+            .per_frame_submit_data()
+            .get()
+            .deformation_dispatches
+            .borrow();
+
+        if deformation_dispatches.is_empty() {
+            return Ok(());
+        }
+
+        let command_buffer = &begin_execute_graph_context.command_buffer;
+        let deformation_pipeline = &self
+            .frame_packet
+            .per_frame_data()
+            .get()
+            .deformation_pipeline;
+        command_buffer.cmd_bind_pipeline(&deformation_pipeline.get_raw().pipeline)?;
+
+        for deformation_dispatch in deformation_dispatches.iter() {
+            deformation_dispatch.descriptor_set.bind(command_buffer)?;
+            command_buffer.cmd_dispatch(
+                deformation_dispatch
+                    .vertex_count
+                    .div_ceil(DEFORMATION_WORKGROUP_SIZE),
+                1,
+                1,
+            )?;
+        }
+
+        // The deformed vertices are drawn in every render phase of every graph this frame. Make the
+        // compute shader writes visible to vertex input before any of them run.
+        let buffers: Vec<_> = deformation_dispatches
+            .iter()
+            .map(|deformation_dispatch| {
+                deformation_dispatch
+                    .deformed_vertex_buffer
+                    .get_raw()
+                    .buffer
+                    .clone()
+            })
+            .collect();
+        let buffer_barriers: Vec<_> = buffers
+            .iter()
+            .map(|buffer| RafxBufferBarrier {
+                buffer: buffer.as_ref(),
+                src_state: RafxResourceState::UNORDERED_ACCESS,
+                dst_state: RafxResourceState::VERTEX_AND_CONSTANT_BUFFER,
+                queue_transition: RafxBarrierQueueTransition::None,
+            })
+            .collect();
+
+        command_buffer.cmd_resource_barrier(&buffer_barriers, &[])
+    }
+
     fn view_frame_index(
         &self,
         view: &RenderView,
@@ -174,12 +241,19 @@ impl<'write> RenderFeatureWriteJob<'write> for MeshWriteJob<'write> {
             per_material_descriptor_set.bind(command_buffer)?;
         }
 
+        // Skinned and morphed meshes are drawn with vertices that were deformed this frame by the
+        // dispatches in on_begin_execute_graph
+        let vertex_buffer = submit_node_data
+            .deformed_vertex_buffer
+            .as_ref()
+            .unwrap_or(&mesh_asset.inner.vertex_buffer);
+
         command_buffer.cmd_bind_vertex_buffers(
             0,
             &[
                 // NOTE(dvd): Bind the mesh vertex data.
                 RafxVertexBufferBinding {
-                    buffer: &mesh_asset.inner.vertex_buffer.get_raw().buffer,
+                    buffer: &vertex_buffer.get_raw().buffer,
                     byte_offset: mesh_part.vertex_buffer_offset_in_bytes as u64,
                 },
                 // NOTE(dvd): Bind the mesh model matrices. We pass these through instanced vertex
@@ -199,11 +273,11 @@ impl<'write> RenderFeatureWriteJob<'write> for MeshWriteJob<'write> {
         command_buffer.cmd_bind_index_buffer(&RafxIndexBufferBinding {
             buffer: &mesh_asset.inner.index_buffer.get_raw().buffer,
             byte_offset: mesh_part.index_buffer_offset_in_bytes as u64,
-            index_type: RafxIndexType::Uint16,
+            index_type: mesh_part.index_type,
         })?;
 
         command_buffer.cmd_draw_indexed(
-            mesh_part.index_buffer_size_in_bytes / 2, //sizeof(u16)
+            mesh_part.index_buffer_size_in_bytes / mesh_part.index_type.size_in_bytes() as u32,
             0,
             0,
         )?;
@@ -6,10 +6,11 @@ use crate::phases::{
     WireframeRenderPhase,
 };
 use distill::loader::handle::Handle;
-use rafx::assets::MaterialAsset;
+use rafx::assets::{ComputePipelineAsset, MaterialAsset};
 
 pub struct MeshStaticResources {
     pub depth_material: Handle<MaterialAsset>,
+    pub deformation_pipeline: Handle<ComputePipelineAsset>,
 }
 
 pub struct MeshRendererPlugin {
@@ -84,9 +85,22 @@ impl RenderFeaturePlugin for MeshRendererPlugin {
         let depth_material =
             asset_resource.load_asset_path::<MaterialAsset, _>("materials/depth.material");
 
+        let deformation_pipeline = asset_resource.load_asset_path::<ComputePipelineAsset, _>(
+            "compute_pipelines/mesh_deformation.compute",
+        );
+
         asset_manager.wait_for_asset_to_load(&depth_material, asset_resource, "depth")?;
 
-        render_resources.insert(MeshStaticResources { depth_material });
+        asset_manager.wait_for_asset_to_load(
+            &deformation_pipeline,
+            asset_resource,
+            "mesh deformation",
+        )?;
+
+        render_resources.insert(MeshStaticResources {
+            depth_material,
+            deformation_pipeline,
+        });
 
         render_resources.insert(ShadowMapResource::default());
 
@@ -121,16 +135,17 @@ impl RenderFeaturePlugin for MeshRendererPlugin {
         extract_context: &RenderJobExtractContext<'extract>,
         frame_packet: Box<dyn RenderFeatureFramePacket>,
     ) -> Arc<dyn RenderFeatureExtractJob<'extract> + 'extract> {
-        let depth_material = extract_context
+        let static_resources = extract_context
             .render_resources
-            .fetch::<MeshStaticResources>()
-            .depth_material
-            .clone();
+            .fetch::<MeshStaticResources>();
+        let depth_material = static_resources.depth_material.clone();
+        let deformation_pipeline = static_resources.deformation_pipeline.clone();
 
         MeshExtractJob::new(
             extract_context,
             frame_packet.into_concrete(),
             depth_material,
+            deformation_pipeline,
             self.render_objects.clone(),
         )
     }
@@ -16,6 +16,7 @@ use rafx::render_features::{
     ExtractResources, RenderFeatureMask, RenderFeatureMaskBuilder, RenderPhaseMask,
     RenderPhaseMaskBuilder, RenderView, RenderViewDepthRange, RenderViewSet,
 };
+use rafx::renderer::ViewportsResource;
 use rafx::visibility::{ObjectId, ViewFrustumArc};
 
 #[derive(Debug, PartialEq, Eq, Hash)]
@@ -156,6 +157,27 @@ fn calculate_shadow_map_views(
 
     let render_options = extract_resources.fetch::<RenderOptions>();
 
+    // Shadow maps only need the objects that can cast a shadow into something that is drawn
+    let shadowed_view_frustums = {
+        let viewports_resource = extract_resources.fetch::<ViewportsResource>();
+        let mut shadowed_view_frustums = Vec::default();
+        if let Some(main_view_meta) = &viewports_resource.main_view_meta {
+            shadowed_view_frustums.push(main_view_meta.view_frustum.clone());
+        }
+
+        for surface in viewports_resource.surfaces.values() {
+            if let Some(view_meta) = &surface.view_meta {
+                shadowed_view_frustums.push(view_meta.view_frustum.clone());
+            }
+        }
+
+        for view_meta in viewports_resource.render_targets.values() {
+            shadowed_view_frustums.push(view_meta.view_frustum.clone());
+        }
+
+        shadowed_view_frustums
+    };
+
     let shadow_map_feature_mask = if render_options.show_surfaces
         && render_options.show_shadows
         && render_options.enable_lighting
@@ -196,8 +218,9 @@ fn calculate_shadow_map_views(
             glam::Vec3::new(0.0, 0.0, 1.0),
         );
 
-        let view = render_view_set.create_view(
+        let view = render_view_set.create_shadow_map_view(
             view_frustum.clone(),
+            shadowed_view_frustums.clone(),
             eye_position,
             view,
             projection.as_rh_mat4(),
@@ -244,8 +267,9 @@ fn calculate_shadow_map_views(
             glam::Vec3::new(0.0, 0.0, 1.0),
         );
 
-        let view = render_view_set.create_view(
+        let view = render_view_set.create_shadow_map_view(
             view_frustum,
+            shadowed_view_frustums.clone(),
             eye_position,
             view,
             projection.as_rh_mat4(),
@@ -281,6 +305,7 @@ fn calculate_shadow_map_views(
             phase_mask: RenderPhaseMask,
             feature_mask: RenderFeatureMask,
             render_view_set: &RenderViewSet,
+            shadowed_view_frustums: &[ViewFrustumArc],
             light: &PointLightComponent,
             position: glam::Vec3,
             face_idx: usize,
@@ -311,8 +336,9 @@ fn calculate_shadow_map_views(
                 cube_map_view_directions.1,
             );
 
-            render_view_set.create_view(
+            render_view_set.create_shadow_map_view(
                 view_frustum,
+                shadowed_view_frustums.to_vec(),
                 position,
                 view,
                 projection.as_lh_mat4(),
@@ -327,12 +353,12 @@ fn calculate_shadow_map_views(
 
         #[rustfmt::skip]
         let cube_map_views = [
-            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, light, transform.translation, 0, &cube_map_view_directions[0]),
-            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, light, transform.translation, 1, &cube_map_view_directions[1]),
-            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, light, transform.translation, 2, &cube_map_view_directions[2]),
-            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, light, transform.translation, 3, &cube_map_view_directions[3]),
-            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, light, transform.translation, 4, &cube_map_view_directions[4]),
-            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, light, transform.translation, 5, &cube_map_view_directions[5]),
+            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, &shadowed_view_frustums, light, transform.translation, 0, &cube_map_view_directions[0]),
+            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, &shadowed_view_frustums, light, transform.translation, 1, &cube_map_view_directions[1]),
+            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, &shadowed_view_frustums, light, transform.translation, 2, &cube_map_view_directions[2]),
+            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, &shadowed_view_frustums, light, transform.translation, 3, &cube_map_view_directions[3]),
+            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, &shadowed_view_frustums, light, transform.translation, 4, &cube_map_view_directions[4]),
+            cube_map_face(shadow_map_phase_mask, shadow_map_feature_mask, &render_view_set, &shadowed_view_frustums, light, transform.translation, 5, &cube_map_view_directions[5]),
         ];
 
         let index = shadow_map_render_views.len();
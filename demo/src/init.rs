@@ -100,7 +100,8 @@ pub fn rendering_init(
         .add_render_feature(tile_layer_renderer_plugin)
         .add_render_feature(debug3d_renderer_plugin)
         .add_render_feature(text_renderer_plugin)
-        .add_render_feature(egui_renderer_plugin);
+        .add_render_feature(egui_renderer_plugin)
+        .pipeline_cache_path(std::env::temp_dir().join("rafx-demo-pipeline-cache.bin"));
 
     let mut renderer_builder_result = {
         let extract_resources = ExtractResources::default();
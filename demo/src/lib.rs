@@ -13,6 +13,7 @@ use crate::daemon_args::AssetDaemonArgs;
 use crate::scenes::SceneManager;
 use crate::time::TimeState;
 use rafx::assets::distill_impl::AssetResource;
+use rafx::graph::RenderGraphGpuTimings;
 use rafx::render_features::ExtractResources;
 use rafx::renderer::{AssetSource, Renderer};
 use rafx::renderer::{RendererConfigResource, ViewportsResource};
@@ -265,13 +266,83 @@ impl RenderOptions {
     }
 }
 
+fn gpu_timings_ui(
+    ui: &mut egui::Ui,
+    gpu_timings: &RenderGraphGpuTimings,
+) {
+    ui.label(format!(
+        "Frame {}: {:.3} ms",
+        gpu_timings.frame_index,
+        gpu_timings.total_ms()
+    ));
+    ui.separator();
+
+    // Draw each node as a bar on a shared timeline so that gaps between nodes are visible
+    let total_ms = gpu_timings.total_ms().max(0.001);
+    let timeline_width = 200.0;
+    for node in &gpu_timings.nodes {
+        ui.horizontal(|ui| {
+            let (rect, _) =
+                ui.allocate_exact_size(egui::vec2(timeline_width, 12.0), egui::Sense::hover());
+            let painter = ui.painter();
+            painter.rect_filled(rect, 0.0, egui::Color32::from_gray(40));
+
+            let x0 = rect.min.x + (node.begin_ms / total_ms) as f32 * timeline_width;
+            let x1 = rect.min.x + (node.end_ms / total_ms) as f32 * timeline_width;
+            let bar = egui::Rect::from_min_max(
+                egui::pos2(x0, rect.min.y),
+                egui::pos2(x1.max(x0 + 1.0), rect.max.y),
+            );
+            painter.rect_filled(bar, 0.0, egui::Color32::from_rgb(100, 180, 100));
+
+            ui.label(format!(
+                "{:.3} ms {}",
+                node.duration_ms(),
+                node.name.unwrap_or("unnamed")
+            ));
+        });
+    }
+}
+
+// GPU and CPU clocks aren't correlated, so the GPU frame is placed so that it ends when the
+// results are reported. Durations are accurate, but the absolute position in the timeline is not.
+#[cfg(feature = "profile-with-puffin")]
+fn report_gpu_timings_to_puffin(gpu_timings: &RenderGraphGpuTimings) {
+    use profiling::puffin;
+
+    let now_ns = puffin::now_ns();
+    let start_ns = now_ns - (gpu_timings.total_ms() * 1_000_000.0) as puffin::NanoSecond;
+    let to_ns = |ms: f64| start_ns + (ms * 1_000_000.0) as puffin::NanoSecond;
+
+    let mut stream = puffin::Stream::default();
+    let frame_offset = stream.begin_scope(start_ns, "GPU Frame", "", "");
+    for node in &gpu_timings.nodes {
+        let offset =
+            stream.begin_scope(to_ns(node.begin_ms), node.name.unwrap_or("unnamed"), "", "");
+        stream.end_scope(offset, to_ns(node.end_ms));
+    }
+    stream.end_scope(frame_offset, now_ns);
+
+    puffin::GlobalProfiler::lock().report(
+        puffin::ThreadInfo {
+            start_time_ns: None,
+            name: "GPU".to_string(),
+        },
+        stream,
+    );
+}
+
 #[derive(Default)]
 pub struct DebugUiState {
     show_render_options: bool,
     show_asset_list: bool,
+    show_gpu_timings: bool,
 
     #[cfg(feature = "profile-with-puffin")]
     show_profiler: bool,
+
+    #[cfg(feature = "profile-with-puffin")]
+    last_reported_gpu_frame: Option<u64>,
 }
 
 #[derive(StructOpt)]
@@ -441,6 +512,8 @@ pub fn run(args: &DemoArgs) -> RafxResult<()> {
 
                         ui.checkbox(&mut debug_ui_state.show_asset_list, "Asset List");
 
+                        ui.checkbox(&mut debug_ui_state.show_gpu_timings, "GPU Timings");
+
                         #[cfg(feature = "profile-with-puffin")]
                         if ui
                             .checkbox(&mut debug_ui_state.show_profiler, "Profiler")
@@ -506,6 +579,28 @@ pub fn run(args: &DemoArgs) -> RafxResult<()> {
                     });
             }
 
+            let gpu_timings = resources.get::<Renderer>().unwrap().gpu_timings();
+            if debug_ui_state.show_gpu_timings {
+                egui::Window::new("GPU Timings")
+                    .open(&mut debug_ui_state.show_gpu_timings)
+                    .show(&ctx, |ui| match &gpu_timings {
+                        Some(gpu_timings) => gpu_timings_ui(ui, gpu_timings),
+                        None => {
+                            ui.label("GPU timings are not available");
+                        }
+                    });
+            }
+
+            #[cfg(feature = "profile-with-puffin")]
+            if let Some(gpu_timings) = &gpu_timings {
+                if debug_ui_state.show_profiler
+                    && debug_ui_state.last_reported_gpu_frame != Some(gpu_timings.frame_index)
+                {
+                    debug_ui_state.last_reported_gpu_frame = Some(gpu_timings.frame_index);
+                    report_gpu_timings_to_puffin(gpu_timings);
+                }
+            }
+
             #[cfg(feature = "profile-with-puffin")]
             if debug_ui_state.show_profiler {
                 profiling::scope!("puffin profiler");
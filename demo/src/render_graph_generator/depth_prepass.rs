@@ -40,6 +40,10 @@ pub(super) fn depth_prepass(context: &mut RenderGraphContext) -> Option<DepthPre
         .graph
         .add_render_phase_dependency::<DepthPrepassRenderPhase>(node);
 
+    context
+        .graph
+        .set_renderpass_uses_secondary_command_buffers(node);
+
     let main_view = context.main_view.clone();
 
     context.graph.set_renderpass_callback(node, move |args| {
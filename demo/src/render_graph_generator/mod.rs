@@ -1,7 +1,4 @@
-use rafx::api::{
-    RafxFormat, RafxPrimitiveTopology, RafxResourceState, RafxResourceType, RafxResult,
-    RafxSampleCount,
-};
+use rafx::api::{RafxFormat, RafxPrimitiveTopology, RafxResourceType, RafxResult, RafxSampleCount};
 use rafx::framework::VertexDataSetLayout;
 use rafx::framework::{ImageViewResource, ResourceArc};
 use rafx::framework::{RenderResources, ResourceContext};
@@ -23,6 +20,7 @@ use crate::RenderOptions;
 use bloom_extract_pass::BloomExtractPass;
 use rafx::assets::AssetManager;
 use rafx::renderer::RenderGraphGenerator;
+use rafx::renderer::RenderTargetView;
 use rafx::renderer::SwapchainResources;
 
 mod bloom_blur_pass;
@@ -33,6 +31,8 @@ mod ui_pass;
 
 mod compute_test;
 
+mod render_target_pass;
+
 lazy_static::lazy_static! {
     pub static ref EMPTY_VERTEX_LAYOUT : VertexDataSetLayout = {
         VertexDataSetLayout::new(vec![], RafxPrimitiveTopology::TriangleList)
@@ -68,7 +68,9 @@ impl RenderGraphGenerator for DemoRenderGraphGenerator {
         &self,
         asset_manager: &AssetManager,
         swapchain_image: ResourceArc<ImageViewResource>,
+        swapchain_resources: &SwapchainResources,
         main_view: RenderView,
+        render_target_views: &[RenderTargetView],
         extract_resources: &ExtractResources,
         render_resources: &RenderResources,
     ) -> RafxResult<PreparedRenderGraph> {
@@ -76,7 +78,6 @@ impl RenderGraphGenerator for DemoRenderGraphGenerator {
 
         let device_context = asset_manager.device_context();
         let resource_context = asset_manager.resource_manager().resource_context();
-        let swapchain_resources = render_resources.fetch::<SwapchainResources>();
         let static_resources = render_resources.fetch::<DemoStaticResources>();
 
         let graph_config = {
@@ -117,11 +118,24 @@ impl RenderGraphGenerator for DemoRenderGraphGenerator {
             extract_resources,
         };
 
+        // Secondary cameras are drawn first so that the main view can sample them
+        let render_targets: Vec<_> = render_target_views
+            .iter()
+            .map(|render_target_view| {
+                render_target_pass::render_target_pass(&mut graph_context, render_target_view)
+            })
+            .collect();
+
         let depth_prepass = depth_prepass::depth_prepass(&mut graph_context);
 
         let shadow_maps = shadow_map_pass::shadow_map_passes(&mut graph_context);
 
-        let opaque_pass = opaque_pass::opaque_pass(&mut graph_context, depth_prepass, &shadow_maps);
+        let opaque_pass = opaque_pass::opaque_pass(
+            &mut graph_context,
+            depth_prepass,
+            &shadow_maps,
+            &render_targets,
+        );
 
         {
             let compute_test_pipeline = asset_manager
@@ -204,7 +218,7 @@ impl RenderGraphGenerator for DemoRenderGraphGenerator {
                 mip_count: 1,
             },
             Default::default(),
-            RafxResourceState::PRESENT,
+            swapchain_resources.swapchain_image_final_state,
         );
 
         let prepared_render_graph = PreparedRenderGraph::new(
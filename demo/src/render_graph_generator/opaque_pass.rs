@@ -17,6 +17,7 @@ pub(super) fn opaque_pass(
     context: &mut RenderGraphContext,
     depth_prepass: Option<DepthPrepass>,
     shadow_map_passes: &[ShadowMapImageResources],
+    render_targets: &[RenderGraphImageUsageId],
 ) -> OpaquePass {
     let node = context
         .graph
@@ -82,10 +83,22 @@ pub(super) fn opaque_pass(
         shadow_maps.push(sampled_image);
     }
 
+    // Materials drawn in this pass may sample render targets, so they must be drawn first
+    for render_target in render_targets {
+        context
+            .graph
+            .sample_image(node, *render_target, Default::default(), Default::default());
+    }
+
     context
         .graph
         .add_render_phase_dependency::<OpaqueRenderPhase>(node);
 
+    // Everything in this pass is drawn with write_view_phase, so it can be written in parallel
+    context
+        .graph
+        .set_renderpass_uses_secondary_command_buffers(node);
+
     let main_view = context.main_view.clone();
     let show_models = context.graph_config.show_surfaces;
 
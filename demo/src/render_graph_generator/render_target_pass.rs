@@ -0,0 +1,103 @@
+use crate::phases::{OpaqueRenderPhase, TransparentRenderPhase};
+use rafx::api::{
+    RafxColorClearValue, RafxDepthStencilClearValue, RafxResourceState, RafxResourceType,
+    RafxSampleCount,
+};
+use rafx::graph::*;
+
+use super::RenderGraphContext;
+use rafx::render_features::RenderJobCommandBufferContext;
+use rafx::renderer::RenderTargetView;
+
+// Draws a secondary camera (security monitor, mirror, minimap, etc.) into the persistent image
+// created by AssetManager::create_render_target. The result is set as an output image so that
+// materials sampling the render target see it later in the frame. Returns the color image so that
+// nodes drawing those materials can depend on it.
+pub(super) fn render_target_pass(
+    context: &mut RenderGraphContext,
+    render_target_view: &RenderTargetView,
+) -> RenderGraphImageUsageId {
+    let node = context
+        .graph
+        .add_node("RenderTarget", RenderGraphQueue::DefaultGraphics);
+
+    let extents = RenderGraphImageExtents::Custom(
+        render_target_view.view.extents_width(),
+        render_target_view.view.extents_height(),
+        1,
+    );
+
+    let color = context.graph.create_color_attachment(
+        node,
+        0,
+        Some(RafxColorClearValue([0.0, 0.0, 0.0, 0.0])),
+        RenderGraphImageConstraint {
+            samples: Some(RafxSampleCount::SampleCount1),
+            format: Some(render_target_view.format),
+            extents: Some(extents),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(color, "render target color");
+
+    let depth = context.graph.create_depth_attachment(
+        node,
+        Some(RafxDepthStencilClearValue {
+            depth: 0.0,
+            stencil: 0,
+        }),
+        RenderGraphImageConstraint {
+            samples: Some(RafxSampleCount::SampleCount1),
+            format: Some(context.graph_config.depth_format),
+            extents: Some(extents),
+            ..Default::default()
+        },
+        Default::default(),
+    );
+    context.graph.set_image_name(depth, "render target depth");
+
+    context
+        .graph
+        .add_render_phase_dependency::<OpaqueRenderPhase>(node);
+    context
+        .graph
+        .add_render_phase_dependency::<TransparentRenderPhase>(node);
+
+    context
+        .graph
+        .set_renderpass_uses_secondary_command_buffers(node);
+
+    let view = render_target_view.view.clone();
+    context.graph.set_renderpass_callback(node, move |args| {
+        profiling::scope!("Render Target Pass");
+
+        let mut write_context =
+            RenderJobCommandBufferContext::from_graph_visit_render_pass_args(&args);
+
+        args.graph_context
+            .prepared_render_data()
+            .write_view_phase::<OpaqueRenderPhase>(&view, &mut write_context)?;
+
+        args.graph_context
+            .prepared_render_data()
+            .write_view_phase::<TransparentRenderPhase>(&view, &mut write_context)
+    });
+
+    context.graph.set_output_image(
+        color,
+        render_target_view.image.clone(),
+        RenderGraphImageSpecification {
+            samples: RafxSampleCount::SampleCount1,
+            format: render_target_view.format,
+            resource_type: RafxResourceType::TEXTURE | RafxResourceType::RENDER_TARGET_COLOR,
+            extents,
+            layer_count: 1,
+            mip_count: 1,
+        },
+        Default::default(),
+        RafxResourceState::SHADER_RESOURCE,
+    );
+
+    color
+}
@@ -1,10 +1,18 @@
+use crate::assets::gltf::{PrefabAsset, PrefabLightKind};
 use crate::components::{
-    DirectionalLightComponent, PointLightComponent, SpotLightComponent, TransformComponent,
+    CameraComponent, DirectionalLightComponent, MeshComponent, MorphTargetWeightsComponent,
+    PointLightComponent, SpotLightComponent, TransformComponent, VisibilityComponent,
 };
 use crate::features::debug3d::Debug3DResource;
+use crate::features::mesh::{MeshRenderObject, MeshRenderObjectSet};
+use distill::loader::handle::Handle;
 use glam::Vec3;
 use legion::IntoQuery;
-use legion::{Read, Resources, World};
+use legion::{Entity, Read, Resources, World};
+use rafx::api::RafxResult;
+use rafx::assets::distill_impl::AssetResource;
+use rafx::assets::AssetManager;
+use rafx::visibility::{CullModel, ObjectId, VisibilityRegion};
 use rand::Rng;
 use sdl2::event::Event;
 
@@ -240,3 +248,158 @@ fn add_point_light(
 
     world.extend(vec![(position_component, light_component)]);
 }
+
+// Light ranges are optional in glTF, but the shadow and light culling code needs a bounded range
+const PREFAB_DEFAULT_LIGHT_RANGE: f32 = 25.0;
+
+// glTF light intensities are physical (candela for point and spot lights, lux for directional
+// lights) but the mesh shaders expect values relative to a fixed exposure. The conversion assumes a
+// scene exposed at EV100 9, roughly an overcast day. Candela at distance d and lux are related by
+// 1/d^2, which matches the shader's falloff, so the same scale applies to every kind of light.
+const PREFAB_LIGHT_EXPOSURE_EV100: f32 = 9.0;
+
+fn prefab_light_intensity(intensity: f32) -> f32 {
+    // Luminance that saturates a sensor at the given EV100 (with the usual 1.2 calibration factor)
+    let max_luminance = 1.2 * 2.0f32.powf(PREFAB_LIGHT_EXPOSURE_EV100);
+    intensity / max_luminance
+}
+
+// Creates an entity for every node in the prefab that has a mesh, a light or a camera. Nodes are
+// placed relative to root_transform. Spawned cameras are not used by the renderer, the scene is
+// responsible for building its views from them.
+pub fn spawn_prefab(
+    resources: &Resources,
+    world: &mut World,
+    prefab_handle: &Handle<PrefabAsset>,
+    root_transform: glam::Mat4,
+) -> RafxResult<Vec<Entity>> {
+    let mut asset_manager = resources.get_mut::<AssetManager>().unwrap();
+    let mut asset_resource = resources.get_mut::<AssetResource>().unwrap();
+    let mut mesh_render_objects = resources.get_mut::<MeshRenderObjectSet>().unwrap();
+    let visibility_region = resources.get::<VisibilityRegion>().unwrap();
+
+    asset_manager.wait_for_asset_to_load(prefab_handle, &mut asset_resource, "prefab")?;
+    let prefab = asset_manager
+        .committed_asset(prefab_handle)
+        .ok_or("Prefab asset failed to load")?
+        .clone();
+
+    let world_transforms = prefab.world_transforms(root_transform);
+
+    let mut entities = Vec::default();
+    for (node, world_transform) in prefab.inner.nodes.iter().zip(world_transforms) {
+        let (scale, rotation, translation) = world_transform.to_scale_rotation_translation();
+        let transform_component = TransformComponent {
+            translation,
+            rotation,
+            scale,
+        };
+
+        if let Some(mesh) = &node.mesh {
+            asset_manager.wait_for_asset_to_load(mesh, &mut asset_resource, "prefab mesh")?;
+            let mesh_asset = asset_manager
+                .committed_asset(mesh)
+                .ok_or("Prefab mesh asset failed to load")?;
+            let visible_bounds = mesh_asset.inner.asset_data.visible_bounds;
+
+            let mesh_render_object =
+                mesh_render_objects.register_render_object(MeshRenderObject { mesh: mesh.clone() });
+
+            let mesh_component = MeshComponent {
+                render_object_handle: mesh_render_object.clone(),
+            };
+
+            let entity = world.push((transform_component, mesh_component));
+            let mut entry = world.entry(entity).unwrap();
+            entry.add_component(VisibilityComponent {
+                visibility_object_handle: {
+                    let handle = visibility_region.register_dynamic_object(
+                        ObjectId::from(entity),
+                        CullModel::VisibleBounds(visible_bounds),
+                    );
+                    handle.set_transform(
+                        transform_component.translation,
+                        transform_component.rotation,
+                        transform_component.scale,
+                    );
+                    handle.add_render_object(&mesh_render_object);
+                    handle
+                },
+            });
+
+            if !node.morph_target_weights.is_empty() {
+                entry.add_component(MorphTargetWeightsComponent {
+                    weights: node.morph_target_weights.clone(),
+                });
+            }
+
+            entities.push(entity);
+        }
+
+        if let Some(light) = &node.light {
+            let color = glam::Vec4::new(light.color[0], light.color[1], light.color[2], 1.0);
+            let range = light.range.unwrap_or(PREFAB_DEFAULT_LIGHT_RANGE);
+            let intensity = prefab_light_intensity(light.intensity);
+            let direction = world_transform
+                .transform_vector3(-glam::Vec3::Z)
+                .normalize();
+
+            let entity = match light.kind {
+                PrefabLightKind::Directional => world.push((DirectionalLightComponent {
+                    direction,
+                    color,
+                    intensity,
+                    view_frustum: visibility_region.register_view_frustum(),
+                },)),
+                PrefabLightKind::Point => world.push((
+                    TransformComponent {
+                        translation,
+                        ..Default::default()
+                    },
+                    PointLightComponent {
+                        color,
+                        range,
+                        intensity,
+                        view_frustums: [
+                            visibility_region.register_view_frustum(),
+                            visibility_region.register_view_frustum(),
+                            visibility_region.register_view_frustum(),
+                            visibility_region.register_view_frustum(),
+                            visibility_region.register_view_frustum(),
+                            visibility_region.register_view_frustum(),
+                        ],
+                    },
+                )),
+                PrefabLightKind::Spot {
+                    outer_cone_angle, ..
+                } => world.push((
+                    TransformComponent {
+                        translation,
+                        ..Default::default()
+                    },
+                    SpotLightComponent {
+                        direction,
+                        color,
+                        spotlight_half_angle: outer_cone_angle,
+                        range,
+                        intensity,
+                        view_frustum: visibility_region.register_view_frustum(),
+                    },
+                )),
+            };
+            entities.push(entity);
+        }
+
+        if let Some(camera) = &node.camera {
+            let entity = world.push((
+                transform_component,
+                CameraComponent {
+                    projection: camera.projection.clone(),
+                },
+            ));
+            entities.push(entity);
+        }
+    }
+
+    Ok(entities)
+}
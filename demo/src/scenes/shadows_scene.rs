@@ -1,4 +1,4 @@
-use crate::assets::gltf::MeshAsset;
+use crate::assets::gltf::{MeshAsset, PrefabAsset};
 use crate::components::{
     DirectionalLightComponent, MeshComponent, PointLightComponent, TransformComponent,
 };
@@ -20,7 +20,7 @@ use crate::phases::{
 use crate::time::TimeState;
 use crate::RenderOptions;
 use distill::loader::handle::Handle;
-use glam::Vec3;
+use glam::{Mat4, Vec3};
 use legion::IntoQuery;
 use legion::{Read, Resources, World, Write};
 use rafx::assets::distill_impl::AssetResource;
@@ -43,6 +43,22 @@ impl ShadowsScene {
         world: &mut World,
         resources: &Resources,
     ) -> Self {
+        //
+        // Add a floor. It's spawned from the prefab so that the node transforms from the file are
+        // used
+        //
+        let floor_prefab_asset = resources
+            .get::<AssetResource>()
+            .unwrap()
+            .load_asset_path::<PrefabAsset, _>("blender/cement_floor.glb");
+        super::spawn_prefab(
+            resources,
+            world,
+            &floor_prefab_asset,
+            Mat4::from_translation(Vec3::new(0.0, 0.0, -1.0)),
+        )
+        .unwrap();
+
         let mut asset_manager = resources.get_mut::<AssetManager>().unwrap();
         let mut asset_resource = resources.get_mut::<AssetResource>().unwrap();
 
@@ -53,8 +69,6 @@ impl ShadowsScene {
 
         let visibility_region = resources.get::<VisibilityRegion>().unwrap();
 
-        let floor_mesh_asset =
-            asset_resource.load_asset_path::<MeshAsset, _>("blender/cement_floor.glb");
         let container_1_asset = asset_resource.load_asset_path("blender/storage_container1.glb");
         let container_2_asset = asset_resource.load_asset_path("blender/storage_container2.glb");
         let blue_icosphere_asset =
@@ -73,45 +87,6 @@ impl ShadowsScene {
                 .visible_bounds
         };
 
-        //
-        // Add a floor
-        //
-        {
-            let position = Vec3::new(0.0, 0.0, -1.0);
-
-            let floor_mesh_render_object =
-                mesh_render_objects.register_render_object(MeshRenderObject {
-                    mesh: floor_mesh_asset.clone(),
-                });
-
-            let transform_component = TransformComponent {
-                translation: position,
-                ..Default::default()
-            };
-
-            let mesh_component = MeshComponent {
-                render_object_handle: floor_mesh_render_object.clone(),
-            };
-
-            let entity = world.push((transform_component.clone(), mesh_component));
-            let mut entry = world.entry(entity).unwrap();
-            entry.add_component(VisibilityComponent {
-                visibility_object_handle: {
-                    let handle = visibility_region.register_static_object(
-                        ObjectId::from(entity),
-                        CullModel::VisibleBounds(load_visible_bounds(&floor_mesh_asset)),
-                    );
-                    handle.set_transform(
-                        transform_component.translation,
-                        transform_component.rotation,
-                        transform_component.scale,
-                    );
-                    handle.add_render_object(&floor_mesh_render_object);
-                    handle
-                },
-            });
-        }
-
         //
         // Add some meshes
         //
@@ -33,6 +33,8 @@ impl RafxDeviceContextEmpty {
     pub fn create_graphics_pipeline(&self, graphics_pipeline_def: &RafxGraphicsPipelineDef) -> RafxResult<RafxPipelineEmpty> { unimplemented!(); }
     pub fn create_compute_pipeline(&self, compute_pipeline_def: &RafxComputePipelineDef) -> RafxResult<RafxPipelineEmpty> { unimplemented!(); }
     pub fn create_shader_module(&self, data: RafxShaderModuleDefEmpty) -> RafxResult<RafxShaderModuleEmpty> { unimplemented!(); }
+    pub fn create_query_pool(&self, query_pool_def: &RafxQueryPoolDef) -> RafxResult<RafxQueryPoolEmpty> { unimplemented!(); }
+    pub fn create_pipeline_cache(&self, pipeline_cache_def: &RafxPipelineCacheDef) -> RafxResult<RafxPipelineCacheEmpty> { unimplemented!(); }
 
     pub fn wait_for_fences(&self, fences: &[&RafxFenceEmpty]) -> RafxResult<()> { unimplemented!(); }
 
@@ -47,6 +49,7 @@ impl RafxDeviceContextEmpty {
 pub struct RafxBufferEmpty;
 impl RafxBufferEmpty {
     pub fn buffer_def(&self) -> &RafxBufferDef { unimplemented!() }
+    pub fn set_debug_name(&self, name: impl AsRef<str>) { unimplemented!() }
     pub fn map_buffer(&self) -> RafxResult<*mut u8> { unimplemented!() }
     pub fn unmap_buffer(&self) -> RafxResult<()> { unimplemented!() }
     pub fn mapped_memory(&self) -> Option<*mut u8> { unimplemented!() }
@@ -59,6 +62,7 @@ impl RafxBufferEmpty {
 pub struct RafxTextureEmpty;
 impl RafxTextureEmpty {
     pub fn texture_def(&self) -> &RafxTextureDef { unimplemented!() }
+    pub fn set_debug_name(&self, name: impl AsRef<str>) { unimplemented!() }
 }
 
 #[derive(Clone, Debug)]
@@ -87,6 +91,7 @@ pub struct RafxPipelineEmpty;
 impl RafxPipelineEmpty {
     pub fn pipeline_type(&self) -> RafxPipelineType { unimplemented!(); }
     pub fn root_signature(&self) -> &RafxRootSignature { unimplemented!(); }
+    pub fn set_debug_name(&self, name: impl AsRef<str>) { unimplemented!(); }
 }
 
 //
@@ -133,8 +138,11 @@ impl RafxCommandBufferEmpty {
     pub fn begin(&self) -> RafxResult<()> { unimplemented!() }
     pub fn end(&self) -> RafxResult<()> { unimplemented!() }
     pub fn return_to_pool(&self) -> RafxResult<()> { unimplemented!() }
+    pub fn begin_secondary(&self, primary_command_buffer: &RafxCommandBufferEmpty) -> RafxResult<()> { unimplemented!() }
 
     pub fn cmd_begin_render_pass(&self, color_targets: &[RafxColorRenderTargetBinding], depth_target: Option<RafxDepthStencilRenderTargetBinding>) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_begin_render_pass_with_secondary_command_buffers(&self, color_targets: &[RafxColorRenderTargetBinding], depth_target: Option<RafxDepthStencilRenderTargetBinding>) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_execute_secondary(&self, secondary_command_buffers: &[&RafxCommandBufferEmpty]) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_end_render_pass(&self) -> RafxResult<()> { unimplemented!() }
 
     pub fn cmd_set_viewport(&self, x: f32, y: f32, width: f32, height: f32, depth_min: f32, depth_max: f32) -> RafxResult<()> { unimplemented!() }
@@ -146,6 +154,7 @@ impl RafxCommandBufferEmpty {
     pub fn cmd_bind_index_buffer(&self, binding: &RafxIndexBufferBinding) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_bind_descriptor_set(&self, descriptor_set_array: &RafxDescriptorSetArrayEmpty, index: u32) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_bind_descriptor_set_handle(&self, root_signature: &RafxRootSignatureEmpty, set_index: u32, descriptor_set_handle: &RafxDescriptorSetHandleEmpty) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_push_constants(&self, root_signature: &RafxRootSignatureEmpty, offset_in_bytes: u32, data: &[u8]) -> RafxResult<()> { unimplemented!() }
 
     pub fn cmd_draw(&self, vertex_count: u32, first_vertex: u32) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_draw_instanced(&self, vertex_count: u32, first_vertex: u32, instance_count: u32, first_instance: u32) -> RafxResult<()> { unimplemented!() }
@@ -153,10 +162,27 @@ impl RafxCommandBufferEmpty {
     pub fn cmd_draw_indexed_instanced(&self, index_count: u32,  first_index: u32,  instance_count: u32,  first_instance: u32,vertex_offset: i32) -> RafxResult<()> { unimplemented!() }
 
     pub fn cmd_dispatch(&self, group_count_x: u32,  group_count_y: u32, group_count_z: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_draw_indirect(&self, indirect_buffer: &RafxBufferEmpty, indirect_buffer_offset_in_bytes: u32, draw_count: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_draw_indexed_indirect(&self, indirect_buffer: &RafxBufferEmpty, indirect_buffer_offset_in_bytes: u32, draw_count: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_draw_indirect_count(&self, indirect_buffer: &RafxBufferEmpty, indirect_buffer_offset_in_bytes: u32, count_buffer: &RafxBufferEmpty, count_buffer_offset_in_bytes: u32, max_draw_count: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_draw_indexed_indirect_count(&self, indirect_buffer: &RafxBufferEmpty, indirect_buffer_offset_in_bytes: u32, count_buffer: &RafxBufferEmpty, count_buffer_offset_in_bytes: u32, max_draw_count: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_dispatch_indirect(&self, indirect_buffer: &RafxBufferEmpty, indirect_buffer_offset_in_bytes: u32) -> RafxResult<()> { unimplemented!() }
 
     pub fn cmd_resource_barrier(&self, buffer_barriers: &[RafxBufferBarrier], texture_barriers: &[RafxTextureBarrier]) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_copy_buffer_to_buffer(&self, src_buffer: &RafxBufferEmpty, dst_buffer: &RafxBufferEmpty, src_offset: u64, dst_offset: u64, size: u64) -> RafxResult<()> { unimplemented!() }
     pub fn cmd_copy_buffer_to_texture(&self, src_buffer: &RafxBufferEmpty, dst_texture: &RafxTextureEmpty, params: &RafxCmdCopyBufferToTextureParams) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_copy_texture_to_buffer(&self, src_texture: &RafxTextureEmpty, dst_buffer: &RafxBufferEmpty, params: &RafxCmdCopyTextureToBufferParams) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_copy_texture_to_texture(&self, src_texture: &RafxTextureEmpty, dst_texture: &RafxTextureEmpty, params: &RafxCmdCopyTextureToTextureParams) -> RafxResult<()> { unimplemented!() }
+
+    pub fn cmd_reset_queries(&self, query_pool: &RafxQueryPoolEmpty, first_query: u32, query_count: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_write_timestamp(&self, query_pool: &RafxQueryPoolEmpty, query_index: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_begin_query(&self, query_pool: &RafxQueryPoolEmpty, query_index: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_end_query(&self, query_pool: &RafxQueryPoolEmpty, query_index: u32) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_resolve_queries(&self, query_pool: &RafxQueryPoolEmpty, first_query: u32, query_count: u32, dst_buffer: &RafxBufferEmpty, dst_offset: u64) -> RafxResult<()> { unimplemented!() }
+
+    pub fn cmd_push_group_debug_name(&self, name: impl AsRef<str>) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_pop_group_debug_name(&self) -> RafxResult<()> { unimplemented!() }
+    pub fn cmd_insert_debug_marker(&self, name: impl AsRef<str>) -> RafxResult<()> { unimplemented!() }
 }
 
 //
@@ -171,6 +197,23 @@ impl RafxFenceEmpty {
 
 pub struct RafxSemaphoreEmpty;
 
+//
+// Pipeline Cache
+//
+pub struct RafxPipelineCacheEmpty;
+impl RafxPipelineCacheEmpty {
+    pub fn get_data(&self) -> RafxResult<Vec<u8>> { unimplemented!() }
+}
+
+//
+// Queries
+//
+pub struct RafxQueryPoolEmpty;
+impl RafxQueryPoolEmpty {
+    pub fn query_pool_def(&self) -> &RafxQueryPoolDef { unimplemented!() }
+    pub fn get_results(&self, first_query: u32, query_count: u32, results: &mut [u64]) -> RafxResult<bool> { unimplemented!() }
+}
+
 //
 // Swapchain
 //
@@ -27,10 +27,12 @@ impl RafxApiGles2 {
 
     pub fn new(
         window: &dyn HasRawWindowHandle,
-        _api_def: &RafxApiDef,
+        api_def: &RafxApiDef,
         gl_api_def: &RafxApiDefGles2,
     ) -> RafxResult<Self> {
-        let inner = Arc::new(RafxDeviceContextGles2Inner::new(window, gl_api_def)?);
+        let inner = Arc::new(RafxDeviceContextGles2Inner::new(
+            window, api_def, gl_api_def,
+        )?);
         let device_context = RafxDeviceContextGles2::new(inner)?;
 
         Ok(RafxApiGles2 {
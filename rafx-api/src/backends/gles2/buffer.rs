@@ -104,6 +104,24 @@ impl RafxBufferGles2 {
         self.target
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        // Buffers that are emulated in CPU memory (i.e. uniform buffers) have no GL object to name
+        if let Some(buffer_id) = self.buffer_id {
+            if self.device_context.device_info().debug_names_enabled {
+                if let Err(e) = self.device_context.gl_context().gl_object_label(
+                    gles2_bindings::BUFFER,
+                    buffer_id.0,
+                    name.as_ref(),
+                ) {
+                    log::warn!("Failed to set debug name on buffer: {:?}", e);
+                }
+            }
+        }
+    }
+
     pub(crate) fn buffer_contents(&self) -> &Gles2BufferContents {
         &self.buffer_contents
     }
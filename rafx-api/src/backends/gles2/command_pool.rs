@@ -45,6 +45,12 @@ pub(crate) struct CommandPoolGles2StateInner {
     pub(crate) bound_descriptor_sets_root_signature: Option<RafxRootSignatureGles2>,
     pub(crate) descriptor_sets_update_index: [u64; MAX_DESCRIPTOR_SET_LAYOUTS],
 
+    // Push constants are emulated with uniforms. The data is kept here and pushed into the program
+    // before drawing
+    pub(crate) push_constant_data: Vec<u8>,
+    pub(crate) push_constant_root_signature: Option<RafxRootSignatureGles2>,
+    pub(crate) push_constant_update_index: u64,
+
     // One per possible bound vertex buffer (could be 1 per attribute!)
     pub(crate) vertex_attribute_enabled_bits: AttributeEnabledBits,
     // Holds the currently bound attribute metadata
@@ -129,6 +135,9 @@ impl CommandPoolGles2State {
             bound_descriptor_sets: Default::default(),
             bound_descriptor_sets_root_signature: None,
             descriptor_sets_update_index: Default::default(),
+            push_constant_data: Vec::default(),
+            push_constant_root_signature: None,
+            push_constant_update_index: 0,
             framebuffer_id,
         };
 
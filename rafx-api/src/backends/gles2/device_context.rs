@@ -1,17 +1,18 @@
 use crate::{
-    RafxApiDefGles2, RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef,
-    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxQueueType,
-    RafxResourceType, RafxResult, RafxRootSignatureDef, RafxSampleCount, RafxSamplerDef,
-    RafxShaderModuleDefGles2, RafxShaderStageDef, RafxSwapchainDef, RafxTextureDef,
+    RafxApiDef, RafxApiDefGles2, RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef,
+    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxPipelineCacheDef,
+    RafxQueryPoolDef, RafxQueueType, RafxResourceType, RafxResult, RafxRootSignatureDef,
+    RafxSampleCount, RafxSamplerDef, RafxShaderModuleDefGles2, RafxShaderStageDef,
+    RafxSwapchainDef, RafxTextureDef,
 };
 use raw_window_handle::HasRawWindowHandle;
 use std::sync::Arc;
 
 use crate::gles2::{
     GlContextManager, RafxBufferGles2, RafxDescriptorSetArrayGles2, RafxFenceGles2,
-    RafxPipelineGles2, RafxQueueGles2, RafxRootSignatureGles2, RafxSamplerGles2,
-    RafxSemaphoreGles2, RafxShaderGles2, RafxShaderModuleGles2, RafxSwapchainGles2,
-    RafxTextureGles2,
+    RafxPipelineCacheGles2, RafxPipelineGles2, RafxQueryPoolGles2, RafxQueueGles2,
+    RafxRootSignatureGles2, RafxSamplerGles2, RafxSemaphoreGles2, RafxShaderGles2,
+    RafxShaderModuleGles2, RafxSwapchainGles2, RafxTextureGles2,
 };
 
 use crate::gles2::gles2_bindings;
@@ -56,6 +57,7 @@ impl Drop for RafxDeviceContextGles2Inner {
 impl RafxDeviceContextGles2Inner {
     pub fn new(
         window: &dyn HasRawWindowHandle,
+        api_def: &RafxApiDef,
         gl_api_def: &RafxApiDefGles2,
     ) -> RafxResult<Self> {
         log::debug!("Initializing GL backend");
@@ -84,6 +86,17 @@ impl RafxDeviceContextGles2Inner {
             upload_buffer_texture_row_alignment: pack_alignment,
             supports_clamp_to_border_color: false, // requires GLES 3.2 or an extension
             max_vertex_attribute_count,
+            timestamp_period_ns: 1.0,
+            supports_timestamp_queries: false,
+            supports_pipeline_statistics_queries: false,
+            supports_indirect_draw: false,
+            max_indirect_draw_count: 0,
+            supports_indirect_draw_count: false,
+            supports_indirect_dispatch: false,
+            // Push constants are emulated with uniforms
+            max_push_constant_size: u32::MAX,
+            debug_names_enabled: api_def.enable_debug_names && gl_context.supports_debug_labels(),
+            supports_multithreaded_command_recording: false,
         };
 
         let fullscreen_quad = FullscreenQuad::new(&gl_context)?;
@@ -232,6 +245,20 @@ impl RafxDeviceContextGles2 {
         RafxSemaphoreGles2::new(self)
     }
 
+    pub fn create_query_pool(
+        &self,
+        query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPoolGles2> {
+        RafxQueryPoolGles2::new(self, query_pool_def)
+    }
+
+    pub fn create_pipeline_cache(
+        &self,
+        pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCacheGles2> {
+        RafxPipelineCacheGles2::new(self, pipeline_cache_def)
+    }
+
     pub fn create_swapchain(
         &self,
         raw_window_handle: &dyn HasRawWindowHandle,
@@ -317,6 +344,7 @@ impl RafxDeviceContextGles2 {
     ) -> Option<RafxFormat> {
         if resource_type.intersects(RafxResourceType::RENDER_TARGET_DEPTH_STENCIL)
             || resource_type.intersects(RafxResourceType::RENDER_TARGET_COLOR)
+            || resource_type.intersects(RafxResourceType::TEXTURE)
         {
             for &candidate in candidates {
                 if candidate.gles2_texture_format_info().is_some() {
@@ -200,6 +200,8 @@ pub const BOOL_VEC3: types::GLenum = 0x8B58;
 #[allow(dead_code, non_upper_case_globals)]
 pub const BOOL_VEC4: types::GLenum = 0x8B59;
 #[allow(dead_code, non_upper_case_globals)]
+pub const BUFFER: types::GLenum = 0x82E0;
+#[allow(dead_code, non_upper_case_globals)]
 pub const BUFFER_SIZE: types::GLenum = 0x8764;
 #[allow(dead_code, non_upper_case_globals)]
 pub const BUFFER_USAGE: types::GLenum = 0x8765;
@@ -533,6 +535,8 @@ pub const POLYGON_OFFSET_FACTOR: types::GLenum = 0x8038;
 #[allow(dead_code, non_upper_case_globals)]
 pub const POLYGON_OFFSET_FILL: types::GLenum = 0x8037;
 #[allow(dead_code, non_upper_case_globals)]
+pub const PROGRAM: types::GLenum = 0x82E2;
+#[allow(dead_code, non_upper_case_globals)]
 pub const POLYGON_OFFSET_UNITS: types::GLenum = 0x2A00;
 #[allow(dead_code, non_upper_case_globals)]
 pub const RED_BITS: types::GLenum = 0x0D52;
@@ -900,6 +904,12 @@ pub struct Gles2 {
     pub DebugMessageCallback: FnPtr,
     pub DebugMessageControl: FnPtr,
     pub DebugMessageInsert: FnPtr,
+    /// Fallbacks: ObjectLabelKHR
+    pub ObjectLabel: FnPtr,
+    /// Fallbacks: PopDebugGroupKHR
+    pub PopDebugGroup: FnPtr,
+    /// Fallbacks: PushDebugGroupKHR
+    pub PushDebugGroup: FnPtr,
 
     /// Fallbacks: DeleteBuffersARB
     pub DeleteBuffers: FnPtr,
@@ -1186,6 +1196,9 @@ impl Gles2 {
                 "glDebugMessageInsert",
                 &["glDebugMessageInsertARB", "glDebugMessageInsertKHR"],
             )),
+            ObjectLabel: FnPtr::new(metaloadfn("glObjectLabel", &["glObjectLabelKHR"])),
+            PopDebugGroup: FnPtr::new(metaloadfn("glPopDebugGroup", &["glPopDebugGroupKHR"])),
+            PushDebugGroup: FnPtr::new(metaloadfn("glPushDebugGroup", &["glPushDebugGroupKHR"])),
 
             DeleteBuffers: FnPtr::new(metaloadfn("glDeleteBuffers", &["glDeleteBuffersARB"])),
             DeleteFramebuffers: FnPtr::new(metaloadfn(
@@ -1899,6 +1912,52 @@ impl Gles2 {
         >(self.DebugMessageInsert.f)(source, type_, id, severity, length, buf)
     }
 
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn ObjectLabel(
+        &self,
+        identifier: types::GLenum,
+        name: types::GLuint,
+        length: types::GLsizei,
+        label: *const types::GLchar,
+    ) -> () {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(
+                types::GLenum,
+                types::GLuint,
+                types::GLsizei,
+                *const types::GLchar,
+            ) -> (),
+        >(self.ObjectLabel.f)(identifier, name, length, label)
+    }
+
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn PopDebugGroup(&self) -> () {
+        __gl_imports::mem::transmute::<_, extern "system" fn() -> ()>(self.PopDebugGroup.f)()
+    }
+
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn PushDebugGroup(
+        &self,
+        source: types::GLenum,
+        id: types::GLuint,
+        length: types::GLsizei,
+        message: *const types::GLchar,
+    ) -> () {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(
+                types::GLenum,
+                types::GLuint,
+                types::GLsizei,
+                *const types::GLchar,
+            ) -> (),
+        >(self.PushDebugGroup.f)(source, id, length, message)
+    }
+
     #[allow(non_snake_case, unused_variables, dead_code)]
     #[inline]
     pub unsafe fn DeleteBuffers(
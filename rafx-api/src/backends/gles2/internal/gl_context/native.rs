@@ -253,6 +253,79 @@ impl GlContext {
         gl_get_integerv(&self.gles2, pname)
     }
 
+    // KHR_debug is not available on all platforms. If it is not, debug groups/labels are ignored
+    pub fn supports_debug_labels(&self) -> bool {
+        self.gles2.PushDebugGroup.is_loaded() && self.gles2.ObjectLabel.is_loaded()
+    }
+
+    pub fn gl_push_debug_group(
+        &self,
+        message: &str,
+    ) -> RafxResult<()> {
+        if self.gles2.PushDebugGroup.is_loaded() {
+            unsafe {
+                self.gles2.PushDebugGroup(
+                    gles2_bindings::DEBUG_SOURCE_APPLICATION,
+                    0,
+                    message.len() as _,
+                    message.as_ptr() as _,
+                );
+            }
+            self.check_for_error()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn gl_pop_debug_group(&self) -> RafxResult<()> {
+        if self.gles2.PopDebugGroup.is_loaded() {
+            unsafe {
+                self.gles2.PopDebugGroup();
+            }
+            self.check_for_error()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn gl_debug_message_insert(
+        &self,
+        message: &str,
+    ) -> RafxResult<()> {
+        if self.gles2.DebugMessageInsert.is_loaded() {
+            unsafe {
+                self.gles2.DebugMessageInsert(
+                    gles2_bindings::DEBUG_SOURCE_APPLICATION,
+                    gles2_bindings::DEBUG_TYPE_MARKER,
+                    0,
+                    gles2_bindings::DEBUG_SEVERITY_NOTIFICATION,
+                    message.len() as _,
+                    message.as_ptr() as _,
+                );
+            }
+            self.check_for_error()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn gl_object_label(
+        &self,
+        identifier: GLenum,
+        name: u32,
+        label: &str,
+    ) -> RafxResult<()> {
+        if self.gles2.ObjectLabel.is_loaded() {
+            unsafe {
+                self.gles2
+                    .ObjectLabel(identifier, name, label.len() as _, label.as_ptr() as _);
+            }
+            self.check_for_error()?;
+        }
+
+        Ok(())
+    }
+
     pub fn gl_get_string(
         &self,
         pname: u32,
@@ -1264,6 +1337,56 @@ impl GlContext {
         }
     }
 
+    pub fn gl_read_pixels(
+        &self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        format: GLenum,
+        type_: GLenum,
+        pixels: &mut [u8],
+    ) -> RafxResult<()> {
+        unsafe {
+            self.gles2.ReadPixels(
+                x,
+                y,
+                width as _,
+                height as _,
+                format,
+                type_,
+                pixels.as_mut_ptr() as _,
+            );
+            self.check_for_error()
+        }
+    }
+
+    pub fn gl_copy_tex_sub_image_2d(
+        &self,
+        target: GLenum,
+        mip_level: u8,
+        x_offset: i32,
+        y_offset: i32,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.gles2.CopyTexSubImage2D(
+                target,
+                mip_level as _,
+                x_offset,
+                y_offset,
+                x,
+                y,
+                width as _,
+                height as _,
+            );
+            self.check_for_error()
+        }
+    }
+
     pub fn gl_tex_parameteri(
         &self,
         target: GLenum,
@@ -2,7 +2,8 @@ use crate::gles2::conversions::{Gles2BlendState, Gles2DepthStencilState, Gles2Ra
 use crate::gles2::gles2_bindings::types::GLenum;
 use crate::gles2::reflection::FieldIndex;
 use crate::gles2::{
-    LocationId, ProgramId, RafxDeviceContextGles2, RafxRootSignatureGles2, RafxShaderGles2,
+    gles2_bindings, LocationId, ProgramId, RafxDeviceContextGles2, RafxRootSignatureGles2,
+    RafxShaderGles2,
 };
 use crate::{
     RafxComputePipelineDef, RafxDescriptorIndex, RafxGraphicsPipelineDef, RafxPipelineType,
@@ -34,6 +35,7 @@ pub(crate) struct Gles2PipelineInfo {
     uniform_field_locations: Vec<Option<LocationId>>,
     pub(crate) root_signature: RafxRootSignatureGles2,
     pub(crate) last_descriptor_updates: TrustCell<[u64; MAX_DESCRIPTOR_SET_LAYOUTS]>,
+    pub(crate) last_push_constant_update: TrustCell<u64>,
     pub(crate) last_bound_by_command_pool: TrustCell<u32>,
 }
 
@@ -92,6 +94,26 @@ impl RafxPipelineGles2 {
         self.gl_pipeline_info.program_id
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        let device_context = self
+            .root_signature
+            .gles2_root_signature()
+            .unwrap()
+            .device_context();
+        if device_context.device_info().debug_names_enabled {
+            if let Err(e) = device_context.gl_context().gl_object_label(
+                gles2_bindings::PROGRAM,
+                self.gl_pipeline_info.program_id.0,
+                name.as_ref(),
+            ) {
+                log::warn!("Failed to set debug name on pipeline: {:?}", e);
+            }
+        }
+    }
+
     pub(crate) fn gl_pipeline_info(&self) -> &Arc<Gles2PipelineInfo> {
         &self.gl_pipeline_info
     }
@@ -192,6 +214,7 @@ impl RafxPipelineGles2 {
             uniform_field_locations,
             root_signature: gl_root_signature.clone(),
             last_descriptor_updates: Default::default(),
+            last_push_constant_update: Default::default(),
         };
 
         // Front face needs to be reversed because we render GL with a flipped Y axis:
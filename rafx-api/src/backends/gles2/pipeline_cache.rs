@@ -0,0 +1,19 @@
+use crate::gles2::RafxDeviceContextGles2;
+use crate::{RafxPipelineCacheDef, RafxResult};
+
+// GL ES 2.0 does not support caching compiled programs. This type exists so that the API is the same across backends.
+// Pipelines created with it are compiled as usual.
+pub struct RafxPipelineCacheGles2;
+
+impl RafxPipelineCacheGles2 {
+    pub fn new(
+        _device_context: &RafxDeviceContextGles2,
+        _pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCacheGles2> {
+        Ok(RafxPipelineCacheGles2)
+    }
+
+    pub fn get_data(&self) -> RafxResult<Vec<u8>> {
+        Ok(Vec::default())
+    }
+}
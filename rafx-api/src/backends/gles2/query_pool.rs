@@ -0,0 +1,30 @@
+use crate::gles2::RafxDeviceContextGles2;
+use crate::{RafxQueryPoolDef, RafxResult};
+
+// GL ES 2.0 does not have query objects. This type exists so that the API is the same across
+// backends, but it can never be created.
+pub struct RafxQueryPoolGles2 {
+    query_pool_def: RafxQueryPoolDef,
+}
+
+impl RafxQueryPoolGles2 {
+    pub fn new(
+        _device_context: &RafxDeviceContextGles2,
+        _query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPoolGles2> {
+        Err("Queries are not supported on GL ES 2.0")?
+    }
+
+    pub fn query_pool_def(&self) -> &RafxQueryPoolDef {
+        &self.query_pool_def
+    }
+
+    pub fn get_results(
+        &self,
+        _first_query: u32,
+        _query_count: u32,
+        _results: &mut [u64],
+    ) -> RafxResult<bool> {
+        Err("Queries are not supported on GL ES 2.0")?
+    }
+}
@@ -63,6 +63,15 @@ pub(crate) struct DescriptorInfo {
     pub(crate) first_location_index: Option<u32>,
 }
 
+// GL ES 2.0 has no push constants. The shader processor emits the push constant block as a plain
+// uniform struct, and the members are set from the pushed data before drawing.
+#[derive(Clone, Debug)]
+pub(crate) struct PushConstantInfo {
+    pub(crate) size_in_bytes: u32,
+    // May be none if the variable is not active in any shader
+    pub(crate) uniform_index: Option<UniformIndex>,
+}
+
 #[derive(Default, Debug)]
 pub(crate) struct DescriptorSetLayoutInfo {
     // Settable descriptors, immutable samplers are omitted
@@ -94,6 +103,7 @@ pub(crate) struct RafxRootSignatureGles2Inner {
     // --- gl-specific ---
     pub(crate) immutable_samplers: Vec<ImmutableSampler>,
     pub(crate) uniform_reflection: UniformReflectionData,
+    pub(crate) push_constant: Option<PushConstantInfo>,
     pub(crate) root_signature_id: u32,
 
     pub(crate) location_names: Vec<CString>,
@@ -152,6 +162,10 @@ impl RafxRootSignatureGles2 {
         &self.inner.uniform_reflection
     }
 
+    pub(crate) fn push_constant(&self) -> Option<&PushConstantInfo> {
+        self.inner.push_constant.as_ref()
+    }
+
     #[allow(dead_code)]
     pub(crate) fn uniform_index(
         &self,
@@ -259,7 +273,7 @@ impl RafxRootSignatureGles2 {
                 }
             }
 
-            // Not currently supported
+            // Push constants are merged separately
             assert_ne!(resource.resource_type, RafxResourceType::ROOT_CONSTANT);
 
             // Verify set index is valid
@@ -399,6 +413,18 @@ impl RafxRootSignatureGles2 {
             total_texture_units += next_descriptor_data_texture_offset[i];
         }
 
+        let push_constant = crate::internal_shared::merge_push_constants(
+            root_signature_def,
+            device_context.device_info().max_push_constant_size,
+        )?
+        .map(|resource| PushConstantInfo {
+            size_in_bytes: resource.size_in_bytes,
+            uniform_index: resource
+                .gles_name
+                .as_ref()
+                .and_then(|gl_name| uniform_reflection.uniform_index(gl_name)),
+        });
+
         let root_signature_id = NEXT_ROOT_SIGNATURE_ID.fetch_add(1, Ordering::Relaxed);
 
         for (texture_descriptor_index, sampler_name) in texture_descriptor_index_sampler_names {
@@ -415,6 +441,7 @@ impl RafxRootSignatureGles2 {
             name_to_descriptor_index,
             immutable_samplers,
             uniform_reflection,
+            push_constant,
             root_signature_id,
             location_names,
         };
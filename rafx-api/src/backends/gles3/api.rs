@@ -27,10 +27,12 @@ impl RafxApiGles3 {
 
     pub fn new(
         window: &dyn HasRawWindowHandle,
-        _api_def: &RafxApiDef,
+        api_def: &RafxApiDef,
         gl_api_def: &RafxApiDefGles3,
     ) -> RafxResult<Self> {
-        let inner = Arc::new(RafxDeviceContextGles3Inner::new(window, gl_api_def)?);
+        let inner = Arc::new(RafxDeviceContextGles3Inner::new(
+            window, api_def, gl_api_def,
+        )?);
         let device_context = RafxDeviceContextGles3::new(inner)?;
 
         Ok(RafxApiGles3 {
@@ -1,15 +1,18 @@
 use crate::gles3::{
     AttributeEnabledBits, BoundDescriptorSet, BoundVertexBuffer, CommandPoolGles3State,
-    CommandPoolGles3StateInner, DescriptorSetArrayData, GlContext, Gles3PipelineInfo,
-    RafxBufferGles3, RafxCommandPoolGles3, RafxDescriptorSetArrayGles3,
-    RafxDescriptorSetHandleGles3, RafxPipelineGles3, RafxQueueGles3, RafxRootSignatureGles3,
-    RafxTextureGles3, NONE_BUFFER, NONE_FRAMEBUFFER, NONE_PROGRAM, NONE_TEXTURE,
+    CommandPoolGles3StateInner, DescriptorSetArrayData, FramebufferId, GlContext,
+    Gles3PipelineInfo, RafxBufferGles3, RafxCommandPoolGles3, RafxDescriptorSetArrayGles3,
+    RafxDescriptorSetHandleGles3, RafxPipelineGles3, RafxQueryPoolGles3, RafxQueueGles3,
+    RafxRootSignatureGles3, RafxTextureGles3, NONE_BUFFER, NONE_FRAMEBUFFER, NONE_PROGRAM,
+    NONE_TEXTURE,
 };
 use crate::{
-    RafxBufferBarrier, RafxCmdCopyBufferToTextureParams, RafxColorFlags,
-    RafxColorRenderTargetBinding, RafxCommandBufferDef, RafxDepthStencilRenderTargetBinding,
-    RafxExtents3D, RafxIndexBufferBinding, RafxIndexType, RafxLoadOp, RafxResourceType, RafxResult,
-    RafxTextureBarrier, RafxVertexBufferBinding, MAX_DESCRIPTOR_SET_LAYOUTS,
+    RafxBufferBarrier, RafxCmdCopyBufferToTextureParams, RafxCmdCopyTextureToBufferParams,
+    RafxCmdCopyTextureToTextureParams, RafxColorFlags, RafxColorRenderTargetBinding,
+    RafxCommandBufferDef, RafxDepthStencilRenderTargetBinding, RafxDrawIndexedIndirectCommand,
+    RafxDrawIndirectCommand, RafxExtents3D, RafxIndexBufferBinding, RafxIndexType, RafxLoadOp,
+    RafxOffset3D, RafxQueryType, RafxResourceType, RafxResult, RafxTextureBarrier,
+    RafxVertexBufferBinding, MAX_DESCRIPTOR_SET_LAYOUTS,
 };
 
 use rafx_base::trust_cell::TrustCell;
@@ -26,6 +29,7 @@ use std::sync::Arc;
 pub struct RafxCommandBufferGles3 {
     queue: RafxQueueGles3,
     command_pool_state: CommandPoolGles3State,
+    is_secondary: bool,
 }
 
 impl RafxCommandBufferGles3 {
@@ -35,11 +39,12 @@ impl RafxCommandBufferGles3 {
 
     pub fn new(
         command_pool: &RafxCommandPoolGles3,
-        _command_buffer_def: &RafxCommandBufferDef,
+        command_buffer_def: &RafxCommandBufferDef,
     ) -> RafxResult<RafxCommandBufferGles3> {
         Ok(RafxCommandBufferGles3 {
             queue: command_pool.queue().clone(),
             command_pool_state: command_pool.command_pool_state().clone(),
+            is_secondary: command_buffer_def.is_secondary,
         })
     }
 
@@ -51,6 +56,43 @@ impl RafxCommandBufferGles3 {
         Ok(())
     }
 
+    // GL ES does not have secondary command buffers. They are emulated by issuing commands as they
+    // are written (just like primary command buffers) so they must be written on the thread that
+    // owns the GL context, in the order they are executed. They must be allocated from a different
+    // pool than the primary so that they have their own state tracking.
+    pub fn begin_secondary(
+        &self,
+        primary_command_buffer: &RafxCommandBufferGles3,
+    ) -> RafxResult<()> {
+        let surface_size = {
+            let primary_state = primary_command_buffer.command_pool_state.borrow();
+            if primary_state.id == self.command_pool_state.borrow().id {
+                Err("Secondary command buffers must be allocated from a different command pool than the primary")?;
+            }
+
+            primary_state
+                .surface_size
+                .ok_or("The primary command buffer is not in a renderpass begun with cmd_begin_render_pass_with_secondary_command_buffers")?
+        };
+
+        {
+            let mut state = self.command_pool_state.borrow_mut();
+            assert!(!state.is_started);
+            state.is_started = true;
+            state.surface_size = Some(surface_size);
+        }
+
+        self.cmd_set_viewport(
+            0.0,
+            0.0,
+            surface_size.width as f32,
+            surface_size.height as f32,
+            0.0,
+            1.0,
+        )?;
+        self.cmd_set_scissor(0, 0, surface_size.width, surface_size.height)
+    }
+
     pub fn end(&self) -> RafxResult<()> {
         let mut state = self.command_pool_state.borrow_mut();
         assert!(state.is_started);
@@ -61,12 +103,25 @@ impl RafxCommandBufferGles3 {
         // framebuffer_stencil_bound. The framebuffer and tracking if a texture is bound should
         // be persisted across frames. The state is private to the command pool.
 
+        if self.is_secondary {
+            // The renderpass belongs to the primary command buffer
+            state.surface_size = None;
+        }
+
         state.is_started = false;
         assert!(state.surface_size.is_none());
+        Self::reset_bindings(gl_context, &mut *state)
+    }
+
+    // Forget everything that is bound so that the next commands re-bind what they need
+    fn reset_bindings(
+        gl_context: &GlContext,
+        state: &mut CommandPoolGles3StateInner,
+    ) -> RafxResult<()> {
         state.current_gl_pipeline_info = None;
         state.stencil_reference_value = 0;
         state.clear_bindings();
-        Self::update_vertex_attributes_in_use(gl_context, &mut *state, 0)?;
+        Self::update_vertex_attributes_in_use(gl_context, state, 0)?;
         assert_eq!(state.vertex_attribute_enabled_bits, 0);
         for attribute in &mut state.vertex_attributes {
             *attribute = None;
@@ -288,6 +343,28 @@ impl RafxCommandBufferGles3 {
         Ok(())
     }
 
+    pub fn cmd_begin_render_pass_with_secondary_command_buffers(
+        &self,
+        color_targets: &[RafxColorRenderTargetBinding],
+        depth_target: Option<RafxDepthStencilRenderTargetBinding>,
+    ) -> RafxResult<()> {
+        self.cmd_begin_render_pass(color_targets, depth_target)?;
+
+        // The secondary command buffers track what is bound separately, so make sure that nothing
+        // bound by this command buffer is assumed to still be bound by either
+        let mut state = self.command_pool_state.borrow_mut();
+        let gl_context = self.queue.device_context().gl_context();
+        Self::reset_bindings(gl_context, &mut *state)
+    }
+
+    pub fn cmd_execute_secondary(
+        &self,
+        _secondary_command_buffers: &[&RafxCommandBufferGles3],
+    ) -> RafxResult<()> {
+        // Nothing to do, the secondary command buffers issued their commands as they were written
+        Ok(())
+    }
+
     pub fn cmd_end_render_pass(&self) -> RafxResult<()> {
         let mut state = self.command_pool_state.borrow_mut();
         assert!(state.is_started);
@@ -762,6 +839,38 @@ impl RafxCommandBufferGles3 {
         state.descriptor_sets_update_index[set_index as usize] += 1;
     }
 
+    // Like descriptor sets, this does not affect the program right away. The data is pushed into
+    // the program's uniforms when we draw
+    pub fn cmd_push_constants(
+        &self,
+        root_signature: &RafxRootSignatureGles3,
+        offset_in_bytes: u32,
+        data: &[u8],
+    ) -> RafxResult<()> {
+        let push_constant = root_signature.push_constant();
+        crate::internal_shared::verify_push_constant_range(
+            push_constant.map(|x| x.size_in_bytes),
+            offset_in_bytes,
+            data.len() as u32,
+        )?;
+        let push_constant = push_constant.unwrap();
+
+        let mut state = self.command_pool_state.borrow_mut();
+        assert!(state.is_started);
+
+        // Pushing constants for a different root signature invalidates the previous data
+        if state.push_constant_root_signature.as_ref() != Some(root_signature) {
+            state.push_constant_data = vec![0; push_constant.size_in_bytes as usize];
+            state.push_constant_root_signature = Some(root_signature.clone());
+        }
+
+        let begin = offset_in_bytes as usize;
+        state.push_constant_data[begin..(begin + data.len())].copy_from_slice(data);
+        state.push_constant_update_index += 1;
+
+        Ok(())
+    }
+
     // Call right before drawing, this just checks that the program is up-to-date with the latest
     // bound descriptor sets
     fn ensure_pipeline_bindings_up_to_date(
@@ -774,6 +883,7 @@ impl RafxCommandBufferGles3 {
         // If the program was previously bound by some other command pool, we can't assume it's in
         // the same state as before. Clear the last_descriptor_updates values to ensure that we push
         // all sets into the program state
+        let mut last_push_constant_update = pipeline.last_push_constant_update.borrow_mut();
         let mut last_bound_by_command_pool = pipeline.last_bound_by_command_pool.borrow_mut();
         if *last_bound_by_command_pool != state.id {
             *last_bound_by_command_pool = state.id;
@@ -781,6 +891,18 @@ impl RafxCommandBufferGles3 {
             for set_index in 0..MAX_DESCRIPTOR_SET_LAYOUTS {
                 last_descriptor_updates[set_index] = 0;
             }
+
+            *last_push_constant_update = 0;
+        }
+
+        if let Some(push_constant_root_signature) = &state.push_constant_root_signature {
+            // Only update the program if the pushed constants match the root signature
+            if *push_constant_root_signature == pipeline.root_signature
+                && *last_push_constant_update < state.push_constant_update_index
+            {
+                Self::do_push_constants(gl_context, pipeline, &state.push_constant_data)?;
+                *last_push_constant_update = state.push_constant_update_index;
+            }
         }
 
         if let Some(bound_descriptor_sets_root_signature) =
@@ -812,6 +934,25 @@ impl RafxCommandBufferGles3 {
         Ok(())
     }
 
+    // Sets the uniforms that emulate push constants
+    fn do_push_constants(
+        gl_context: &GlContext,
+        pipeline_info: &Arc<Gles3PipelineInfo>,
+        push_constant_data: &[u8],
+    ) -> RafxResult<()> {
+        for field in &pipeline_info.push_constant_fields {
+            gl_type_util::set_uniform(
+                gl_context,
+                &field.location,
+                &push_constant_data[field.offset as usize],
+                field.gl_type,
+                field.element_count,
+            )?;
+        }
+
+        Ok(())
+    }
+
     // Does the actual descriptor set binding
     fn do_bind_descriptor_set(
         gl_context: &GlContext,
@@ -1086,6 +1227,133 @@ impl RafxCommandBufferGles3 {
         unimplemented!("Compute shaders not supported in GL ES 2.0");
     }
 
+    // GL ES 3.0 has no indirect draws. Buffers that are not bound as vertex, index or uniform
+    // buffers are kept in CPU memory, so the arguments are read when the command is recorded and
+    // issued as regular draws.
+    fn read_indirect_arguments<T: Copy>(
+        buffer: &RafxBufferGles3,
+        offset_in_bytes: u32,
+        count: u32,
+    ) -> RafxResult<Vec<T>> {
+        let data = unsafe {
+            buffer
+                .buffer_contents()
+                .try_as_slice_with_offset(offset_in_bytes as u64)
+        }
+        .ok_or("Indirect argument buffers must be CPU-visible on GL ES 3.0")?;
+
+        let size = std::mem::size_of::<T>();
+        if data.len() < size * count as usize {
+            Err("Indirect argument buffer is too small for the number of draws")?;
+        }
+
+        Ok((0..count as usize)
+            .map(|i| unsafe { std::ptr::read_unaligned(data.as_ptr().add(i * size) as *const T) })
+            .collect())
+    }
+
+    fn read_indirect_count(
+        count_buffer: &RafxBufferGles3,
+        count_buffer_offset_in_bytes: u32,
+        max_draw_count: u32,
+    ) -> RafxResult<u32> {
+        let count =
+            Self::read_indirect_arguments::<u32>(count_buffer, count_buffer_offset_in_bytes, 1)?;
+        Ok(count[0].min(max_draw_count))
+    }
+
+    pub fn cmd_draw_indirect(
+        &self,
+        indirect_buffer: &RafxBufferGles3,
+        indirect_buffer_offset_in_bytes: u32,
+        draw_count: u32,
+    ) -> RafxResult<()> {
+        let commands = Self::read_indirect_arguments::<RafxDrawIndirectCommand>(
+            indirect_buffer,
+            indirect_buffer_offset_in_bytes,
+            draw_count,
+        )?;
+
+        for command in commands {
+            if command.instance_count == 0 {
+                continue;
+            }
+
+            if command.instance_count != 1 || command.first_instance != 0 {
+                Err("Instanced indirect draws are not supported on GL ES 3.0")?;
+            }
+
+            self.cmd_draw(command.vertex_count, command.first_vertex)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_draw_indexed_indirect(
+        &self,
+        indirect_buffer: &RafxBufferGles3,
+        indirect_buffer_offset_in_bytes: u32,
+        draw_count: u32,
+    ) -> RafxResult<()> {
+        let commands = Self::read_indirect_arguments::<RafxDrawIndexedIndirectCommand>(
+            indirect_buffer,
+            indirect_buffer_offset_in_bytes,
+            draw_count,
+        )?;
+
+        for command in commands {
+            if command.instance_count == 0 {
+                continue;
+            }
+
+            if command.instance_count != 1 || command.first_instance != 0 {
+                Err("Instanced indirect draws are not supported on GL ES 3.0")?;
+            }
+
+            self.cmd_draw_indexed(
+                command.index_count,
+                command.first_index,
+                command.vertex_offset,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_draw_indirect_count(
+        &self,
+        indirect_buffer: &RafxBufferGles3,
+        indirect_buffer_offset_in_bytes: u32,
+        count_buffer: &RafxBufferGles3,
+        count_buffer_offset_in_bytes: u32,
+        max_draw_count: u32,
+    ) -> RafxResult<()> {
+        let draw_count =
+            Self::read_indirect_count(count_buffer, count_buffer_offset_in_bytes, max_draw_count)?;
+        self.cmd_draw_indirect(indirect_buffer, indirect_buffer_offset_in_bytes, draw_count)
+    }
+
+    pub fn cmd_draw_indexed_indirect_count(
+        &self,
+        indirect_buffer: &RafxBufferGles3,
+        indirect_buffer_offset_in_bytes: u32,
+        count_buffer: &RafxBufferGles3,
+        count_buffer_offset_in_bytes: u32,
+        max_draw_count: u32,
+    ) -> RafxResult<()> {
+        let draw_count =
+            Self::read_indirect_count(count_buffer, count_buffer_offset_in_bytes, max_draw_count)?;
+        self.cmd_draw_indexed_indirect(indirect_buffer, indirect_buffer_offset_in_bytes, draw_count)
+    }
+
+    pub fn cmd_dispatch_indirect(
+        &self,
+        _indirect_buffer: &RafxBufferGles3,
+        _indirect_buffer_offset_in_bytes: u32,
+    ) -> RafxResult<()> {
+        Err("Compute shaders are not supported in GL ES 3.0")?
+    }
+
     pub fn cmd_resource_barrier(
         &self,
         _buffer_barriers: &[RafxBufferBarrier],
@@ -1121,6 +1389,139 @@ impl RafxCommandBufferGles3 {
         gl_context.gl_bind_buffer(gl_target, NONE_BUFFER)
     }
 
+    pub fn cmd_reset_queries(
+        &self,
+        _query_pool: &RafxQueryPoolGles3,
+        _first_query: u32,
+        _query_count: u32,
+    ) -> RafxResult<()> {
+        // GL queries are implicitly reset when they are written
+        Ok(())
+    }
+
+    pub fn cmd_write_timestamp(
+        &self,
+        query_pool: &RafxQueryPoolGles3,
+        query_index: u32,
+    ) -> RafxResult<()> {
+        let state = self.command_pool_state.borrow();
+        assert!(state.is_started);
+        assert_eq!(
+            query_pool.query_pool_def().query_type,
+            RafxQueryType::Timestamp
+        );
+
+        let gl_context = self.queue.device_context().gl_context();
+        gl_context.gl_query_counter(
+            query_pool.gl_query_id(query_index),
+            gles3_bindings::TIMESTAMP_EXT,
+        )
+    }
+
+    pub fn cmd_begin_query(
+        &self,
+        _query_pool: &RafxQueryPoolGles3,
+        _query_index: u32,
+    ) -> RafxResult<()> {
+        Err("Pipeline statistics queries are not supported on GL ES 3.0")?
+    }
+
+    pub fn cmd_end_query(
+        &self,
+        _query_pool: &RafxQueryPoolGles3,
+        _query_index: u32,
+    ) -> RafxResult<()> {
+        Err("Pipeline statistics queries are not supported on GL ES 3.0")?
+    }
+
+    pub fn cmd_resolve_queries(
+        &self,
+        query_pool: &RafxQueryPoolGles3,
+        first_query: u32,
+        query_count: u32,
+        dst_buffer: &RafxBufferGles3,
+        dst_offset: u64,
+    ) -> RafxResult<()> {
+        let state = self.command_pool_state.borrow();
+        assert!(state.is_started);
+
+        // Commands are executed immediately, so this stalls until the GPU catches up
+        let results = query_pool.wait_for_results(first_query, query_count)?;
+        let size = (results.len() * std::mem::size_of::<u64>()) as u64;
+        assert!(dst_offset + size <= dst_buffer.buffer_def().size);
+
+        if let Some(dst_data) = unsafe { dst_buffer.buffer_contents().try_as_mut_ptr() } {
+            unsafe {
+                std::ptr::copy_nonoverlapping(
+                    results.as_ptr() as *const u8,
+                    dst_data.add(dst_offset as usize),
+                    size as usize,
+                );
+            }
+        }
+
+        if let Some(buffer_id) = dst_buffer.gl_buffer_id() {
+            let gl_context = self.queue.device_context().gl_context();
+            let gl_target = dst_buffer.gl_target();
+            gl_context.gl_bind_buffer(gl_target, buffer_id)?;
+            gl_context.gl_buffer_sub_data(
+                gl_target,
+                dst_offset as _,
+                size,
+                results.as_ptr() as *const u8,
+            )?;
+            gl_context.gl_bind_buffer(gl_target, NONE_BUFFER)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_push_group_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) -> RafxResult<()> {
+        let state = self.command_pool_state.borrow();
+        assert!(state.is_started);
+
+        let device_context = self.queue.device_context();
+        if device_context.device_info().debug_names_enabled {
+            device_context
+                .gl_context()
+                .gl_push_debug_group(name.as_ref())?;
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_pop_group_debug_name(&self) -> RafxResult<()> {
+        let state = self.command_pool_state.borrow();
+        assert!(state.is_started);
+
+        let device_context = self.queue.device_context();
+        if device_context.device_info().debug_names_enabled {
+            device_context.gl_context().gl_pop_debug_group()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_insert_debug_marker(
+        &self,
+        name: impl AsRef<str>,
+    ) -> RafxResult<()> {
+        let state = self.command_pool_state.borrow();
+        assert!(state.is_started);
+
+        let device_context = self.queue.device_context();
+        if device_context.device_info().debug_names_enabled {
+            device_context
+                .gl_context()
+                .gl_debug_message_insert(name.as_ref())?;
+        }
+
+        Ok(())
+    }
+
     pub fn cmd_copy_buffer_to_texture(
         &self,
         src_buffer: &RafxBufferGles3,
@@ -1169,4 +1570,223 @@ impl RafxCommandBufferGles3 {
         )?;
         gl_context.gl_bind_texture(dst_texture.gl_target(), NONE_TEXTURE)
     }
+
+    // GL ES can only read texels through a framebuffer, so the source texture is attached to a
+    // temporary framebuffer
+    fn bind_read_framebuffer(
+        gl_context: &GlContext,
+        texture: &RafxTextureGles3,
+        array_layer: u16,
+        mip_level: u8,
+    ) -> RafxResult<FramebufferId> {
+        let format = texture.texture_def().format;
+        if format.has_depth() || format.has_stencil() {
+            Err("Copying from a depth/stencil texture is not supported in GL ES 3.0")?;
+        }
+
+        let framebuffer_id = gl_context.gl_create_framebuffer()?;
+        gl_context.gl_bind_framebuffer(gles3_bindings::FRAMEBUFFER, framebuffer_id)?;
+        Self::bind_framebuffer(
+            gl_context,
+            texture,
+            gles3_bindings::COLOR_ATTACHMENT0,
+            array_layer,
+            mip_level,
+        )?;
+
+        let result = gl_context.gl_check_framebuffer_status(gles3_bindings::FRAMEBUFFER)?;
+        if result != gles3_bindings::FRAMEBUFFER_COMPLETE {
+            Self::unbind_read_framebuffer(gl_context, framebuffer_id)?;
+            Err(format!(
+                "Framebuffer Status is not FRAMEBUFFER_COMPLETE, result: {:#x}",
+                result
+            ))?;
+        }
+
+        Ok(framebuffer_id)
+    }
+
+    fn unbind_read_framebuffer(
+        gl_context: &GlContext,
+        framebuffer_id: FramebufferId,
+    ) -> RafxResult<()> {
+        gl_context.gl_bind_framebuffer(gles3_bindings::FRAMEBUFFER, NONE_FRAMEBUFFER)?;
+        gl_context.gl_destroy_framebuffer(framebuffer_id)
+    }
+
+    fn do_read_pixels(
+        gl_context: &GlContext,
+        src_texture: &RafxTextureGles3,
+        offset: RafxOffset3D,
+        extents: RafxExtents3D,
+        row_pitch: usize,
+        row_size: usize,
+        dst: &mut [u8],
+    ) -> RafxResult<()> {
+        let format_info = src_texture.gl_format_info();
+        let pack_alignment = gl_context.gl_get_integerv(gles3_bindings::PACK_ALIGNMENT);
+        gl_context.gl_pixel_storei(gles3_bindings::PACK_ALIGNMENT, 1)?;
+
+        // Rows are not flipped here. glReadPixels returns rows starting from y = 0, which is the
+        // first row in memory. Since GL rendering is done with a flipped Y axis (see the front face
+        // note in pipeline.rs) the first row in memory is the top of the image, same as the first
+        // row written by Vulkan and Metal.
+        //
+        // Read everything at once if the rows are tightly packed, otherwise read one row at a time
+        let result = if row_pitch == row_size {
+            gl_context.gl_read_pixels(
+                offset.x as _,
+                offset.y as _,
+                extents.width,
+                extents.height,
+                format_info.gl_format,
+                format_info.gl_type,
+                dst,
+            )
+        } else {
+            (0..extents.height).try_for_each(|row| {
+                let begin = row as usize * row_pitch;
+                gl_context.gl_read_pixels(
+                    offset.x as _,
+                    (offset.y + row) as _,
+                    extents.width,
+                    1,
+                    format_info.gl_format,
+                    format_info.gl_type,
+                    &mut dst[begin..begin + row_size],
+                )
+            })
+        };
+
+        gl_context.gl_pixel_storei(gles3_bindings::PACK_ALIGNMENT, pack_alignment)?;
+        result
+    }
+
+    pub fn cmd_copy_texture_to_buffer(
+        &self,
+        src_texture: &RafxTextureGles3,
+        dst_buffer: &RafxBufferGles3,
+        params: &RafxCmdCopyTextureToBufferParams,
+    ) -> RafxResult<()> {
+        let state = self.command_pool_state.borrow();
+        assert!(state.is_started);
+        if state.surface_size.is_some() {
+            Err("cmd_copy_texture_to_buffer cannot be called during a render pass")?;
+        }
+
+        let texture_def = src_texture.texture_def();
+        let format = texture_def.format;
+        let extents = params.copy_extents(texture_def);
+        if extents.depth != 1 || params.src_offset.z != 0 {
+            Err("Only a single depth slice can be copied at a time in GL ES 3.0")?;
+        }
+
+        // Nothing to copy, and the size calculation below requires at least one row
+        if extents.width == 0 || extents.height == 0 {
+            return Ok(());
+        }
+
+        //TODO: Compressed texture support?
+        let row_pitch = params.row_pitch(format, extents) as usize;
+        let row_size = (extents.width * format.block_or_pixel_size_in_bytes()) as usize;
+        let size = row_pitch * (extents.height as usize - 1) + row_size;
+        assert!(params.buffer_offset + size as u64 <= dst_buffer.buffer_def().size);
+
+        let dst = unsafe {
+            let dst_data = dst_buffer
+                .buffer_contents()
+                .try_as_mut_ptr()
+                .ok_or("dst buffer must be CPU-visible in cmd_copy_texture_to_buffer")?;
+            std::slice::from_raw_parts_mut(dst_data.add(params.buffer_offset as usize), size)
+        };
+
+        // Commands are executed immediately, so this stalls until the GPU catches up
+        let gl_context = self.queue.device_context().gl_context();
+        let framebuffer_id = Self::bind_read_framebuffer(
+            gl_context,
+            src_texture,
+            params.array_layer,
+            params.mip_level,
+        )?;
+        let result = Self::do_read_pixels(
+            gl_context,
+            src_texture,
+            params.src_offset,
+            extents,
+            row_pitch,
+            row_size,
+            dst,
+        );
+        Self::unbind_read_framebuffer(gl_context, framebuffer_id)?;
+        result?;
+
+        if let Some(buffer_id) = dst_buffer.gl_buffer_id() {
+            let gl_target = dst_buffer.gl_target();
+            gl_context.gl_bind_buffer(gl_target, buffer_id)?;
+            gl_context.gl_buffer_sub_data(
+                gl_target,
+                params.buffer_offset as _,
+                size as u64,
+                dst.as_ptr(),
+            )?;
+            gl_context.gl_bind_buffer(gl_target, NONE_BUFFER)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_copy_texture_to_texture(
+        &self,
+        src_texture: &RafxTextureGles3,
+        dst_texture: &RafxTextureGles3,
+        params: &RafxCmdCopyTextureToTextureParams,
+    ) -> RafxResult<()> {
+        let state = self.command_pool_state.borrow();
+        assert!(state.is_started);
+        if state.surface_size.is_some() {
+            Err("cmd_copy_texture_to_texture cannot be called during a render pass")?;
+        }
+
+        let extents = params.copy_extents(src_texture.texture_def());
+        if extents.depth != 1 || params.src_offset.z != 0 || params.dst_offset.z != 0 {
+            Err("Only a single depth slice can be copied at a time in GL ES 3.0")?;
+        }
+
+        let dst_texture_id = dst_texture
+            .gl_raw_image()
+            .gl_texture_id()
+            .ok_or("Cannot use cmd_copy_texture_to_texture with swapchain image in GL ES 3.0")?;
+
+        let mut dst_subtarget = dst_texture.gl_target();
+        if dst_subtarget == gles3_bindings::TEXTURE_CUBE_MAP {
+            dst_subtarget = array_layer_to_cube_map_target(params.dst_array_layer);
+        }
+
+        let gl_context = self.queue.device_context().gl_context();
+        let framebuffer_id = Self::bind_read_framebuffer(
+            gl_context,
+            src_texture,
+            params.src_array_layer,
+            params.src_mip_level,
+        )?;
+
+        let result = gl_context
+            .gl_bind_texture(dst_texture.gl_target(), dst_texture_id)
+            .and_then(|_| {
+                gl_context.gl_copy_tex_sub_image_2d(
+                    dst_subtarget,
+                    params.dst_mip_level,
+                    params.dst_offset.x as _,
+                    params.dst_offset.y as _,
+                    params.src_offset.x as _,
+                    params.src_offset.y as _,
+                    extents.width,
+                    extents.height,
+                )
+            })
+            .and_then(|_| gl_context.gl_bind_texture(dst_texture.gl_target(), NONE_TEXTURE));
+
+        Self::unbind_read_framebuffer(gl_context, framebuffer_id)?;
+        result
+    }
 }
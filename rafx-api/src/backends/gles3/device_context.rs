@@ -1,17 +1,18 @@
 use crate::{
-    RafxApiDefGles3, RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef,
-    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxQueueType,
-    RafxResourceType, RafxResult, RafxRootSignatureDef, RafxSampleCount, RafxSamplerDef,
-    RafxShaderModuleDefGles3, RafxShaderStageDef, RafxSwapchainDef, RafxTextureDef,
+    RafxApiDef, RafxApiDefGles3, RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef,
+    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxPipelineCacheDef,
+    RafxQueryPoolDef, RafxQueueType, RafxResourceType, RafxResult, RafxRootSignatureDef,
+    RafxSampleCount, RafxSamplerDef, RafxShaderModuleDefGles3, RafxShaderStageDef,
+    RafxSwapchainDef, RafxTextureDef,
 };
 use raw_window_handle::HasRawWindowHandle;
 use std::sync::Arc;
 
 use crate::gles3::{
     GlContextManager, RafxBufferGles3, RafxDescriptorSetArrayGles3, RafxFenceGles3,
-    RafxPipelineGles3, RafxQueueGles3, RafxRootSignatureGles3, RafxSamplerGles3,
-    RafxSemaphoreGles3, RafxShaderGles3, RafxShaderModuleGles3, RafxSwapchainGles3,
-    RafxTextureGles3,
+    RafxPipelineCacheGles3, RafxPipelineGles3, RafxQueryPoolGles3, RafxQueueGles3,
+    RafxRootSignatureGles3, RafxSamplerGles3, RafxSemaphoreGles3, RafxShaderGles3,
+    RafxShaderModuleGles3, RafxSwapchainGles3, RafxTextureGles3,
 };
 
 use crate::gles3::gles3_bindings;
@@ -56,6 +57,7 @@ impl Drop for RafxDeviceContextGles3Inner {
 impl RafxDeviceContextGles3Inner {
     pub fn new(
         window: &dyn HasRawWindowHandle,
+        api_def: &RafxApiDef,
         gl_api_def: &RafxApiDefGles3,
     ) -> RafxResult<Self> {
         log::debug!("Initializing GL backend");
@@ -81,6 +83,10 @@ impl RafxDeviceContextGles3Inner {
             gl_context.gl_get_integerv(gles3_bindings::UNIFORM_BUFFER_OFFSET_ALIGNMENT) as u32;
         //let min_storage_buffer_offset_alignment = gl_context.gl_get_integerv(gles2_bindings::STORAGE_BUFFER_OFFSET_ALIGNMENT);
 
+        // Timer queries are an extension on ES 3.0, and core in desktop GL 3.3
+        let supports_timestamp_queries = gl_context.has_extension("GL_EXT_disjoint_timer_query")
+            || gl_context.has_extension("GL_ARB_timer_query");
+
         let device_info = RafxDeviceInfo {
             min_uniform_buffer_offset_alignment,
             min_storage_buffer_offset_alignment: pack_alignment,
@@ -88,6 +94,21 @@ impl RafxDeviceContextGles3Inner {
             upload_buffer_texture_row_alignment: pack_alignment,
             supports_clamp_to_border_color: false, // requires GLES 3.2 or an extension
             max_vertex_attribute_count,
+            // Timer queries return nanoseconds
+            timestamp_period_ns: 1.0,
+            supports_timestamp_queries,
+            supports_pipeline_statistics_queries: false,
+            // Indirect draws are emulated by reading the arguments on the CPU when the command is
+            // recorded, so arguments written by the GPU would not be seen. The emulation works for
+            // arguments written by the CPU, but isn't advertised as support.
+            supports_indirect_draw: false,
+            max_indirect_draw_count: 0,
+            supports_indirect_draw_count: false,
+            supports_indirect_dispatch: false,
+            // Push constants are emulated with uniforms
+            max_push_constant_size: u32::MAX,
+            debug_names_enabled: api_def.enable_debug_names && gl_context.supports_debug_labels(),
+            supports_multithreaded_command_recording: false,
         };
 
         // Enable sRGB framebuffers on desktop GL. This is enabled by default on ES 3.0
@@ -244,6 +265,20 @@ impl RafxDeviceContextGles3 {
         RafxSemaphoreGles3::new(self)
     }
 
+    pub fn create_query_pool(
+        &self,
+        query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPoolGles3> {
+        RafxQueryPoolGles3::new(self, query_pool_def)
+    }
+
+    pub fn create_pipeline_cache(
+        &self,
+        pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCacheGles3> {
+        RafxPipelineCacheGles3::new(self, pipeline_cache_def)
+    }
+
     pub fn create_swapchain(
         &self,
         raw_window_handle: &dyn HasRawWindowHandle,
@@ -329,6 +364,7 @@ impl RafxDeviceContextGles3 {
     ) -> Option<RafxFormat> {
         if resource_type.intersects(RafxResourceType::RENDER_TARGET_DEPTH_STENCIL)
             || resource_type.intersects(RafxResourceType::RENDER_TARGET_COLOR)
+            || resource_type.intersects(RafxResourceType::TEXTURE)
         {
             for &candidate in candidates {
                 if candidate.gles3_texture_format_info().is_some() {
@@ -219,6 +219,8 @@ pub const BUFFER_MAP_OFFSET: types::GLenum = 0x9121;
 #[allow(dead_code, non_upper_case_globals)]
 pub const BUFFER_MAP_POINTER: types::GLenum = 0x88BD;
 #[allow(dead_code, non_upper_case_globals)]
+pub const BUFFER: types::GLenum = 0x82E0;
+#[allow(dead_code, non_upper_case_globals)]
 pub const BUFFER_SIZE: types::GLenum = 0x8764;
 #[allow(dead_code, non_upper_case_globals)]
 pub const BUFFER_USAGE: types::GLenum = 0x8765;
@@ -606,6 +608,8 @@ pub const GENERATE_MIPMAP_HINT: types::GLenum = 0x8192;
 #[allow(dead_code, non_upper_case_globals)]
 pub const GEQUAL: types::GLenum = 0x0206;
 #[allow(dead_code, non_upper_case_globals)]
+pub const GPU_DISJOINT_EXT: types::GLenum = 0x8FBB;
+#[allow(dead_code, non_upper_case_globals)]
 pub const GREATER: types::GLenum = 0x0204;
 #[allow(dead_code, non_upper_case_globals)]
 pub const GREEN: types::GLenum = 0x1904;
@@ -858,6 +862,8 @@ pub const POLYGON_OFFSET_FACTOR: types::GLenum = 0x8038;
 #[allow(dead_code, non_upper_case_globals)]
 pub const POLYGON_OFFSET_FILL: types::GLenum = 0x8037;
 #[allow(dead_code, non_upper_case_globals)]
+pub const PROGRAM: types::GLenum = 0x82E2;
+#[allow(dead_code, non_upper_case_globals)]
 pub const POLYGON_OFFSET_UNITS: types::GLenum = 0x2A00;
 #[allow(dead_code, non_upper_case_globals)]
 pub const PRIMITIVE_RESTART_FIXED_INDEX: types::GLenum = 0x8D69;
@@ -1282,6 +1288,8 @@ pub const TEXTURE_WRAP_T: types::GLenum = 0x2803;
 #[allow(dead_code, non_upper_case_globals)]
 pub const TIMEOUT_EXPIRED: types::GLenum = 0x911B;
 #[allow(dead_code, non_upper_case_globals)]
+pub const TIMESTAMP_EXT: types::GLenum = 0x8E28;
+#[allow(dead_code, non_upper_case_globals)]
 pub const TIMEOUT_IGNORED: types::GLuint64 = 0xFFFFFFFFFFFFFFFF;
 #[allow(dead_code, non_upper_case_globals)]
 pub const TRANSFORM_FEEDBACK: types::GLenum = 0x8E22;
@@ -1567,6 +1575,12 @@ pub struct Gles3 {
     pub DebugMessageControl: FnPtr,
     /// Fallbacks: DebugMessageInsertARB, DebugMessageInsertKHR
     pub DebugMessageInsert: FnPtr,
+    /// Fallbacks: ObjectLabelKHR
+    pub ObjectLabel: FnPtr,
+    /// Fallbacks: PopDebugGroupKHR
+    pub PopDebugGroup: FnPtr,
+    /// Fallbacks: PushDebugGroupKHR
+    pub PushDebugGroup: FnPtr,
 
     /// Fallbacks: DeleteBuffersARB
     pub DeleteBuffers: FnPtr,
@@ -1675,6 +1689,8 @@ pub struct Gles3 {
     pub GetProgramBinary: FnPtr,
     pub GetProgramInfoLog: FnPtr,
     pub GetProgramiv: FnPtr,
+    /// Fallbacks: GetQueryObjectui64v
+    pub GetQueryObjectui64vEXT: FnPtr,
     /// Fallbacks: GetQueryObjectuivARB
     pub GetQueryObjectuiv: FnPtr,
     /// Fallbacks: GetQueryivARB
@@ -1751,6 +1767,8 @@ pub struct Gles3 {
     pub ProgramBinary: FnPtr,
     /// Fallbacks: ProgramParameteriARB, ProgramParameteriEXT
     pub ProgramParameteri: FnPtr,
+    /// Fallbacks: QueryCounter
+    pub QueryCounterEXT: FnPtr,
     pub ReadBuffer: FnPtr,
     pub ReadPixels: FnPtr,
     pub ReleaseShaderCompiler: FnPtr,
@@ -2038,6 +2056,9 @@ impl Gles3 {
                 "glDebugMessageInsert",
                 &["glDebugMessageInsertARB", "glDebugMessageInsertKHR"],
             )),
+            ObjectLabel: FnPtr::new(metaloadfn("glObjectLabel", &["glObjectLabelKHR"])),
+            PopDebugGroup: FnPtr::new(metaloadfn("glPopDebugGroup", &["glPopDebugGroupKHR"])),
+            PushDebugGroup: FnPtr::new(metaloadfn("glPushDebugGroup", &["glPushDebugGroupKHR"])),
 
             DeleteBuffers: FnPtr::new(metaloadfn("glDeleteBuffers", &["glDeleteBuffersARB"])),
             DeleteFramebuffers: FnPtr::new(metaloadfn(
@@ -2197,6 +2218,10 @@ impl Gles3 {
             )),
             GetProgramInfoLog: FnPtr::new(metaloadfn("glGetProgramInfoLog", &[])),
             GetProgramiv: FnPtr::new(metaloadfn("glGetProgramiv", &[])),
+            GetQueryObjectui64vEXT: FnPtr::new(metaloadfn(
+                "glGetQueryObjectui64vEXT",
+                &["glGetQueryObjectui64v"],
+            )),
             GetQueryObjectuiv: FnPtr::new(metaloadfn(
                 "glGetQueryObjectuiv",
                 &["glGetQueryObjectuivARB"],
@@ -2288,6 +2313,7 @@ impl Gles3 {
                 "glProgramParameteri",
                 &["glProgramParameteriARB", "glProgramParameteriEXT"],
             )),
+            QueryCounterEXT: FnPtr::new(metaloadfn("glQueryCounterEXT", &["glQueryCounter"])),
             ReadBuffer: FnPtr::new(metaloadfn("glReadBuffer", &[])),
             ReadPixels: FnPtr::new(metaloadfn("glReadPixels", &[])),
             ReleaseShaderCompiler: FnPtr::new(metaloadfn("glReleaseShaderCompiler", &[])),
@@ -3260,6 +3286,52 @@ impl Gles3 {
         >(self.DebugMessageInsert.f)(source, type_, id, severity, length, buf)
     }
 
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn ObjectLabel(
+        &self,
+        identifier: types::GLenum,
+        name: types::GLuint,
+        length: types::GLsizei,
+        label: *const types::GLchar,
+    ) -> () {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(
+                types::GLenum,
+                types::GLuint,
+                types::GLsizei,
+                *const types::GLchar,
+            ) -> (),
+        >(self.ObjectLabel.f)(identifier, name, length, label)
+    }
+
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn PopDebugGroup(&self) -> () {
+        __gl_imports::mem::transmute::<_, extern "system" fn() -> ()>(self.PopDebugGroup.f)()
+    }
+
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
+    pub unsafe fn PushDebugGroup(
+        &self,
+        source: types::GLenum,
+        id: types::GLuint,
+        length: types::GLsizei,
+        message: *const types::GLchar,
+    ) -> () {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(
+                types::GLenum,
+                types::GLuint,
+                types::GLsizei,
+                *const types::GLchar,
+            ) -> (),
+        >(self.PushDebugGroup.f)(source, id, length, message)
+    }
+
     #[allow(non_snake_case, unused_variables, dead_code)]
     #[inline]
     pub unsafe fn DeleteBuffers(
@@ -4170,6 +4242,19 @@ impl Gles3 {
     }
     #[allow(non_snake_case, unused_variables, dead_code)]
     #[inline]
+    pub unsafe fn GetQueryObjectui64vEXT(
+        &self,
+        id: types::GLuint,
+        pname: types::GLenum,
+        params: *mut types::GLuint64,
+    ) -> () {
+        __gl_imports::mem::transmute::<
+            _,
+            extern "system" fn(types::GLuint, types::GLenum, *mut types::GLuint64) -> (),
+        >(self.GetQueryObjectui64vEXT.f)(id, pname, params)
+    }
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
     pub unsafe fn GetQueryObjectuiv(
         &self,
         id: types::GLuint,
@@ -4822,6 +4907,17 @@ impl Gles3 {
     }
     #[allow(non_snake_case, unused_variables, dead_code)]
     #[inline]
+    pub unsafe fn QueryCounterEXT(
+        &self,
+        id: types::GLuint,
+        target: types::GLenum,
+    ) -> () {
+        __gl_imports::mem::transmute::<_, extern "system" fn(types::GLuint, types::GLenum) -> ()>(
+            self.QueryCounterEXT.f,
+        )(id, target)
+    }
+    #[allow(non_snake_case, unused_variables, dead_code)]
+    #[inline]
     pub unsafe fn ReadBuffer(
         &self,
         src: types::GLenum,
@@ -51,6 +51,9 @@ pub const NONE_SHADER: ShaderId = ShaderId(gles3_bindings::NONE);
 pub struct ProgramId(pub u32);
 pub const NONE_PROGRAM: ProgramId = ProgramId(gles3_bindings::NONE);
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct QueryId(pub u32);
+
 pub struct ActiveUniformInfo {
     pub name: CString,
     pub size: u32,
@@ -4,7 +4,8 @@ use super::gles3_bindings::Gles3;
 use super::WindowHash;
 use crate::gles3::gles3_bindings::types::{GLboolean, GLint};
 use crate::gles3::{
-    ActiveUniformInfo, BufferId, FramebufferId, ProgramId, RenderbufferId, ShaderId, TextureId,
+    ActiveUniformInfo, BufferId, FramebufferId, ProgramId, QueryId, RenderbufferId, ShaderId,
+    TextureId,
 };
 use crate::internal_shared::gl_window;
 use crate::{RafxError, RafxResult};
@@ -256,6 +257,79 @@ impl GlContext {
         gl_get_integerv(&self.gles3, pname)
     }
 
+    // KHR_debug is not available on all platforms. If it is not, debug groups/labels are ignored
+    pub fn supports_debug_labels(&self) -> bool {
+        self.gles3.PushDebugGroup.is_loaded() && self.gles3.ObjectLabel.is_loaded()
+    }
+
+    pub fn gl_push_debug_group(
+        &self,
+        message: &str,
+    ) -> RafxResult<()> {
+        if self.gles3.PushDebugGroup.is_loaded() {
+            unsafe {
+                self.gles3.PushDebugGroup(
+                    gles3_bindings::DEBUG_SOURCE_APPLICATION,
+                    0,
+                    message.len() as _,
+                    message.as_ptr() as _,
+                );
+            }
+            self.check_for_error()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn gl_pop_debug_group(&self) -> RafxResult<()> {
+        if self.gles3.PopDebugGroup.is_loaded() {
+            unsafe {
+                self.gles3.PopDebugGroup();
+            }
+            self.check_for_error()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn gl_debug_message_insert(
+        &self,
+        message: &str,
+    ) -> RafxResult<()> {
+        if self.gles3.DebugMessageInsert.is_loaded() {
+            unsafe {
+                self.gles3.DebugMessageInsert(
+                    gles3_bindings::DEBUG_SOURCE_APPLICATION,
+                    gles3_bindings::DEBUG_TYPE_MARKER,
+                    0,
+                    gles3_bindings::DEBUG_SEVERITY_NOTIFICATION,
+                    message.len() as _,
+                    message.as_ptr() as _,
+                );
+            }
+            self.check_for_error()?;
+        }
+
+        Ok(())
+    }
+
+    pub fn gl_object_label(
+        &self,
+        identifier: GLenum,
+        name: u32,
+        label: &str,
+    ) -> RafxResult<()> {
+        if self.gles3.ObjectLabel.is_loaded() {
+            unsafe {
+                self.gles3
+                    .ObjectLabel(identifier, name, label.len() as _, label.as_ptr() as _);
+            }
+            self.check_for_error()?;
+        }
+
+        Ok(())
+    }
+
     pub fn gl_get_string(
         &self,
         pname: u32,
@@ -425,6 +499,65 @@ impl GlContext {
         }
     }
 
+    pub fn gl_create_query(&self) -> RafxResult<QueryId> {
+        unsafe {
+            let mut query = 0;
+            self.gles3.GenQueries(1, &mut query);
+            self.check_for_error()?;
+            Ok(QueryId(query))
+        }
+    }
+
+    pub fn gl_destroy_query(
+        &self,
+        query_id: QueryId,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.gles3.DeleteQueries(1, &query_id.0);
+            self.check_for_error()
+        }
+    }
+
+    // Requires GL_EXT_disjoint_timer_query (or GL 3.3 on desktop)
+    pub fn gl_query_counter(
+        &self,
+        query_id: QueryId,
+        target: GLenum,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.gles3.QueryCounterEXT(query_id.0, target);
+            self.check_for_error()
+        }
+    }
+
+    pub fn gl_get_query_object_uiv(
+        &self,
+        query_id: QueryId,
+        pname: GLenum,
+    ) -> RafxResult<u32> {
+        unsafe {
+            let mut value = 0;
+            self.gles3.GetQueryObjectuiv(query_id.0, pname, &mut value);
+            self.check_for_error()?;
+            Ok(value)
+        }
+    }
+
+    // Requires GL_EXT_disjoint_timer_query (or GL 3.3 on desktop)
+    pub fn gl_get_query_object_ui64v(
+        &self,
+        query_id: QueryId,
+        pname: GLenum,
+    ) -> RafxResult<u64> {
+        unsafe {
+            let mut value = 0;
+            self.gles3
+                .GetQueryObjectui64vEXT(query_id.0, pname, &mut value);
+            self.check_for_error()?;
+            Ok(value)
+        }
+    }
+
     pub fn gl_bind_buffer(
         &self,
         target: GLenum,
@@ -1370,6 +1503,56 @@ impl GlContext {
         }
     }
 
+    pub fn gl_read_pixels(
+        &self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        format: GLenum,
+        type_: GLenum,
+        pixels: &mut [u8],
+    ) -> RafxResult<()> {
+        unsafe {
+            self.gles3.ReadPixels(
+                x,
+                y,
+                width as _,
+                height as _,
+                format,
+                type_,
+                pixels.as_mut_ptr() as _,
+            );
+            self.check_for_error()
+        }
+    }
+
+    pub fn gl_copy_tex_sub_image_2d(
+        &self,
+        target: GLenum,
+        mip_level: u8,
+        x_offset: i32,
+        y_offset: i32,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.gles3.CopyTexSubImage2D(
+                target,
+                mip_level as _,
+                x_offset,
+                y_offset,
+                x,
+                y,
+                width as _,
+                height as _,
+            );
+            self.check_for_error()
+        }
+    }
+
     pub fn gl_tex_parameteri(
         &self,
         target: GLenum,
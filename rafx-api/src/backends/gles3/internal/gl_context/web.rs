@@ -1,6 +1,6 @@
 use crate::gles3::gles3_bindings::types::*;
 use crate::gles3::{
-    gles3_bindings, ActiveUniformInfo, BufferId, FramebufferId, ProgramId, RenderbufferId,
+    gles3_bindings, ActiveUniformInfo, BufferId, FramebufferId, ProgramId, QueryId, RenderbufferId,
     ShaderId, TextureId, WindowHash, NONE_BUFFER, NONE_FRAMEBUFFER, NONE_PROGRAM,
     NONE_RENDERBUFFER, NONE_TEXTURE,
 };
@@ -165,6 +165,38 @@ impl GlContext {
         convert_js_to_i32(&self.context.get_parameter(pname).unwrap()).unwrap()
     }
 
+    // WebGL does not support KHR_debug, so debug groups/labels are ignored
+    pub fn supports_debug_labels(&self) -> bool {
+        false
+    }
+
+    pub fn gl_push_debug_group(
+        &self,
+        _message: &str,
+    ) -> RafxResult<()> {
+        Ok(())
+    }
+
+    pub fn gl_pop_debug_group(&self) -> RafxResult<()> {
+        Ok(())
+    }
+
+    pub fn gl_debug_message_insert(
+        &self,
+        _message: &str,
+    ) -> RafxResult<()> {
+        Ok(())
+    }
+
+    pub fn gl_object_label(
+        &self,
+        _identifier: GLenum,
+        _name: u32,
+        _label: &str,
+    ) -> RafxResult<()> {
+        Ok(())
+    }
+
     pub fn gl_get_string(
         &self,
         pname: u32,
@@ -310,6 +342,43 @@ impl GlContext {
         self.check_for_error()
     }
 
+    // WebGL exposes timer queries through EXT_disjoint_timer_query objects, which are not
+    // supported yet
+    pub fn gl_create_query(&self) -> RafxResult<QueryId> {
+        Err("Queries are not supported on web")?
+    }
+
+    pub fn gl_destroy_query(
+        &self,
+        _query_id: QueryId,
+    ) -> RafxResult<()> {
+        Err("Queries are not supported on web")?
+    }
+
+    pub fn gl_query_counter(
+        &self,
+        _query_id: QueryId,
+        _target: GLenum,
+    ) -> RafxResult<()> {
+        Err("Queries are not supported on web")?
+    }
+
+    pub fn gl_get_query_object_uiv(
+        &self,
+        _query_id: QueryId,
+        _pname: GLenum,
+    ) -> RafxResult<u32> {
+        Err("Queries are not supported on web")?
+    }
+
+    pub fn gl_get_query_object_ui64v(
+        &self,
+        _query_id: QueryId,
+        _pname: GLenum,
+    ) -> RafxResult<u64> {
+        Err("Queries are not supported on web")?
+    }
+
     pub fn gl_bind_buffer(
         &self,
         target: GLenum,
@@ -1141,6 +1210,54 @@ impl GlContext {
         self.check_for_error()
     }
 
+    pub fn gl_read_pixels(
+        &self,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+        format: GLenum,
+        type_: GLenum,
+        pixels: &mut [u8],
+    ) -> RafxResult<()> {
+        self.context
+            .read_pixels_with_opt_u8_array(
+                x,
+                y,
+                width as _,
+                height as _,
+                format,
+                type_,
+                Some(pixels),
+            )
+            .map_err(|x| format!("{:?}", x))?;
+        self.check_for_error()
+    }
+
+    pub fn gl_copy_tex_sub_image_2d(
+        &self,
+        target: GLenum,
+        mip_level: u8,
+        x_offset: i32,
+        y_offset: i32,
+        x: i32,
+        y: i32,
+        width: u32,
+        height: u32,
+    ) -> RafxResult<()> {
+        self.context.copy_tex_sub_image_2d(
+            target,
+            mip_level as _,
+            x_offset,
+            y_offset,
+            x,
+            y,
+            width as _,
+            height as _,
+        );
+        self.check_for_error()
+    }
+
     pub fn gl_tex_parameteri(
         &self,
         target: GLenum,
@@ -23,6 +23,15 @@ pub(crate) struct Gles3Attribute {
     pub(crate) byte_offset: u32,
 }
 
+// A member of the uniform struct that emulates push constants
+#[derive(Debug, Clone)]
+pub(crate) struct Gles3PushConstantField {
+    pub(crate) location: LocationId,
+    pub(crate) gl_type: GLenum,
+    pub(crate) element_count: u32,
+    pub(crate) offset: u32,
+}
+
 #[derive(Debug)]
 pub(crate) struct Gles3PipelineInfo {
     pub(crate) gl_rasterizer_state: Gles3RasterizerState,
@@ -33,8 +42,10 @@ pub(crate) struct Gles3PipelineInfo {
     pub(crate) program_id: ProgramId,
     resource_locations: Vec<Option<LocationId>>,
     pub(crate) uniform_block_sizes: Vec<Option<u32>>,
+    pub(crate) push_constant_fields: Vec<Gles3PushConstantField>,
     pub(crate) root_signature: RafxRootSignatureGles3,
     pub(crate) last_descriptor_updates: TrustCell<[u64; MAX_DESCRIPTOR_SET_LAYOUTS]>,
+    pub(crate) last_push_constant_update: TrustCell<u64>,
     pub(crate) last_bound_by_command_pool: TrustCell<u32>,
 }
 
@@ -86,6 +97,26 @@ impl RafxPipelineGles3 {
         self.gl_pipeline_info.program_id
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        let device_context = self
+            .root_signature
+            .gles3_root_signature()
+            .unwrap()
+            .device_context();
+        if device_context.device_info().debug_names_enabled {
+            if let Err(e) = device_context.gl_context().gl_object_label(
+                gles3_bindings::PROGRAM,
+                self.gl_pipeline_info.program_id.0,
+                name.as_ref(),
+            ) {
+                log::warn!("Failed to set debug name on pipeline: {:?}", e);
+            }
+        }
+    }
+
     pub(crate) fn gl_pipeline_info(&self) -> &Arc<Gles3PipelineInfo> {
         &self.gl_pipeline_info
     }
@@ -189,6 +220,40 @@ impl RafxPipelineGles3 {
             }
         }
 
+        let mut push_constant_fields = Vec::default();
+        if let Some(push_constant) = gl_root_signature.push_constant() {
+            let active_uniform_count =
+                gl_context.gl_get_programiv(program_id, gles3_bindings::ACTIVE_UNIFORMS)? as u32;
+            let max_name_length_hint =
+                gl_context.get_active_uniform_max_name_length_hint(program_id)?;
+
+            // Find the active uniforms that are members of the push constant struct. Members may
+            // not be active if the shader doesn't use them
+            for i in 0..active_uniform_count {
+                let uniform_info =
+                    gl_context.gl_get_active_uniform(program_id, i, &max_name_length_hint)?;
+
+                let name = uniform_info.name.to_string_lossy();
+                let member = push_constant
+                    .uniform_members
+                    .iter()
+                    .find(|x| x.name == *name);
+
+                if let Some(member) = member {
+                    if let Some(location) =
+                        gl_context.gl_get_uniform_location(program_id, &uniform_info.name)?
+                    {
+                        push_constant_fields.push(Gles3PushConstantField {
+                            location,
+                            gl_type: uniform_info.ty,
+                            element_count: uniform_info.size,
+                            offset: member.offset,
+                        });
+                    }
+                }
+            }
+        }
+
         let gl_topology = pipeline_def
             .primitive_topology
             .gles3_topology()
@@ -209,8 +274,10 @@ impl RafxPipelineGles3 {
             program_id,
             resource_locations,
             uniform_block_sizes,
+            push_constant_fields,
             root_signature: gl_root_signature.clone(),
             last_descriptor_updates: Default::default(),
+            last_push_constant_update: Default::default(),
         };
 
         // Front face needs to be reversed because we render GL with a flipped Y axis:
@@ -0,0 +1,20 @@
+use crate::gles3::RafxDeviceContextGles3;
+use crate::{RafxPipelineCacheDef, RafxResult};
+
+// This could be implemented with program binaries (glGetProgramBinary) in the future, but for
+// now nothing is cached. This type exists so that the API is the same across backends.
+// Pipelines created with it are compiled as usual.
+pub struct RafxPipelineCacheGles3;
+
+impl RafxPipelineCacheGles3 {
+    pub fn new(
+        _device_context: &RafxDeviceContextGles3,
+        _pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCacheGles3> {
+        Ok(RafxPipelineCacheGles3)
+    }
+
+    pub fn get_data(&self) -> RafxResult<Vec<u8>> {
+        Ok(Vec::default())
+    }
+}
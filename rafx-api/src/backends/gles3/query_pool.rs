@@ -0,0 +1,118 @@
+use crate::gles3::{gles3_bindings, QueryId, RafxDeviceContextGles3};
+use crate::{RafxQueryPoolDef, RafxQueryType, RafxResult};
+
+pub struct RafxQueryPoolGles3 {
+    device_context: RafxDeviceContextGles3,
+    query_pool_def: RafxQueryPoolDef,
+    queries: Vec<QueryId>,
+}
+
+impl Drop for RafxQueryPoolGles3 {
+    fn drop(&mut self) {
+        for &query in &self.queries {
+            self.device_context
+                .gl_context()
+                .gl_destroy_query(query)
+                .unwrap();
+        }
+    }
+}
+
+impl RafxQueryPoolGles3 {
+    pub fn new(
+        device_context: &RafxDeviceContextGles3,
+        query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPoolGles3> {
+        query_pool_def.verify();
+
+        if query_pool_def.query_type != RafxQueryType::Timestamp {
+            Err("Only timestamp queries are supported on GL ES 3.0")?;
+        }
+
+        if !device_context.device_info().supports_timestamp_queries {
+            Err("Timestamp queries require GL_EXT_disjoint_timer_query")?;
+        }
+
+        let gl_context = device_context.gl_context();
+        let mut queries = Vec::with_capacity(query_pool_def.query_count as usize);
+        for _ in 0..query_pool_def.query_count {
+            queries.push(gl_context.gl_create_query()?);
+        }
+
+        Ok(RafxQueryPoolGles3 {
+            device_context: device_context.clone(),
+            query_pool_def: query_pool_def.clone(),
+            queries,
+        })
+    }
+
+    pub fn query_pool_def(&self) -> &RafxQueryPoolDef {
+        &self.query_pool_def
+    }
+
+    pub fn gl_query_id(
+        &self,
+        query_index: u32,
+    ) -> QueryId {
+        self.queries[query_index as usize]
+    }
+
+    // If the GPU's timer was interrupted (for example, by a power state change) results are not
+    // meaningful. The flag is cleared when it is read.
+    fn check_disjoint(&self) -> RafxResult<()> {
+        let gl_context = self.device_context.gl_context();
+        if gl_context.has_extension("GL_EXT_disjoint_timer_query")
+            && gl_context.gl_get_integerv(gles3_bindings::GPU_DISJOINT_EXT) != 0
+        {
+            Err("GPU timer was disjoint, query results are not valid")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn get_results(
+        &self,
+        first_query: u32,
+        query_count: u32,
+        results: &mut [u64],
+    ) -> RafxResult<bool> {
+        assert!(first_query + query_count <= self.query_pool_def.query_count);
+        assert!(results.len() >= query_count as usize);
+
+        let gl_context = self.device_context.gl_context();
+        for i in 0..query_count {
+            let query = self.gl_query_id(first_query + i);
+            let available = gl_context
+                .gl_get_query_object_uiv(query, gles3_bindings::QUERY_RESULT_AVAILABLE)?;
+            if available == gles3_bindings::FALSE as u32 {
+                return Ok(false);
+            }
+
+            results[i as usize] =
+                gl_context.gl_get_query_object_ui64v(query, gles3_bindings::QUERY_RESULT)?;
+        }
+
+        self.check_disjoint()?;
+        Ok(true)
+    }
+
+    // Blocks until the results are available
+    pub(crate) fn wait_for_results(
+        &self,
+        first_query: u32,
+        query_count: u32,
+    ) -> RafxResult<Vec<u64>> {
+        assert!(first_query + query_count <= self.query_pool_def.query_count);
+
+        let gl_context = self.device_context.gl_context();
+        let mut results = Vec::with_capacity(query_count as usize);
+        for i in 0..query_count {
+            let query = self.gl_query_id(first_query + i);
+            results
+                .push(gl_context.gl_get_query_object_ui64v(query, gles3_bindings::QUERY_RESULT)?);
+        }
+
+        self.check_disjoint()?;
+        Ok(results)
+    }
+}
@@ -1,7 +1,7 @@
 use crate::gles3::{RafxDeviceContextGles3, RafxSamplerGles3};
 use crate::{
-    RafxDescriptorIndex, RafxPipelineType, RafxResourceType, RafxResult, RafxRootSignatureDef,
-    MAX_DESCRIPTOR_SET_LAYOUTS,
+    RafxDescriptorIndex, RafxGlUniformMember, RafxPipelineType, RafxResourceType, RafxResult,
+    RafxRootSignatureDef, MAX_DESCRIPTOR_SET_LAYOUTS,
 };
 use fnv::FnvHashMap;
 use std::ffi::CString;
@@ -61,6 +61,15 @@ pub(crate) struct DescriptorInfo {
     pub(crate) first_location_index: Option<u32>,
 }
 
+// GL ES 3.0 has no push constants. The shader processor emits the push constant block as a plain
+// uniform struct (not a uniform block), and the members are set from the pushed data before
+// drawing.
+#[derive(Clone, Debug)]
+pub(crate) struct PushConstantInfo {
+    pub(crate) size_in_bytes: u32,
+    pub(crate) uniform_members: Vec<RafxGlUniformMember>,
+}
+
 #[derive(Default, Debug)]
 pub(crate) struct DescriptorSetLayoutInfo {
     // Settable descriptors, immutable samplers are omitted
@@ -92,6 +101,7 @@ pub(crate) struct RafxRootSignatureGles3Inner {
     // --- gl-specific ---
     pub(crate) immutable_samplers: Vec<ImmutableSampler>,
     pub(crate) uniform_block_descriptors: Vec<RafxDescriptorIndex>,
+    pub(crate) push_constant: Option<PushConstantInfo>,
     pub(crate) root_signature_id: u32,
 
     pub(crate) location_names: Vec<CString>,
@@ -146,6 +156,10 @@ impl RafxRootSignatureGles3 {
         self.inner.descriptors.get(descriptor_index.0 as usize)
     }
 
+    pub(crate) fn push_constant(&self) -> Option<&PushConstantInfo> {
+        self.inner.push_constant.as_ref()
+    }
+
     #[allow(dead_code)]
     pub(crate) fn uniform_block_binding(
         &self,
@@ -242,7 +256,7 @@ impl RafxRootSignatureGles3 {
                 }
             }
 
-            // Not currently supported
+            // Push constants are merged separately
             assert_ne!(resource.resource_type, RafxResourceType::ROOT_CONSTANT);
 
             // Verify set index is valid
@@ -384,6 +398,15 @@ impl RafxRootSignatureGles3 {
             total_texture_units += next_descriptor_data_texture_offset[i];
         }
 
+        let push_constant = crate::internal_shared::merge_push_constants(
+            root_signature_def,
+            device_context.device_info().max_push_constant_size,
+        )?
+        .map(|resource| PushConstantInfo {
+            size_in_bytes: resource.size_in_bytes,
+            uniform_members: resource.gles2_uniform_members,
+        });
+
         let root_signature_id = NEXT_ROOT_SIGNATURE_ID.fetch_add(1, Ordering::Relaxed);
 
         for (texture_descriptor_index, sampler_name) in texture_descriptor_index_sampler_names {
@@ -400,6 +423,7 @@ impl RafxRootSignatureGles3 {
             name_to_descriptor_index,
             immutable_samplers,
             uniform_block_descriptors,
+            push_constant,
             root_signature_id,
             location_names,
         };
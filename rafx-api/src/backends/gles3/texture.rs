@@ -94,6 +94,24 @@ impl RafxTextureGles3 {
         self.inner.gl_target
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        if let Some(texture_id) = self.inner.image.gl_texture_id() {
+            let device_context = &self.inner.device_context;
+            if device_context.device_info().debug_names_enabled {
+                if let Err(e) = device_context.gl_context().gl_object_label(
+                    gles3_bindings::TEXTURE,
+                    texture_id.0,
+                    name.as_ref(),
+                ) {
+                    log::warn!("Failed to set debug name on texture: {:?}", e);
+                }
+            }
+        }
+    }
+
     pub fn gl_format_info(&self) -> &GlTextureFormatInfo {
         &self.inner.format_info
     }
@@ -30,10 +30,10 @@ impl RafxApiMetal {
     /// behavior on the CPU for reasons other than interacting with the GPU.
     pub unsafe fn new(
         _window: &dyn HasRawWindowHandle,
-        _api_def: &RafxApiDef,
+        api_def: &RafxApiDef,
         _metal_api_def: &RafxApiDefMetal,
     ) -> RafxResult<Self> {
-        let inner = Arc::new(RafxDeviceContextMetalInner::new()?);
+        let inner = Arc::new(RafxDeviceContextMetalInner::new(api_def)?);
         let device_context = RafxDeviceContextMetal::new(inner)?;
 
         Ok(RafxApiMetal {
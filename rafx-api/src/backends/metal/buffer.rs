@@ -21,6 +21,15 @@ impl RafxBufferMetal {
         self.buffer.as_ref()
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        if self.device_context.device_info().debug_names_enabled {
+            self.buffer.set_label(name.as_ref());
+        }
+    }
+
     pub fn map_buffer(&self) -> RafxResult<*mut u8> {
         if self.buffer_def.memory_usage == RafxMemoryUsage::GpuOnly {
             return Err("Cannot map GPU-only buffer")?;
@@ -1,14 +1,17 @@
 use crate::metal::{
     ArgumentBufferData, BarrierFlagsMetal, RafxBufferMetal, RafxCommandPoolMetal,
-    RafxDescriptorSetArrayMetal, RafxDescriptorSetHandleMetal, RafxPipelineMetal, RafxQueueMetal,
-    RafxRootSignatureMetal, RafxTextureMetal,
+    RafxDescriptorSetArrayMetal, RafxDescriptorSetHandleMetal, RafxPipelineMetal,
+    RafxQueryPoolMetal, RafxQueueMetal, RafxRootSignatureMetal, RafxTextureMetal,
 };
 use crate::{
-    RafxBufferBarrier, RafxCmdCopyBufferToTextureParams, RafxColorRenderTargetBinding,
-    RafxCommandBufferDef, RafxDepthStencilRenderTargetBinding, RafxExtents3D,
-    RafxIndexBufferBinding, RafxIndexType, RafxLoadOp, RafxPipelineType, RafxResourceState,
-    RafxResult, RafxTextureBarrier, RafxVertexBufferBinding,
+    RafxBufferBarrier, RafxCmdCopyBufferToTextureParams, RafxCmdCopyTextureToBufferParams,
+    RafxCmdCopyTextureToTextureParams, RafxColorRenderTargetBinding, RafxCommandBufferDef,
+    RafxDepthStencilRenderTargetBinding, RafxDrawIndexedIndirectCommand, RafxDrawIndirectCommand,
+    RafxExtents3D, RafxIndexBufferBinding, RafxIndexType, RafxLoadOp, RafxPipelineType,
+    RafxQueryType, RafxResourceState, RafxResult, RafxShaderStageFlags, RafxTextureBarrier,
+    RafxVertexBufferBinding, METAL_PUSH_CONSTANT_BUFFER_INDEX,
 };
+use cocoa_foundation::foundation::NSUInteger;
 use fnv::FnvHashSet;
 use metal_rs::{
     MTLBlitOption, MTLIndexType, MTLOrigin, MTLPrimitiveType, MTLRenderStages, MTLResourceUsage,
@@ -22,6 +25,11 @@ pub struct RafxCommandBufferMetalInner {
     render_targets_to_make_readable: FnvHashSet<RafxTextureMetal>,
     command_buffer: Option<metal_rs::CommandBuffer>,
     render_encoder: Option<metal_rs::RenderCommandEncoder>,
+    // Used when the contents of a render pass are written by secondary command buffers. Each
+    // secondary command buffer gets its own render encoder from this encoder.
+    parallel_render_encoder: Option<metal_rs::ParallelRenderCommandEncoder>,
+    // Set if secondary command buffers need to wait for the queue's fence
+    parallel_render_encoder_needs_fence: bool,
     compute_encoder: Option<metal_rs::ComputeCommandEncoder>,
     blit_encoder: Option<metal_rs::BlitCommandEncoder>,
     current_index_buffer: Option<metal_rs::Buffer>,
@@ -35,6 +43,8 @@ pub struct RafxCommandBufferMetalInner {
     compute_threads_per_group_x: u32,
     compute_threads_per_group_y: u32,
     compute_threads_per_group_z: u32,
+    // set_bytes replaces the whole buffer, so keep a copy to support updating part of the range
+    push_constant_data: Vec<u8>,
 }
 
 unsafe impl Send for RafxCommandBufferMetalInner {}
@@ -107,6 +117,8 @@ impl RafxCommandBufferMetal {
             render_targets_to_make_readable: Default::default(),
             command_buffer: None,
             render_encoder: None,
+            parallel_render_encoder: None,
+            parallel_render_encoder_needs_fence: false,
             compute_encoder: None,
             blit_encoder: None,
             last_pipeline_type: None,
@@ -120,6 +132,7 @@ impl RafxCommandBufferMetal {
             current_index_buffer_byte_offset: 0,
             current_index_buffer_type: MTLIndexType::UInt16,
             current_index_buffer_stride: 0,
+            push_constant_data: Vec::default(),
         };
 
         Ok(RafxCommandBufferMetal {
@@ -138,6 +151,44 @@ impl RafxCommandBufferMetal {
         })
     }
 
+    pub fn begin_secondary(
+        &self,
+        primary_command_buffer: &RafxCommandBufferMetal,
+    ) -> RafxResult<()> {
+        objc::rc::autoreleasepool(|| {
+            let primary_inner = primary_command_buffer.inner.borrow();
+            let parallel_render_encoder = primary_inner
+                .parallel_render_encoder
+                .as_ref()
+                .ok_or("The primary command buffer is not in a renderpass begun with cmd_begin_render_pass_with_secondary_command_buffers")?;
+
+            // Encoders created from a parallel render encoder are executed in the order they are
+            // created, which is why secondary command buffers must be begun in execution order
+            let render_encoder = parallel_render_encoder.render_command_encoder();
+            if primary_inner.parallel_render_encoder_needs_fence {
+                render_encoder.wait_for_fence(self.queue.metal_fence(), MTLRenderStages::Vertex);
+            }
+
+            let mut inner = self.inner.borrow_mut();
+            inner.render_encoder = Some(render_encoder.to_owned());
+            inner.current_render_targets_width = primary_inner.current_render_targets_width;
+            inner.current_render_targets_height = primary_inner.current_render_targets_height;
+            inner.last_pipeline_type = None;
+            Ok(())
+        })?;
+
+        let (width, height) = {
+            let inner = self.inner.borrow();
+            (
+                inner.current_render_targets_width,
+                inner.current_render_targets_height,
+            )
+        };
+
+        self.cmd_set_viewport(0.0, 0.0, width as f32, height as f32, 0.0, 1.0)?;
+        self.cmd_set_scissor(0, 0, width, height)
+    }
+
     pub fn end(&self) -> RafxResult<()> {
         objc::rc::autoreleasepool(|| self.end_current_encoders(true))
     }
@@ -153,6 +204,23 @@ impl RafxCommandBufferMetal {
         &self,
         color_targets: &[RafxColorRenderTargetBinding],
         depth_target: Option<RafxDepthStencilRenderTargetBinding>,
+    ) -> RafxResult<()> {
+        self.do_cmd_begin_render_pass(color_targets, depth_target, false)
+    }
+
+    pub fn cmd_begin_render_pass_with_secondary_command_buffers(
+        &self,
+        color_targets: &[RafxColorRenderTargetBinding],
+        depth_target: Option<RafxDepthStencilRenderTargetBinding>,
+    ) -> RafxResult<()> {
+        self.do_cmd_begin_render_pass(color_targets, depth_target, true)
+    }
+
+    fn do_cmd_begin_render_pass(
+        &self,
+        color_targets: &[RafxColorRenderTargetBinding],
+        depth_target: Option<RafxDepthStencilRenderTargetBinding>,
+        use_secondary_command_buffers: bool,
     ) -> RafxResult<()> {
         // if self.has_active_renderpass.load(Ordering::Relaxed) {
         //     self.cmd_end_render_pass()?;
@@ -250,15 +318,29 @@ impl RafxCommandBufferMetal {
             // end encoders
             Self::do_end_current_encoders(&self.queue, &mut *inner, false)?;
             let cmd_buffer = inner.command_buffer.as_ref().unwrap();
-            let render_encoder = cmd_buffer.new_render_command_encoder(descriptor);
-            inner.render_encoder = Some(render_encoder.to_owned());
-            self.wait_for_barriers(&*inner)?;
+            if use_secondary_command_buffers {
+                // Parallel render encoders can't wait on fences, so the secondary command buffers
+                // do it when they create their encoders
+                let parallel_render_encoder =
+                    cmd_buffer.new_parallel_render_command_encoder(descriptor);
+                inner.parallel_render_encoder = Some(parallel_render_encoder.to_owned());
+                inner.parallel_render_encoder_needs_fence = !self.queue.barrier_flags().is_empty();
+                self.queue.clear_barrier_flags();
+            } else {
+                let render_encoder = cmd_buffer.new_render_command_encoder(descriptor);
+                inner.render_encoder = Some(render_encoder.to_owned());
+                self.wait_for_barriers(&*inner)?;
+            }
             // set heaps?
 
             Ok(())
         });
         result?;
 
+        if use_secondary_command_buffers {
+            return Ok(());
+        }
+
         self.cmd_set_viewport(
             0.0,
             0.0,
@@ -285,7 +367,10 @@ impl RafxCommandBufferMetal {
     ) -> RafxResult<()> {
         let barrier_flags = queue.barrier_flags();
 
-        if let Some(render_encoder) = inner.render_encoder.take() {
+        if let Some(parallel_render_encoder) = inner.parallel_render_encoder.take() {
+            // The secondary command buffers update the fence when they are ended
+            parallel_render_encoder.end_encoding();
+        } else if let Some(render_encoder) = inner.render_encoder.take() {
             if !barrier_flags.is_empty() || force_barrier {
                 render_encoder.update_fence(queue.metal_fence(), MTLRenderStages::Fragment);
                 queue.add_barrier_flags(BarrierFlagsMetal::FENCE);
@@ -340,6 +425,16 @@ impl RafxCommandBufferMetal {
         Ok(())
     }
 
+    pub fn cmd_execute_secondary(
+        &self,
+        _secondary_command_buffers: &[&RafxCommandBufferMetal],
+    ) -> RafxResult<()> {
+        // The secondary command buffers encode directly into the parallel render encoder created
+        // by cmd_begin_render_pass_with_secondary_command_buffers, in the order they were begun
+        assert!(self.inner.borrow().parallel_render_encoder.is_some());
+        Ok(())
+    }
+
     pub fn cmd_set_viewport(
         &self,
         x: f32,
@@ -629,6 +724,62 @@ impl RafxCommandBufferMetal {
         Ok(())
     }
 
+    pub fn cmd_push_constants(
+        &self,
+        root_signature: &RafxRootSignatureMetal,
+        offset_in_bytes: u32,
+        data: &[u8],
+    ) -> RafxResult<()> {
+        let push_constant = root_signature.push_constant();
+        crate::internal_shared::verify_push_constant_range(
+            push_constant.map(|x| x.size_in_bytes),
+            offset_in_bytes,
+            data.len() as u32,
+        )?;
+        let push_constant = push_constant.unwrap();
+
+        let mut inner = self.inner.borrow_mut();
+        let inner = &mut *inner;
+        inner
+            .push_constant_data
+            .resize(push_constant.size_in_bytes as usize, 0);
+        let begin = offset_in_bytes as usize;
+        inner.push_constant_data[begin..(begin + data.len())].copy_from_slice(data);
+
+        let length = inner.push_constant_data.len() as NSUInteger;
+        let bytes = inner.push_constant_data.as_ptr() as *const std::ffi::c_void;
+        let index = METAL_PUSH_CONSTANT_BUFFER_INDEX as NSUInteger;
+        match root_signature.pipeline_type() {
+            RafxPipelineType::Graphics => {
+                let render_encoder = inner
+                    .render_encoder
+                    .as_ref()
+                    .ok_or("Must begin render pass before pushing graphics push constants")?;
+                if push_constant
+                    .used_in_shader_stages
+                    .intersects(RafxShaderStageFlags::VERTEX)
+                {
+                    render_encoder.set_vertex_bytes(index, length, bytes);
+                }
+                if push_constant
+                    .used_in_shader_stages
+                    .intersects(RafxShaderStageFlags::FRAGMENT)
+                {
+                    render_encoder.set_fragment_bytes(index, length, bytes);
+                }
+            }
+            RafxPipelineType::Compute => {
+                let compute_encoder = inner
+                    .compute_encoder
+                    .as_ref()
+                    .ok_or("Must bind compute pipeline before pushing compute push constants")?;
+                compute_encoder.set_bytes(index, length, bytes);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn cmd_draw(
         &self,
         vertex_count: u32,
@@ -806,6 +957,100 @@ impl RafxCommandBufferMetal {
         Ok(())
     }
 
+    pub fn cmd_draw_indirect(
+        &self,
+        indirect_buffer: &RafxBufferMetal,
+        indirect_buffer_offset_in_bytes: u32,
+        draw_count: u32,
+    ) -> RafxResult<()> {
+        let inner = self.inner.borrow();
+        let render_encoder = inner.render_encoder.as_ref().unwrap();
+
+        // Metal reads a single set of arguments per draw
+        let stride = std::mem::size_of::<RafxDrawIndirectCommand>() as u64;
+        for i in 0..draw_count as u64 {
+            render_encoder.draw_primitives_indirect(
+                inner.primitive_type,
+                indirect_buffer.metal_buffer(),
+                (indirect_buffer_offset_in_bytes as u64 + i * stride) as _,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_draw_indexed_indirect(
+        &self,
+        indirect_buffer: &RafxBufferMetal,
+        indirect_buffer_offset_in_bytes: u32,
+        draw_count: u32,
+    ) -> RafxResult<()> {
+        let inner = self.inner.borrow();
+        let render_encoder = inner.render_encoder.as_ref().unwrap();
+
+        // Metal reads a single set of arguments per draw
+        let stride = std::mem::size_of::<RafxDrawIndexedIndirectCommand>() as u64;
+        for i in 0..draw_count as u64 {
+            render_encoder.draw_indexed_primitives_indirect(
+                inner.primitive_type,
+                inner.current_index_buffer_type,
+                inner.current_index_buffer.as_ref().unwrap(),
+                inner.current_index_buffer_byte_offset as _,
+                indirect_buffer.metal_buffer(),
+                (indirect_buffer_offset_in_bytes as u64 + i * stride) as _,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_draw_indirect_count(
+        &self,
+        _indirect_buffer: &RafxBufferMetal,
+        _indirect_buffer_offset_in_bytes: u32,
+        _count_buffer: &RafxBufferMetal,
+        _count_buffer_offset_in_bytes: u32,
+        _max_draw_count: u32,
+    ) -> RafxResult<()> {
+        Err("Indirect draws with a count buffer are not supported on metal")?
+    }
+
+    pub fn cmd_draw_indexed_indirect_count(
+        &self,
+        _indirect_buffer: &RafxBufferMetal,
+        _indirect_buffer_offset_in_bytes: u32,
+        _count_buffer: &RafxBufferMetal,
+        _count_buffer_offset_in_bytes: u32,
+        _max_draw_count: u32,
+    ) -> RafxResult<()> {
+        Err("Indirect draws with a count buffer are not supported on metal")?
+    }
+
+    pub fn cmd_dispatch_indirect(
+        &self,
+        indirect_buffer: &RafxBufferMetal,
+        indirect_buffer_offset_in_bytes: u32,
+    ) -> RafxResult<()> {
+        let inner = self.inner.borrow();
+        self.wait_for_barriers(&*inner)?;
+        let thread_per_group = MTLSize {
+            width: inner.compute_threads_per_group_x as _,
+            height: inner.compute_threads_per_group_y as _,
+            depth: inner.compute_threads_per_group_z as _,
+        };
+
+        inner
+            .compute_encoder
+            .as_ref()
+            .unwrap()
+            .dispatch_thread_groups_indirect(
+                indirect_buffer.metal_buffer(),
+                indirect_buffer_offset_in_bytes as _,
+                thread_per_group,
+            );
+        Ok(())
+    }
+
     pub fn cmd_resource_barrier(
         &self,
         buffer_barriers: &[RafxBufferBarrier],
@@ -875,6 +1120,197 @@ impl RafxCommandBufferMetal {
         Ok(())
     }
 
+    pub fn cmd_reset_queries(
+        &self,
+        _query_pool: &RafxQueryPoolMetal,
+        _first_query: u32,
+        _query_count: u32,
+    ) -> RafxResult<()> {
+        // Counter sample buffers do not need to be reset before samples are overwritten
+        Ok(())
+    }
+
+    pub fn cmd_write_timestamp(
+        &self,
+        query_pool: &RafxQueryPoolMetal,
+        query_index: u32,
+    ) -> RafxResult<()> {
+        use foreign_types_shared::ForeignType;
+        use foreign_types_shared::ForeignTypeRef;
+        use objc::runtime::{Object, YES};
+        use objc::{msg_send, sel, sel_impl};
+
+        assert_eq!(
+            query_pool.query_pool_def().query_type,
+            RafxQueryType::Timestamp
+        );
+
+        let mut inner = self.inner.borrow_mut();
+
+        // Sample within whatever encoder is active. If there isn't one, open a blit encoder
+        let encoder: *mut Object = if let Some(render_encoder) = &inner.render_encoder {
+            render_encoder.as_ptr() as _
+        } else if let Some(compute_encoder) = &inner.compute_encoder {
+            compute_encoder.as_ptr() as _
+        } else if let Some(blit_encoder) = &inner.blit_encoder {
+            blit_encoder.as_ptr() as _
+        } else {
+            let result: RafxResult<*mut Object> = objc::rc::autoreleasepool(|| {
+                Self::do_end_current_encoders(&self.queue, &mut *inner, false)?;
+                let encoder = inner
+                    .command_buffer
+                    .as_ref()
+                    .unwrap()
+                    .new_blit_command_encoder();
+                inner.blit_encoder = Some(encoder.to_owned());
+                Ok(encoder.as_ptr() as _)
+            });
+            result?
+        };
+
+        unsafe {
+            let () = msg_send![
+                encoder,
+                sampleCountersInBuffer: query_pool.metal_counter_sample_buffer()
+                atSampleIndex: query_index as NSUInteger
+                withBarrier: YES
+            ];
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_begin_query(
+        &self,
+        _query_pool: &RafxQueryPoolMetal,
+        _query_index: u32,
+    ) -> RafxResult<()> {
+        Err("Pipeline statistics queries are not supported on metal")?
+    }
+
+    pub fn cmd_end_query(
+        &self,
+        _query_pool: &RafxQueryPoolMetal,
+        _query_index: u32,
+    ) -> RafxResult<()> {
+        Err("Pipeline statistics queries are not supported on metal")?
+    }
+
+    pub fn cmd_resolve_queries(
+        &self,
+        query_pool: &RafxQueryPoolMetal,
+        first_query: u32,
+        query_count: u32,
+        dst_buffer: &RafxBufferMetal,
+        dst_offset: u64,
+    ) -> RafxResult<()> {
+        use objc::{msg_send, sel, sel_impl};
+
+        let mut inner = self.inner.borrow_mut();
+        let blit_encoder = inner.blit_encoder.as_ref();
+        let blit_encoder = match blit_encoder {
+            Some(x) => x,
+            None => {
+                let result: RafxResult<&metal_rs::BlitCommandEncoderRef> =
+                    objc::rc::autoreleasepool(|| {
+                        Self::do_end_current_encoders(&self.queue, &mut *inner, false)?;
+                        let encoder = inner
+                            .command_buffer
+                            .as_ref()
+                            .unwrap()
+                            .new_blit_command_encoder();
+                        inner.blit_encoder = Some(encoder.to_owned());
+                        Ok(inner.blit_encoder.as_ref().unwrap().as_ref())
+                    });
+                result?
+            }
+        };
+
+        unsafe {
+            let range = metal_rs::NSRange::new(first_query as _, query_count as _);
+            let () = msg_send![
+                blit_encoder,
+                resolveCounters: query_pool.metal_counter_sample_buffer()
+                inRange: range
+                destinationBuffer: dst_buffer.metal_buffer()
+                destinationOffset: dst_offset as NSUInteger
+            ];
+        }
+
+        Ok(())
+    }
+
+    // Groups are pushed on the command buffer rather than an encoder so that they can span
+    // multiple encoders (i.e. a render pass that was split by a compute dispatch)
+    pub fn cmd_push_group_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) -> RafxResult<()> {
+        use cocoa_foundation::base::nil;
+        use cocoa_foundation::foundation::NSString;
+        use objc::{msg_send, sel, sel_impl};
+
+        if self
+            .queue
+            .device_context()
+            .device_info()
+            .debug_names_enabled
+        {
+            let inner = self.inner.borrow();
+            let command_buffer = inner.command_buffer.as_ref().unwrap();
+            unsafe {
+                let name = NSString::alloc(nil).init_str(name.as_ref());
+                let () = msg_send![command_buffer.as_ref(), pushDebugGroup: name];
+                let () = msg_send![name, release];
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_pop_group_debug_name(&self) -> RafxResult<()> {
+        use objc::{msg_send, sel, sel_impl};
+
+        if self
+            .queue
+            .device_context()
+            .device_info()
+            .debug_names_enabled
+        {
+            let inner = self.inner.borrow();
+            let command_buffer = inner.command_buffer.as_ref().unwrap();
+            unsafe {
+                let () = msg_send![command_buffer.as_ref(), popDebugGroup];
+            }
+        }
+
+        Ok(())
+    }
+
+    // Signposts can only be inserted into an encoder. If no encoder is open, the marker is dropped
+    pub fn cmd_insert_debug_marker(
+        &self,
+        name: impl AsRef<str>,
+    ) -> RafxResult<()> {
+        if self
+            .queue
+            .device_context()
+            .device_info()
+            .debug_names_enabled
+        {
+            let inner = self.inner.borrow();
+            if let Some(render_encoder) = &inner.render_encoder {
+                render_encoder.insert_debug_signpost(name.as_ref());
+            } else if let Some(compute_encoder) = &inner.compute_encoder {
+                compute_encoder.insert_debug_signpost(name.as_ref());
+            } else if let Some(blit_encoder) = &inner.blit_encoder {
+                blit_encoder.insert_debug_signpost(name.as_ref());
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn cmd_copy_buffer_to_texture(
         &self,
         src_buffer: &RafxBufferMetal,
@@ -947,4 +1383,119 @@ impl RafxCommandBufferMetal {
         );
         Ok(())
     }
+
+    pub fn cmd_copy_texture_to_buffer(
+        &self,
+        src_texture: &RafxTextureMetal,
+        dst_buffer: &RafxBufferMetal,
+        params: &RafxCmdCopyTextureToBufferParams,
+    ) -> RafxResult<()> {
+        let mut inner = self.inner.borrow_mut();
+        let blit_encoder = inner.blit_encoder.as_ref();
+        let blit_encoder = match blit_encoder {
+            Some(x) => x,
+            None => {
+                let result: RafxResult<&metal_rs::BlitCommandEncoderRef> =
+                    objc::rc::autoreleasepool(|| {
+                        Self::do_end_current_encoders(&self.queue, &mut *inner, false)?;
+                        let encoder = inner
+                            .command_buffer
+                            .as_ref()
+                            .unwrap()
+                            .new_blit_command_encoder();
+                        inner.blit_encoder = Some(encoder.to_owned());
+                        Ok(inner.blit_encoder.as_ref().unwrap().as_ref())
+                    });
+                result?
+            }
+        };
+
+        let texture_def = src_texture.texture_def();
+        let format = texture_def.format;
+        let extents = params.copy_extents(texture_def);
+        let row_pitch = params.row_pitch(format, extents);
+        let slice_pitch = params.slice_pitch(format, extents);
+
+        // Only one aspect of a combined depth/stencil texture could be copied, and it wouldn't
+        // match the size of the format that the buffer is laid out for
+        if format.has_depth() && format.has_stencil() {
+            Err("Copying a combined depth/stencil texture to a buffer is not supported")?;
+        }
+
+        blit_encoder.copy_from_texture_to_buffer(
+            src_texture.metal_texture(),
+            params.array_layer as _,
+            params.mip_level as _,
+            MTLOrigin {
+                x: params.src_offset.x as _,
+                y: params.src_offset.y as _,
+                z: params.src_offset.z as _,
+            },
+            MTLSize {
+                width: extents.width as _,
+                height: extents.height as _,
+                depth: extents.depth as _,
+            },
+            dst_buffer.metal_buffer(),
+            params.buffer_offset as _,
+            row_pitch as _,
+            slice_pitch as _,
+            MTLBlitOption::empty(),
+        );
+        Ok(())
+    }
+
+    pub fn cmd_copy_texture_to_texture(
+        &self,
+        src_texture: &RafxTextureMetal,
+        dst_texture: &RafxTextureMetal,
+        params: &RafxCmdCopyTextureToTextureParams,
+    ) -> RafxResult<()> {
+        let mut inner = self.inner.borrow_mut();
+        let blit_encoder = inner.blit_encoder.as_ref();
+        let blit_encoder = match blit_encoder {
+            Some(x) => x,
+            None => {
+                let result: RafxResult<&metal_rs::BlitCommandEncoderRef> =
+                    objc::rc::autoreleasepool(|| {
+                        Self::do_end_current_encoders(&self.queue, &mut *inner, false)?;
+                        let encoder = inner
+                            .command_buffer
+                            .as_ref()
+                            .unwrap()
+                            .new_blit_command_encoder();
+                        inner.blit_encoder = Some(encoder.to_owned());
+                        Ok(inner.blit_encoder.as_ref().unwrap().as_ref())
+                    });
+                result?
+            }
+        };
+
+        let extents = params.copy_extents(src_texture.texture_def());
+
+        blit_encoder.copy_from_texture(
+            src_texture.metal_texture(),
+            params.src_array_layer as _,
+            params.src_mip_level as _,
+            MTLOrigin {
+                x: params.src_offset.x as _,
+                y: params.src_offset.y as _,
+                z: params.src_offset.z as _,
+            },
+            MTLSize {
+                width: extents.width as _,
+                height: extents.height as _,
+                depth: extents.depth as _,
+            },
+            dst_texture.metal_texture(),
+            params.dst_array_layer as _,
+            params.dst_mip_level as _,
+            MTLOrigin {
+                x: params.dst_offset.x as _,
+                y: params.dst_offset.y as _,
+                z: params.dst_offset.z as _,
+            },
+        );
+        Ok(())
+    }
 }
@@ -1,17 +1,19 @@
 use crate::{
-    RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef, RafxDeviceContext,
-    RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxQueueType, RafxResourceType,
-    RafxResult, RafxRootSignatureDef, RafxSampleCount, RafxSamplerDef, RafxShaderModuleDefMetal,
-    RafxShaderStageDef, RafxSwapchainDef, RafxTextureDef,
+    RafxApiDef, RafxBufferDef, RafxComputePipelineDef, RafxDescriptorSetArrayDef,
+    RafxDeviceContext, RafxDeviceInfo, RafxFormat, RafxGraphicsPipelineDef, RafxPipelineCacheDef,
+    RafxQueryPoolDef, RafxQueueType, RafxResourceType, RafxResult, RafxRootSignatureDef,
+    RafxSampleCount, RafxSamplerDef, RafxShaderModuleDefMetal, RafxShaderStageDef,
+    RafxSwapchainDef, RafxTextureDef,
 };
 use raw_window_handle::HasRawWindowHandle;
 use std::sync::Arc;
 
 use crate::metal::features::MetalFeatures;
 use crate::metal::{
-    RafxBufferMetal, RafxDescriptorSetArrayMetal, RafxFenceMetal, RafxPipelineMetal,
-    RafxQueueMetal, RafxRootSignatureMetal, RafxSamplerMetal, RafxSemaphoreMetal, RafxShaderMetal,
-    RafxShaderModuleMetal, RafxSwapchainMetal, RafxTextureMetal,
+    RafxBufferMetal, RafxDescriptorSetArrayMetal, RafxFenceMetal, RafxPipelineCacheMetal,
+    RafxPipelineMetal, RafxQueryPoolMetal, RafxQueueMetal, RafxRootSignatureMetal,
+    RafxSamplerMetal, RafxSemaphoreMetal, RafxShaderMetal, RafxShaderModuleMetal,
+    RafxSwapchainMetal, RafxTextureMetal,
 };
 
 #[cfg(debug_assertions)]
@@ -47,18 +49,7 @@ impl Drop for RafxDeviceContextMetalInner {
 }
 
 impl RafxDeviceContextMetalInner {
-    pub fn new() -> RafxResult<Self> {
-        let device_info = RafxDeviceInfo {
-            // pretty sure this is consistent across macOS device (maybe not M1, not sure)
-            min_uniform_buffer_offset_alignment: 256,
-            // based on one of the loosest vulkan limits (intel iGPU), can't find official value
-            min_storage_buffer_offset_alignment: 64,
-            upload_buffer_texture_alignment: 16,
-            upload_buffer_texture_row_alignment: 1,
-            supports_clamp_to_border_color: true, //TODO: Check for iOS support
-            max_vertex_attribute_count: 31,
-        };
-
+    pub fn new(api_def: &RafxApiDef) -> RafxResult<Self> {
         #[cfg(debug_assertions)]
         #[cfg(feature = "track-device-contexts")]
         let all_contexts = {
@@ -72,6 +63,37 @@ impl RafxDeviceContextMetalInner {
 
         let metal_features = MetalFeatures::from_device(device.as_ref());
 
+        let device_info = RafxDeviceInfo {
+            // pretty sure this is consistent across macOS device (maybe not M1, not sure)
+            min_uniform_buffer_offset_alignment: 256,
+            // based on one of the loosest vulkan limits (intel iGPU), can't find official value
+            min_storage_buffer_offset_alignment: 64,
+            upload_buffer_texture_alignment: 16,
+            upload_buffer_texture_row_alignment: 1,
+            supports_clamp_to_border_color: true, //TODO: Check for iOS support
+            max_vertex_attribute_count: 31,
+            // GPU timestamp ticks are not nanoseconds on all devices (i.e. Apple silicon).
+            // RafxQueryPoolMetal converts them to nanoseconds when results are read.
+            timestamp_period_ns: 1.0,
+            supports_timestamp_queries: metal_features.supports_timestamp_counter_sampling,
+            supports_pipeline_statistics_queries: false,
+            // Indirect arguments require the same GPU families as base vertex/instance drawing.
+            // Multiple draws are issued as separate indirect draws.
+            supports_indirect_draw: metal_features.supports_base_vertex_instance_drawing,
+            max_indirect_draw_count: if metal_features.supports_base_vertex_instance_drawing {
+                u32::MAX
+            } else {
+                0
+            },
+            // Would require indirect command buffers
+            supports_indirect_draw_count: false,
+            supports_indirect_dispatch: metal_features.supports_base_vertex_instance_drawing,
+            // Push constants are set with set_bytes, which is intended for data up to 4KB
+            max_push_constant_size: 4096,
+            debug_names_enabled: api_def.enable_debug_names,
+            supports_multithreaded_command_recording: true,
+        };
+
         log::debug!("Device: {}", metal_features.device_name);
         log::debug!(
             "GPU family (apple={:?} mac={:?} common={:?}",
@@ -206,6 +228,20 @@ impl RafxDeviceContextMetal {
         RafxSemaphoreMetal::new(self)
     }
 
+    pub fn create_query_pool(
+        &self,
+        query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPoolMetal> {
+        RafxQueryPoolMetal::new(self, query_pool_def)
+    }
+
+    pub fn create_pipeline_cache(
+        &self,
+        pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCacheMetal> {
+        RafxPipelineCacheMetal::new(self, pipeline_cache_def)
+    }
+
     pub fn create_swapchain(
         &self,
         raw_window_handle: &dyn HasRawWindowHandle,
@@ -315,7 +351,10 @@ impl RafxDeviceContextMetal {
                 .inner
                 .metal_features
                 .pixel_format_capabilities(candidate.into());
-            if capabilities.contains(required_capabilities) {
+            // Formats that can't be used at all (like BC formats on iOS) have no capabilities
+            let can_sample =
+                !resource_type.intersects(RafxResourceType::TEXTURE) || !capabilities.is_empty();
+            if capabilities.contains(required_capabilities) && can_sample {
                 return Some(candidate);
             }
         }
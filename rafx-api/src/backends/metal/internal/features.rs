@@ -80,6 +80,26 @@ fn find_supported_feature_set(
     return None;
 }
 
+// Timestamps are written between commands, so sampling must be supported in render, compute and
+// blit encoders. (Apple GPUs only support sampling at stage boundaries, which would require
+// specifying sample buffers when the encoder is created.)
+fn supports_counter_sampling_at_command_boundaries(device: &metal_rs::DeviceRef) -> bool {
+    use metal_rs::MTLCounterSamplingPoint;
+    use objc::runtime::{BOOL, YES};
+    use objc::{msg_send, sel, sel_impl};
+
+    // Requires macOS 10.15, iOS 14.0
+    let responds: BOOL =
+        unsafe { msg_send![device, respondsToSelector: sel!(supportsCounterSampling:)] };
+    if responds != YES {
+        return false;
+    }
+
+    device.supports_counter_sampling(MTLCounterSamplingPoint::AtDrawBoundary)
+        && device.supports_counter_sampling(MTLCounterSamplingPoint::AtDispatchBoundary)
+        && device.supports_counter_sampling(MTLCounterSamplingPoint::AtBlitBoundary)
+}
+
 fn pixel_format_capabilities(
     feature_set: MTLFeatureSet,
     pixel_format: MTLPixelFormat,
@@ -233,6 +253,7 @@ pub struct MetalFeatures {
     pub supports_combined_msaa_store_and_resolve_action: bool,
     pub supports_cube_map_texture_arrays: bool,
     pub supports_resource_heaps: bool,
+    pub supports_timestamp_counter_sampling: bool,
 }
 
 impl MetalFeatures {
@@ -302,6 +323,9 @@ impl MetalFeatures {
             is_low_power = true;
         }
 
+        let supports_timestamp_counter_sampling =
+            supports_counter_sampling_at_command_boundaries(device);
+
         MetalFeatures {
             device_name,
             unified_memory,
@@ -320,6 +344,7 @@ impl MetalFeatures {
             supports_combined_msaa_store_and_resolve_action,
             supports_cube_map_texture_arrays,
             supports_resource_heaps,
+            supports_timestamp_counter_sampling,
         }
     }
 
@@ -13,6 +13,12 @@ pub use texture::*;
 mod semaphore;
 pub use semaphore::*;
 
+mod query_pool;
+pub use query_pool::*;
+
+mod pipeline_cache;
+pub use pipeline_cache::*;
+
 mod fence;
 pub use fence::*;
 
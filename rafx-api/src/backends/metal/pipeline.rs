@@ -3,6 +3,8 @@ use crate::{
     RafxComputePipelineDef, RafxGraphicsPipelineDef, RafxPipelineType, RafxResult,
     RafxRootSignature, RafxShaderStageFlags,
 };
+use foreign_types_shared::ForeignType;
+use objc::runtime::Object;
 
 fn metal_entry_point_name(name: &str) -> &str {
     // "main" is not an allowed entry point name. spirv_cross adds a 0 to the end of any
@@ -80,6 +82,14 @@ impl RafxPipelineMetal {
         }
     }
 
+    pub fn set_debug_name(
+        &self,
+        _name: impl AsRef<str>,
+    ) {
+        // Metal pipeline state labels are immutable, they can only be set on the descriptor before
+        // the pipeline is created
+    }
+
     pub fn new_graphics_pipeline(
         device_context: &RafxDeviceContextMetal,
         pipeline_def: &RafxGraphicsPipelineDef,
@@ -180,9 +190,21 @@ impl RafxPipelineMetal {
             }
         }
 
+        let pipeline_cache = pipeline_def
+            .pipeline_cache
+            .map(|x| x.metal_pipeline_cache().unwrap());
+        if let Some(pipeline_cache) = pipeline_cache {
+            pipeline_cache.set_binary_archives(pipeline.as_ptr() as *mut Object);
+        }
+
+        let pipeline_descriptor = pipeline;
         let pipeline = device_context
             .device()
-            .new_render_pipeline_state(pipeline.as_ref())?;
+            .new_render_pipeline_state(pipeline_descriptor.as_ref())?;
+
+        if let Some(pipeline_cache) = pipeline_cache {
+            pipeline_cache.add_render_pipeline_functions(pipeline_descriptor.as_ref());
+        }
 
         let mtl_cull_mode = pipeline_def.rasterizer_state.cull_mode.into();
         let mtl_triangle_fill_mode = pipeline_def.rasterizer_state.fill_mode.into();
@@ -260,9 +282,23 @@ impl RafxPipelineMetal {
 
         let compute_function = compute_function.ok_or("Could not find compute function")?;
 
-        let pipeline = device_context
-            .device()
-            .new_compute_pipeline_state_with_function(compute_function.as_ref())?;
+        let pipeline = if let Some(pipeline_cache) = pipeline_def.pipeline_cache {
+            // Binary archives can only be used with a pipeline descriptor
+            let pipeline_cache = pipeline_cache.metal_pipeline_cache().unwrap();
+            let pipeline_descriptor = metal_rs::ComputePipelineDescriptor::new();
+            pipeline_descriptor.set_compute_function(Some(compute_function.as_ref()));
+            pipeline_cache.set_binary_archives(pipeline_descriptor.as_ptr() as *mut Object);
+
+            let pipeline = device_context
+                .device()
+                .new_compute_pipeline_state(pipeline_descriptor.as_ref())?;
+            pipeline_cache.add_compute_pipeline_functions(pipeline_descriptor.as_ref());
+            pipeline
+        } else {
+            device_context
+                .device()
+                .new_compute_pipeline_state_with_function(compute_function.as_ref())?
+        };
 
         let compute_encoder_info = PipelineComputeEncoderInfo {
             compute_threads_per_group: compute_threads_per_group.unwrap(),
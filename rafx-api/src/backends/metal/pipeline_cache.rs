@@ -0,0 +1,279 @@
+use crate::metal::RafxDeviceContextMetal;
+use crate::{RafxPipelineCacheDef, RafxResult};
+use cocoa_foundation::base::nil;
+use cocoa_foundation::foundation::NSString;
+use objc::runtime::{Object, BOOL, NO};
+use objc::{class, msg_send, sel, sel_impl};
+use std::convert::TryInto;
+use std::ffi::CStr;
+use std::os::raw::c_char;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Metal serializes binary archives to a file and does not record what device they were built for,
+// so we prepend a small header that lets us reject data produced on a different device.
+const PIPELINE_CACHE_MAGIC: &[u8; 4] = b"RPCM";
+const PIPELINE_CACHE_VERSION: u32 = 1;
+const PIPELINE_CACHE_HEADER_SIZE: usize = 16;
+
+static NEXT_TEMP_FILE_INDEX: AtomicUsize = AtomicUsize::new(0);
+
+fn temp_file_path() -> PathBuf {
+    let index = NEXT_TEMP_FILE_INDEX.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!(
+        "rafx-pipeline-cache-{}-{}.metallib",
+        std::process::id(),
+        index
+    ))
+}
+
+unsafe fn error_description(error: *mut Object) -> String {
+    if error.is_null() {
+        "unknown error".to_string()
+    } else {
+        let description: *mut Object = msg_send![error, localizedDescription];
+        let description: *const c_char = msg_send![description, UTF8String];
+        CStr::from_ptr(description).to_string_lossy().to_string()
+    }
+}
+
+// Returns an autoreleased NSURL, must be called within an autorelease pool
+unsafe fn file_url(path: &Path) -> *mut Object {
+    let path = NSString::alloc(nil).init_str(&path.to_string_lossy());
+    let url: *mut Object = msg_send![class!(NSURL), fileURLWithPath: path];
+    let () = msg_send![path, release];
+    url
+}
+
+// metal-rs does not wrap binary archives yet, so this talks to the objective-c API directly. The
+// archive is an id<MTLBinaryArchive>. It will be None if the device does not support binary
+// archives (requires macOS 11/iOS 14), in which case pipelines are compiled as usual.
+pub struct RafxPipelineCacheMetal {
+    device_context: RafxDeviceContextMetal,
+    binary_archive: Option<*mut Object>,
+}
+
+// For the binary archive
+unsafe impl Send for RafxPipelineCacheMetal {}
+unsafe impl Sync for RafxPipelineCacheMetal {}
+
+impl Drop for RafxPipelineCacheMetal {
+    fn drop(&mut self) {
+        if let Some(binary_archive) = self.binary_archive {
+            unsafe {
+                let () = msg_send![binary_archive, release];
+            }
+        }
+    }
+}
+
+impl RafxPipelineCacheMetal {
+    pub fn new(
+        device_context: &RafxDeviceContextMetal,
+        pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCacheMetal> {
+        let device: &metal_rs::DeviceRef = device_context.device();
+        let supports_binary_archives: BOOL = unsafe {
+            msg_send![device, respondsToSelector: sel!(newBinaryArchiveWithDescriptor:error:)]
+        };
+
+        let binary_archive = if supports_binary_archives != NO {
+            let archive_data = pipeline_cache_def
+                .data
+                .and_then(|data| Self::strip_header(device_context, data));
+
+            let mut binary_archive = None;
+            if let Some(archive_data) = archive_data {
+                match Self::load_binary_archive(device, archive_data) {
+                    Ok(archive) => binary_archive = Some(archive),
+                    Err(e) => log::warn!(
+                        "Failed to load pipeline cache data, it will be ignored: {:?}",
+                        e
+                    ),
+                }
+            }
+
+            match binary_archive {
+                Some(archive) => Some(archive),
+                None => Some(Self::create_binary_archive(device, None)?),
+            }
+        } else {
+            None
+        };
+
+        Ok(RafxPipelineCacheMetal {
+            device_context: device_context.clone(),
+            binary_archive,
+        })
+    }
+
+    pub fn metal_binary_archive(&self) -> Option<*mut Object> {
+        self.binary_archive
+    }
+
+    fn strip_header<'a>(
+        device_context: &RafxDeviceContextMetal,
+        data: &'a [u8],
+    ) -> Option<&'a [u8]> {
+        if data.len() < PIPELINE_CACHE_HEADER_SIZE
+            || &data[0..4] != PIPELINE_CACHE_MAGIC
+            || u32::from_le_bytes(data[4..8].try_into().unwrap()) != PIPELINE_CACHE_VERSION
+        {
+            log::warn!("Pipeline cache data is not valid, it will be ignored");
+            return None;
+        }
+
+        let registry_id = u64::from_le_bytes(data[8..16].try_into().unwrap());
+        if registry_id != device_context.device().registry_id() {
+            log::warn!(
+                "Pipeline cache data is not compatible with this device, it will be ignored"
+            );
+            return None;
+        }
+
+        Some(&data[PIPELINE_CACHE_HEADER_SIZE..])
+    }
+
+    fn load_binary_archive(
+        device: &metal_rs::DeviceRef,
+        archive_data: &[u8],
+    ) -> RafxResult<*mut Object> {
+        // Binary archives can only be loaded from a file
+        let path = temp_file_path();
+        std::fs::write(&path, archive_data)?;
+        let result = Self::create_binary_archive(device, Some(&path));
+        let _ = std::fs::remove_file(&path);
+        result
+    }
+
+    fn create_binary_archive(
+        device: &metal_rs::DeviceRef,
+        path: Option<&Path>,
+    ) -> RafxResult<*mut Object> {
+        objc::rc::autoreleasepool(|| unsafe {
+            let descriptor: *mut Object = msg_send![class!(MTLBinaryArchiveDescriptor), new];
+            if let Some(path) = path {
+                let () = msg_send![descriptor, setUrl: file_url(path)];
+            }
+
+            let mut error: *mut Object = std::ptr::null_mut();
+            let binary_archive: *mut Object = msg_send![
+                device,
+                newBinaryArchiveWithDescriptor: descriptor
+                error: &mut error
+            ];
+            let () = msg_send![descriptor, release];
+
+            if binary_archive.is_null() {
+                Err(format!(
+                    "Failed to create binary archive: {}",
+                    error_description(error)
+                ))?;
+            }
+
+            Ok(binary_archive)
+        })
+    }
+
+    // Sets this cache as the binary archive to search when creating a pipeline with the given
+    // MTLRenderPipelineDescriptor or MTLComputePipelineDescriptor
+    pub(crate) fn set_binary_archives(
+        &self,
+        pipeline_descriptor: *mut Object,
+    ) {
+        if let Some(binary_archive) = self.binary_archive {
+            objc::rc::autoreleasepool(|| unsafe {
+                let binary_archives: *mut Object =
+                    msg_send![class!(NSArray), arrayWithObject: binary_archive];
+                let () = msg_send![pipeline_descriptor, setBinaryArchives: binary_archives];
+            });
+        }
+    }
+
+    pub(crate) fn add_render_pipeline_functions(
+        &self,
+        descriptor: &metal_rs::RenderPipelineDescriptorRef,
+    ) {
+        if let Some(binary_archive) = self.binary_archive {
+            let result = objc::rc::autoreleasepool(|| unsafe {
+                let mut error: *mut Object = std::ptr::null_mut();
+                let succeeded: BOOL = msg_send![
+                    binary_archive,
+                    addRenderPipelineFunctionsWithDescriptor: descriptor
+                    error: &mut error
+                ];
+                if succeeded == NO {
+                    Err(error_description(error))
+                } else {
+                    Ok(())
+                }
+            });
+
+            if let Err(e) = result {
+                log::warn!("Failed to add render pipeline to pipeline cache: {}", e);
+            }
+        }
+    }
+
+    pub(crate) fn add_compute_pipeline_functions(
+        &self,
+        descriptor: &metal_rs::ComputePipelineDescriptorRef,
+    ) {
+        if let Some(binary_archive) = self.binary_archive {
+            let result = objc::rc::autoreleasepool(|| unsafe {
+                let mut error: *mut Object = std::ptr::null_mut();
+                let succeeded: BOOL = msg_send![
+                    binary_archive,
+                    addComputePipelineFunctionsWithDescriptor: descriptor
+                    error: &mut error
+                ];
+                if succeeded == NO {
+                    Err(error_description(error))
+                } else {
+                    Ok(())
+                }
+            });
+
+            if let Err(e) = result {
+                log::warn!("Failed to add compute pipeline to pipeline cache: {}", e);
+            }
+        }
+    }
+
+    pub fn get_data(&self) -> RafxResult<Vec<u8>> {
+        let binary_archive = match self.binary_archive {
+            Some(binary_archive) => binary_archive,
+            None => return Ok(Vec::default()),
+        };
+
+        // Binary archives can only be serialized to a file
+        let path = temp_file_path();
+        let result: RafxResult<()> = objc::rc::autoreleasepool(|| unsafe {
+            let mut error: *mut Object = std::ptr::null_mut();
+            let succeeded: BOOL = msg_send![
+                binary_archive,
+                serializeToURL: file_url(&path)
+                error: &mut error
+            ];
+            if succeeded == NO {
+                Err(format!(
+                    "Failed to serialize binary archive: {}",
+                    error_description(error)
+                ))?;
+            }
+
+            Ok(())
+        });
+
+        let archive_data = result.and_then(|_| Ok(std::fs::read(&path)?));
+        let _ = std::fs::remove_file(&path);
+        let archive_data = archive_data?;
+
+        let mut data = Vec::with_capacity(PIPELINE_CACHE_HEADER_SIZE + archive_data.len());
+        data.extend_from_slice(PIPELINE_CACHE_MAGIC);
+        data.extend_from_slice(&PIPELINE_CACHE_VERSION.to_le_bytes());
+        data.extend_from_slice(&self.device_context.device().registry_id().to_le_bytes());
+        data.extend_from_slice(&archive_data);
+        Ok(data)
+    }
+}
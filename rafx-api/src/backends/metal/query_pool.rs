@@ -0,0 +1,205 @@
+use crate::metal::RafxDeviceContextMetal;
+use crate::{RafxQueryPoolDef, RafxQueryType, RafxResult};
+use cocoa_foundation::foundation::NSUInteger;
+use objc::runtime::Object;
+use objc::{class, msg_send, sel, sel_impl};
+use std::ffi::CStr;
+use std::os::raw::c_char;
+
+// Written to a sample that could not be collected (MTLCounterErrorValue)
+const COUNTER_ERROR_VALUE: u64 = !0;
+
+// A CPU timestamp (nanoseconds) and GPU timestamp (ticks) sampled at the same time
+#[derive(Copy, Clone, Debug)]
+struct TimestampCalibration {
+    cpu_timestamp: u64,
+    gpu_timestamp: u64,
+}
+
+impl TimestampCalibration {
+    fn sample(device: &metal_rs::DeviceRef) -> Self {
+        let mut cpu_timestamp: u64 = 0;
+        let mut gpu_timestamp: u64 = 0;
+        unsafe {
+            let () = msg_send![
+                device,
+                sampleTimestamps: &mut cpu_timestamp
+                gpuTimestamp: &mut gpu_timestamp
+            ];
+        }
+
+        TimestampCalibration {
+            cpu_timestamp,
+            gpu_timestamp,
+        }
+    }
+}
+
+// metal-rs does not wrap counter sample buffers yet, so this talks to the objective-c API
+// directly. The buffer is an id<MTLCounterSampleBuffer>
+pub struct RafxQueryPoolMetal {
+    device_context: RafxDeviceContextMetal,
+    query_pool_def: RafxQueryPoolDef,
+    counter_sample_buffer: *mut Object,
+    // GPU timestamps are in ticks that are not nanoseconds on all devices. This sample is compared
+    // with one taken when results are read to find the length of a tick
+    calibration: TimestampCalibration,
+}
+
+// For the counter sample buffer
+unsafe impl Send for RafxQueryPoolMetal {}
+unsafe impl Sync for RafxQueryPoolMetal {}
+
+impl Drop for RafxQueryPoolMetal {
+    fn drop(&mut self) {
+        unsafe {
+            let () = msg_send![self.counter_sample_buffer, release];
+        }
+    }
+}
+
+impl RafxQueryPoolMetal {
+    pub fn new(
+        device_context: &RafxDeviceContextMetal,
+        query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPoolMetal> {
+        query_pool_def.verify();
+
+        if query_pool_def.query_type != RafxQueryType::Timestamp {
+            Err("Only timestamp queries are supported on metal")?;
+        }
+
+        if !device_context.device_info().supports_timestamp_queries {
+            Err("Timestamp queries are not supported by this device")?;
+        }
+
+        let device: &metal_rs::DeviceRef = device_context.device();
+        let result: RafxResult<*mut Object> = objc::rc::autoreleasepool(|| unsafe {
+            let counter_set = Self::find_timestamp_counter_set(device)
+                .ok_or("Device does not have a timestamp counter set")?;
+
+            let descriptor: *mut Object = msg_send![class!(MTLCounterSampleBufferDescriptor), new];
+            let () = msg_send![descriptor, setCounterSet: counter_set];
+            let storage_mode = metal_rs::MTLStorageMode::Shared as NSUInteger;
+            let () = msg_send![descriptor, setStorageMode: storage_mode];
+            let () =
+                msg_send![descriptor, setSampleCount: query_pool_def.query_count as NSUInteger];
+
+            let mut error: *mut Object = std::ptr::null_mut();
+            let counter_sample_buffer: *mut Object = msg_send![
+                device,
+                newCounterSampleBufferWithDescriptor: descriptor
+                error: &mut error
+            ];
+            let () = msg_send![descriptor, release];
+
+            if counter_sample_buffer.is_null() {
+                let description = if error.is_null() {
+                    "unknown error".to_string()
+                } else {
+                    let description: *mut Object = msg_send![error, localizedDescription];
+                    let description: *const c_char = msg_send![description, UTF8String];
+                    CStr::from_ptr(description).to_string_lossy().to_string()
+                };
+
+                Err(format!(
+                    "Failed to create counter sample buffer: {}",
+                    description
+                ))?;
+            }
+
+            Ok(counter_sample_buffer)
+        });
+        let counter_sample_buffer = result?;
+        let calibration = TimestampCalibration::sample(device);
+
+        Ok(RafxQueryPoolMetal {
+            device_context: device_context.clone(),
+            query_pool_def: query_pool_def.clone(),
+            counter_sample_buffer,
+            calibration,
+        })
+    }
+
+    unsafe fn find_timestamp_counter_set(device: &metal_rs::DeviceRef) -> Option<*mut Object> {
+        let counter_sets: *mut Object = msg_send![device, counterSets];
+        if counter_sets.is_null() {
+            return None;
+        }
+
+        let count: NSUInteger = msg_send![counter_sets, count];
+        for i in 0..count {
+            let counter_set: *mut Object = msg_send![counter_sets, objectAtIndex: i];
+            let name: *mut Object = msg_send![counter_set, name];
+            let name: *const c_char = msg_send![name, UTF8String];
+            // MTLCommonCounterSetTimestamp
+            if CStr::from_ptr(name).to_bytes() == b"timestamp" {
+                return Some(counter_set);
+            }
+        }
+
+        None
+    }
+
+    pub fn query_pool_def(&self) -> &RafxQueryPoolDef {
+        &self.query_pool_def
+    }
+
+    pub fn metal_counter_sample_buffer(&self) -> *mut Object {
+        self.counter_sample_buffer
+    }
+
+    pub fn get_results(
+        &self,
+        first_query: u32,
+        query_count: u32,
+        results: &mut [u64],
+    ) -> RafxResult<bool> {
+        assert!(first_query + query_count <= self.query_pool_def.query_count);
+        assert!(results.len() >= query_count as usize);
+
+        objc::rc::autoreleasepool(|| unsafe {
+            let range = metal_rs::NSRange::new(first_query as _, query_count as _);
+            let data: *mut Object =
+                msg_send![self.counter_sample_buffer, resolveCounterRange: range];
+            if data.is_null() {
+                return Ok(false);
+            }
+
+            // Each sample is a MTLCounterResultTimestamp, which is a single u64
+            let length: NSUInteger = msg_send![data, length];
+            let bytes: *const u64 = msg_send![data, bytes];
+            let sample_count =
+                (length as usize / std::mem::size_of::<u64>()).min(query_count as usize);
+            let samples = std::slice::from_raw_parts(bytes, sample_count);
+
+            if sample_count < query_count as usize || samples.contains(&COUNTER_ERROR_VALUE) {
+                return Ok(false);
+            }
+
+            // Convert from GPU ticks to nanoseconds, so that timestamp_period_ns (1.0) is correct
+            let calibration = TimestampCalibration::sample(self.device_context.device());
+            let gpu_elapsed = calibration
+                .gpu_timestamp
+                .saturating_sub(self.calibration.gpu_timestamp);
+            let cpu_elapsed = calibration
+                .cpu_timestamp
+                .saturating_sub(self.calibration.cpu_timestamp);
+            let ns_per_tick = if gpu_elapsed > 0 {
+                cpu_elapsed as f64 / gpu_elapsed as f64
+            } else {
+                1.0
+            };
+
+            for (result, sample) in results.iter_mut().zip(samples) {
+                let ticks_since_calibration =
+                    *sample as i64 - self.calibration.gpu_timestamp as i64;
+                let ns_since_calibration = (ticks_since_calibration as f64 * ns_per_tick) as i64;
+                *result =
+                    (self.calibration.cpu_timestamp as i64 + ns_since_calibration).max(0) as u64;
+            }
+
+            Ok(true)
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use crate::metal::{RafxDeviceContextMetal, RafxSamplerMetal};
 use crate::{
     RafxDescriptorIndex, RafxPipelineType, RafxResourceType, RafxResult, RafxRootSignatureDef,
-    MAX_DESCRIPTOR_SET_LAYOUTS,
+    RafxShaderStageFlags, MAX_DESCRIPTOR_SET_LAYOUTS,
 };
 use cocoa_foundation::foundation::NSUInteger;
 use fnv::FnvHashMap;
@@ -40,6 +40,13 @@ pub(crate) struct DescriptorInfo {
     pub(crate) argument_buffer_id: NSUInteger,
 }
 
+// Push constants are bound with set_bytes at METAL_PUSH_CONSTANT_BUFFER_INDEX
+#[derive(Clone, Debug)]
+pub(crate) struct PushConstantInfo {
+    pub(crate) size_in_bytes: u32,
+    pub(crate) used_in_shader_stages: RafxShaderStageFlags,
+}
+
 #[derive(Default, Debug)]
 pub(crate) struct DescriptorSetLayoutInfo {
     // Settable descriptors, immutable samplers are omitted
@@ -73,6 +80,7 @@ pub(crate) struct RafxRootSignatureMetalInner {
         [Vec<metal_rs::ArgumentDescriptor>; MAX_DESCRIPTOR_SET_LAYOUTS],
     pub(crate) argument_buffer_resource_usages:
         [Arc<Vec<MTLResourceUsage>>; MAX_DESCRIPTOR_SET_LAYOUTS],
+    pub(crate) push_constant: Option<PushConstantInfo>,
 }
 
 // for metal_rs::ArgumentDescriptor
@@ -119,6 +127,10 @@ impl RafxRootSignatureMetal {
         self.inner.descriptors.get(descriptor_index.0 as usize)
     }
 
+    pub(crate) fn push_constant(&self) -> Option<&PushConstantInfo> {
+        self.inner.push_constant.as_ref()
+    }
+
     pub fn new(
         device_context: &RafxDeviceContextMetal,
         root_signature_def: &RafxRootSignatureDef,
@@ -158,7 +170,7 @@ impl RafxRootSignatureMetal {
         for resource in &merged_resources {
             resource.validate()?;
 
-            // Not currently supported
+            // Push constants are merged separately
             assert_ne!(resource.resource_type, RafxResourceType::ROOT_CONSTANT);
 
             // Verify set index is valid
@@ -282,6 +294,15 @@ impl RafxRootSignatureMetal {
             Arc::new(std::mem::take(&mut resource_usages[3])),
         ];
 
+        let push_constant = crate::internal_shared::merge_push_constants(
+            root_signature_def,
+            device_context.device_info().max_push_constant_size,
+        )?
+        .map(|resource| PushConstantInfo {
+            size_in_bytes: resource.size_in_bytes,
+            used_in_shader_stages: resource.used_in_shader_stages,
+        });
+
         let inner = RafxRootSignatureMetalInner {
             device_context: device_context.clone(),
             pipeline_type,
@@ -290,6 +311,7 @@ impl RafxRootSignatureMetal {
             name_to_descriptor_index,
             argument_buffer_resource_usages,
             argument_descriptors,
+            push_constant,
         };
 
         Ok(RafxRootSignatureMetal {
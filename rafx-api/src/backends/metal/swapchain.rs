@@ -76,6 +76,8 @@ impl RafxSwapchainMetal {
         layer.set_pixel_format(metal_rs::MTLPixelFormat::BGRA8Unorm_sRGB);
         layer.set_presents_with_transaction(false);
         layer.set_display_sync_enabled(swapchain_def.enable_vsync);
+        // Allows blitting out of the drawable (i.e. for screenshots)
+        layer.set_framebuffer_only(false);
 
         //TODO: disable timeout on acquire drawable?
         layer.set_drawable_size(metal_rs::CGSize::new(
@@ -82,6 +82,15 @@ impl RafxTextureMetal {
         &self.inner.mip_level_uav_views
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        if self.inner.device_context.device_info().debug_names_enabled {
+            self.metal_texture().set_label(name.as_ref());
+        }
+    }
+
     pub fn new(
         device_context: &RafxDeviceContextMetal,
         texture_def: &RafxTextureDef,
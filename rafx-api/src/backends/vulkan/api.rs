@@ -86,7 +86,7 @@ impl RafxApiVulkan {
     /// behavior on the CPU for reasons other than interacting with the GPU.
     pub unsafe fn new(
         window: &dyn HasRawWindowHandle,
-        _api_def: &RafxApiDef,
+        api_def: &RafxApiDef,
         vk_api_def: &RafxApiDefVulkan,
     ) -> RafxResult<Self> {
         let link_method = vk_api_def.link_method;
@@ -117,6 +117,7 @@ impl RafxApiVulkan {
             &app_name,
             require_validation_layers_present,
             validation_layer_debug_report_flags,
+            api_def.enable_debug_names,
         )?;
 
         let inner = Arc::new(RafxDeviceContextVulkanInner::new(&instance)?);
@@ -44,6 +44,14 @@ impl RafxBufferVulkan {
         &self.buffer_def
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        self.device_context
+            .set_debug_name(self.vk_buffer(), name.as_ref());
+    }
+
     pub fn map_buffer(&self) -> RafxResult<*mut u8> {
         let ptr = self
             .device_context
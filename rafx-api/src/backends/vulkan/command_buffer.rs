@@ -2,7 +2,18 @@ use crate::vulkan::*;
 use crate::*;
 use ash::version::DeviceV1_0;
 use ash::vk;
+use std::ffi::CString;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+// The renderpass that secondary command buffers begun from this command buffer will continue
+#[derive(Debug, Copy, Clone)]
+struct SecondaryCommandBufferInheritance {
+    vk_renderpass: vk::RenderPass,
+    vk_framebuffer: vk::Framebuffer,
+    width: u32,
+    height: u32,
+}
 
 #[derive(Debug)]
 pub struct RafxCommandBufferVulkan {
@@ -12,6 +23,7 @@ pub struct RafxCommandBufferVulkan {
     queue_type: RafxQueueType,
     queue_family_index: u32,
     has_active_renderpass: AtomicBool,
+    secondary_command_buffer_inheritance: Mutex<Option<SecondaryCommandBufferInheritance>>,
 }
 
 impl Into<RafxCommandBuffer> for RafxCommandBufferVulkan {
@@ -54,6 +66,7 @@ impl RafxCommandBufferVulkan {
             queue_type: command_pool.queue_type(),
             queue_family_index: command_pool.queue_family_index(),
             has_active_renderpass: AtomicBool::new(false),
+            secondary_command_buffer_inheritance: Mutex::new(None),
         })
     }
 
@@ -76,6 +89,43 @@ impl RafxCommandBufferVulkan {
         Ok(())
     }
 
+    pub fn begin_secondary(
+        &self,
+        primary_command_buffer: &RafxCommandBufferVulkan,
+    ) -> RafxResult<()> {
+        let inheritance = primary_command_buffer
+            .secondary_command_buffer_inheritance
+            .lock()
+            .unwrap()
+            .ok_or("The primary command buffer is not in a renderpass begun with cmd_begin_render_pass_with_secondary_command_buffers")?;
+
+        let inheritance_info = vk::CommandBufferInheritanceInfo::builder()
+            .render_pass(inheritance.vk_renderpass)
+            .subpass(0)
+            .framebuffer(inheritance.vk_framebuffer);
+
+        let begin_info = vk::CommandBufferBeginInfo::builder()
+            .flags(vk::CommandBufferUsageFlags::RENDER_PASS_CONTINUE)
+            .inheritance_info(&*inheritance_info);
+
+        unsafe {
+            self.device_context
+                .device()
+                .begin_command_buffer(self.vk_command_buffer, &*begin_info)?;
+        }
+
+        // Dynamic state is not inherited from the primary
+        self.cmd_set_viewport(
+            0.0,
+            0.0,
+            inheritance.width as f32,
+            inheritance.height as f32,
+            0.0,
+            1.0,
+        )?;
+        self.cmd_set_scissor(0, 0, inheritance.width, inheritance.height)
+    }
+
     pub fn end(&self) -> RafxResult<()> {
         if self.has_active_renderpass.load(Ordering::Relaxed) {
             unsafe {
@@ -85,6 +135,7 @@ impl RafxCommandBufferVulkan {
             }
 
             self.has_active_renderpass.store(false, Ordering::Relaxed);
+            *self.secondary_command_buffer_inheritance.lock().unwrap() = None;
         }
 
         unsafe {
@@ -110,6 +161,27 @@ impl RafxCommandBufferVulkan {
         &self,
         color_targets: &[RafxColorRenderTargetBinding],
         depth_target: Option<RafxDepthStencilRenderTargetBinding>,
+    ) -> RafxResult<()> {
+        self.do_cmd_begin_render_pass(color_targets, depth_target, vk::SubpassContents::INLINE)
+    }
+
+    pub fn cmd_begin_render_pass_with_secondary_command_buffers(
+        &self,
+        color_targets: &[RafxColorRenderTargetBinding],
+        depth_target: Option<RafxDepthStencilRenderTargetBinding>,
+    ) -> RafxResult<()> {
+        self.do_cmd_begin_render_pass(
+            color_targets,
+            depth_target,
+            vk::SubpassContents::SECONDARY_COMMAND_BUFFERS,
+        )
+    }
+
+    fn do_cmd_begin_render_pass(
+        &self,
+        color_targets: &[RafxColorRenderTargetBinding],
+        depth_target: Option<RafxDepthStencilRenderTargetBinding>,
+        subpass_contents: vk::SubpassContents,
     ) -> RafxResult<()> {
         if self.has_active_renderpass.load(Ordering::Relaxed) {
             self.cmd_end_render_pass()?;
@@ -229,12 +301,25 @@ impl RafxCommandBufferVulkan {
             self.device_context.device().cmd_begin_render_pass(
                 self.vk_command_buffer,
                 &*begin_renderpass_create_info,
-                vk::SubpassContents::INLINE,
+                subpass_contents,
             );
         }
 
         self.has_active_renderpass.store(true, Ordering::Relaxed);
 
+        if subpass_contents == vk::SubpassContents::SECONDARY_COMMAND_BUFFERS {
+            // Commands can't be written inline, the secondary command buffers will set the
+            // viewport and scissor
+            *self.secondary_command_buffer_inheritance.lock().unwrap() =
+                Some(SecondaryCommandBufferInheritance {
+                    vk_renderpass: renderpass.vk_renderpass(),
+                    vk_framebuffer: framebuffer.vk_framebuffer(),
+                    width: framebuffer.width(),
+                    height: framebuffer.height(),
+                });
+            return Ok(());
+        }
+
         self.cmd_set_viewport(
             0.0,
             0.0,
@@ -257,6 +342,25 @@ impl RafxCommandBufferVulkan {
                 .cmd_end_render_pass(self.vk_command_buffer);
             self.has_active_renderpass.store(false, Ordering::Relaxed);
         }
+        *self.secondary_command_buffer_inheritance.lock().unwrap() = None;
+
+        Ok(())
+    }
+
+    pub fn cmd_execute_secondary(
+        &self,
+        secondary_command_buffers: &[&RafxCommandBufferVulkan],
+    ) -> RafxResult<()> {
+        let vk_command_buffers: Vec<_> = secondary_command_buffers
+            .iter()
+            .map(|x| x.vk_command_buffer)
+            .collect();
+
+        unsafe {
+            self.device_context
+                .device()
+                .cmd_execute_commands(self.vk_command_buffer, &vk_command_buffers);
+        }
 
         Ok(())
     }
@@ -421,6 +525,32 @@ impl RafxCommandBufferVulkan {
         Ok(())
     }
 
+    pub fn cmd_push_constants(
+        &self,
+        root_signature: &RafxRootSignatureVulkan,
+        offset_in_bytes: u32,
+        data: &[u8],
+    ) -> RafxResult<()> {
+        let push_constant = root_signature.push_constant();
+        crate::internal_shared::verify_push_constant_range(
+            push_constant.map(|x| x.vk_push_constant_range.size),
+            offset_in_bytes,
+            data.len() as u32,
+        )?;
+
+        unsafe {
+            self.device_context.device().cmd_push_constants(
+                self.vk_command_buffer,
+                root_signature.vk_pipeline_layout(),
+                push_constant.unwrap().vk_push_constant_range.stage_flags,
+                offset_in_bytes,
+                data,
+            );
+        }
+
+        Ok(())
+    }
+
     pub fn cmd_draw(
         &self,
         vertex_count: u32,
@@ -519,6 +649,116 @@ impl RafxCommandBufferVulkan {
         Ok(())
     }
 
+    pub fn cmd_draw_indirect(
+        &self,
+        indirect_buffer: &RafxBufferVulkan,
+        indirect_buffer_offset_in_bytes: u32,
+        draw_count: u32,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.device_context.device().cmd_draw_indirect(
+                self.vk_command_buffer,
+                indirect_buffer.vk_buffer(),
+                indirect_buffer_offset_in_bytes as u64,
+                draw_count,
+                std::mem::size_of::<RafxDrawIndirectCommand>() as u32,
+            )
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_draw_indexed_indirect(
+        &self,
+        indirect_buffer: &RafxBufferVulkan,
+        indirect_buffer_offset_in_bytes: u32,
+        draw_count: u32,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.device_context.device().cmd_draw_indexed_indirect(
+                self.vk_command_buffer,
+                indirect_buffer.vk_buffer(),
+                indirect_buffer_offset_in_bytes as u64,
+                draw_count,
+                std::mem::size_of::<RafxDrawIndexedIndirectCommand>() as u32,
+            )
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_draw_indirect_count(
+        &self,
+        indirect_buffer: &RafxBufferVulkan,
+        indirect_buffer_offset_in_bytes: u32,
+        count_buffer: &RafxBufferVulkan,
+        count_buffer_offset_in_bytes: u32,
+        max_draw_count: u32,
+    ) -> RafxResult<()> {
+        let loader = self
+            .device_context
+            .draw_indirect_count_loader()
+            .ok_or("VK_KHR_draw_indirect_count is not supported by this device")?;
+
+        unsafe {
+            loader.cmd_draw_indirect_count(
+                self.vk_command_buffer,
+                indirect_buffer.vk_buffer(),
+                indirect_buffer_offset_in_bytes as u64,
+                count_buffer.vk_buffer(),
+                count_buffer_offset_in_bytes as u64,
+                max_draw_count,
+                std::mem::size_of::<RafxDrawIndirectCommand>() as u32,
+            )
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_draw_indexed_indirect_count(
+        &self,
+        indirect_buffer: &RafxBufferVulkan,
+        indirect_buffer_offset_in_bytes: u32,
+        count_buffer: &RafxBufferVulkan,
+        count_buffer_offset_in_bytes: u32,
+        max_draw_count: u32,
+    ) -> RafxResult<()> {
+        let loader = self
+            .device_context
+            .draw_indirect_count_loader()
+            .ok_or("VK_KHR_draw_indirect_count is not supported by this device")?;
+
+        unsafe {
+            loader.cmd_draw_indexed_indirect_count(
+                self.vk_command_buffer,
+                indirect_buffer.vk_buffer(),
+                indirect_buffer_offset_in_bytes as u64,
+                count_buffer.vk_buffer(),
+                count_buffer_offset_in_bytes as u64,
+                max_draw_count,
+                std::mem::size_of::<RafxDrawIndexedIndirectCommand>() as u32,
+            )
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_dispatch_indirect(
+        &self,
+        indirect_buffer: &RafxBufferVulkan,
+        indirect_buffer_offset_in_bytes: u32,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.device_context.device().cmd_dispatch_indirect(
+                self.vk_command_buffer,
+                indirect_buffer.vk_buffer(),
+                indirect_buffer_offset_in_bytes as u64,
+            )
+        }
+
+        Ok(())
+    }
+
     pub fn cmd_resource_barrier(
         &self,
         buffer_barriers: &[RafxBufferBarrier],
@@ -776,6 +1016,275 @@ impl RafxCommandBufferVulkan {
         Ok(())
     }
 
+    pub fn cmd_copy_texture_to_buffer(
+        &self,
+        src_texture: &RafxTextureVulkan,
+        dst_buffer: &RafxBufferVulkan,
+        params: &RafxCmdCopyTextureToBufferParams,
+    ) -> RafxResult<()> {
+        let texture_def = src_texture.texture_def();
+        let format = texture_def.format;
+        let extents = params.copy_extents(texture_def);
+        let row_pitch = params.row_pitch(format, extents);
+        let slice_pitch = params.slice_pitch(format, extents);
+
+        // Vulkan describes the layout of the buffer in texels rather than bytes
+        let block_size_in_bytes = format.block_or_pixel_size_in_bytes();
+        if row_pitch % block_size_in_bytes != 0 {
+            Err("Buffer row pitch must be a multiple of the format's block size")?;
+        }
+
+        if slice_pitch % row_pitch != 0 {
+            Err("Buffer slice pitch must be a multiple of the row pitch")?;
+        }
+
+        // A copy between a buffer and an image may only include a single aspect, and the texel
+        // size of one aspect doesn't match the size of the format that the buffer is laid out for
+        if format.has_depth() && format.has_stencil() {
+            Err("Copying a combined depth/stencil texture to a buffer is not supported")?;
+        }
+
+        let aspect_mask = src_texture.vk_aspect_mask();
+
+        let buffer_row_length = row_pitch / block_size_in_bytes * format.block_width_in_pixels();
+        let buffer_image_height = slice_pitch / row_pitch * format.block_height_in_pixels();
+
+        unsafe {
+            self.device_context.device().cmd_copy_image_to_buffer(
+                self.vk_command_buffer,
+                src_texture.vk_image(),
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                dst_buffer.vk_buffer(),
+                &[vk::BufferImageCopy {
+                    image_extent: extents.into(),
+                    image_offset: params.src_offset.into(),
+                    image_subresource: vk::ImageSubresourceLayers {
+                        aspect_mask,
+                        mip_level: params.mip_level as u32,
+                        base_array_layer: params.array_layer as u32,
+                        layer_count: 1,
+                    },
+                    buffer_offset: params.buffer_offset,
+                    buffer_image_height,
+                    buffer_row_length,
+                }],
+            );
+        }
+
+        Ok(())
+    }
+
+    // Makes transfer writes to the buffer (i.e. copying a texture into it) visible to the host, so
+    // that the buffer can be mapped and read once the command buffer has finished executing
+    pub fn cmd_buffer_host_read_barrier(
+        &self,
+        buffer: &RafxBufferVulkan,
+    ) -> RafxResult<()> {
+        let buffer_barrier = vk::BufferMemoryBarrier::builder()
+            .src_access_mask(vk::AccessFlags::TRANSFER_WRITE)
+            .dst_access_mask(vk::AccessFlags::HOST_READ)
+            .src_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .dst_queue_family_index(vk::QUEUE_FAMILY_IGNORED)
+            .buffer(buffer.vk_buffer())
+            .offset(0)
+            .size(vk::WHOLE_SIZE)
+            .build();
+
+        unsafe {
+            self.device_context.device().cmd_pipeline_barrier(
+                self.vk_command_buffer,
+                vk::PipelineStageFlags::TRANSFER,
+                vk::PipelineStageFlags::HOST,
+                vk::DependencyFlags::empty(),
+                &[],
+                &[buffer_barrier],
+                &[],
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_copy_texture_to_texture(
+        &self,
+        src_texture: &RafxTextureVulkan,
+        dst_texture: &RafxTextureVulkan,
+        params: &RafxCmdCopyTextureToTextureParams,
+    ) -> RafxResult<()> {
+        let extents = params.copy_extents(src_texture.texture_def());
+
+        unsafe {
+            self.device_context.device().cmd_copy_image(
+                self.vk_command_buffer,
+                src_texture.vk_image(),
+                vk::ImageLayout::TRANSFER_SRC_OPTIMAL,
+                dst_texture.vk_image(),
+                vk::ImageLayout::TRANSFER_DST_OPTIMAL,
+                &[vk::ImageCopy {
+                    src_subresource: vk::ImageSubresourceLayers {
+                        aspect_mask: src_texture.vk_aspect_mask(),
+                        mip_level: params.src_mip_level as u32,
+                        base_array_layer: params.src_array_layer as u32,
+                        layer_count: 1,
+                    },
+                    src_offset: params.src_offset.into(),
+                    dst_subresource: vk::ImageSubresourceLayers {
+                        aspect_mask: dst_texture.vk_aspect_mask(),
+                        mip_level: params.dst_mip_level as u32,
+                        base_array_layer: params.dst_array_layer as u32,
+                        layer_count: 1,
+                    },
+                    dst_offset: params.dst_offset.into(),
+                    extent: extents.into(),
+                }],
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_reset_queries(
+        &self,
+        query_pool: &RafxQueryPoolVulkan,
+        first_query: u32,
+        query_count: u32,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.device_context.device().cmd_reset_query_pool(
+                self.vk_command_buffer,
+                query_pool.vk_query_pool(),
+                first_query,
+                query_count,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_write_timestamp(
+        &self,
+        query_pool: &RafxQueryPoolVulkan,
+        query_index: u32,
+    ) -> RafxResult<()> {
+        assert_eq!(
+            query_pool.query_pool_def().query_type,
+            RafxQueryType::Timestamp
+        );
+
+        unsafe {
+            self.device_context.device().cmd_write_timestamp(
+                self.vk_command_buffer,
+                vk::PipelineStageFlags::BOTTOM_OF_PIPE,
+                query_pool.vk_query_pool(),
+                query_index,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_begin_query(
+        &self,
+        query_pool: &RafxQueryPoolVulkan,
+        query_index: u32,
+    ) -> RafxResult<()> {
+        assert_ne!(
+            query_pool.query_pool_def().query_type,
+            RafxQueryType::Timestamp
+        );
+
+        unsafe {
+            self.device_context.device().cmd_begin_query(
+                self.vk_command_buffer,
+                query_pool.vk_query_pool(),
+                query_index,
+                vk::QueryControlFlags::empty(),
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_end_query(
+        &self,
+        query_pool: &RafxQueryPoolVulkan,
+        query_index: u32,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.device_context.device().cmd_end_query(
+                self.vk_command_buffer,
+                query_pool.vk_query_pool(),
+                query_index,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_resolve_queries(
+        &self,
+        query_pool: &RafxQueryPoolVulkan,
+        first_query: u32,
+        query_count: u32,
+        dst_buffer: &RafxBufferVulkan,
+        dst_offset: u64,
+    ) -> RafxResult<()> {
+        unsafe {
+            self.device_context.device().cmd_copy_query_pool_results(
+                self.vk_command_buffer,
+                query_pool.vk_query_pool(),
+                first_query,
+                query_count,
+                dst_buffer.vk_buffer(),
+                dst_offset,
+                query_pool.result_stride(),
+                vk::QueryResultFlags::TYPE_64 | vk::QueryResultFlags::WAIT,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_push_group_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) -> RafxResult<()> {
+        if let Some(debug_utils) = self.device_context.debug_utils_loader() {
+            let name = CString::new(name.as_ref()).map_err(|x| format!("{:?}", x))?;
+            let label = vk::DebugUtilsLabelEXT::builder().label_name(&name);
+            unsafe {
+                debug_utils.cmd_begin_debug_utils_label(self.vk_command_buffer, &label);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_pop_group_debug_name(&self) -> RafxResult<()> {
+        if let Some(debug_utils) = self.device_context.debug_utils_loader() {
+            unsafe {
+                debug_utils.cmd_end_debug_utils_label(self.vk_command_buffer);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn cmd_insert_debug_marker(
+        &self,
+        name: impl AsRef<str>,
+    ) -> RafxResult<()> {
+        if let Some(debug_utils) = self.device_context.debug_utils_loader() {
+            let name = CString::new(name.as_ref()).map_err(|x| format!("{:?}", x))?;
+            let label = vk::DebugUtilsLabelEXT::builder().label_name(&name);
+            unsafe {
+                debug_utils.cmd_insert_debug_utils_label(self.vk_command_buffer, &label);
+            }
+        }
+
+        Ok(())
+    }
+
     pub fn cmd_blit_image(
         &self,
         src_texture: &RafxTextureVulkan,
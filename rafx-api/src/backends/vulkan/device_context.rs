@@ -6,13 +6,15 @@ use raw_window_handle::HasRawWindowHandle;
 use std::sync::{Arc, Mutex};
 
 use crate::vulkan::{
-    RafxBufferVulkan, RafxDescriptorSetArrayVulkan, RafxFenceVulkan, RafxPipelineVulkan,
-    RafxQueueVulkan, RafxRootSignatureVulkan, RafxSamplerVulkan, RafxSemaphoreVulkan,
-    RafxShaderModuleVulkan, RafxShaderVulkan, RafxSwapchainVulkan, RafxTextureVulkan,
+    RafxBufferVulkan, RafxDescriptorSetArrayVulkan, RafxFenceVulkan, RafxPipelineCacheVulkan,
+    RafxPipelineVulkan, RafxQueryPoolVulkan, RafxQueueVulkan, RafxRootSignatureVulkan,
+    RafxSamplerVulkan, RafxSemaphoreVulkan, RafxShaderModuleVulkan, RafxShaderVulkan,
+    RafxSwapchainVulkan, RafxTextureVulkan,
 };
+use ash::extensions::ext::DebugUtils;
 use ash::extensions::khr;
 use fnv::FnvHashMap;
-use std::ffi::CStr;
+use std::ffi::{CStr, CString};
 #[cfg(debug_assertions)]
 #[cfg(feature = "track-device-contexts")]
 use std::sync::atomic::AtomicU64;
@@ -63,6 +65,17 @@ pub struct PhysicalDeviceInfo {
     pub all_queue_families: Vec<ash::vk::QueueFamilyProperties>,
 }
 
+impl PhysicalDeviceInfo {
+    pub fn supports_extension(
+        &self,
+        extension_name: &CStr,
+    ) -> bool {
+        self.extension_properties.iter().any(|extension| unsafe {
+            CStr::from_ptr(extension.extension_name.as_ptr()) == extension_name
+        })
+    }
+}
+
 #[derive(Default, Clone, Debug)]
 pub struct VkQueueFamilyIndices {
     pub graphics_queue_family_index: u32,
@@ -87,6 +100,8 @@ pub struct RafxDeviceContextVulkanInner {
     instance: ash::Instance,
     physical_device: vk::PhysicalDevice,
     physical_device_info: PhysicalDeviceInfo,
+    draw_indirect_count: Option<khr::DrawIndirectCount>,
+    debug_utils: Option<DebugUtils>,
 
     #[cfg(debug_assertions)]
     #[cfg(feature = "track-device-contexts")]
@@ -157,7 +172,18 @@ impl RafxDeviceContextVulkanInner {
 
         let allocator = vk_mem::Allocator::new(&allocator_create_info)?;
 
+        let draw_indirect_count =
+            if physical_device_info.supports_extension(khr::DrawIndirectCount::name()) {
+                Some(khr::DrawIndirectCount::new(
+                    &instance.instance,
+                    &logical_device,
+                ))
+            } else {
+                None
+            };
+
         let limits = &physical_device_info.properties.limits;
+        let features = &physical_device_info.features;
 
         let device_info = RafxDeviceInfo {
             min_uniform_buffer_offset_alignment: limits.min_uniform_buffer_offset_alignment as u32,
@@ -167,6 +193,20 @@ impl RafxDeviceContextVulkanInner {
                 as u32,
             supports_clamp_to_border_color: true,
             max_vertex_attribute_count: limits.max_vertex_input_attributes,
+            timestamp_period_ns: limits.timestamp_period,
+            supports_timestamp_queries: limits.timestamp_compute_and_graphics == vk::TRUE,
+            supports_pipeline_statistics_queries: features.pipeline_statistics_query == vk::TRUE,
+            supports_indirect_draw: true,
+            max_indirect_draw_count: if features.multi_draw_indirect == vk::TRUE {
+                limits.max_draw_indirect_count
+            } else {
+                1
+            },
+            supports_indirect_draw_count: draw_indirect_count.is_some(),
+            supports_indirect_dispatch: true,
+            max_push_constant_size: limits.max_push_constants_size,
+            debug_names_enabled: instance.debug_utils.is_some(),
+            supports_multithreaded_command_recording: true,
         };
 
         let resource_cache = RafxDeviceVulkanResourceCache::default();
@@ -191,6 +231,8 @@ impl RafxDeviceContextVulkanInner {
             instance: instance.instance.clone(),
             physical_device,
             physical_device_info,
+            draw_indirect_count,
+            debug_utils: instance.debug_utils.clone(),
             device: logical_device,
             allocator,
             destroyed: AtomicBool::new(false),
@@ -314,6 +356,47 @@ impl RafxDeviceContextVulkan {
         &self.physical_device_info().properties.limits
     }
 
+    // Loaded if VK_KHR_draw_indirect_count is supported
+    pub fn draw_indirect_count_loader(&self) -> Option<&khr::DrawIndirectCount> {
+        self.inner.draw_indirect_count.as_ref()
+    }
+
+    // Loaded if debug names are enabled and VK_EXT_debug_utils is supported
+    pub fn debug_utils_loader(&self) -> Option<&DebugUtils> {
+        self.inner.debug_utils.as_ref()
+    }
+
+    // Names the given vulkan object so that it is visible in debugging tools. This is a no-op if
+    // debug names are not enabled
+    pub fn set_debug_name<T: vk::Handle>(
+        &self,
+        handle: T,
+        name: &str,
+    ) {
+        if let Some(debug_utils) = &self.inner.debug_utils {
+            let name = match CString::new(name) {
+                Ok(name) => name,
+                Err(_) => {
+                    log::warn!("Debug name {:?} contains a null character", name);
+                    return;
+                }
+            };
+
+            let name_info = vk::DebugUtilsObjectNameInfoEXT::builder()
+                .object_type(T::TYPE)
+                .object_handle(handle.as_raw())
+                .object_name(&name);
+
+            unsafe {
+                if let Err(e) =
+                    debug_utils.debug_utils_set_object_name(self.device().handle(), &name_info)
+                {
+                    log::warn!("Failed to set debug name {:?}: {:?}", name, e);
+                }
+            }
+        }
+    }
+
     pub fn allocator(&self) -> &vk_mem::Allocator {
         &self.inner.allocator
     }
@@ -360,6 +443,20 @@ impl RafxDeviceContextVulkan {
         RafxSemaphoreVulkan::new(self)
     }
 
+    pub fn create_query_pool(
+        &self,
+        query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPoolVulkan> {
+        RafxQueryPoolVulkan::new(self, query_pool_def)
+    }
+
+    pub fn create_pipeline_cache(
+        &self,
+        pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCacheVulkan> {
+        RafxPipelineCacheVulkan::new(self, pipeline_cache_def)
+    }
+
     pub fn create_swapchain(
         &self,
         raw_window_handle: &dyn HasRawWindowHandle,
@@ -475,6 +572,14 @@ impl RafxDeviceContextVulkan {
             features |= vk::FormatFeatureFlags::DEPTH_STENCIL_ATTACHMENT;
         }
 
+        if resource_type.intersects(RafxResourceType::TEXTURE) {
+            features |= vk::FormatFeatureFlags::SAMPLED_IMAGE;
+        }
+
+        if resource_type.intersects(RafxResourceType::TEXTURE_READ_WRITE) {
+            features |= vk::FormatFeatureFlags::STORAGE_IMAGE;
+        }
+
         do_find_supported_format(
             &self.inner.instance,
             self.inner.physical_device,
@@ -803,6 +908,11 @@ fn create_logical_device(
 
     let mut device_extension_names = vec![khr::Swapchain::name().as_ptr()];
 
+    // Optional, used by cmd_draw_indirect_count/cmd_draw_indexed_indirect_count
+    if physical_device_info.supports_extension(khr::DrawIndirectCount::name()) {
+        device_extension_names.push(khr::DrawIndirectCount::name().as_ptr());
+    }
+
     // Add VK_KHR_portability_subset if the extension exists (this is mandated by spec)
     let portability_subset_extension_name = khr_portability_subset_extension_name();
     for extension in &physical_device_info.extension_properties {
@@ -820,7 +930,17 @@ fn create_logical_device(
         .sampler_anisotropy(true)
         .sample_rate_shading(true)
         // Used for debug drawing lines/points
-        .fill_mode_non_solid(true);
+        .fill_mode_non_solid(true)
+        // Optional, used for profiling. Queries fail to be created if it's unsupported
+        .pipeline_statistics_query(
+            physical_device_info.features.pipeline_statistics_query == vk::TRUE,
+        )
+        // Optional, allows drawing more than one set of indirect arguments per call
+        .multi_draw_indirect(physical_device_info.features.multi_draw_indirect == vk::TRUE)
+        // Optional, allows a non-zero first_instance in indirect arguments
+        .draw_indirect_first_instance(
+            physical_device_info.features.draw_indirect_first_instance == vk::TRUE,
+        );
 
     let mut queue_families_to_create = FnvHashMap::default();
     for (&queue_family_index, &count) in &queue_requirements.queue_counts {
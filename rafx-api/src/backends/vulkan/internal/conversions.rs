@@ -1,9 +1,9 @@
 use crate::{
     RafxAddressMode, RafxBlendFactor, RafxBlendOp, RafxColorClearValue, RafxColorFlags,
-    RafxCompareOp, RafxCullMode, RafxDepthStencilClearValue, RafxFillMode, RafxFilterType,
-    RafxFrontFace, RafxIndexType, RafxLoadOp, RafxMemoryUsage, RafxMipMapMode,
-    RafxPrimitiveTopology, RafxSampleCount, RafxShaderStageFlags, RafxStencilOp, RafxStoreOp,
-    RafxVertexAttributeRate,
+    RafxCompareOp, RafxCullMode, RafxDepthStencilClearValue, RafxExtents3D, RafxFillMode,
+    RafxFilterType, RafxFrontFace, RafxIndexType, RafxLoadOp, RafxMemoryUsage, RafxMipMapMode,
+    RafxOffset3D, RafxPrimitiveTopology, RafxQueryType, RafxSampleCount, RafxShaderStageFlags,
+    RafxStencilOp, RafxStoreOp, RafxVertexAttributeRate,
 };
 use ash::vk;
 
@@ -137,6 +137,15 @@ impl Into<vk::IndexType> for RafxIndexType {
     }
 }
 
+impl Into<vk::QueryType> for RafxQueryType {
+    fn into(self) -> vk::QueryType {
+        match self {
+            RafxQueryType::Timestamp => vk::QueryType::TIMESTAMP,
+            RafxQueryType::PipelineStatistics => vk::QueryType::PIPELINE_STATISTICS,
+        }
+    }
+}
+
 impl Into<vk::BlendFactor> for RafxBlendFactor {
     fn into(self) -> vk::BlendFactor {
         match self {
@@ -274,3 +283,23 @@ impl Into<vk::ClearValue> for RafxDepthStencilClearValue {
         }
     }
 }
+
+impl Into<vk::Offset3D> for RafxOffset3D {
+    fn into(self) -> vk::Offset3D {
+        vk::Offset3D {
+            x: self.x as i32,
+            y: self.y as i32,
+            z: self.z as i32,
+        }
+    }
+}
+
+impl Into<vk::Extent3D> for RafxExtents3D {
+    fn into(self) -> vk::Extent3D {
+        vk::Extent3D {
+            width: self.width,
+            height: self.height,
+            depth: self.depth,
+        }
+    }
+}
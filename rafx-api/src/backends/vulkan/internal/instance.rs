@@ -17,6 +17,8 @@ pub struct VkInstance {
     pub entry: Arc<VkEntry>,
     pub instance: ash::Instance,
     pub debug_reporter: Option<VkDebugReporter>,
+    // Loaded if debug names are enabled and VK_EXT_debug_utils is available
+    pub debug_utils: Option<DebugUtils>,
 }
 
 #[derive(Debug)]
@@ -66,6 +68,7 @@ impl VkInstance {
         app_name: &CString,
         require_validation_layers_present: bool,
         validation_layer_debug_report_flags: vk::DebugUtilsMessageSeverityFlagsEXT,
+        enable_debug_names: bool,
     ) -> Result<VkInstance, VkCreateInstanceError> {
         // Determine the supported version of vulkan that's available
         let vulkan_version = match entry.try_enumerate_instance_version()? {
@@ -109,8 +112,14 @@ impl VkInstance {
             .engine_version(0)
             .api_version(vulkan_version);
 
+        let debug_extension = DebugUtils::name();
+        let has_debug_extension = extensions.iter().any(|extension| unsafe {
+            debug_extension == CStr::from_ptr(extension.extension_name.as_ptr())
+        });
+
         let mut layer_names = vec![];
         let mut extension_names = ash_window::enumerate_required_extensions(window)?;
+        let mut enable_debug_extension = false;
         if !validation_layer_debug_report_flags.is_empty() {
             // Find the best validation layer that's available
             let best_validation_layer = VkInstance::find_best_validation_layer(&layers);
@@ -123,11 +132,6 @@ impl VkInstance {
                 }
             }
 
-            let debug_extension = DebugUtils::name();
-            let has_debug_extension = extensions.iter().any(|extension| unsafe {
-                debug_extension == CStr::from_ptr(extension.extension_name.as_ptr())
-            });
-
             if !has_debug_extension {
                 if require_validation_layers_present {
                     log::error!("Could not find the debug extension. Check that the vulkan SDK has been installed or disable validation.");
@@ -140,11 +144,24 @@ impl VkInstance {
             if let Some(best_validation_layer) = best_validation_layer {
                 if has_debug_extension {
                     layer_names.push(best_validation_layer);
-                    extension_names.push(DebugUtils::name());
+                    enable_debug_extension = true;
                 }
             }
         }
 
+        // Debug names/labels use the same extension as the validation callback
+        if enable_debug_names {
+            if has_debug_extension {
+                enable_debug_extension = true;
+            } else {
+                log::warn!("Could not find the debug extension. Debug names will not be visible in debugging tools.");
+            }
+        }
+
+        if enable_debug_extension {
+            extension_names.push(DebugUtils::name());
+        }
+
         if log::log_enabled!(log::Level::Debug) {
             log::debug!("Using layers: {:?}", layer_names);
             log::debug!("Using extensions: {:?}", extension_names);
@@ -163,12 +180,19 @@ impl VkInstance {
         let instance: ash::Instance = unsafe { entry.create_instance(&create_info, None)? };
 
         // Setup the debug callback for the validation layer
-        let debug_reporter = if !validation_layer_debug_report_flags.is_empty() {
-            Some(Self::setup_vulkan_debug_callback(
-                &entry,
-                &instance,
-                validation_layer_debug_report_flags,
-            )?)
+        let debug_reporter =
+            if !validation_layer_debug_report_flags.is_empty() && enable_debug_extension {
+                Some(Self::setup_vulkan_debug_callback(
+                    &entry,
+                    &instance,
+                    validation_layer_debug_report_flags,
+                )?)
+            } else {
+                None
+            };
+
+        let debug_utils = if enable_debug_names && has_debug_extension {
+            Some(DebugUtils::new(&entry, &instance))
         } else {
             None
         };
@@ -177,6 +201,7 @@ impl VkInstance {
             entry: Arc::new(entry),
             instance,
             debug_reporter,
+            debug_utils,
         })
     }
 
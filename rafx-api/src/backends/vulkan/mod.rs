@@ -28,6 +28,12 @@ pub use fence::*;
 mod semaphore;
 pub use semaphore::*;
 
+mod query_pool;
+pub use query_pool::*;
+
+mod pipeline_cache;
+pub use pipeline_cache::*;
+
 mod texture;
 pub use texture::*;
 
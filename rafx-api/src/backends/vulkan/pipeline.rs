@@ -42,6 +42,17 @@ impl RafxPipelineVulkan {
         self.pipeline
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        self.root_signature
+            .vk_root_signature()
+            .unwrap()
+            .device_context()
+            .set_debug_name(self.pipeline, name.as_ref());
+    }
+
     pub fn new_graphics_pipeline(
         device_context: &RafxDeviceContextVulkan,
         pipeline_def: &RafxGraphicsPipelineDef,
@@ -208,9 +219,14 @@ impl RafxPipelineVulkan {
         //     depth_state.into_vk_builder()
         // };
 
+        let vk_pipeline_cache = pipeline_def
+            .pipeline_cache
+            .map(|x| x.vk_pipeline_cache().unwrap().vk_pipeline_cache())
+            .unwrap_or_else(vk::PipelineCache::null);
+
         let pipeline = unsafe {
             match device_context.device().create_graphics_pipelines(
-                vk::PipelineCache::null(),
+                vk_pipeline_cache,
                 &[pipeline_create_info],
                 None,
             ) {
@@ -266,9 +282,14 @@ impl RafxPipelineVulkan {
             .base_pipeline_index(-1)
             .build();
 
+        let vk_pipeline_cache = pipeline_def
+            .pipeline_cache
+            .map(|x| x.vk_pipeline_cache().unwrap().vk_pipeline_cache())
+            .unwrap_or_else(vk::PipelineCache::null);
+
         let pipeline = unsafe {
             match device_context.device().create_compute_pipelines(
-                vk::PipelineCache::null(),
+                vk_pipeline_cache,
                 &[pipeline_create_info],
                 None,
             ) {
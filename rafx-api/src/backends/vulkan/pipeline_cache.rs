@@ -0,0 +1,100 @@
+use crate::vulkan::RafxDeviceContextVulkan;
+use crate::{RafxPipelineCacheDef, RafxResult};
+use ash::version::DeviceV1_0;
+use ash::vk;
+use std::convert::TryInto;
+
+// Size of the header defined by the vulkan spec for VK_PIPELINE_CACHE_HEADER_VERSION_ONE
+const PIPELINE_CACHE_HEADER_SIZE: usize = 32;
+
+// The driver is required to reject data it can't use, but some drivers have been known to crash on
+// data from a different device/driver, so we check the header ourselves and drop anything that
+// doesn't match this device.
+fn is_pipeline_cache_data_compatible(
+    device_context: &RafxDeviceContextVulkan,
+    data: &[u8],
+) -> bool {
+    if data.len() < PIPELINE_CACHE_HEADER_SIZE {
+        return false;
+    }
+
+    let read_u32 = |offset: usize| u32::from_ne_bytes(data[offset..offset + 4].try_into().unwrap());
+
+    let header_size = read_u32(0);
+    let header_version = read_u32(4);
+    let vendor_id = read_u32(8);
+    let device_id = read_u32(12);
+    let pipeline_cache_uuid = &data[16..32];
+
+    let properties = &device_context.physical_device_info().properties;
+    header_size as usize >= PIPELINE_CACHE_HEADER_SIZE
+        && header_version == vk::PipelineCacheHeaderVersion::ONE.as_raw() as u32
+        && vendor_id == properties.vendor_id
+        && device_id == properties.device_id
+        && pipeline_cache_uuid == &properties.pipeline_cache_uuid[..]
+}
+
+pub struct RafxPipelineCacheVulkan {
+    device_context: RafxDeviceContextVulkan,
+    vk_pipeline_cache: vk::PipelineCache,
+}
+
+impl Drop for RafxPipelineCacheVulkan {
+    fn drop(&mut self) {
+        unsafe {
+            self.device_context
+                .device()
+                .destroy_pipeline_cache(self.vk_pipeline_cache, None)
+        }
+    }
+}
+
+impl RafxPipelineCacheVulkan {
+    pub fn device_context(&self) -> &RafxDeviceContextVulkan {
+        &self.device_context
+    }
+
+    pub fn vk_pipeline_cache(&self) -> vk::PipelineCache {
+        self.vk_pipeline_cache
+    }
+
+    pub fn new(
+        device_context: &RafxDeviceContextVulkan,
+        pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCacheVulkan> {
+        let initial_data = pipeline_cache_def.data.filter(|data| {
+            let compatible = is_pipeline_cache_data_compatible(device_context, data);
+            if !compatible {
+                log::warn!(
+                    "Pipeline cache data is not compatible with this device/driver, it will be ignored"
+                );
+            }
+            compatible
+        });
+
+        let mut create_info = vk::PipelineCacheCreateInfo::builder();
+        if let Some(initial_data) = initial_data {
+            create_info = create_info.initial_data(initial_data);
+        }
+
+        let vk_pipeline_cache = unsafe {
+            device_context
+                .device()
+                .create_pipeline_cache(&*create_info, None)?
+        };
+
+        Ok(RafxPipelineCacheVulkan {
+            device_context: device_context.clone(),
+            vk_pipeline_cache,
+        })
+    }
+
+    pub fn get_data(&self) -> RafxResult<Vec<u8>> {
+        unsafe {
+            Ok(self
+                .device_context
+                .device()
+                .get_pipeline_cache_data(self.vk_pipeline_cache)?)
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::vulkan::RafxDeviceContextVulkan;
+use crate::{RafxQueryPoolDef, RafxQueryType, RafxResult};
+use ash::version::DeviceV1_0;
+use ash::vk;
+
+// Results are written in bit order, which matches the field order of RafxPipelineStatistics
+fn pipeline_statistics_flags() -> vk::QueryPipelineStatisticFlags {
+    vk::QueryPipelineStatisticFlags::INPUT_ASSEMBLY_VERTICES
+        | vk::QueryPipelineStatisticFlags::INPUT_ASSEMBLY_PRIMITIVES
+        | vk::QueryPipelineStatisticFlags::VERTEX_SHADER_INVOCATIONS
+        | vk::QueryPipelineStatisticFlags::CLIPPING_INVOCATIONS
+        | vk::QueryPipelineStatisticFlags::CLIPPING_PRIMITIVES
+        | vk::QueryPipelineStatisticFlags::FRAGMENT_SHADER_INVOCATIONS
+        | vk::QueryPipelineStatisticFlags::COMPUTE_SHADER_INVOCATIONS
+}
+
+pub struct RafxQueryPoolVulkan {
+    device_context: RafxDeviceContextVulkan,
+    query_pool_def: RafxQueryPoolDef,
+    vk_query_pool: vk::QueryPool,
+}
+
+impl Drop for RafxQueryPoolVulkan {
+    fn drop(&mut self) {
+        unsafe {
+            self.device_context
+                .device()
+                .destroy_query_pool(self.vk_query_pool, None)
+        }
+    }
+}
+
+impl RafxQueryPoolVulkan {
+    pub fn new(
+        device_context: &RafxDeviceContextVulkan,
+        query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPoolVulkan> {
+        query_pool_def.verify();
+
+        let device_info = device_context.device_info();
+        let pipeline_statistics = match query_pool_def.query_type {
+            RafxQueryType::Timestamp => {
+                if !device_info.supports_timestamp_queries {
+                    Err("Timestamp queries are not supported by this device")?;
+                }
+
+                vk::QueryPipelineStatisticFlags::empty()
+            }
+            RafxQueryType::PipelineStatistics => {
+                if !device_info.supports_pipeline_statistics_queries {
+                    Err("Pipeline statistics queries are not supported by this device")?;
+                }
+
+                pipeline_statistics_flags()
+            }
+        };
+
+        let create_info = vk::QueryPoolCreateInfo::builder()
+            .query_type(query_pool_def.query_type.into())
+            .query_count(query_pool_def.query_count)
+            .pipeline_statistics(pipeline_statistics);
+
+        let vk_query_pool = unsafe {
+            device_context
+                .device()
+                .create_query_pool(&*create_info, None)?
+        };
+
+        Ok(RafxQueryPoolVulkan {
+            device_context: device_context.clone(),
+            query_pool_def: query_pool_def.clone(),
+            vk_query_pool,
+        })
+    }
+
+    pub fn query_pool_def(&self) -> &RafxQueryPoolDef {
+        &self.query_pool_def
+    }
+
+    pub fn vk_query_pool(&self) -> vk::QueryPool {
+        self.vk_query_pool
+    }
+
+    pub(crate) fn result_stride(&self) -> u64 {
+        (self.query_pool_def.result_count_per_query() as usize * std::mem::size_of::<u64>()) as u64
+    }
+
+    pub fn get_results(
+        &self,
+        first_query: u32,
+        query_count: u32,
+        results: &mut [u64],
+    ) -> RafxResult<bool> {
+        assert!(first_query + query_count <= self.query_pool_def.query_count);
+        let value_count =
+            query_count as usize * self.query_pool_def.result_count_per_query() as usize;
+        assert!(results.len() >= value_count);
+
+        // ash's wrapper assumes one element of the slice per query, so call the function pointer
+        // directly to support multiple values per query
+        let result = unsafe {
+            self.device_context
+                .device()
+                .fp_v1_0()
+                .get_query_pool_results(
+                    self.device_context.device().handle(),
+                    self.vk_query_pool,
+                    first_query,
+                    query_count,
+                    value_count * std::mem::size_of::<u64>(),
+                    results.as_mut_ptr() as *mut std::ffi::c_void,
+                    self.result_stride(),
+                    vk::QueryResultFlags::TYPE_64,
+                )
+        };
+
+        match result {
+            vk::Result::SUCCESS => Ok(true),
+            vk::Result::NOT_READY => Ok(false),
+            e => Err(e)?,
+        }
+    }
+}
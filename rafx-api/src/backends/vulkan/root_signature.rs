@@ -8,13 +8,10 @@ use std::sync::Arc;
 // Not currently exposed
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub(crate) struct DynamicDescriptorIndex(pub(crate) u32);
-#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
-pub(crate) struct PushConstantIndex(pub(crate) u32);
 
 #[derive(Clone, Debug)]
 pub(crate) struct PushConstantInfo {
     pub(crate) name: Option<String>,
-    pub(crate) push_constant_index: PushConstantIndex,
     pub(crate) vk_push_constant_range: vk::PushConstantRange,
 }
 
@@ -72,8 +69,8 @@ pub(crate) struct RafxRootSignatureVulkanInner {
     immutable_samplers: Vec<RafxSampler>, //empty_descriptor_sets: [vk::DescriptorSet; MAX_DESCRIPTOR_SETS],
 
     // --- vulkan-specific ---
-    pub(crate) name_to_push_constant_index: FnvHashMap<String, PushConstantIndex>,
-    pub(crate) push_constants: Vec<PushConstantInfo>,
+    // All stages share a single push constant range
+    pub(crate) push_constant: Option<PushConstantInfo>,
     pub(crate) pipeline_layout: vk::PipelineLayout,
     pub(crate) descriptor_set_layouts: [vk::DescriptorSetLayout; MAX_DESCRIPTOR_SET_LAYOUTS],
 }
@@ -132,6 +129,10 @@ impl RafxRootSignatureVulkan {
         self.inner.descriptors.get(descriptor_index.0 as usize)
     }
 
+    pub(crate) fn push_constant(&self) -> Option<&PushConstantInfo> {
+        self.inner.push_constant.as_ref()
+    }
+
     pub fn vk_pipeline_layout(&self) -> vk::PipelineLayout {
         self.inner.pipeline_layout
     }
@@ -157,9 +158,7 @@ impl RafxRootSignatureVulkan {
         // If we update this constant, update the arrays in this function
         assert_eq!(MAX_DESCRIPTOR_SET_LAYOUTS, 4);
 
-        let mut push_constants = vec![];
         let mut descriptors = vec![];
-        let mut vk_push_constant_ranges = vec![];
 
         let vk_immutable_samplers: Vec<Vec<vk::Sampler>> = root_signature_def
             .immutable_samplers
@@ -193,167 +192,166 @@ impl RafxRootSignatureVulkan {
         let mut vk_set_bindings = [vec![], vec![], vec![], vec![]];
 
         let mut name_to_descriptor_index = FnvHashMap::default();
-        let mut name_to_push_constant_index = FnvHashMap::default();
 
         //
         // Create bindings (vulkan representation) and descriptors (what we use)
         // We don't create descriptors for immutable samplers
         //
         for resource in &merged_resources {
-            let vk_stage_flags = resource.used_in_shader_stages.into();
-            let vk_descriptor_type =
-                super::util::resource_type_to_descriptor_type(resource.resource_type).unwrap();
-
             resource.validate()?;
 
-            if resource.resource_type != RafxResourceType::ROOT_CONSTANT {
-                // It's not a push constant, so create a vk binding for it
-                let mut binding = vk::DescriptorSetLayoutBinding::builder()
-                    .binding(resource.binding)
-                    .descriptor_count(resource.element_count_normalized())
-                    .descriptor_type(vk_descriptor_type)
-                    .stage_flags(vk_stage_flags);
-
-                // Determine if flagged as root constant buffer/dynamic uniform buffer. If so, update
-                // the type. This was being done by detecting a pattern in the name string. For now
-                // this is dead code. It should probably be done by checking the descriptor type.
-                // let is_dynamic_uniform_buffer = false;
-                // if is_dynamic_uniform_buffer {
-                //     if resource.descriptor_count == 1 {
-                //         binding =
-                //             binding.descriptor_type(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC);
-                //     } else {
-                //         Err("Cannot use dynamic uniform buffer an array")?;
-                //     }
-                // }
-
-                let immutable_sampler = crate::internal_shared::find_immutable_sampler_index(
-                    root_signature_def.immutable_samplers,
-                    &resource.name,
-                    resource.set_index,
-                    resource.binding,
-                );
-                if let Some(immutable_sampler_index) = immutable_sampler {
-                    if resource.element_count_normalized() as usize
-                        != vk_immutable_samplers[immutable_sampler_index].len()
-                    {
-                        Err(format!(
-                            "Descriptor (set={:?} binding={:?}) named {:?} specifies {} elements but the count of provided immutable samplers ({}) did not match",
-                            resource.set_index,
-                            resource.binding,
-                            resource.name,
-                            resource.element_count_normalized(),
-                            vk_immutable_samplers[immutable_sampler_index].len()
-                        ))?;
-                    }
-
-                    // immutable_samplers is heap allocated, not modified, and kept in scope. So the
-                    // pointer to a value within should remain valid for long enough.
-                    binding =
-                        binding.immutable_samplers(&vk_immutable_samplers[immutable_sampler_index]);
-                }
-
-                let layout: &mut DescriptorSetLayoutInfo =
-                    &mut layouts[resource.set_index as usize];
+            // Push constants are merged separately
+            assert_ne!(resource.resource_type, RafxResourceType::ROOT_CONSTANT);
 
-                let vk_bindings: &mut Vec<vk::DescriptorSetLayoutBinding> =
-                    &mut vk_set_bindings[resource.set_index as usize];
+            let vk_stage_flags = resource.used_in_shader_stages.into();
+            let vk_descriptor_type =
+                super::util::resource_type_to_descriptor_type(resource.resource_type).unwrap();
 
-                if immutable_sampler.is_some()
-                    && !resource
-                        .resource_type
-                        .intersects(RafxResourceType::COMBINED_IMAGE_SAMPLER)
-                {
-                    // don't expose a immutable sampler unless the image needs to be settable
-                    // although we might just not support combined image samplers
-                } else if immutable_sampler.is_none()
-                    && vk_descriptor_type == vk::DescriptorType::COMBINED_IMAGE_SAMPLER
+            let mut binding = vk::DescriptorSetLayoutBinding::builder()
+                .binding(resource.binding)
+                .descriptor_count(resource.element_count_normalized())
+                .descriptor_type(vk_descriptor_type)
+                .stage_flags(vk_stage_flags);
+
+            // Determine if flagged as root constant buffer/dynamic uniform buffer. If so, update
+            // the type. This was being done by detecting a pattern in the name string. For now
+            // this is dead code. It should probably be done by checking the descriptor type.
+            // let is_dynamic_uniform_buffer = false;
+            // if is_dynamic_uniform_buffer {
+            //     if resource.descriptor_count == 1 {
+            //         binding =
+            //             binding.descriptor_type(vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC);
+            //     } else {
+            //         Err("Cannot use dynamic uniform buffer an array")?;
+            //     }
+            // }
+
+            let immutable_sampler = crate::internal_shared::find_immutable_sampler_index(
+                root_signature_def.immutable_samplers,
+                &resource.name,
+                resource.set_index,
+                resource.binding,
+            );
+            if let Some(immutable_sampler_index) = immutable_sampler {
+                if resource.element_count_normalized() as usize
+                    != vk_immutable_samplers[immutable_sampler_index].len()
                 {
                     Err(format!(
-                        "Descriptor (set={:?} binding={:?}) named {:?} is a combined image sampler but the sampler is NOT immutable. This is not supported. Use separate sampler/image bindings",
+                        "Descriptor (set={:?} binding={:?}) named {:?} specifies {} elements but the count of provided immutable samplers ({}) did not match",
                         resource.set_index,
                         resource.binding,
-                        resource.name
+                        resource.name,
+                        resource.element_count_normalized(),
+                        vk_immutable_samplers[immutable_sampler_index].len()
                     ))?;
-                } else {
-                    // dynamic storage buffers not supported
-                    assert_ne!(
-                        binding.descriptor_type,
-                        vk::DescriptorType::STORAGE_BUFFER_DYNAMIC
-                    );
-
-                    // More than one dynamic descriptor not supported right now
-                    assert!(layout.dynamic_descriptor_indexes.is_empty());
-
-                    //
-                    // Keep a lookup for dynamic descriptors
-                    //
-                    let descriptor_index = RafxDescriptorIndex(descriptors.len() as u32);
-                    let dynamic_descriptor_index =
-                        if binding.descriptor_type == vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC {
-                            layout.dynamic_descriptor_indexes.push(descriptor_index);
-                            Some(DynamicDescriptorIndex(
-                                (layout.dynamic_descriptor_indexes.len() - 1) as u32,
-                            ))
-                        } else {
-                            None
-                        };
-
-                    let update_data_offset_in_set = Some(layout.update_data_count_per_set);
-
-                    // Add it to the descriptor list
-                    descriptors.push(DescriptorInfo {
-                        name: resource.name.clone(),
-                        resource_type: resource.resource_type,
-                        //texture_dimensions: resource.texture_dimensions,
-                        set_index: resource.set_index,
-                        binding: resource.binding,
-                        element_count: resource.element_count_normalized(),
-                        descriptor_index,
-                        dynamic_descriptor_index,
-                        update_data_offset_in_set,
-                        has_immutable_sampler: immutable_sampler.is_some(),
-                        vk_type: binding.descriptor_type,
-                        vk_stages: binding.stage_flags,
-                    });
-
-                    if let Some(name) = resource.name.as_ref() {
-                        name_to_descriptor_index.insert(name.clone(), descriptor_index);
-                    }
-
-                    layout.descriptors.push(descriptor_index);
-                    layout
-                        .binding_to_descriptor_index
-                        .insert(resource.binding, descriptor_index);
-
-                    layout.update_data_count_per_set += resource.element_count_normalized();
                 }
 
-                // Add the binding to the list
-                vk_bindings.push(binding.build());
+                // immutable_samplers is heap allocated, not modified, and kept in scope. So the
+                // pointer to a value within should remain valid for long enough.
+                binding =
+                    binding.immutable_samplers(&vk_immutable_samplers[immutable_sampler_index]);
+            }
+
+            let layout: &mut DescriptorSetLayoutInfo = &mut layouts[resource.set_index as usize];
+
+            let vk_bindings: &mut Vec<vk::DescriptorSetLayoutBinding> =
+                &mut vk_set_bindings[resource.set_index as usize];
+
+            if immutable_sampler.is_some()
+                && !resource
+                    .resource_type
+                    .intersects(RafxResourceType::COMBINED_IMAGE_SAMPLER)
+            {
+                // don't expose a immutable sampler unless the image needs to be settable
+                // although we might just not support combined image samplers
+            } else if immutable_sampler.is_none()
+                && vk_descriptor_type == vk::DescriptorType::COMBINED_IMAGE_SAMPLER
+            {
+                Err(format!(
+                    "Descriptor (set={:?} binding={:?}) named {:?} is a combined image sampler but the sampler is NOT immutable. This is not supported. Use separate sampler/image bindings",
+                    resource.set_index,
+                    resource.binding,
+                    resource.name
+                ))?;
             } else {
-                let push_constant_index = PushConstantIndex(push_constants.len() as u32);
-                let vk_push_constant_range = vk::PushConstantRange::builder()
-                    .offset(0)
-                    .size(resource.size_in_bytes)
-                    .stage_flags(vk_stage_flags)
-                    .build();
-
-                // it's a push constant
-                let push_constant = PushConstantInfo {
+                // dynamic storage buffers not supported
+                assert_ne!(
+                    binding.descriptor_type,
+                    vk::DescriptorType::STORAGE_BUFFER_DYNAMIC
+                );
+
+                // More than one dynamic descriptor not supported right now
+                assert!(layout.dynamic_descriptor_indexes.is_empty());
+
+                //
+                // Keep a lookup for dynamic descriptors
+                //
+                let descriptor_index = RafxDescriptorIndex(descriptors.len() as u32);
+                let dynamic_descriptor_index =
+                    if binding.descriptor_type == vk::DescriptorType::UNIFORM_BUFFER_DYNAMIC {
+                        layout.dynamic_descriptor_indexes.push(descriptor_index);
+                        Some(DynamicDescriptorIndex(
+                            (layout.dynamic_descriptor_indexes.len() - 1) as u32,
+                        ))
+                    } else {
+                        None
+                    };
+
+                let update_data_offset_in_set = Some(layout.update_data_count_per_set);
+
+                // Add it to the descriptor list
+                descriptors.push(DescriptorInfo {
                     name: resource.name.clone(),
-                    push_constant_index,
-                    vk_push_constant_range,
-                };
+                    resource_type: resource.resource_type,
+                    //texture_dimensions: resource.texture_dimensions,
+                    set_index: resource.set_index,
+                    binding: resource.binding,
+                    element_count: resource.element_count_normalized(),
+                    descriptor_index,
+                    dynamic_descriptor_index,
+                    update_data_offset_in_set,
+                    has_immutable_sampler: immutable_sampler.is_some(),
+                    vk_type: binding.descriptor_type,
+                    vk_stages: binding.stage_flags,
+                });
 
-                push_constants.push(push_constant);
-                vk_push_constant_ranges.push(vk_push_constant_range);
                 if let Some(name) = resource.name.as_ref() {
-                    name_to_push_constant_index.insert(name.clone(), push_constant_index);
+                    name_to_descriptor_index.insert(name.clone(), descriptor_index);
                 }
+
+                layout.descriptors.push(descriptor_index);
+                layout
+                    .binding_to_descriptor_index
+                    .insert(resource.binding, descriptor_index);
+
+                layout.update_data_count_per_set += resource.element_count_normalized();
             }
+
+            // Add the binding to the list
+            vk_bindings.push(binding.build());
         }
 
+        //
+        // Push constants are a single range visible to all stages that declare them
+        //
+        let push_constant = crate::internal_shared::merge_push_constants(
+            root_signature_def,
+            device_context.device_info().max_push_constant_size,
+        )?
+        .map(|resource| PushConstantInfo {
+            name: resource.name.clone(),
+            vk_push_constant_range: vk::PushConstantRange::builder()
+                .offset(0)
+                .size(resource.size_in_bytes)
+                .stage_flags(resource.used_in_shader_stages.into())
+                .build(),
+        });
+
+        let vk_push_constant_ranges: Vec<_> = push_constant
+            .iter()
+            .map(|x| x.vk_push_constant_range)
+            .collect();
+
         //
         // Create descriptor set layouts
         //
@@ -426,10 +424,9 @@ impl RafxRootSignatureVulkan {
             descriptors,
             name_to_descriptor_index,
             immutable_samplers,
-            push_constants,
+            push_constant,
             pipeline_layout,
             descriptor_set_layouts,
-            name_to_push_constant_index,
         };
 
         Ok(RafxRootSignatureVulkan {
@@ -365,7 +365,15 @@ impl RafxSwapchainVulkanInstance {
                 .graphics_queue_family_index,
         )?;
 
-        let swapchain_image_usage_flags = vk::ImageUsageFlags::COLOR_ATTACHMENT;
+        // Allow copying out of the swapchain images (i.e. for screenshots) if the surface supports it
+        let mut swapchain_image_usage_flags = vk::ImageUsageFlags::COLOR_ATTACHMENT;
+        if surface_capabilities
+            .supported_usage_flags
+            .contains(vk::ImageUsageFlags::TRANSFER_SRC)
+        {
+            swapchain_image_usage_flags |= vk::ImageUsageFlags::TRANSFER_SRC;
+        }
+
         let create_swapchain_result = Self::create_swapchain(
             device_context,
             surface,
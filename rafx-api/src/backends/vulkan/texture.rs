@@ -152,6 +152,15 @@ impl RafxTextureVulkan {
         &self.inner.device_context
     }
 
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        self.inner
+            .device_context
+            .set_debug_name(self.vk_image(), name.as_ref());
+    }
+
     // Color/Depth
     pub fn vk_srv_view(&self) -> Option<vk::ImageView> {
         self.inner.srv_view
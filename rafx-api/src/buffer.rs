@@ -136,6 +136,34 @@ impl RafxBuffer {
         }
     }
 
+    /// Sets a name for the buffer that will be visible in debugging tools like RenderDoc or Xcode.
+    /// This is a no-op if debug names are not enabled (see `RafxDeviceInfo::debug_names_enabled`)
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxBuffer::Vk(inner) => inner.set_debug_name(name),
+            #[cfg(feature = "rafx-metal")]
+            RafxBuffer::Metal(inner) => inner.set_debug_name(name),
+            #[cfg(feature = "rafx-gles2")]
+            RafxBuffer::Gles2(inner) => inner.set_debug_name(name),
+            #[cfg(feature = "rafx-gles3")]
+            RafxBuffer::Gles3(inner) => inner.set_debug_name(name),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxBuffer::Empty(inner) => inner.set_debug_name(name),
+        }
+    }
+
     /// Map the contents of the buffer into CPU memory. This function will fail if the buffer is not
     /// possible to map into CPU memory (i.e. it's GPU-only).
     ///
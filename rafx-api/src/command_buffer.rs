@@ -17,10 +17,11 @@ use crate::metal::RafxCommandBufferMetal;
 #[cfg(feature = "rafx-vulkan")]
 use crate::vulkan::RafxCommandBufferVulkan;
 use crate::{
-    RafxBuffer, RafxBufferBarrier, RafxCmdCopyBufferToTextureParams, RafxColorRenderTargetBinding,
-    RafxDepthStencilRenderTargetBinding, RafxDescriptorSetArray, RafxDescriptorSetHandle,
-    RafxIndexBufferBinding, RafxPipeline, RafxResult, RafxRootSignature, RafxTexture,
-    RafxTextureBarrier, RafxVertexBufferBinding,
+    RafxBuffer, RafxBufferBarrier, RafxCmdCopyBufferToTextureParams,
+    RafxCmdCopyTextureToBufferParams, RafxCmdCopyTextureToTextureParams,
+    RafxColorRenderTargetBinding, RafxDepthStencilRenderTargetBinding, RafxDescriptorSetArray,
+    RafxDescriptorSetHandle, RafxIndexBufferBinding, RafxPipeline, RafxQueryPool, RafxResult,
+    RafxRootSignature, RafxTexture, RafxTextureBarrier, RafxVertexBufferBinding,
 };
 
 /// A list of commands recorded by the CPU and submitted to the GPU.
@@ -121,6 +122,51 @@ impl RafxCommandBuffer {
         }
     }
 
+    /// Begins writing a secondary command buffer that will be executed within the render pass that
+    /// is currently active on `primary_command_buffer`. The primary must have begun the render pass
+    /// with `cmd_begin_render_pass_with_secondary_command_buffers`. The viewport and scissor are
+    /// reset to cover the render targets.
+    ///
+    /// Secondary command buffers must be begun in the same order that they will be passed to
+    /// `cmd_execute_secondary` (metal executes them in the order they were begun.) After they are
+    /// begun, they may be written on other threads if
+    /// `RafxDeviceInfo::supports_multithreaded_command_recording` is true.
+    pub fn begin_secondary(
+        &self,
+        primary_command_buffer: &RafxCommandBuffer,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => {
+                inner.begin_secondary(primary_command_buffer.vk_command_buffer().unwrap())
+            }
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => {
+                inner.begin_secondary(primary_command_buffer.metal_command_buffer().unwrap())
+            }
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => {
+                inner.begin_secondary(primary_command_buffer.gles2_command_buffer().unwrap())
+            }
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => {
+                inner.begin_secondary(primary_command_buffer.gles3_command_buffer().unwrap())
+            }
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => {
+                inner.begin_secondary(primary_command_buffer.empty_command_buffer().unwrap())
+            }
+        }
+    }
+
     /// This returns the command buffer to the pool, allowing it to be allocated again. This must
     /// not be called if the command buffer is still in-use by the GPU.
     ///
@@ -192,6 +238,42 @@ impl RafxCommandBuffer {
         }
     }
 
+    /// Begin a new renderpass like `cmd_begin_render_pass`, but the contents of the renderpass
+    /// will be written to secondary command buffers (see `begin_secondary`) and executed with
+    /// `cmd_execute_secondary`. No other commands may be written to this command buffer until the
+    /// renderpass is ended.
+    pub fn cmd_begin_render_pass_with_secondary_command_buffers(
+        &self,
+        color_targets: &[RafxColorRenderTargetBinding],
+        depth_target: Option<RafxDepthStencilRenderTargetBinding>,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner
+                .cmd_begin_render_pass_with_secondary_command_buffers(color_targets, depth_target),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner
+                .cmd_begin_render_pass_with_secondary_command_buffers(color_targets, depth_target),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner
+                .cmd_begin_render_pass_with_secondary_command_buffers(color_targets, depth_target),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner
+                .cmd_begin_render_pass_with_secondary_command_buffers(color_targets, depth_target),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner
+                .cmd_begin_render_pass_with_secondary_command_buffers(color_targets, depth_target),
+        }
+    }
+
     /// Finish the renderpass.
     pub fn cmd_end_render_pass(&self) -> RafxResult<()> {
         match self {
@@ -216,6 +298,65 @@ impl RafxCommandBuffer {
         }
     }
 
+    /// Execute secondary command buffers within the current renderpass. The renderpass must have
+    /// been begun with `cmd_begin_render_pass_with_secondary_command_buffers`, and the secondary
+    /// command buffers must have been begun with `begin_secondary` (in this order) and ended.
+    pub fn cmd_execute_secondary(
+        &self,
+        secondary_command_buffers: &[&RafxCommandBuffer],
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => {
+                let secondary_command_buffers: Vec<_> = secondary_command_buffers
+                    .iter()
+                    .map(|x| x.vk_command_buffer().unwrap())
+                    .collect();
+                inner.cmd_execute_secondary(&secondary_command_buffers)
+            }
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => {
+                let secondary_command_buffers: Vec<_> = secondary_command_buffers
+                    .iter()
+                    .map(|x| x.metal_command_buffer().unwrap())
+                    .collect();
+                inner.cmd_execute_secondary(&secondary_command_buffers)
+            }
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => {
+                let secondary_command_buffers: Vec<_> = secondary_command_buffers
+                    .iter()
+                    .map(|x| x.gles2_command_buffer().unwrap())
+                    .collect();
+                inner.cmd_execute_secondary(&secondary_command_buffers)
+            }
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => {
+                let secondary_command_buffers: Vec<_> = secondary_command_buffers
+                    .iter()
+                    .map(|x| x.gles3_command_buffer().unwrap())
+                    .collect();
+                inner.cmd_execute_secondary(&secondary_command_buffers)
+            }
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => {
+                let secondary_command_buffers: Vec<_> = secondary_command_buffers
+                    .iter()
+                    .map(|x| x.empty_command_buffer().unwrap())
+                    .collect();
+                inner.cmd_execute_secondary(&secondary_command_buffers)
+            }
+        }
+    }
+
     /// Set the viewport state. This may be called inside or outside of a renderpass.
     ///
     /// Viewport state defines where on the screen the draw will occur.
@@ -526,6 +667,61 @@ impl RafxCommandBuffer {
         }
     }
 
+    /// Writes `data` into the push constants of the given root signature, starting at
+    /// `offset_in_bytes`. The values are visible to all shader stages that declare the push
+    /// constant block and remain set for subsequent draws/dispatches that use a compatible root
+    /// signature. `offset_in_bytes` and the length of `data` must be multiples of 4.
+    ///
+    /// On metal, push constants must be set after beginning a render pass (or binding a compute
+    /// pipeline). GL ES backends emulate push constants with plain uniforms.
+    pub fn cmd_push_constants(
+        &self,
+        root_signature: &RafxRootSignature,
+        offset_in_bytes: u32,
+        data: &[u8],
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_push_constants(
+                root_signature.vk_root_signature().unwrap(),
+                offset_in_bytes,
+                data,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_push_constants(
+                root_signature.metal_root_signature().unwrap(),
+                offset_in_bytes,
+                data,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_push_constants(
+                root_signature.gles2_root_signature().unwrap(),
+                offset_in_bytes,
+                data,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_push_constants(
+                root_signature.gles3_root_signature().unwrap(),
+                offset_in_bytes,
+                data,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_push_constants(
+                root_signature.empty_root_signature().unwrap(),
+                offset_in_bytes,
+                data,
+            ),
+        }
+    }
+
     /// Draw primitives using the currently bound pipeline and vertex buffer
     pub fn cmd_draw(
         &self,
@@ -736,6 +932,279 @@ impl RafxCommandBuffer {
         }
     }
 
+    /// Draw primitives using arguments read from a buffer of `draw_count` tightly packed
+    /// `RafxDrawIndirectCommand`s. Check `RafxDeviceInfo::supports_indirect_draw` and
+    /// `max_indirect_draw_count`. The buffer must be in the INDIRECT_ARGUMENT state.
+    pub fn cmd_draw_indirect(
+        &self,
+        indirect_buffer: &RafxBuffer,
+        indirect_buffer_offset_in_bytes: u32,
+        draw_count: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_draw_indirect(
+                indirect_buffer.vk_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_draw_indirect(
+                indirect_buffer.metal_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_draw_indirect(
+                indirect_buffer.gles2_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_draw_indirect(
+                indirect_buffer.gles3_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_draw_indirect(
+                indirect_buffer.empty_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+        }
+    }
+
+    /// Draw primitives using the currently bound index buffer and arguments read from a buffer of
+    /// `draw_count` tightly packed `RafxDrawIndexedIndirectCommand`s. Check
+    /// `RafxDeviceInfo::supports_indirect_draw` and `max_indirect_draw_count`. The buffer must be
+    /// in the INDIRECT_ARGUMENT state.
+    pub fn cmd_draw_indexed_indirect(
+        &self,
+        indirect_buffer: &RafxBuffer,
+        indirect_buffer_offset_in_bytes: u32,
+        draw_count: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_draw_indexed_indirect(
+                indirect_buffer.vk_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_draw_indexed_indirect(
+                indirect_buffer.metal_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_draw_indexed_indirect(
+                indirect_buffer.gles2_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_draw_indexed_indirect(
+                indirect_buffer.gles3_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_draw_indexed_indirect(
+                indirect_buffer.empty_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                draw_count,
+            ),
+        }
+    }
+
+    /// Like `cmd_draw_indirect`, but the number of draws is a u32 read from `count_buffer`, clamped
+    /// to `max_draw_count`. Check `RafxDeviceInfo::supports_indirect_draw_count`.
+    pub fn cmd_draw_indirect_count(
+        &self,
+        indirect_buffer: &RafxBuffer,
+        indirect_buffer_offset_in_bytes: u32,
+        count_buffer: &RafxBuffer,
+        count_buffer_offset_in_bytes: u32,
+        max_draw_count: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_draw_indirect_count(
+                indirect_buffer.vk_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.vk_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_draw_indirect_count(
+                indirect_buffer.metal_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.metal_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_draw_indirect_count(
+                indirect_buffer.gles2_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.gles2_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_draw_indirect_count(
+                indirect_buffer.gles3_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.gles3_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_draw_indirect_count(
+                indirect_buffer.empty_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.empty_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+        }
+    }
+
+    /// Like `cmd_draw_indexed_indirect`, but the number of draws is a u32 read from
+    /// `count_buffer`, clamped to `max_draw_count`. Check
+    /// `RafxDeviceInfo::supports_indirect_draw_count`.
+    pub fn cmd_draw_indexed_indirect_count(
+        &self,
+        indirect_buffer: &RafxBuffer,
+        indirect_buffer_offset_in_bytes: u32,
+        count_buffer: &RafxBuffer,
+        count_buffer_offset_in_bytes: u32,
+        max_draw_count: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_draw_indexed_indirect_count(
+                indirect_buffer.vk_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.vk_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_draw_indexed_indirect_count(
+                indirect_buffer.metal_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.metal_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_draw_indexed_indirect_count(
+                indirect_buffer.gles2_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.gles2_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_draw_indexed_indirect_count(
+                indirect_buffer.gles3_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.gles3_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_draw_indexed_indirect_count(
+                indirect_buffer.empty_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+                count_buffer.empty_buffer().unwrap(),
+                count_buffer_offset_in_bytes,
+                max_draw_count,
+            ),
+        }
+    }
+
+    /// Dispatch the current compute pipeline using a `RafxDispatchIndirectCommand` read from a
+    /// buffer. Check `RafxDeviceInfo::supports_indirect_dispatch`. The buffer must be in the
+    /// INDIRECT_ARGUMENT state.
+    pub fn cmd_dispatch_indirect(
+        &self,
+        indirect_buffer: &RafxBuffer,
+        indirect_buffer_offset_in_bytes: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_dispatch_indirect(
+                indirect_buffer.vk_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_dispatch_indirect(
+                indirect_buffer.metal_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_dispatch_indirect(
+                indirect_buffer.gles2_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_dispatch_indirect(
+                indirect_buffer.gles3_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_dispatch_indirect(
+                indirect_buffer.empty_buffer().unwrap(),
+                indirect_buffer_offset_in_bytes,
+            ),
+        }
+    }
+
     /// Add a memory barrier for one or more resources. This must occur OUTSIDE of a renderpass.
     pub fn cmd_resource_barrier(
         &self,
@@ -886,6 +1355,419 @@ impl RafxCommandBuffer {
         }
     }
 
+    /// Copy a region of a texture into a buffer. This occurs on the GPU and, with a buffer that
+    /// is readable by the CPU, allows reading back the contents of a texture. See
+    /// `extra::readback` for a helper that handles the details. Rows are written top to bottom on
+    /// all backends. Depth-only and stencil-only textures can be copied, but combined
+    /// depth/stencil formats return an error. GL ES does not support copying depth or stencil.
+    pub fn cmd_copy_texture_to_buffer(
+        &self,
+        src_texture: &RafxTexture,
+        dst_buffer: &RafxBuffer,
+        params: &RafxCmdCopyTextureToBufferParams,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_copy_texture_to_buffer(
+                src_texture.vk_texture().unwrap(),
+                dst_buffer.vk_buffer().unwrap(),
+                params,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_copy_texture_to_buffer(
+                src_texture.metal_texture().unwrap(),
+                dst_buffer.metal_buffer().unwrap(),
+                params,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_copy_texture_to_buffer(
+                src_texture.gles2_texture().unwrap(),
+                dst_buffer.gles2_buffer().unwrap(),
+                params,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_copy_texture_to_buffer(
+                src_texture.gles3_texture().unwrap(),
+                dst_buffer.gles3_buffer().unwrap(),
+                params,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_copy_texture_to_buffer(
+                src_texture.empty_texture().unwrap(),
+                dst_buffer.empty_buffer().unwrap(),
+                params,
+            ),
+        }
+    }
+
+    /// Copy a region of a texture into another texture. This occurs on the GPU.
+    pub fn cmd_copy_texture_to_texture(
+        &self,
+        src_texture: &RafxTexture,
+        dst_texture: &RafxTexture,
+        params: &RafxCmdCopyTextureToTextureParams,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_copy_texture_to_texture(
+                src_texture.vk_texture().unwrap(),
+                dst_texture.vk_texture().unwrap(),
+                params,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_copy_texture_to_texture(
+                src_texture.metal_texture().unwrap(),
+                dst_texture.metal_texture().unwrap(),
+                params,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_copy_texture_to_texture(
+                src_texture.gles2_texture().unwrap(),
+                dst_texture.gles2_texture().unwrap(),
+                params,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_copy_texture_to_texture(
+                src_texture.gles3_texture().unwrap(),
+                dst_texture.gles3_texture().unwrap(),
+                params,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_copy_texture_to_texture(
+                src_texture.empty_texture().unwrap(),
+                dst_texture.empty_texture().unwrap(),
+                params,
+            ),
+        }
+    }
+
+    /// Reset a range of queries in the pool so that they can be written again. Queries must be
+    /// reset before they are first used and before they are reused.
+    pub fn cmd_reset_queries(
+        &self,
+        query_pool: &RafxQueryPool,
+        first_query: u32,
+        query_count: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_reset_queries(
+                query_pool.vk_query_pool().unwrap(),
+                first_query,
+                query_count,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_reset_queries(
+                query_pool.metal_query_pool().unwrap(),
+                first_query,
+                query_count,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_reset_queries(
+                query_pool.gles2_query_pool().unwrap(),
+                first_query,
+                query_count,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_reset_queries(
+                query_pool.gles3_query_pool().unwrap(),
+                first_query,
+                query_count,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_reset_queries(
+                query_pool.empty_query_pool().unwrap(),
+                first_query,
+                query_count,
+            ),
+        }
+    }
+
+    /// Write the GPU's current timestamp to a query once all previously recorded work has
+    /// completed. The pool must have been created with `RafxQueryType::Timestamp`.
+    pub fn cmd_write_timestamp(
+        &self,
+        query_pool: &RafxQueryPool,
+        query_index: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => {
+                inner.cmd_write_timestamp(query_pool.vk_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => {
+                inner.cmd_write_timestamp(query_pool.metal_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => {
+                inner.cmd_write_timestamp(query_pool.gles2_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => {
+                inner.cmd_write_timestamp(query_pool.gles3_query_pool().unwrap(), query_index)
+            }
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => {
+                inner.cmd_write_timestamp(query_pool.empty_query_pool().unwrap(), query_index)
+            }
+        }
+    }
+
+    /// Begin accumulating counters for a query. The pool must have been created with
+    /// `RafxQueryType::PipelineStatistics`.
+    pub fn cmd_begin_query(
+        &self,
+        query_pool: &RafxQueryPool,
+        query_index: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => {
+                inner.cmd_begin_query(query_pool.vk_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => {
+                inner.cmd_begin_query(query_pool.metal_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => {
+                inner.cmd_begin_query(query_pool.gles2_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => {
+                inner.cmd_begin_query(query_pool.gles3_query_pool().unwrap(), query_index)
+            }
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => {
+                inner.cmd_begin_query(query_pool.empty_query_pool().unwrap(), query_index)
+            }
+        }
+    }
+
+    /// Stop accumulating counters for a query previously started with `cmd_begin_query`
+    pub fn cmd_end_query(
+        &self,
+        query_pool: &RafxQueryPool,
+        query_index: u32,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => {
+                inner.cmd_end_query(query_pool.vk_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => {
+                inner.cmd_end_query(query_pool.metal_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => {
+                inner.cmd_end_query(query_pool.gles2_query_pool().unwrap(), query_index)
+            }
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => {
+                inner.cmd_end_query(query_pool.gles3_query_pool().unwrap(), query_index)
+            }
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => {
+                inner.cmd_end_query(query_pool.empty_query_pool().unwrap(), query_index)
+            }
+        }
+    }
+
+    /// Copy the results of a range of queries into a buffer as tightly packed u64 values. Each query
+    /// produces `RafxQueryPoolDef::result_count_per_query()` values. The GPU waits for the queries
+    /// to be available before copying.
+    pub fn cmd_resolve_queries(
+        &self,
+        query_pool: &RafxQueryPool,
+        first_query: u32,
+        query_count: u32,
+        dst_buffer: &RafxBuffer,
+        dst_offset: u64,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_resolve_queries(
+                query_pool.vk_query_pool().unwrap(),
+                first_query,
+                query_count,
+                dst_buffer.vk_buffer().unwrap(),
+                dst_offset,
+            ),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_resolve_queries(
+                query_pool.metal_query_pool().unwrap(),
+                first_query,
+                query_count,
+                dst_buffer.metal_buffer().unwrap(),
+                dst_offset,
+            ),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_resolve_queries(
+                query_pool.gles2_query_pool().unwrap(),
+                first_query,
+                query_count,
+                dst_buffer.gles2_buffer().unwrap(),
+                dst_offset,
+            ),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_resolve_queries(
+                query_pool.gles3_query_pool().unwrap(),
+                first_query,
+                query_count,
+                dst_buffer.gles3_buffer().unwrap(),
+                dst_offset,
+            ),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_resolve_queries(
+                query_pool.empty_query_pool().unwrap(),
+                first_query,
+                query_count,
+                dst_buffer.empty_buffer().unwrap(),
+                dst_offset,
+            ),
+        }
+    }
+
+    /// Begins a labeled group of commands that will be visible in debugging tools like RenderDoc or
+    /// Xcode. Must be paired with a call to `cmd_pop_group_debug_name`. Groups may be nested. This
+    /// is a no-op if debug names are not enabled (see `RafxDeviceInfo::debug_names_enabled`)
+    pub fn cmd_push_group_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_push_group_debug_name(name),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_push_group_debug_name(name),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_push_group_debug_name(name),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_push_group_debug_name(name),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_push_group_debug_name(name),
+        }
+    }
+
+    /// Ends the group of commands begun by the most recent call to `cmd_push_group_debug_name`
+    pub fn cmd_pop_group_debug_name(&self) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_pop_group_debug_name(),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_pop_group_debug_name(),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_pop_group_debug_name(),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_pop_group_debug_name(),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_pop_group_debug_name(),
+        }
+    }
+
+    /// Inserts a single labeled marker into the command stream that will be visible in debugging
+    /// tools like RenderDoc or Xcode. This is a no-op if debug names are not enabled (see
+    /// `RafxDeviceInfo::debug_names_enabled`)
+    pub fn cmd_insert_debug_marker(
+        &self,
+        name: impl AsRef<str>,
+    ) -> RafxResult<()> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxCommandBuffer::Vk(inner) => inner.cmd_insert_debug_marker(name),
+            #[cfg(feature = "rafx-metal")]
+            RafxCommandBuffer::Metal(inner) => inner.cmd_insert_debug_marker(name),
+            #[cfg(feature = "rafx-gles2")]
+            RafxCommandBuffer::Gles2(inner) => inner.cmd_insert_debug_marker(name),
+            #[cfg(feature = "rafx-gles3")]
+            RafxCommandBuffer::Gles3(inner) => inner.cmd_insert_debug_marker(name),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxCommandBuffer::Empty(inner) => inner.cmd_insert_debug_marker(name),
+        }
+    }
+
     /// Get the underlying vulkan API object. This provides access to any internally created
     /// vulkan objects.
     #[cfg(feature = "rafx-vulkan")]
@@ -596,6 +596,80 @@ impl RafxDeviceContext {
         })
     }
 
+    /// Create a query pool
+    pub fn create_query_pool(
+        &self,
+        query_pool_def: &RafxQueryPoolDef,
+    ) -> RafxResult<RafxQueryPool> {
+        Ok(match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxDeviceContext::Vk(inner) => {
+                RafxQueryPool::Vk(inner.create_query_pool(query_pool_def)?)
+            }
+            #[cfg(feature = "rafx-metal")]
+            RafxDeviceContext::Metal(inner) => {
+                RafxQueryPool::Metal(inner.create_query_pool(query_pool_def)?)
+            }
+            #[cfg(feature = "rafx-gles2")]
+            RafxDeviceContext::Gles2(inner) => {
+                RafxQueryPool::Gles2(inner.create_query_pool(query_pool_def)?)
+            }
+            #[cfg(feature = "rafx-gles3")]
+            RafxDeviceContext::Gles3(inner) => {
+                RafxQueryPool::Gles3(inner.create_query_pool(query_pool_def)?)
+            }
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxDeviceContext::Empty(inner) => {
+                RafxQueryPool::Empty(inner.create_query_pool(query_pool_def)?)
+            }
+        })
+    }
+
+    /// Create a pipeline cache, optionally populated with data from a previous run
+    pub fn create_pipeline_cache(
+        &self,
+        pipeline_cache_def: &RafxPipelineCacheDef,
+    ) -> RafxResult<RafxPipelineCache> {
+        Ok(match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxDeviceContext::Vk(inner) => {
+                RafxPipelineCache::Vk(inner.create_pipeline_cache(pipeline_cache_def)?)
+            }
+            #[cfg(feature = "rafx-metal")]
+            RafxDeviceContext::Metal(inner) => {
+                RafxPipelineCache::Metal(inner.create_pipeline_cache(pipeline_cache_def)?)
+            }
+            #[cfg(feature = "rafx-gles2")]
+            RafxDeviceContext::Gles2(inner) => {
+                RafxPipelineCache::Gles2(inner.create_pipeline_cache(pipeline_cache_def)?)
+            }
+            #[cfg(feature = "rafx-gles3")]
+            RafxDeviceContext::Gles3(inner) => {
+                RafxPipelineCache::Gles3(inner.create_pipeline_cache(pipeline_cache_def)?)
+            }
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxDeviceContext::Empty(inner) => {
+                RafxPipelineCache::Empty(inner.create_pipeline_cache(pipeline_cache_def)?)
+            }
+        })
+    }
+
     /// Get the underlying vulkan API object. This provides access to any internally created
     /// vulkan objects.
     #[cfg(feature = "rafx-vulkan")]
@@ -0,0 +1,251 @@
+use crate::{
+    RafxBuffer, RafxBufferDef, RafxCmdCopyTextureToBufferParams, RafxCommandBuffer,
+    RafxCommandBufferDef, RafxCommandPool, RafxCommandPoolDef, RafxDeviceContext, RafxExtents3D,
+    RafxFence, RafxFenceStatus, RafxFormat, RafxMemoryUsage, RafxQueue, RafxQueueType,
+    RafxResourceState, RafxResourceType, RafxResult, RafxTexture, RafxTextureBarrier,
+};
+
+/// A CPU-readable buffer sized to hold a single mip level and array layer of a texture. Use this
+/// directly to record the copy into a command buffer that is submitted elsewhere, for example at
+/// the end of a frame. The caller must ensure the command buffer has finished executing before
+/// calling `read_data()`. `RafxTextureReadback` wraps this for one-off readbacks.
+pub struct RafxTextureReadbackBuffer {
+    buffer: RafxBuffer,
+    params: RafxCmdCopyTextureToBufferParams,
+    format: RafxFormat,
+    extents: RafxExtents3D,
+    row_pitch: u32,
+    slice_pitch: u32,
+}
+
+impl RafxTextureReadbackBuffer {
+    pub fn new(
+        device_context: &RafxDeviceContext,
+        queue_type: RafxQueueType,
+        texture: &RafxTexture,
+        array_layer: u16,
+        mip_level: u8,
+    ) -> RafxResult<Self> {
+        let texture_def = texture.texture_def();
+        let format = texture_def.format;
+        let extents = texture_def.mip_extents(mip_level);
+
+        let mut params = RafxCmdCopyTextureToBufferParams {
+            array_layer,
+            mip_level,
+            ..Default::default()
+        };
+
+        // Pad rows to the alignment the device prefers, as long as the pitch remains a multiple
+        // of the block size
+        let tight_row_pitch = params.row_pitch(format, extents);
+        let row_pitch = rafx_base::memory::round_size_up_to_alignment_u32(
+            tight_row_pitch,
+            device_context
+                .device_info()
+                .upload_buffer_texture_row_alignment
+                .max(1),
+        );
+        if row_pitch % format.block_or_pixel_size_in_bytes() == 0 {
+            params.buffer_row_pitch = row_pitch;
+        }
+
+        let row_pitch = params.row_pitch(format, extents);
+        let slice_pitch = params.slice_pitch(format, extents);
+
+        let buffer = device_context.create_buffer(&RafxBufferDef {
+            size: slice_pitch as u64 * extents.depth as u64,
+            memory_usage: RafxMemoryUsage::GpuToCpu,
+            queue_type,
+            resource_type: RafxResourceType::BUFFER,
+            ..Default::default()
+        })?;
+
+        Ok(RafxTextureReadbackBuffer {
+            buffer,
+            params,
+            format,
+            extents,
+            row_pitch,
+            slice_pitch,
+        })
+    }
+
+    pub fn format(&self) -> RafxFormat {
+        self.format
+    }
+
+    pub fn extents(&self) -> RafxExtents3D {
+        self.extents
+    }
+
+    /// Records the copy from the texture into this buffer. The texture must be in
+    /// `texture_state`. It is transitioned to COPY_SRC for the copy and then back to
+    /// `texture_state`.
+    pub fn cmd_copy_from_texture(
+        &self,
+        command_buffer: &RafxCommandBuffer,
+        texture: &RafxTexture,
+        texture_state: RafxResourceState,
+    ) -> RafxResult<()> {
+        if texture_state != RafxResourceState::COPY_SRC {
+            command_buffer.cmd_resource_barrier(
+                &[],
+                &[RafxTextureBarrier::state_transition(
+                    texture,
+                    texture_state,
+                    RafxResourceState::COPY_SRC,
+                )],
+            )?;
+        }
+
+        command_buffer.cmd_copy_texture_to_buffer(texture, &self.buffer, &self.params)?;
+
+        // Vulkan requires a barrier to make the copied data visible when the buffer is mapped
+        #[cfg(feature = "rafx-vulkan")]
+        if let Some(command_buffer) = command_buffer.vk_command_buffer() {
+            command_buffer.cmd_buffer_host_read_barrier(self.buffer.vk_buffer().unwrap())?;
+        }
+
+        if texture_state != RafxResourceState::COPY_SRC {
+            command_buffer.cmd_resource_barrier(
+                &[],
+                &[RafxTextureBarrier::state_transition(
+                    texture,
+                    RafxResourceState::COPY_SRC,
+                    texture_state,
+                )],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the texels with rows tightly packed. The copy must have finished executing.
+    pub fn read_data(&self) -> RafxResult<Vec<u8>> {
+        let block_width = self.format.block_width_in_pixels();
+        let block_height = self.format.block_height_in_pixels();
+        let width_in_blocks = (self.extents.width + block_width - 1) / block_width;
+        let height_in_blocks = (self.extents.height + block_height - 1) / block_height;
+        let row_size = (width_in_blocks * self.format.block_or_pixel_size_in_bytes()) as usize;
+
+        let mut data =
+            Vec::with_capacity(row_size * height_in_blocks as usize * self.extents.depth as usize);
+
+        let buffer_data = self.buffer.map_buffer()?;
+        for slice in 0..self.extents.depth {
+            for row in 0..height_in_blocks {
+                let offset = (slice * self.slice_pitch + row * self.row_pitch) as usize;
+                let row_data =
+                    unsafe { std::slice::from_raw_parts(buffer_data.add(offset), row_size) };
+                data.extend_from_slice(row_data);
+            }
+        }
+        self.buffer.unmap_buffer()?;
+
+        Ok(data)
+    }
+}
+
+/// Copies a single mip level and array layer of a texture into a CPU-readable buffer. The copy is
+/// submitted to the queue when the readback is created. Poll `is_complete()` to avoid stalling,
+/// or call `read_data()` to block until the data is available.
+///
+/// Devices may require padding at the end of each row of texels in the buffer. This is removed by
+/// `read_data()`, so the returned data is tightly packed.
+pub struct RafxTextureReadback {
+    // Kept alive until the submit completes
+    _command_pool: RafxCommandPool,
+    _command_buffer: RafxCommandBuffer,
+
+    readback_buffer: RafxTextureReadbackBuffer,
+    fence: RafxFence,
+}
+
+impl RafxTextureReadback {
+    /// The texture must be in `texture_state`. It is transitioned to COPY_SRC for the copy and
+    /// then back to `texture_state`.
+    pub fn new(
+        device_context: &RafxDeviceContext,
+        queue: &RafxQueue,
+        texture: &RafxTexture,
+        texture_state: RafxResourceState,
+        array_layer: u16,
+        mip_level: u8,
+    ) -> RafxResult<Self> {
+        let readback_buffer = RafxTextureReadbackBuffer::new(
+            device_context,
+            queue.queue_type(),
+            texture,
+            array_layer,
+            mip_level,
+        )?;
+
+        let mut command_pool =
+            queue.create_command_pool(&RafxCommandPoolDef { transient: true })?;
+        let command_buffer = command_pool.create_command_buffer(&RafxCommandBufferDef {
+            is_secondary: false,
+        })?;
+
+        command_buffer.begin()?;
+        readback_buffer.cmd_copy_from_texture(&command_buffer, texture, texture_state)?;
+        command_buffer.end()?;
+
+        let fence = device_context.create_fence()?;
+        queue.submit(&[&command_buffer], &[], &[], Some(&fence))?;
+
+        Ok(RafxTextureReadback {
+            _command_pool: command_pool,
+            _command_buffer: command_buffer,
+            readback_buffer,
+            fence,
+        })
+    }
+
+    pub fn format(&self) -> RafxFormat {
+        self.readback_buffer.format()
+    }
+
+    pub fn extents(&self) -> RafxExtents3D {
+        self.readback_buffer.extents()
+    }
+
+    /// Returns true if the copy has finished and `read_data()` will not block
+    pub fn is_complete(&self) -> RafxResult<bool> {
+        Ok(self.fence.get_fence_status()? != RafxFenceStatus::Incomplete)
+    }
+
+    /// Blocks until the copy finishes and returns the texels with rows tightly packed
+    pub fn read_data(&self) -> RafxResult<Vec<u8>> {
+        self.fence.wait()?;
+        self.readback_buffer.read_data()
+    }
+}
+
+impl Drop for RafxTextureReadback {
+    fn drop(&mut self) {
+        // If the copy is in flight, wait for it to complete
+        self.fence.wait().unwrap();
+    }
+}
+
+/// Reads back a single mip level and array layer of a texture, blocking until the data is
+/// available. See `RafxTextureReadback` for details.
+pub fn read_texture(
+    device_context: &RafxDeviceContext,
+    queue: &RafxQueue,
+    texture: &RafxTexture,
+    texture_state: RafxResourceState,
+    array_layer: u16,
+    mip_level: u8,
+) -> RafxResult<Vec<u8>> {
+    RafxTextureReadback::new(
+        device_context,
+        queue,
+        texture,
+        texture_state,
+        array_layer,
+        mip_level,
+    )?
+    .read_data()
+}
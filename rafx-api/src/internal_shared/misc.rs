@@ -212,6 +212,95 @@ pub(crate) fn merge_resources<'a>(
     ))
 }
 
+#[cfg(any(
+    feature = "rafx-metal",
+    feature = "rafx-vulkan",
+    feature = "rafx-gles2",
+    feature = "rafx-gles3"
+))]
+pub(crate) fn merge_push_constants(
+    root_signature_def: &RafxRootSignatureDef,
+    max_push_constant_size: u32,
+) -> RafxResult<Option<RafxShaderResource>> {
+    merge_push_constant_resources(
+        root_signature_def
+            .shaders
+            .iter()
+            .filter_map(|shader| shader.pipeline_reflection().push_constant.as_ref()),
+        max_push_constant_size,
+    )
+}
+
+#[cfg(any(
+    feature = "rafx-metal",
+    feature = "rafx-vulkan",
+    feature = "rafx-gles2",
+    feature = "rafx-gles3"
+))]
+fn merge_push_constant_resources<'a>(
+    push_constants: impl Iterator<Item = &'a RafxShaderResource>,
+    max_push_constant_size: u32,
+) -> RafxResult<Option<RafxShaderResource>> {
+    let mut merged_push_constant: Option<RafxShaderResource> = None;
+    for push_constant in push_constants {
+        log::trace!(
+            "  Merge push constant (name={:?} size_in_bytes={:?})",
+            push_constant.name,
+            push_constant.size_in_bytes
+        );
+
+        if let Some(merged_push_constant) = &mut merged_push_constant {
+            merged_push_constant.merge_push_constant(push_constant)?;
+        } else {
+            merged_push_constant = Some(push_constant.clone());
+        }
+    }
+
+    if let Some(push_constant) = &merged_push_constant {
+        push_constant.validate()?;
+
+        if push_constant.size_in_bytes > max_push_constant_size {
+            Err(format!(
+                "Push constants (name={:?}) are {} bytes but the device supports at most {} bytes",
+                push_constant.name, push_constant.size_in_bytes, max_push_constant_size
+            ))?;
+        }
+    }
+
+    Ok(merged_push_constant)
+}
+
+#[cfg(any(
+    feature = "rafx-metal",
+    feature = "rafx-vulkan",
+    feature = "rafx-gles2",
+    feature = "rafx-gles3"
+))]
+pub(crate) fn verify_push_constant_range(
+    push_constant_size_in_bytes: Option<u32>,
+    offset_in_bytes: u32,
+    size_in_bytes: u32,
+) -> RafxResult<()> {
+    let push_constant_size_in_bytes =
+        push_constant_size_in_bytes.ok_or("The root signature does not have push constants")?;
+
+    if offset_in_bytes % 4 != 0 || size_in_bytes % 4 != 0 {
+        Err(format!(
+            "Push constant offset ({}) and size ({}) must be multiples of 4",
+            offset_in_bytes, size_in_bytes
+        ))?;
+    }
+
+    if offset_in_bytes + size_in_bytes > push_constant_size_in_bytes {
+        Err(format!(
+            "Tried to write push constants at offset {} with size {} but the push constants are only {} bytes",
+            offset_in_bytes, size_in_bytes, push_constant_size_in_bytes
+        ))?;
+    }
+
+    Ok(())
+}
+
 #[cfg(any(
     feature = "rafx-metal",
     feature = "rafx-vulkan",
@@ -263,3 +352,145 @@ fn verify_resources_can_overlap(
 
     Ok(())
 }
+
+#[cfg(test)]
+#[cfg(any(
+    feature = "rafx-metal",
+    feature = "rafx-vulkan",
+    feature = "rafx-gles2",
+    feature = "rafx-gles3"
+))]
+mod test {
+    use super::*;
+    use crate::{RafxGlUniformMember, RafxResourceType};
+
+    fn push_constant(
+        stages: RafxShaderStageFlags,
+        size_in_bytes: u32,
+        members: &[(&str, u32)],
+    ) -> RafxShaderResource {
+        RafxShaderResource {
+            resource_type: RafxResourceType::ROOT_CONSTANT,
+            size_in_bytes,
+            used_in_shader_stages: stages,
+            name: Some("PushConstants".to_string()),
+            gles_name: Some("push_constants".to_string()),
+            gles2_uniform_members: members
+                .iter()
+                .map(|(name, offset)| RafxGlUniformMember::new(*name, *offset))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_merge_no_push_constants() {
+        let merged = merge_push_constant_resources(std::iter::empty(), 128).unwrap();
+        assert!(merged.is_none());
+    }
+
+    #[test]
+    fn test_merge_overlapping_push_constants() {
+        let vertex = push_constant(
+            RafxShaderStageFlags::VERTEX,
+            64,
+            &[("push_constants.transform", 0)],
+        );
+        let fragment = push_constant(
+            RafxShaderStageFlags::FRAGMENT,
+            80,
+            &[
+                ("push_constants.transform", 0),
+                ("push_constants.color", 64),
+            ],
+        );
+
+        let merged = merge_push_constant_resources([&vertex, &fragment].iter().copied(), 128)
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.size_in_bytes, 80);
+        assert_eq!(
+            merged.used_in_shader_stages,
+            RafxShaderStageFlags::VERTEX | RafxShaderStageFlags::FRAGMENT
+        );
+        assert_eq!(
+            merged.gles2_uniform_members,
+            vec![
+                RafxGlUniformMember::new("push_constants.transform", 0),
+                RafxGlUniformMember::new("push_constants.color", 64),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_disjoint_push_constants() {
+        // Each stage only declares the members it uses
+        let vertex = push_constant(
+            RafxShaderStageFlags::VERTEX,
+            16,
+            &[("push_constants.offset", 0)],
+        );
+        let fragment = push_constant(
+            RafxShaderStageFlags::FRAGMENT,
+            32,
+            &[("push_constants.color", 16)],
+        );
+
+        let merged = merge_push_constant_resources([&fragment, &vertex].iter().copied(), 128)
+            .unwrap()
+            .unwrap();
+        assert_eq!(merged.size_in_bytes, 32);
+        assert_eq!(
+            merged.used_in_shader_stages,
+            RafxShaderStageFlags::VERTEX | RafxShaderStageFlags::FRAGMENT
+        );
+        assert_eq!(merged.gles2_uniform_members.len(), 2);
+    }
+
+    #[test]
+    fn test_merge_mismatched_push_constants() {
+        let vertex = push_constant(
+            RafxShaderStageFlags::VERTEX,
+            16,
+            &[("push_constants.color", 0)],
+        );
+        let fragment = push_constant(
+            RafxShaderStageFlags::FRAGMENT,
+            32,
+            &[("push_constants.color", 16)],
+        );
+        assert!(merge_push_constant_resources([&vertex, &fragment].iter().copied(), 128).is_err());
+
+        let mut renamed = fragment.clone();
+        renamed.gles2_uniform_members.clear();
+        renamed.gles_name = Some("other_push_constants".to_string());
+        assert!(merge_push_constant_resources([&vertex, &renamed].iter().copied(), 128).is_err());
+    }
+
+    #[test]
+    fn test_merge_push_constants_too_large() {
+        let vertex = push_constant(RafxShaderStageFlags::VERTEX, 64, &[]);
+        let fragment = push_constant(RafxShaderStageFlags::FRAGMENT, 256, &[]);
+        assert!(merge_push_constant_resources([&vertex].iter().copied(), 128).is_ok());
+        assert!(merge_push_constant_resources([&vertex, &fragment].iter().copied(), 128).is_err());
+    }
+
+    #[test]
+    fn test_verify_push_constant_range() {
+        assert!(verify_push_constant_range(Some(64), 0, 64).is_ok());
+        assert!(verify_push_constant_range(Some(64), 16, 48).is_ok());
+        assert!(verify_push_constant_range(Some(64), 60, 4).is_ok());
+
+        // Out of range
+        assert!(verify_push_constant_range(Some(64), 0, 68).is_err());
+        assert!(verify_push_constant_range(Some(64), 64, 4).is_err());
+        assert!(verify_push_constant_range(Some(64), 32, 48).is_err());
+
+        // Not aligned
+        assert!(verify_push_constant_range(Some(64), 2, 4).is_err());
+        assert!(verify_push_constant_range(Some(64), 0, 6).is_err());
+
+        // No push constants
+        assert!(verify_push_constant_range(None, 0, 4).is_err());
+    }
+}
@@ -31,6 +31,8 @@
 //! * [RafxDeviceContext] - A cloneable, thread-safe handle used to create graphics resources.
 //! * [RafxFence] - A GPU -> CPU synchronization mechanism.
 //! * [RafxPipeline] - Represents a complete GPU configuration for executing work.
+//! * [RafxPipelineCache] - Stores compiled pipelines so they can be created faster in later runs
+//! * [RafxQueryPool] - A set of GPU queries used to measure GPU timings and pipeline statistics
 //! * [RafxQueue] - A queue allows work to be submitted to the GPU
 //! * [RafxRootSignature] - Represents the full "layout" or "interface" of a shader (or set of shaders.)
 //! * [RafxSampler] - Configures how images will be sampled by the GPU
@@ -286,6 +288,8 @@ mod descriptor_set_array;
 mod device_context;
 mod fence;
 mod pipeline;
+mod pipeline_cache;
+mod query_pool;
 mod queue;
 mod root_signature;
 mod sampler;
@@ -306,6 +310,9 @@ pub const MAX_DESCRIPTOR_SET_LAYOUTS: usize = 4;
 pub const MAX_RENDER_TARGET_ATTACHMENTS: usize = 8;
 // Vulkan guarantees up to 16
 pub const MAX_VERTEX_INPUT_BINDINGS: usize = 16;
+/// The buffer index push constants are bound to in metal shaders. Argument buffers for descriptor
+/// sets use the indices below it and vertex buffers count down from 30
+pub const METAL_PUSH_CONSTANT_BUFFER_INDEX: u32 = MAX_DESCRIPTOR_SET_LAYOUTS as u32;
 
 //
 // Exported public API
@@ -320,6 +327,8 @@ pub use error::*;
 pub use extra::swapchain_helper::*;
 pub use fence::*;
 pub use pipeline::*;
+pub use pipeline_cache::*;
+pub use query_pool::*;
 pub use queue::*;
 pub use root_signature::*;
 pub use sampler::*;
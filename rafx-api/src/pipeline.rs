@@ -102,6 +102,34 @@ impl RafxPipeline {
         }
     }
 
+    /// Sets a name for the pipeline that will be visible in debugging tools like RenderDoc or Xcode.
+    /// This is a no-op if debug names are not enabled (see `RafxDeviceInfo::debug_names_enabled`)
+    pub fn set_debug_name(
+        &self,
+        name: impl AsRef<str>,
+    ) {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxPipeline::Vk(inner) => inner.set_debug_name(name),
+            #[cfg(feature = "rafx-metal")]
+            RafxPipeline::Metal(inner) => inner.set_debug_name(name),
+            #[cfg(feature = "rafx-gles2")]
+            RafxPipeline::Gles2(inner) => inner.set_debug_name(name),
+            #[cfg(feature = "rafx-gles3")]
+            RafxPipeline::Gles3(inner) => inner.set_debug_name(name),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxPipeline::Empty(inner) => inner.set_debug_name(name),
+        }
+    }
+
     /// Get the underlying vulkan API object. This provides access to any internally created
     /// vulkan objects.
     #[cfg(feature = "rafx-vulkan")]
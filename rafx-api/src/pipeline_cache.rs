@@ -0,0 +1,226 @@
+#[cfg(any(
+    feature = "rafx-empty",
+    not(any(
+        feature = "rafx-metal",
+        feature = "rafx-vulkan",
+        feature = "rafx-gles2",
+        feature = "rafx-gles3"
+    ))
+))]
+use crate::empty::RafxPipelineCacheEmpty;
+#[cfg(feature = "rafx-gles2")]
+use crate::gles2::RafxPipelineCacheGles2;
+#[cfg(feature = "rafx-gles3")]
+use crate::gles3::RafxPipelineCacheGles3;
+#[cfg(feature = "rafx-metal")]
+use crate::metal::RafxPipelineCacheMetal;
+#[cfg(feature = "rafx-vulkan")]
+use crate::vulkan::RafxPipelineCacheVulkan;
+use crate::RafxResult;
+
+/// Stores compiled pipelines so that creating the same pipeline again (in this run or a later one)
+/// is faster.
+///
+/// Pass the cache in `RafxGraphicsPipelineDef::pipeline_cache` or
+/// `RafxComputePipelineDef::pipeline_cache` when creating pipelines. The contents of the cache can
+/// be retrieved with `get_data()`, written to disk, and passed back in `RafxPipelineCacheDef` on
+/// the next launch. Data from a different device or driver version is discarded on creation.
+///
+/// On vulkan this is a VkPipelineCache. On metal, this is a binary archive (macOS 11.0/iOS 14.0
+/// and later, otherwise the cache does nothing.) GL ES does not support pipeline caches, so the
+/// cache does nothing.
+pub enum RafxPipelineCache {
+    #[cfg(feature = "rafx-vulkan")]
+    Vk(RafxPipelineCacheVulkan),
+    #[cfg(feature = "rafx-metal")]
+    Metal(RafxPipelineCacheMetal),
+    #[cfg(feature = "rafx-gles2")]
+    Gles2(RafxPipelineCacheGles2),
+    #[cfg(feature = "rafx-gles3")]
+    Gles3(RafxPipelineCacheGles3),
+    #[cfg(any(
+        feature = "rafx-empty",
+        not(any(
+            feature = "rafx-metal",
+            feature = "rafx-vulkan",
+            feature = "rafx-gles2",
+            feature = "rafx-gles3"
+        ))
+    ))]
+    Empty(RafxPipelineCacheEmpty),
+}
+
+impl std::fmt::Debug for RafxPipelineCache {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("RafxPipelineCache").finish()
+    }
+}
+
+impl RafxPipelineCache {
+    /// Serialize the contents of the cache so that it can be saved and passed to
+    /// `RafxPipelineCacheDef::data` in a later run. The data may be empty if the backend does not
+    /// support pipeline caches.
+    pub fn get_data(&self) -> RafxResult<Vec<u8>> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxPipelineCache::Vk(inner) => inner.get_data(),
+            #[cfg(feature = "rafx-metal")]
+            RafxPipelineCache::Metal(inner) => inner.get_data(),
+            #[cfg(feature = "rafx-gles2")]
+            RafxPipelineCache::Gles2(inner) => inner.get_data(),
+            #[cfg(feature = "rafx-gles3")]
+            RafxPipelineCache::Gles3(inner) => inner.get_data(),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxPipelineCache::Empty(inner) => inner.get_data(),
+        }
+    }
+
+    /// Get the underlying vulkan API object. This provides access to any internally created
+    /// vulkan objects.
+    #[cfg(feature = "rafx-vulkan")]
+    pub fn vk_pipeline_cache(&self) -> Option<&RafxPipelineCacheVulkan> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxPipelineCache::Vk(inner) => Some(inner),
+            #[cfg(feature = "rafx-metal")]
+            RafxPipelineCache::Metal(_) => None,
+            #[cfg(feature = "rafx-gles2")]
+            RafxPipelineCache::Gles2(_) => None,
+            #[cfg(feature = "rafx-gles3")]
+            RafxPipelineCache::Gles3(_) => None,
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxPipelineCache::Empty(_) => None,
+        }
+    }
+
+    /// Get the underlying metal API object. This provides access to any internally created
+    /// metal objects.
+    #[cfg(feature = "rafx-metal")]
+    pub fn metal_pipeline_cache(&self) -> Option<&RafxPipelineCacheMetal> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxPipelineCache::Vk(_) => None,
+            #[cfg(feature = "rafx-metal")]
+            RafxPipelineCache::Metal(inner) => Some(inner),
+            #[cfg(feature = "rafx-gles2")]
+            RafxPipelineCache::Gles2(_) => None,
+            #[cfg(feature = "rafx-gles3")]
+            RafxPipelineCache::Gles3(_) => None,
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxPipelineCache::Empty(_) => None,
+        }
+    }
+
+    /// Get the underlying gles2 API object. This provides access to any internally created
+    /// gles2 objects.
+    #[cfg(feature = "rafx-gles2")]
+    pub fn gles2_pipeline_cache(&self) -> Option<&RafxPipelineCacheGles2> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxPipelineCache::Vk(_) => None,
+            #[cfg(feature = "rafx-metal")]
+            RafxPipelineCache::Metal(_) => None,
+            #[cfg(feature = "rafx-gles2")]
+            RafxPipelineCache::Gles2(inner) => Some(inner),
+            #[cfg(feature = "rafx-gles3")]
+            RafxPipelineCache::Gles3(_) => None,
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxPipelineCache::Empty(_) => None,
+        }
+    }
+
+    /// Get the underlying gles3 API object. This provides access to any internally created
+    /// gles3 objects.
+    #[cfg(feature = "rafx-gles3")]
+    pub fn gles3_pipeline_cache(&self) -> Option<&RafxPipelineCacheGles3> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxPipelineCache::Vk(_) => None,
+            #[cfg(feature = "rafx-metal")]
+            RafxPipelineCache::Metal(_) => None,
+            #[cfg(feature = "rafx-gles2")]
+            RafxPipelineCache::Gles2(_) => None,
+            #[cfg(feature = "rafx-gles3")]
+            RafxPipelineCache::Gles3(inner) => Some(inner),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxPipelineCache::Empty(_) => None,
+        }
+    }
+
+    /// Get the underlying empty API object. This provides access to any internally created
+    /// empty objects.
+    #[cfg(any(
+        feature = "rafx-empty",
+        not(any(
+            feature = "rafx-metal",
+            feature = "rafx-vulkan",
+            feature = "rafx-gles2",
+            feature = "rafx-gles3"
+        ))
+    ))]
+    pub fn empty_pipeline_cache(&self) -> Option<&RafxPipelineCacheEmpty> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxPipelineCache::Vk(_) => None,
+            #[cfg(feature = "rafx-metal")]
+            RafxPipelineCache::Metal(_) => None,
+            #[cfg(feature = "rafx-gles2")]
+            RafxPipelineCache::Gles2(_) => None,
+            #[cfg(feature = "rafx-gles3")]
+            RafxPipelineCache::Gles3(_) => None,
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxPipelineCache::Empty(inner) => Some(inner),
+        }
+    }
+}
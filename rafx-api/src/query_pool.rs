@@ -0,0 +1,248 @@
+#[cfg(any(
+    feature = "rafx-empty",
+    not(any(
+        feature = "rafx-metal",
+        feature = "rafx-vulkan",
+        feature = "rafx-gles2",
+        feature = "rafx-gles3"
+    ))
+))]
+use crate::empty::RafxQueryPoolEmpty;
+#[cfg(feature = "rafx-gles2")]
+use crate::gles2::RafxQueryPoolGles2;
+#[cfg(feature = "rafx-gles3")]
+use crate::gles3::RafxQueryPoolGles3;
+#[cfg(feature = "rafx-metal")]
+use crate::metal::RafxQueryPoolMetal;
+#[cfg(feature = "rafx-vulkan")]
+use crate::vulkan::RafxQueryPoolVulkan;
+use crate::{RafxQueryPoolDef, RafxResult};
+
+/// A fixed-size set of GPU queries, used to measure GPU timings and pipeline statistics.
+///
+/// Queries are written by a command buffer (`cmd_write_timestamp`, `cmd_begin_query`/
+/// `cmd_end_query`) and must be reset with `cmd_reset_queries` before they are reused. Results
+/// can be copied into a buffer on the GPU with `cmd_resolve_queries` or read back on the CPU with
+/// `get_results` once the command buffer that wrote them has completed.
+///
+/// Check `RafxDeviceInfo::supports_timestamp_queries` and
+/// `RafxDeviceInfo::supports_pipeline_statistics_queries` before creating a query pool.
+///
+/// Query pools must not be dropped if they are in use by the GPU.
+pub enum RafxQueryPool {
+    #[cfg(feature = "rafx-vulkan")]
+    Vk(RafxQueryPoolVulkan),
+    #[cfg(feature = "rafx-metal")]
+    Metal(RafxQueryPoolMetal),
+    #[cfg(feature = "rafx-gles2")]
+    Gles2(RafxQueryPoolGles2),
+    #[cfg(feature = "rafx-gles3")]
+    Gles3(RafxQueryPoolGles3),
+    #[cfg(any(
+        feature = "rafx-empty",
+        not(any(
+            feature = "rafx-metal",
+            feature = "rafx-vulkan",
+            feature = "rafx-gles2",
+            feature = "rafx-gles3"
+        ))
+    ))]
+    Empty(RafxQueryPoolEmpty),
+}
+
+impl RafxQueryPool {
+    /// Returns the def used to create the query pool
+    pub fn query_pool_def(&self) -> &RafxQueryPoolDef {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxQueryPool::Vk(inner) => inner.query_pool_def(),
+            #[cfg(feature = "rafx-metal")]
+            RafxQueryPool::Metal(inner) => inner.query_pool_def(),
+            #[cfg(feature = "rafx-gles2")]
+            RafxQueryPool::Gles2(inner) => inner.query_pool_def(),
+            #[cfg(feature = "rafx-gles3")]
+            RafxQueryPool::Gles3(inner) => inner.query_pool_def(),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxQueryPool::Empty(inner) => inner.query_pool_def(),
+        }
+    }
+
+    /// Copy the results of `query_count` queries starting at `first_query` into `results`. Each
+    /// query produces `RafxQueryPoolDef::result_count_per_query()` values.
+    ///
+    /// This does not block. If any of the queries are not yet available, `Ok(false)` is returned
+    /// and the contents of `results` are unspecified.
+    pub fn get_results(
+        &self,
+        first_query: u32,
+        query_count: u32,
+        results: &mut [u64],
+    ) -> RafxResult<bool> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxQueryPool::Vk(inner) => inner.get_results(first_query, query_count, results),
+            #[cfg(feature = "rafx-metal")]
+            RafxQueryPool::Metal(inner) => inner.get_results(first_query, query_count, results),
+            #[cfg(feature = "rafx-gles2")]
+            RafxQueryPool::Gles2(inner) => inner.get_results(first_query, query_count, results),
+            #[cfg(feature = "rafx-gles3")]
+            RafxQueryPool::Gles3(inner) => inner.get_results(first_query, query_count, results),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxQueryPool::Empty(inner) => inner.get_results(first_query, query_count, results),
+        }
+    }
+
+    /// Get the underlying vulkan API object. This provides access to any internally created
+    /// vulkan objects.
+    #[cfg(feature = "rafx-vulkan")]
+    pub fn vk_query_pool(&self) -> Option<&RafxQueryPoolVulkan> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxQueryPool::Vk(inner) => Some(inner),
+            #[cfg(feature = "rafx-metal")]
+            RafxQueryPool::Metal(_) => None,
+            #[cfg(feature = "rafx-gles2")]
+            RafxQueryPool::Gles2(_) => None,
+            #[cfg(feature = "rafx-gles3")]
+            RafxQueryPool::Gles3(_) => None,
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxQueryPool::Empty(_) => None,
+        }
+    }
+
+    /// Get the underlying metal API object. This provides access to any internally created
+    /// metal objects.
+    #[cfg(feature = "rafx-metal")]
+    pub fn metal_query_pool(&self) -> Option<&RafxQueryPoolMetal> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxQueryPool::Vk(_) => None,
+            #[cfg(feature = "rafx-metal")]
+            RafxQueryPool::Metal(inner) => Some(inner),
+            #[cfg(feature = "rafx-gles2")]
+            RafxQueryPool::Gles2(_) => None,
+            #[cfg(feature = "rafx-gles3")]
+            RafxQueryPool::Gles3(_) => None,
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxQueryPool::Empty(_) => None,
+        }
+    }
+
+    /// Get the underlying gles2 API object. This provides access to any internally created
+    /// gles2 objects.
+    #[cfg(feature = "rafx-gles2")]
+    pub fn gles2_query_pool(&self) -> Option<&RafxQueryPoolGles2> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxQueryPool::Vk(_) => None,
+            #[cfg(feature = "rafx-metal")]
+            RafxQueryPool::Metal(_) => None,
+            #[cfg(feature = "rafx-gles2")]
+            RafxQueryPool::Gles2(inner) => Some(inner),
+            #[cfg(feature = "rafx-gles3")]
+            RafxQueryPool::Gles3(_) => None,
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxQueryPool::Empty(_) => None,
+        }
+    }
+
+    /// Get the underlying gles3 API object. This provides access to any internally created
+    /// gles3 objects.
+    #[cfg(feature = "rafx-gles3")]
+    pub fn gles3_query_pool(&self) -> Option<&RafxQueryPoolGles3> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxQueryPool::Vk(_) => None,
+            #[cfg(feature = "rafx-metal")]
+            RafxQueryPool::Metal(_) => None,
+            #[cfg(feature = "rafx-gles2")]
+            RafxQueryPool::Gles2(_) => None,
+            #[cfg(feature = "rafx-gles3")]
+            RafxQueryPool::Gles3(inner) => Some(inner),
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxQueryPool::Empty(_) => None,
+        }
+    }
+
+    /// Get the underlying empty API object. This provides access to any internally created
+    /// empty objects.
+    #[cfg(any(
+        feature = "rafx-empty",
+        not(any(
+            feature = "rafx-metal",
+            feature = "rafx-vulkan",
+            feature = "rafx-gles2",
+            feature = "rafx-gles3"
+        ))
+    ))]
+    pub fn empty_query_pool(&self) -> Option<&RafxQueryPoolEmpty> {
+        match self {
+            #[cfg(feature = "rafx-vulkan")]
+            RafxQueryPool::Vk(_) => None,
+            #[cfg(feature = "rafx-metal")]
+            RafxQueryPool::Metal(_) => None,
+            #[cfg(feature = "rafx-gles2")]
+            RafxQueryPool::Gles2(_) => None,
+            #[cfg(feature = "rafx-gles3")]
+            RafxQueryPool::Gles3(_) => None,
+            #[cfg(any(
+                feature = "rafx-empty",
+                not(any(
+                    feature = "rafx-metal",
+                    feature = "rafx-vulkan",
+                    feature = "rafx-gles2",
+                    feature = "rafx-gles3"
+                ))
+            ))]
+            RafxQueryPool::Empty(inner) => Some(inner),
+        }
+    }
+}
@@ -45,7 +45,7 @@ pub struct RafxShaderResource {
     // access it via element_count_normalized(). This ensures that if it
     // is default-initialized to 0, it is treated as 1
     pub element_count: u32,
-    // Valid only for push constants (resource_type != ROOT_CONSTANT)
+    // Valid only for push constants (resource_type == ROOT_CONSTANT)
     pub size_in_bytes: u32,
     pub used_in_shader_stages: RafxShaderStageFlags,
     // Name is optional
@@ -194,6 +194,56 @@ impl RafxShaderResource {
 
         Ok(())
     }
+
+    // Push constants are not bound by set/binding. Shader stages may each declare a block, so they
+    // are combined into a single range visible to all the stages that use it. Blocks may be
+    // different sizes (i.e. a stage only declares the members it needs) but members at the same
+    // offset must have the same name.
+    pub(crate) fn merge_push_constant(
+        &mut self,
+        other: &Self,
+    ) -> RafxResult<()> {
+        assert_eq!(self.resource_type, RafxResourceType::ROOT_CONSTANT);
+        assert_eq!(other.resource_type, RafxResourceType::ROOT_CONSTANT);
+
+        if self.gles_name.is_some()
+            && other.gles_name.is_some()
+            && self.gles_name != other.gles_name
+        {
+            Err(format!(
+                "Push constants are declared with different gles_name {:?} and {:?}",
+                self.gles_name, other.gles_name
+            ))?;
+        }
+
+        for other_member in &other.gles2_uniform_members {
+            if let Some(member) = self
+                .gles2_uniform_members
+                .iter()
+                .find(|x| x.name == other_member.name)
+            {
+                if member.offset != other_member.offset {
+                    Err(format!(
+                        "Push constant member {} is declared with different offsets {} and {}",
+                        member.name, member.offset, other_member.offset
+                    ))?;
+                }
+            } else {
+                self.gles2_uniform_members.push(other_member.clone());
+            }
+        }
+
+        self.size_in_bytes = self.size_in_bytes.max(other.size_in_bytes);
+        self.used_in_shader_stages |= other.used_in_shader_stages;
+        if self.name.is_none() {
+            self.name = other.name.clone();
+        }
+        if self.gles_name.is_none() {
+            self.gles_name = other.gles_name.clone();
+        }
+
+        Ok(())
+    }
 }
 
 /// Reflection data for a single shader stage
@@ -215,7 +265,10 @@ pub struct RafxShaderStageReflection {
 #[derive(Debug)]
 pub struct RafxPipelineReflection {
     pub shader_stages: RafxShaderStageFlags,
+    // Descriptors only, push constants are merged into push_constant
     pub resources: Vec<RafxShaderResource>,
+    // A single ROOT_CONSTANT resource covering the push constants declared by all stages
+    pub push_constant: Option<RafxShaderResource>,
     pub compute_threads_per_group: Option<[u32; 3]>,
 }
 
@@ -270,12 +323,35 @@ impl RafxPipelineReflection {
         let mut merged_resources =
             FnvHashMap::<RafxShaderResourceBindingKey, RafxShaderResource>::default();
 
-        //TODO: Merge push constants
+        //
+        // Merge the push constants
+        //
+        let mut push_constant: Option<RafxShaderResource> = None;
+        for resource in &unmerged_resources {
+            if resource.resource_type != RafxResourceType::ROOT_CONSTANT {
+                continue;
+            }
+
+            log::trace!(
+                "    Push constant {:?} from stage {:?}",
+                resource.name,
+                resource.used_in_shader_stages
+            );
+            if let Some(push_constant) = &mut push_constant {
+                push_constant.merge_push_constant(resource)?;
+            } else {
+                push_constant = Some(resource.clone());
+            }
+        }
 
         //
         // Merge the resources
         //
         for resource in &unmerged_resources {
+            if resource.resource_type == RafxResourceType::ROOT_CONSTANT {
+                continue;
+            }
+
             log::trace!(
                 "    Resource {:?} from stage {:?}",
                 resource.name,
@@ -313,6 +389,7 @@ impl RafxPipelineReflection {
             shader_stages: all_shader_stages,
             compute_threads_per_group,
             resources,
+            push_constant,
         })
     }
 }
@@ -1,5 +1,5 @@
 use super::*;
-use crate::{RafxRootSignature, RafxSampler, RafxShader, RafxShaderModule};
+use crate::{RafxPipelineCache, RafxRootSignature, RafxSampler, RafxShader, RafxShaderModule};
 use rafx_base::DecimalF32;
 use std::hash::{Hash, Hasher};
 
@@ -8,9 +8,18 @@ use fnv::FnvHasher;
 use serde::{Deserialize, Serialize};
 
 /// General configuration that all APIs will make best effort to respect
-#[derive(Default)]
 pub struct RafxApiDef {
-    // Don't have anything that's universal across APIs to add here yet
+    /// Enables attaching names to resources and labeling regions of command buffers so that they
+    /// are visible in debugging tools like RenderDoc or Xcode. Defaults to enabled in debug builds.
+    pub enable_debug_names: bool,
+}
+
+impl Default for RafxApiDef {
+    fn default() -> Self {
+        RafxApiDef {
+            enable_debug_names: cfg!(debug_assertions),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -212,6 +221,18 @@ impl RafxTextureDef {
             "Cannot use depth stencil as UAV"
         );
     }
+
+    /// The size of the given mip level, in texels
+    pub fn mip_extents(
+        &self,
+        mip_level: u8,
+    ) -> RafxExtents3D {
+        RafxExtents3D {
+            width: 1.max(self.extents.width >> mip_level),
+            height: 1.max(self.extents.height >> mip_level),
+            depth: 1.max(self.extents.depth >> mip_level),
+        }
+    }
 }
 
 /// Used to create a `RafxCommandPool`
@@ -229,6 +250,35 @@ pub struct RafxCommandBufferDef {
     pub is_secondary: bool,
 }
 
+/// Used to create a `RafxQueryPool`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RafxQueryPoolDef {
+    /// The kind of query every entry in the pool holds
+    pub query_type: RafxQueryType,
+    /// The number of queries in the pool
+    pub query_count: u32,
+}
+
+impl RafxQueryPoolDef {
+    pub fn verify(&self) {
+        assert_ne!(self.query_count, 0);
+    }
+
+    /// The number of u64 values produced when a single query in the pool is read back
+    pub fn result_count_per_query(&self) -> u32 {
+        self.query_type.result_count()
+    }
+}
+
+/// Used to create a `RafxPipelineCache`
+#[derive(Debug, Clone, Default)]
+pub struct RafxPipelineCacheDef<'a> {
+    /// Data previously returned by `RafxPipelineCache::get_data()`. The data is checked against the
+    /// current device and driver. If it is incompatible or corrupt, it is ignored and the cache
+    /// starts out empty.
+    pub data: Option<&'a [u8]>,
+}
+
 /// Used to create a `RafxSwapchain`
 #[derive(Clone, Debug)]
 pub struct RafxSwapchainDef {
@@ -654,6 +704,9 @@ pub struct RafxGraphicsPipelineDef<'a> {
     pub color_formats: &'a [RafxFormat],
     pub depth_stencil_format: Option<RafxFormat>,
     pub sample_count: RafxSampleCount,
+    /// If provided, the cache is used to speed up creating the pipeline, and the newly created
+    /// pipeline is added to it
+    pub pipeline_cache: Option<&'a RafxPipelineCache>,
     //indirect_commands_enable: bool
 }
 
@@ -662,6 +715,9 @@ pub struct RafxGraphicsPipelineDef<'a> {
 pub struct RafxComputePipelineDef<'a> {
     pub shader: &'a RafxShader,
     pub root_signature: &'a RafxRootSignature,
+    /// If provided, the cache is used to speed up creating the pipeline, and the newly created
+    /// pipeline is added to it
+    pub pipeline_cache: Option<&'a RafxPipelineCache>,
 }
 
 /// Used to create a `RafxDescriptorSetArray`
@@ -1,7 +1,7 @@
 #[cfg(feature = "serde-support")]
 use serde::{Deserialize, Serialize};
 
-use crate::{RafxBuffer, RafxSampler, RafxTexture};
+use crate::{RafxBuffer, RafxFormat, RafxSampler, RafxTexture, RafxTextureDef};
 use rafx_base::DecimalF32;
 use std::hash::{Hash, Hasher};
 
@@ -45,6 +45,30 @@ pub struct RafxDeviceInfo {
     pub supports_clamp_to_border_color: bool,
 
     pub max_vertex_attribute_count: u32,
+
+    // Timestamp queries return ticks. Multiply by this to get nanoseconds.
+    pub timestamp_period_ns: f32,
+    pub supports_timestamp_queries: bool,
+    pub supports_pipeline_statistics_queries: bool,
+
+    // cmd_draw_indirect/cmd_draw_indexed_indirect, and the max draw_count that may be passed to
+    // them in a single call
+    pub supports_indirect_draw: bool,
+    pub max_indirect_draw_count: u32,
+    // cmd_draw_indirect_count/cmd_draw_indexed_indirect_count
+    pub supports_indirect_draw_count: bool,
+    pub supports_indirect_dispatch: bool,
+
+    // The largest push constant block that may be used by a root signature
+    pub max_push_constant_size: u32,
+
+    // True if set_debug_name/cmd_push_group_debug_name/etc. will be visible in debugging tools
+    pub debug_names_enabled: bool,
+
+    // If true, secondary command buffers may be recorded on any thread. Otherwise (GL ES) they
+    // are emulated by issuing commands as they are recorded, so they must be recorded on the
+    // thread that owns the primary, in the order they will be executed
+    pub supports_multithreaded_command_recording: bool,
     //max_vertex_input_binding_count: u32,
     // max_root_signature_dwords: u32,
     // wave_lane_count: u32,
@@ -70,6 +94,60 @@ pub enum RafxQueueType {
     Transfer,
 }
 
+/// The kind of data recorded by the queries in a `RafxQueryPool`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RafxQueryType {
+    /// A single u64 written by `cmd_write_timestamp`. Values are in ticks, use
+    /// `RafxDeviceInfo::timestamp_period_ns` to convert to nanoseconds.
+    Timestamp,
+
+    /// Counters accumulated between `cmd_begin_query` and `cmd_end_query`. Each query produces
+    /// `RafxPipelineStatistics::COUNTER_COUNT` u64 values. See `RafxPipelineStatistics`
+    PipelineStatistics,
+}
+
+impl RafxQueryType {
+    /// The number of u64 values produced when a single query of this type is read back
+    pub fn result_count(self) -> u32 {
+        match self {
+            RafxQueryType::Timestamp => 1,
+            RafxQueryType::PipelineStatistics => RafxPipelineStatistics::COUNTER_COUNT,
+        }
+    }
+}
+
+/// The results of a single `RafxQueryType::PipelineStatistics` query. Values are read back in the
+/// same order as the fields are declared.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RafxPipelineStatistics {
+    pub input_assembly_vertices: u64,
+    pub input_assembly_primitives: u64,
+    pub vertex_shader_invocations: u64,
+    pub clipping_invocations: u64,
+    pub clipping_primitives: u64,
+    pub fragment_shader_invocations: u64,
+    pub compute_shader_invocations: u64,
+}
+
+impl RafxPipelineStatistics {
+    pub const COUNTER_COUNT: u32 = 7;
+
+    /// Reads a single query's results, as produced by `RafxQueryPool::get_results` or
+    /// `cmd_resolve_queries`
+    pub fn from_results(results: &[u64]) -> Self {
+        assert!(results.len() >= Self::COUNTER_COUNT as usize);
+        RafxPipelineStatistics {
+            input_assembly_vertices: results[0],
+            input_assembly_primitives: results[1],
+            vertex_shader_invocations: results[2],
+            clipping_invocations: results[3],
+            clipping_primitives: results[4],
+            fragment_shader_invocations: results[5],
+            compute_shader_invocations: results[6],
+        }
+    }
+}
+
 /// The color space an image data is in. The correct color space often varies between texture types
 /// (like normal maps vs. albedo maps).
 #[derive(Copy, Clone, Debug)]
@@ -355,6 +433,7 @@ pub enum RafxPipelineType {
 
 /// Affects how quickly vertex attributes are consumed from buffers, similar to VkVertexInputRate
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-support", derive(Serialize, Deserialize))]
 pub enum RafxVertexAttributeRate {
     Vertex,
     Instance,
@@ -412,11 +491,21 @@ pub enum RafxPrimitiveTopology {
 
 /// The size of index buffer elements
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde-support", derive(Serialize, Deserialize))]
 pub enum RafxIndexType {
     Uint32,
     Uint16,
 }
 
+impl RafxIndexType {
+    pub fn size_in_bytes(self) -> usize {
+        match self {
+            RafxIndexType::Uint32 => 4,
+            RafxIndexType::Uint16 => 2,
+        }
+    }
+}
+
 impl Default for RafxIndexType {
     fn default() -> Self {
         RafxIndexType::Uint32
@@ -758,6 +847,154 @@ pub struct RafxCmdCopyBufferToTextureParams {
     pub mip_level: u8,
 }
 
+/// An offset into a texture, in texels
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RafxOffset3D {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+// Size of the region being copied out of a texture's mip level
+fn copy_region_extents(
+    texture_def: &RafxTextureDef,
+    mip_level: u8,
+    offset: RafxOffset3D,
+    extents: Option<RafxExtents3D>,
+) -> RafxExtents3D {
+    extents.unwrap_or_else(|| {
+        let mip_extents = texture_def.mip_extents(mip_level);
+        RafxExtents3D {
+            width: mip_extents.width.saturating_sub(offset.x),
+            height: mip_extents.height.saturating_sub(offset.y),
+            depth: mip_extents.depth.saturating_sub(offset.z),
+        }
+    })
+}
+
+/// Parameters for copying a texture to a buffer. The texture must be in the COPY_SRC state.
+#[derive(Default, Clone, Debug)]
+pub struct RafxCmdCopyTextureToBufferParams {
+    pub buffer_offset: u64,
+    /// Bytes from the start of one row to the start of the next in the buffer (for compressed
+    /// formats, one row of blocks). Must be a multiple of the format's block size. If 0, rows are
+    /// tightly packed.
+    pub buffer_row_pitch: u32,
+    /// Bytes from the start of one depth slice to the start of the next in the buffer. If 0,
+    /// slices are tightly packed.
+    pub buffer_slice_pitch: u32,
+    pub array_layer: u16,
+    pub mip_level: u8,
+    /// Offset of the region to copy within the mip level
+    pub src_offset: RafxOffset3D,
+    /// Size of the region to copy. If None, everything from src_offset to the end of the mip level
+    /// is copied
+    pub extents: Option<RafxExtents3D>,
+}
+
+impl RafxCmdCopyTextureToBufferParams {
+    /// The size of the region that will be copied
+    pub fn copy_extents(
+        &self,
+        texture_def: &RafxTextureDef,
+    ) -> RafxExtents3D {
+        copy_region_extents(texture_def, self.mip_level, self.src_offset, self.extents)
+    }
+
+    /// The row pitch that will be used in the buffer, accounting for tightly packed rows
+    pub fn row_pitch(
+        &self,
+        format: RafxFormat,
+        copy_extents: RafxExtents3D,
+    ) -> u32 {
+        if self.buffer_row_pitch != 0 {
+            self.buffer_row_pitch
+        } else {
+            let block_width = format.block_width_in_pixels();
+            let width_in_blocks = (copy_extents.width + block_width - 1) / block_width;
+            width_in_blocks * format.block_or_pixel_size_in_bytes()
+        }
+    }
+
+    /// The slice pitch that will be used in the buffer, accounting for tightly packed slices
+    pub fn slice_pitch(
+        &self,
+        format: RafxFormat,
+        copy_extents: RafxExtents3D,
+    ) -> u32 {
+        if self.buffer_slice_pitch != 0 {
+            self.buffer_slice_pitch
+        } else {
+            let block_height = format.block_height_in_pixels();
+            let height_in_blocks = (copy_extents.height + block_height - 1) / block_height;
+            height_in_blocks * self.row_pitch(format, copy_extents)
+        }
+    }
+}
+
+/// Parameters for copying a region of one texture to another. The source texture must be in the
+/// COPY_SRC state and the destination texture must be in the COPY_DST state. The textures must
+/// have compatible formats.
+#[derive(Default, Clone, Debug)]
+pub struct RafxCmdCopyTextureToTextureParams {
+    pub src_offset: RafxOffset3D,
+    pub dst_offset: RafxOffset3D,
+    /// Size of the region to copy. If None, everything from src_offset to the end of the source
+    /// mip level is copied
+    pub extents: Option<RafxExtents3D>,
+    pub src_mip_level: u8,
+    pub dst_mip_level: u8,
+    pub src_array_layer: u16,
+    pub dst_array_layer: u16,
+}
+
+impl RafxCmdCopyTextureToTextureParams {
+    /// The size of the region that will be copied
+    pub fn copy_extents(
+        &self,
+        src_texture_def: &RafxTextureDef,
+    ) -> RafxExtents3D {
+        copy_region_extents(
+            src_texture_def,
+            self.src_mip_level,
+            self.src_offset,
+            self.extents,
+        )
+    }
+}
+
+/// Arguments for a single draw in the buffer passed to `cmd_draw_indirect`. The layout matches
+/// what the GPU consumes, so these can be written by a compute shader.
+#[repr(C)]
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RafxDrawIndirectCommand {
+    pub vertex_count: u32,
+    pub instance_count: u32,
+    pub first_vertex: u32,
+    pub first_instance: u32,
+}
+
+/// Arguments for a single draw in the buffer passed to `cmd_draw_indexed_indirect`. The layout
+/// matches what the GPU consumes, so these can be written by a compute shader.
+#[repr(C)]
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RafxDrawIndexedIndirectCommand {
+    pub index_count: u32,
+    pub instance_count: u32,
+    pub first_index: u32,
+    pub vertex_offset: i32,
+    pub first_instance: u32,
+}
+
+/// Arguments in the buffer passed to `cmd_dispatch_indirect`
+#[repr(C)]
+#[derive(Default, Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RafxDispatchIndirectCommand {
+    pub group_count_x: u32,
+    pub group_count_y: u32,
+    pub group_count_z: u32,
+}
+
 /// Parameters for blitting one image to another (vulkan backend only)
 pub struct RafxCmdBlitParams {
     pub src_state: RafxResourceState,
@@ -8,7 +8,8 @@ use distill::loader::handle::Handle;
 use rafx_framework::{
     DescriptorSetAllocatorMetrics, DescriptorSetAllocatorProvider, DescriptorSetAllocatorRef,
     DescriptorSetLayoutResource, DescriptorSetWriteSet, DynResourceAllocatorSet,
-    GraphicsPipelineCache, MaterialPass, ResourceArc, SlotNameLookup,
+    GraphicsPipelineCache, ImageViewResource, MaterialPass, ResourceArc, SlotNameLookup,
+    UniformBufferLayoutLookup,
 };
 
 use super::upload::UploadManager;
@@ -23,7 +24,10 @@ use crate::distill_impl::AssetResource;
 use distill::loader::handle::AssetHandle;
 use distill::loader::storage::LoadStatus;
 use fnv::FnvHashMap;
-use rafx_api::{RafxDeviceContext, RafxQueue, RafxResult};
+use rafx_api::{
+    RafxDeviceContext, RafxExtents2D, RafxExtents3D, RafxFormat, RafxQueue, RafxResourceType,
+    RafxResult, RafxSampleCount, RafxTextureDef,
+};
 use rafx_framework::descriptor_sets::{
     DescriptorSetElementKey, DescriptorSetWriteElementBuffer, DescriptorSetWriteElementBufferData,
     DescriptorSetWriteElementImage,
@@ -57,6 +61,10 @@ pub struct AssetManager {
     graphics_queue: RafxQueue,
     transfer_queue: RafxQueue,
 
+    // Persistent images that are drawn by render target views and can be sampled by material
+    // instances via MaterialInstanceSlotAssignment::render_target
+    render_targets: FnvHashMap<String, ResourceArc<ImageViewResource>>,
+
     asset_types: FnvHashMap<TypeId, Box<dyn AssetTypeHandler>>,
     // Extremely rare that we modify asset_registration_order but we need to iterate it while
     // having a mut reference to asset manager. Better to just reallocate the vec every time we
@@ -87,6 +95,8 @@ impl AssetManager {
             graphics_queue: graphics_queue.clone(),
             transfer_queue: transfer_queue.clone(),
 
+            render_targets: Default::default(),
+
             asset_types: Default::default(),
             asset_registration_order: Default::default(),
         }
@@ -257,6 +267,49 @@ impl AssetManager {
             .create_descriptor_set_allocator_provider()
     }
 
+    /// Creates a persistent color image that can be drawn into by a render target view (see
+    /// `ViewportsResource::render_targets` in rafx-renderer) and sampled by material instances
+    /// that reference it by name. This must be called before loading any material instance that
+    /// references the render target.
+    pub fn create_render_target(
+        &mut self,
+        name: &str,
+        extents: RafxExtents2D,
+        format: RafxFormat,
+    ) -> RafxResult<ResourceArc<ImageViewResource>> {
+        if self.render_targets.contains_key(name) {
+            Err(format!("A render target named {} already exists", name))?;
+        }
+
+        let texture = self.device_context.create_texture(&RafxTextureDef {
+            extents: RafxExtents3D {
+                width: extents.width,
+                height: extents.height,
+                depth: 1,
+            },
+            format,
+            sample_count: RafxSampleCount::SampleCount1,
+            resource_type: RafxResourceType::TEXTURE | RafxResourceType::RENDER_TARGET_COLOR,
+            ..Default::default()
+        })?;
+
+        let image = self
+            .resources()
+            .insert_image_with_debug_name(texture, &format!("render target {}", name));
+        let image_view = self.resources().get_or_create_image_view(&image, None)?;
+
+        self.render_targets
+            .insert(name.to_string(), image_view.clone());
+        Ok(image_view)
+    }
+
+    pub fn render_target(
+        &self,
+        name: &str,
+    ) -> Option<&ResourceArc<ImageViewResource>> {
+        self.render_targets.get(name)
+    }
+
     pub(crate) fn material_instance_descriptor_sets_mut(&mut self) -> &mut DescriptorSetAllocator {
         &mut self.material_instance_descriptor_sets
     }
@@ -327,6 +380,7 @@ impl AssetManager {
         &self,
         slot_assignment: &MaterialInstanceSlotAssignment,
         pass_slot_name_lookup: &SlotNameLookup,
+        pass_uniform_buffer_layout_lookup: &UniformBufferLayoutLookup,
         resources: &ResourceLookupSet,
         material_pass_write_set: &mut Vec<DescriptorSetWriteSet>,
     ) -> RafxResult<()> {
@@ -364,6 +418,18 @@ impl AssetManager {
                                 Some(rafx_framework::descriptor_sets::DescriptorSetWriteElementImageValue::Resource(
                                     loaded_image.image_view.clone(),
                                 ));
+                        } else if let Some(render_target) = &slot_assignment.render_target {
+                            let image_view =
+                                self.render_target(render_target).ok_or_else(|| {
+                                    format!(
+                                        "Slot {} references render target {} which has not been created",
+                                        slot_assignment.slot_name, render_target
+                                    )
+                                })?;
+                            write_image.image_view =
+                                Some(rafx_framework::descriptor_sets::DescriptorSetWriteElementImageValue::Resource(
+                                    image_view.clone(),
+                                ));
                         }
                     }
 
@@ -380,10 +446,26 @@ impl AssetManager {
                 if what_to_bind.bind_buffers {
                     let mut write_buffer = DescriptorSetWriteElementBuffer { buffer: None };
 
-                    if let Some(buffer_data) = &slot_assignment.buffer_data {
-                        write_buffer.buffer = Some(DescriptorSetWriteElementBufferData::Data(
-                            buffer_data.clone(),
-                        ));
+                    let mut buffer_data = slot_assignment.buffer_data.clone();
+
+                    // Named members are written on top of the raw buffer data (if any)
+                    if !slot_assignment.buffer_members.is_empty() {
+                        let layout = pass_uniform_buffer_layout_lookup.get(location).ok_or_else(|| {
+                            format!(
+                                "Slot {} has buffer members assigned but is not a uniform buffer with an internal buffer",
+                                slot_assignment.slot_name
+                            )
+                        })?;
+
+                        let buffer_data = buffer_data.get_or_insert_with(Default::default);
+                        for (member_name, value) in &slot_assignment.buffer_members {
+                            layout.write_member(member_name, value, buffer_data)?;
+                        }
+                    }
+
+                    if let Some(buffer_data) = buffer_data {
+                        write_buffer.buffer =
+                            Some(DescriptorSetWriteElementBufferData::Data(buffer_data));
                     }
 
                     write.buffer_info = write_buffer;
@@ -410,6 +492,7 @@ impl AssetManager {
             self.apply_material_instance_slot_assignment(
                 slot,
                 &pass.pass_slot_name_lookup,
+                &pass.pass_uniform_buffer_layout_lookup,
                 resources,
                 &mut pass_descriptor_set_writes,
             )?;
@@ -437,6 +520,7 @@ impl Drop for AssetManager {
         // Drop all descriptors. These bind to raw resources, so we need to drop them before
         // dropping resources
         self.material_instance_descriptor_sets.destroy().unwrap();
+        self.render_targets.clear();
 
         log::info!("Dropping asset manager");
         log::trace!("Asset Manager Metrics:\n{:#?}", self.metrics());
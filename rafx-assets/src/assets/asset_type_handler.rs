@@ -2,6 +2,7 @@ use crate::distill_impl::{AssetResource, ResourceAssetLoader};
 use crate::{AssetLookup, AssetManager, DynAssetLookup, LoadQueues};
 use crossbeam_channel::Sender;
 use distill::loader::storage::AssetLoadOp;
+use distill::loader::AssetUuid;
 use rafx_api::RafxResult;
 use std::any::TypeId;
 use std::marker::PhantomData;
@@ -36,6 +37,15 @@ pub trait DefaultAssetTypeLoadHandler<AssetDataT, AssetT> {
         asset_manager: &mut AssetManager,
         font_asset: AssetDataT,
     ) -> RafxResult<AssetT>;
+
+    /// Called after load() succeeds with the UUID of the asset, for asset types that need to be
+    /// identified across runs
+    fn on_loaded(
+        _asset_manager: &mut AssetManager,
+        _asset_uuid: AssetUuid,
+        _asset: &AssetT,
+    ) {
+    }
 }
 
 pub struct DefaultAssetTypeHandler<AssetDataT, AssetT, LoadHandlerT>
@@ -87,6 +97,9 @@ where
                 request.load_handle
             );
             let loaded_asset = LoadHandlerT::load(asset_manager, request.asset);
+            if let Ok(loaded_asset) = &loaded_asset {
+                LoadHandlerT::on_loaded(asset_manager, request.asset_uuid, loaded_asset);
+            }
             handle_load_result(
                 request.load_op,
                 loaded_asset,
@@ -5,6 +5,7 @@ use crate::{
     AssetManager, DefaultAssetTypeHandler, DefaultAssetTypeLoadHandler, ImageAsset, ShaderAsset,
 };
 use distill::loader::handle::Handle;
+use distill::loader::AssetUuid;
 use fnv::FnvHashMap;
 use rafx_api::{
     RafxBlendState, RafxBlendStateRenderTarget, RafxCompareOp, RafxCullMode, RafxDepthState,
@@ -14,10 +15,11 @@ use rafx_framework::render_features::{RenderPhase, RenderPhaseIndex};
 pub use rafx_framework::DescriptorSetLayoutResource;
 pub use rafx_framework::GraphicsPipelineResource;
 use rafx_framework::{
-    DescriptorSetArc, FixedFunctionState, MaterialPass, MaterialPassResource, MaterialShaderStage,
-    ResourceArc,
+    DescriptorSetArc, FixedFunctionState, MaterialParameterValue, MaterialPass,
+    MaterialPassAssetId, MaterialPassResource, MaterialShaderStage, ResourceArc,
 };
 use rafx_framework::{DescriptorSetWriteSet, SamplerResource};
+use std::collections::BTreeMap;
 use std::hash::Hash;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -206,6 +208,10 @@ pub struct MaterialPassData {
     pub phase: Option<String>,
     pub fixed_function_state: FixedFunctionStateData,
     pub shaders: Vec<GraphicsPipelineShaderStage>,
+    // Selects the shader permutation compiled with these keywords defined. Keywords that a stage's
+    // shader does not declare are ignored for that stage.
+    #[serde(default)]
+    pub keywords: Vec<String>,
 }
 
 impl MaterialPassData {
@@ -230,9 +236,19 @@ impl MaterialPassData {
             );
 
             let shader_asset = asset_manager.latest_asset(&stage.shader_module).unwrap();
-            shader_modules.push(shader_asset.shader_module.clone());
+            let (shader_module, reflection_data) = shader_asset
+                .find_variant(&self.keywords)
+                .map_err(|x| {
+                    let error_message = format!(
+                        "Load Material Failed - Pass '{:?}' could not select a shader permutation: {}",
+                        self.name, x
+                    );
+                    log::error!("{}", error_message);
+                    error_message
+                })?;
+            shader_modules.push(shader_module.clone());
 
-            let reflection_data = shader_asset.reflection_data.get(&stage.entry_name);
+            let reflection_data = reflection_data.get(&stage.entry_name);
             let reflection_data = reflection_data.ok_or_else(|| {
                 let error_message = format!(
                     "Load Material Failed - Pass refers to entry point named {}, but no matching reflection data was found",
@@ -425,11 +441,16 @@ pub struct MaterialInstanceSlotAssignment {
     pub array_index: usize,
 
     pub image: Option<Handle<ImageAsset>>,
+    // Name of an image created with AssetManager::create_render_target. Used if image is None
+    pub render_target: Option<String>,
     pub sampler: Option<RafxSamplerDef>,
 
     // Would be nice to use this, but I don't think it works with Option
     //#[serde(with = "serde_bytes")]
     pub buffer_data: Option<Vec<u8>>,
+    // Values for named members of a uniform buffer, written on top of buffer_data
+    #[serde(default)]
+    pub buffer_members: BTreeMap<String, MaterialParameterValue>,
 }
 
 #[derive(TypeUuid, Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -533,6 +554,25 @@ impl DefaultAssetTypeLoadHandler<MaterialAssetData, MaterialAsset> for MaterialL
             pass_phase_to_index,
         ))
     }
+
+    fn on_loaded(
+        asset_manager: &mut AssetManager,
+        asset_uuid: AssetUuid,
+        asset: &MaterialAsset,
+    ) {
+        // Lets the pipeline manifest refer to the passes by asset, which is stable between runs
+        for (pass_index, pass) in asset.passes.iter().enumerate() {
+            asset_manager
+                .graphics_pipeline_cache()
+                .register_material_pass_asset(
+                    MaterialPassAssetId {
+                        material_asset: asset_uuid.0,
+                        pass_index: pass_index as u32,
+                    },
+                    &pass.material_pass_resource,
+                );
+        }
+    }
 }
 
 pub type MaterialAssetTypeHandler =
@@ -49,8 +49,8 @@ impl Into<basis_universal::BasisTextureFormat> for ImageAssetBasisCompressionTyp
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
 pub struct ImageAssetBasisCompressionSettings {
-    compression_type: ImageAssetBasisCompressionType,
-    quality: u32,
+    pub compression_type: ImageAssetBasisCompressionType,
+    pub quality: u32,
 }
 
 impl ImageAssetBasisCompressionSettings {
@@ -73,6 +73,11 @@ impl ImageAssetBasisCompressionSettings {
 pub enum ImageAssetDataFormat {
     RawRGBA32,
     BasisCompressed,
+    // HDR formats. Data is always linear
+    RawRGBA16Float,
+    RawRGBA32Float,
+    // Unsigned BC6H blocks. Decompressed to RGBA16 float at load time if the device can't sample it
+    Bc6hCompressed,
 }
 
 #[derive(Serialize, Deserialize, Clone, Copy, Debug)]
@@ -81,6 +86,13 @@ pub enum ImageAssetDataFormatConfig {
     BasisCompressed(ImageAssetBasisCompressionSettings),
 }
 
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub enum ImageAssetHdrDataFormatConfig {
+    RawRGBA16Float,
+    RawRGBA32Float,
+    Bc6hCompressed,
+}
+
 #[derive(TypeUuid, Serialize, Deserialize, Clone)]
 #[uuid = "e6166902-8716-401b-9d2e-8b01701c5626"]
 pub struct ImageAssetData {
@@ -206,6 +218,53 @@ impl ImageAssetData {
             }
         }
     }
+
+    // HDR images are always stored in linear color space. raw_rgba_f32 has 4 floats per pixel
+    pub fn from_raw_rgba_f32(
+        width: u32,
+        height: u32,
+        format_config: ImageAssetHdrDataFormatConfig,
+        mip_generation: ImageAssetMipGeneration,
+        resource_type: RafxResourceType,
+        raw_rgba_f32: &[f32],
+    ) -> RafxResult<ImageAssetData> {
+        let generate_mips_at_runtime = match mip_generation {
+            ImageAssetMipGeneration::NoMips => false,
+            ImageAssetMipGeneration::Precomupted => Err(format!(
+                "{:?} cannot store precomputed mipmaps",
+                format_config
+            ))?,
+            // BC6H data will be decompressed at load time so that mips can be generated
+            ImageAssetMipGeneration::Runtime => true,
+        };
+
+        let (format, data) = match format_config {
+            ImageAssetHdrDataFormatConfig::RawRGBA16Float => (
+                ImageAssetDataFormat::RawRGBA16Float,
+                super::hdr::rgba_f32_to_rgba_f16_bytes(raw_rgba_f32),
+            ),
+            ImageAssetHdrDataFormatConfig::RawRGBA32Float => (
+                ImageAssetDataFormat::RawRGBA32Float,
+                super::hdr::rgba_f32_to_bytes(raw_rgba_f32),
+            ),
+            ImageAssetHdrDataFormatConfig::Bc6hCompressed => {
+                log::debug!("Compressing texture");
+                let data = super::hdr::compress_bc6h(width, height, raw_rgba_f32);
+                log::debug!("Compressed texture");
+                (ImageAssetDataFormat::Bc6hCompressed, data)
+            }
+        };
+
+        Ok(ImageAssetData {
+            width,
+            height,
+            color_space: ImageAssetColorSpace::Linear,
+            format,
+            generate_mips_at_runtime,
+            resource_type,
+            data,
+        })
+    }
 }
 
 #[derive(TypeUuid, Clone)]
@@ -0,0 +1,431 @@
+use rafx_api::RafxResult;
+use rafx_base::half::f32_to_f16;
+
+//
+// Helpers for HDR image data. Pixels are linear RGBA floats. Negative and NaN values are not
+// meaningful for color data and are clamped to 0, and values are clamped to the largest finite
+// half float.
+//
+
+const HALF_MAX: f32 = 65504.0;
+const HALF_ONE: u16 = 0x3C00;
+
+fn sanitize_hdr_value(value: f32) -> f32 {
+    if value > 0.0 {
+        value.min(HALF_MAX)
+    } else {
+        // Also catches NaN and negative zero
+        0.0
+    }
+}
+
+pub fn rgba_f32_to_rgba_f16_bytes(pixels: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(pixels.len() * 2);
+    for &value in pixels {
+        bytes.extend_from_slice(&f32_to_f16(sanitize_hdr_value(value)).to_ne_bytes());
+    }
+
+    bytes
+}
+
+pub fn rgba_f32_to_bytes(pixels: &[f32]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(pixels.len() * 4);
+    for &value in pixels {
+        bytes.extend_from_slice(&value.to_ne_bytes());
+    }
+
+    bytes
+}
+
+//
+// BC6H (unsigned). The encoder only produces mode 11 blocks: a single region with two 10-bit
+// endpoints and 4-bit indices. This is simple and fast, but lower quality than an encoder that
+// searches all 14 modes. The decoder only supports what the encoder produces.
+//
+// Endpoints and interpolation operate on values that are linear in the half float bit pattern,
+// which is what makes this format suitable for HDR data.
+//
+const BC6H_BLOCK_SIZE: usize = 16;
+const BC6H_MODE_11: u128 = 0x03;
+const BC6H_ENDPOINT_BITS: u32 = 10;
+const BC6H_WEIGHTS: [i32; 16] = [0, 4, 9, 13, 17, 21, 26, 30, 34, 38, 43, 47, 51, 55, 60, 64];
+
+fn bc6h_unquantize(comp: i32) -> i32 {
+    let max = (1 << BC6H_ENDPOINT_BITS) - 1;
+    if comp == 0 {
+        0
+    } else if comp == max {
+        0xFFFF
+    } else {
+        ((comp << 16) + 0x8000) >> BC6H_ENDPOINT_BITS
+    }
+}
+
+// Inverse of bc6h_finish_unquantize
+fn bc6h_half_to_interpolation_space(half: u16) -> i32 {
+    ((half as i32) * 64 + 15) / 31
+}
+
+fn bc6h_finish_unquantize(value: i32) -> u16 {
+    ((value * 31) >> 6) as u16
+}
+
+fn bc6h_quantize(value: i32) -> i32 {
+    let max = (1 << BC6H_ENDPOINT_BITS) - 1;
+    let comp = ((value << BC6H_ENDPOINT_BITS) >> 16).max(0).min(max);
+
+    // Pick whichever neighbor unquantizes closest to the value
+    let next = (comp + 1).min(max);
+    if (bc6h_unquantize(next) - value).abs() < (bc6h_unquantize(comp) - value).abs() {
+        next
+    } else {
+        comp
+    }
+}
+
+fn bc6h_palette(endpoints: &[[i32; 3]; 2]) -> [[u16; 3]; 16] {
+    let mut palette = [[0; 3]; 16];
+    for (i, weight) in BC6H_WEIGHTS.iter().enumerate() {
+        for channel in 0..3 {
+            let a = bc6h_unquantize(endpoints[0][channel]);
+            let b = bc6h_unquantize(endpoints[1][channel]);
+            let value = (a * (64 - weight) + b * weight + 32) >> 6;
+            palette[i][channel] = bc6h_finish_unquantize(value);
+        }
+    }
+
+    palette
+}
+
+fn bc6h_encode_block(block: &[[u16; 3]; 16]) -> [u8; BC6H_BLOCK_SIZE] {
+    let values: Vec<[i32; 3]> = block
+        .iter()
+        .map(|pixel| {
+            [
+                bc6h_half_to_interpolation_space(pixel[0]),
+                bc6h_half_to_interpolation_space(pixel[1]),
+                bc6h_half_to_interpolation_space(pixel[2]),
+            ]
+        })
+        .collect();
+
+    //
+    // Use the diagonal of the bounding box as the endpoints. The diagonal is chosen by checking
+    // whether each channel increases or decreases along with the channel that has the most range.
+    //
+    let mut min = [i32::MAX; 3];
+    let mut max = [i32::MIN; 3];
+    let mut mean = [0i64; 3];
+    for value in &values {
+        for channel in 0..3 {
+            min[channel] = min[channel].min(value[channel]);
+            max[channel] = max[channel].max(value[channel]);
+            mean[channel] += value[channel] as i64;
+        }
+    }
+
+    for channel_mean in &mut mean {
+        *channel_mean /= values.len() as i64;
+    }
+
+    let main_channel = (0..3).max_by_key(|&c| max[c] - min[c]).unwrap();
+    let mut endpoints = [[0; 3]; 2];
+    for channel in 0..3 {
+        let mut covariance = 0i64;
+        for value in &values {
+            covariance += (value[channel] as i64 - mean[channel])
+                * (value[main_channel] as i64 - mean[main_channel]);
+        }
+
+        let (start, end) = if covariance >= 0 {
+            (min[channel], max[channel])
+        } else {
+            (max[channel], min[channel])
+        };
+
+        endpoints[0][channel] = bc6h_quantize(start);
+        endpoints[1][channel] = bc6h_quantize(end);
+    }
+
+    //
+    // Pick the closest palette entry for each pixel
+    //
+    let palette = bc6h_palette(&endpoints);
+    let mut indices = [0usize; 16];
+    for (pixel_index, pixel) in block.iter().enumerate() {
+        let mut best_error = i64::MAX;
+        for (palette_index, palette_value) in palette.iter().enumerate() {
+            let mut error = 0i64;
+            for channel in 0..3 {
+                let delta = pixel[channel] as i64 - palette_value[channel] as i64;
+                error += delta * delta;
+            }
+
+            if error < best_error {
+                best_error = error;
+                indices[pixel_index] = palette_index;
+            }
+        }
+    }
+
+    // The first index is stored with one less bit, so its high bit must be 0. Swapping the
+    // endpoints and inverting the indices produces the same palette.
+    if indices[0] >= 8 {
+        endpoints.swap(0, 1);
+        for index in &mut indices {
+            *index = 15 - *index;
+        }
+    }
+
+    let mut bits = BC6H_MODE_11;
+    let mut position = 5;
+    for endpoint in &endpoints {
+        for value in endpoint {
+            bits |= (*value as u128) << position;
+            position += BC6H_ENDPOINT_BITS;
+        }
+    }
+
+    for (i, index) in indices.iter().enumerate() {
+        bits |= (*index as u128) << position;
+        position += if i == 0 { 3 } else { 4 };
+    }
+
+    debug_assert_eq!(position, 128);
+    bits.to_le_bytes()
+}
+
+fn bc6h_decode_block(bytes: &[u8]) -> RafxResult<[[u16; 3]; 16]> {
+    let mut block_bytes = [0u8; BC6H_BLOCK_SIZE];
+    block_bytes.copy_from_slice(bytes);
+    let bits = u128::from_le_bytes(block_bytes);
+
+    if bits & 0x1F != BC6H_MODE_11 {
+        Err("Only BC6H blocks that use a single region with 10-bit endpoints can be decoded")?;
+    }
+
+    let read_bits = |position: u32, count: u32| ((bits >> position) & ((1 << count) - 1)) as i32;
+
+    let mut endpoints = [[0; 3]; 2];
+    let mut position = 5;
+    for endpoint in &mut endpoints {
+        for value in endpoint {
+            *value = read_bits(position, BC6H_ENDPOINT_BITS);
+            position += BC6H_ENDPOINT_BITS;
+        }
+    }
+
+    let palette = bc6h_palette(&endpoints);
+    let mut block = [[0; 3]; 16];
+    for (i, pixel) in block.iter_mut().enumerate() {
+        let index_bits = if i == 0 { 3 } else { 4 };
+        *pixel = palette[read_bits(position, index_bits) as usize];
+        position += index_bits;
+    }
+
+    Ok(block)
+}
+
+fn block_count(
+    width: u32,
+    height: u32,
+) -> (u32, u32) {
+    ((width + 3) / 4, (height + 3) / 4)
+}
+
+// Alpha is discarded, BC6H does not store it
+pub fn compress_bc6h(
+    width: u32,
+    height: u32,
+    pixels: &[f32],
+) -> Vec<u8> {
+    let (blocks_x, blocks_y) = block_count(width, height);
+    let mut compressed = Vec::with_capacity((blocks_x * blocks_y) as usize * BC6H_BLOCK_SIZE);
+
+    for block_y in 0..blocks_y {
+        for block_x in 0..blocks_x {
+            let mut block = [[0; 3]; 16];
+            for (i, pixel) in block.iter_mut().enumerate() {
+                // Blocks on the right/bottom edges repeat the last row/column
+                let x = (block_x * 4 + (i as u32 % 4)).min(width - 1);
+                let y = (block_y * 4 + (i as u32 / 4)).min(height - 1);
+                let offset = ((y * width + x) * 4) as usize;
+                for channel in 0..3 {
+                    pixel[channel] = f32_to_f16(sanitize_hdr_value(pixels[offset + channel]));
+                }
+            }
+
+            compressed.extend_from_slice(&bc6h_encode_block(&block));
+        }
+    }
+
+    compressed
+}
+
+// Used when BC6H data is loaded on a device that can't sample it. Produces R16G16B16A16_SFLOAT data
+pub fn decompress_bc6h_to_rgba_f16_bytes(
+    width: u32,
+    height: u32,
+    compressed: &[u8],
+) -> RafxResult<Vec<u8>> {
+    let (blocks_x, blocks_y) = block_count(width, height);
+    if compressed.len() != (blocks_x * blocks_y) as usize * BC6H_BLOCK_SIZE {
+        Err(format!(
+            "BC6H data for a {}x{} image should be {} bytes but is {} bytes",
+            width,
+            height,
+            (blocks_x * blocks_y) as usize * BC6H_BLOCK_SIZE,
+            compressed.len()
+        ))?;
+    }
+
+    let mut bytes = vec![0u8; (width * height) as usize * 8];
+    for (block_index, block_bytes) in compressed.chunks_exact(BC6H_BLOCK_SIZE).enumerate() {
+        let block_x = block_index as u32 % blocks_x;
+        let block_y = block_index as u32 / blocks_x;
+        let block = bc6h_decode_block(block_bytes)?;
+
+        for (i, pixel) in block.iter().enumerate() {
+            let x = block_x * 4 + (i as u32 % 4);
+            let y = block_y * 4 + (i as u32 / 4);
+            if x >= width || y >= height {
+                continue;
+            }
+
+            let offset = ((y * width + x) * 8) as usize;
+            let rgba = [pixel[0], pixel[1], pixel[2], HALF_ONE];
+            for (channel, value) in rgba.iter().enumerate() {
+                bytes[offset + channel * 2..offset + channel * 2 + 2]
+                    .copy_from_slice(&value.to_ne_bytes());
+            }
+        }
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rafx_base::half::f16_to_f32;
+
+    fn decode_f16_bytes(bytes: &[u8]) -> Vec<f32> {
+        bytes
+            .chunks_exact(2)
+            .map(|x| f16_to_f32(u16::from_ne_bytes([x[0], x[1]])))
+            .collect()
+    }
+
+    #[test]
+    fn test_sanitize_hdr_value() {
+        assert_eq!(sanitize_hdr_value(1.5), 1.5);
+        assert_eq!(sanitize_hdr_value(HALF_MAX), HALF_MAX);
+        assert_eq!(sanitize_hdr_value(1.0e6), HALF_MAX);
+        assert_eq!(sanitize_hdr_value(f32::INFINITY), HALF_MAX);
+        assert_eq!(sanitize_hdr_value(f32::NAN), 0.0);
+        assert_eq!(sanitize_hdr_value(f32::NEG_INFINITY), 0.0);
+        assert_eq!(sanitize_hdr_value(-1.0), 0.0);
+
+        // Negative zero would have the sign bit set in the half, which unsigned BC6H can't store
+        assert!(sanitize_hdr_value(-0.0).is_sign_positive());
+    }
+
+    #[test]
+    fn test_rgba_f32_to_rgba_f16_bytes() {
+        let pixels = [
+            1.0,
+            f32::INFINITY,
+            f32::NAN,
+            -2.0,
+            1.0e6,
+            1.0e-6,
+            1.0e-9,
+            -0.0,
+        ];
+        let bytes = rgba_f32_to_rgba_f16_bytes(&pixels);
+        let halfs: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|x| u16::from_ne_bytes([x[0], x[1]]))
+            .collect();
+
+        // 1e-6 is a subnormal half, 1e-9 is too small for a half and flushes to zero
+        assert_eq!(
+            halfs,
+            [0x3c00, 0x7bff, 0x0000, 0x0000, 0x7bff, 0x0011, 0x0000, 0x0000]
+        );
+    }
+
+    #[test]
+    fn test_bc6h_round_trip() {
+        // Odd dimensions to cover the partial blocks on the right and bottom edges. A block only
+        // stores colors along a single line, so the channels vary together. The right blocks are
+        // much brighter to check that the range of a half float is preserved.
+        let width = 7;
+        let height = 6;
+        let mut pixels = Vec::with_capacity((width * height * 4) as usize);
+        for y in 0..height {
+            for x in 0..width {
+                let exposure = if x < 4 { 1.0 } else { 1000.0 };
+                let intensity = exposure * (1.0 + 0.05 * (x + y) as f32);
+                pixels.extend_from_slice(&[intensity, intensity * 0.5, intensity * 2.0, 0.5]);
+            }
+        }
+
+        let compressed = compress_bc6h(width, height, &pixels);
+        assert_eq!(compressed.len(), 2 * 2 * BC6H_BLOCK_SIZE);
+
+        let decompressed = decode_f16_bytes(
+            &decompress_bc6h_to_rgba_f16_bytes(width, height, &compressed).unwrap(),
+        );
+        assert_eq!(decompressed.len(), pixels.len());
+
+        for (original, decoded) in pixels.chunks_exact(4).zip(decompressed.chunks_exact(4)) {
+            for channel in 0..3 {
+                let tolerance = original[channel] * 0.03;
+                assert!(
+                    (original[channel] - decoded[channel]).abs() <= tolerance,
+                    "expected {:?} got {:?}",
+                    original,
+                    decoded
+                );
+            }
+
+            // Alpha isn't stored
+            assert_eq!(decoded[3], 1.0);
+        }
+    }
+
+    #[test]
+    fn test_bc6h_first_index_high_bit_swap() {
+        // The brightest pixel comes first, so without swapping its index would need the high bit
+        let mut block = [[f32_to_f16(0.5); 3]; 16];
+        block[0] = [f32_to_f16(8.0); 3];
+        block[15] = [f32_to_f16(0.0); 3];
+
+        let encoded = bc6h_encode_block(&block);
+        let bits = u128::from_le_bytes(encoded);
+
+        // The first index follows the 60 bits of endpoints and is stored with 3 bits
+        assert_eq!((bits >> 65) & 0x7, 0);
+
+        // The endpoints were swapped, so the first endpoint is the bright one
+        let first_endpoint_red = ((bits >> 5) & 0x3ff) as i32;
+        let second_endpoint_red = ((bits >> 35) & 0x3ff) as i32;
+        assert!(first_endpoint_red > second_endpoint_red);
+
+        let decoded = bc6h_decode_block(&encoded).unwrap();
+        for (bright, dark) in decoded[0].iter().zip(&decoded[15]) {
+            assert!((f16_to_f32(*bright) - 8.0).abs() < 0.1);
+            assert!(f16_to_f32(*dark) < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_bc6h_rejects_unsupported_modes() {
+        let mut encoded = bc6h_encode_block(&[[f32_to_f16(1.0); 3]; 16]);
+        encoded[0] = (encoded[0] & !0x1f) | 0x07;
+        assert!(bc6h_decode_block(&encoded).is_err());
+
+        assert!(decompress_bc6h_to_rgba_f16_bytes(4, 4, &[0; BC6H_BLOCK_SIZE - 1]).is_err());
+    }
+}
@@ -1,14 +1,103 @@
-use crate::assets::image::{ImageAssetColorSpace, ImageAssetData};
+use crate::assets::image::{
+    ImageAssetColorSpace, ImageAssetData, ImageAssetDataFormatConfig,
+    ImageAssetHdrDataFormatConfig, ImageAssetMipGeneration,
+};
 use crate::ImageAssetDataFormat;
 use basis_universal::BasisTextureType;
 use distill::importer::{Error, ImportedAsset, Importer, ImporterValue};
 use distill::{core::AssetUuid, importer::ImportOp};
-use image2::Image;
 use rafx_api::RafxResourceType;
 use serde::{Deserialize, Serialize};
 use std::io::Read;
 use type_uuid::*;
 
+// Stored in the image's .meta file. Changing these causes the image to be reimported.
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "f9816af7-805b-49ca-8441-2b9587ff9536"]
+#[serde(default)]
+pub struct ImageImporterOptions {
+    // Use Linear for data that isn't color, like normal maps or roughness/metalness maps
+    pub color_space: ImageAssetColorSpace,
+    pub format: ImageAssetDataFormatConfig,
+    pub mip_generation: ImageAssetMipGeneration,
+    // If set, images with a width or height larger than this are downscaled to fit, preserving
+    // aspect ratio
+    pub max_resolution: Option<u32>,
+    pub premultiply_alpha: bool,
+}
+
+impl Default for ImageImporterOptions {
+    fn default() -> Self {
+        let (format, mip_generation) = ImageAssetData::default_format_and_mip_generation();
+        ImageImporterOptions {
+            color_space: ImageAssetColorSpace::Srgb,
+            format,
+            mip_generation,
+            max_resolution: None,
+            premultiply_alpha: false,
+        }
+    }
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+fn premultiply_alpha(
+    image: &mut image::RgbaImage,
+    color_space: ImageAssetColorSpace,
+) {
+    for pixel in image.pixels_mut() {
+        let alpha = pixel[3] as f32 / 255.0;
+        for channel in &mut pixel.0[0..3] {
+            let value = *channel as f32 / 255.0;
+            // Blending happens in linear space, so sRGB values must be converted before multiplying
+            let premultiplied = match color_space {
+                ImageAssetColorSpace::Srgb => linear_to_srgb(srgb_to_linear(value) * alpha),
+                ImageAssetColorSpace::Linear => value * alpha,
+            };
+            *channel = (premultiplied * 255.0).round() as u8;
+        }
+    }
+}
+
+fn downscale_to_max_resolution<PixelT: image::Pixel + 'static>(
+    image: image::ImageBuffer<PixelT, Vec<PixelT::Subpixel>>,
+    max_resolution: u32,
+    filter: image::imageops::FilterType,
+) -> image::ImageBuffer<PixelT, Vec<PixelT::Subpixel>> {
+    let (width, height) = image.dimensions();
+    let max_resolution = max_resolution.max(1);
+    if width <= max_resolution && height <= max_resolution {
+        return image;
+    }
+
+    let scale = max_resolution as f32 / width.max(height) as f32;
+    let new_width = ((width as f32 * scale).round() as u32).max(1);
+    let new_height = ((height as f32 * scale).round() as u32).max(1);
+    log::debug!(
+        "Downscale image from {}x{} to {}x{}",
+        width,
+        height,
+        new_width,
+        new_height
+    );
+
+    image::imageops::resize(&image, new_width, new_height, filter)
+}
+
 #[derive(TypeUuid, Serialize, Deserialize, Default)]
 #[uuid = "23f90369-6916-4548-81d0-a76e0b162df2"]
 pub struct ImageImporterState(Option<AssetUuid>);
@@ -21,14 +110,14 @@ impl Importer for ImageImporter {
     where
         Self: Sized,
     {
-        2
+        3
     }
 
     fn version(&self) -> u32 {
         Self::version_static()
     }
 
-    type Options = ();
+    type Options = ImageImporterOptions;
 
     type State = ImageImporterState;
 
@@ -38,7 +127,7 @@ impl Importer for ImageImporter {
         &self,
         _op: &mut ImportOp,
         source: &mut dyn Read,
-        _options: &Self::Options,
+        options: &Self::Options,
         state: &mut Self::State,
     ) -> distill::importer::Result<ImporterValue> {
         let id = state
@@ -48,20 +137,33 @@ impl Importer for ImageImporter {
         let mut bytes = Vec::new();
         source.read_to_end(&mut bytes)?;
 
-        let decoded_image = image2::io::decode::<_, _, image2::Rgba>(&bytes)
-            .map_err(|e| Error::Boxed(Box::new(e)))?;
+        let mut decoded_image = image::load_from_memory(&bytes)
+            .map_err(|e| Error::Boxed(Box::new(e)))?
+            .to_rgba8();
 
-        let (format, mip_generation) = ImageAssetData::default_format_and_mip_generation();
+        if options.premultiply_alpha {
+            premultiply_alpha(&mut decoded_image, options.color_space);
+        }
+
+        if let Some(max_resolution) = options.max_resolution {
+            decoded_image = downscale_to_max_resolution(
+                decoded_image,
+                max_resolution,
+                image::imageops::FilterType::Lanczos3,
+            );
+        }
+
+        let (width, height) = decoded_image.dimensions();
         let asset_data = ImageAssetData::from_raw_rgba32(
-            decoded_image.width() as u32,
-            decoded_image.height() as u32,
-            ImageAssetColorSpace::Srgb,
-            format,
-            mip_generation,
+            width,
+            height,
+            options.color_space,
+            options.format,
+            options.mip_generation,
             RafxResourceType::TEXTURE,
-            decoded_image.data(),
+            &decoded_image.into_raw(),
         )
-        .unwrap();
+        .map_err(|e| Error::Boxed(Box::new(e)))?;
 
         Ok(ImporterValue {
             assets: vec![ImportedAsset {
@@ -148,3 +250,148 @@ impl Importer for BasisImageImporter {
         })
     }
 }
+
+// Stored in the image's .meta file. Changing these causes the image to be reimported.
+#[derive(TypeUuid, Serialize, Deserialize, Clone, Debug)]
+#[uuid = "ec923923-dc84-41b1-b233-00d8e468c516"]
+#[serde(default)]
+pub struct HdrImageImporterOptions {
+    pub format: ImageAssetHdrDataFormatConfig,
+    pub mip_generation: ImageAssetMipGeneration,
+    // If set, images with a width or height larger than this are downscaled to fit, preserving
+    // aspect ratio
+    pub max_resolution: Option<u32>,
+}
+
+impl Default for HdrImageImporterOptions {
+    fn default() -> Self {
+        HdrImageImporterOptions {
+            format: ImageAssetHdrDataFormatConfig::RawRGBA16Float,
+            mip_generation: ImageAssetMipGeneration::Runtime,
+            max_resolution: None,
+        }
+    }
+}
+
+type HdrImage = image::ImageBuffer<image::Rgba<f32>, Vec<f32>>;
+
+const EXR_MAGIC_NUMBER: [u8; 4] = [0x76, 0x2f, 0x31, 0x01];
+
+fn decode_exr(bytes: &[u8]) -> distill::importer::Result<HdrImage> {
+    use exr::prelude::{ReadChannels, ReadLayers};
+
+    let image = exr::image::read::read()
+        .no_deep_data()
+        .largest_resolution_level()
+        .rgba_channels(
+            |resolution, _| HdrImage::new(resolution.width() as u32, resolution.height() as u32),
+            |pixels: &mut HdrImage, position, (r, g, b, a): (f32, f32, f32, f32)| {
+                pixels.put_pixel(
+                    position.x() as u32,
+                    position.y() as u32,
+                    image::Rgba([r, g, b, a]),
+                )
+            },
+        )
+        .first_valid_layer()
+        .all_attributes()
+        .from_buffered(std::io::Cursor::new(bytes))
+        .map_err(|e| Error::Boxed(Box::new(e)))?;
+
+    Ok(image.layer_data.channel_data.pixels)
+}
+
+fn decode_radiance_hdr(bytes: &[u8]) -> distill::importer::Result<HdrImage> {
+    let decoder = image::codecs::hdr::HdrDecoder::new(std::io::Cursor::new(bytes))
+        .map_err(|e| Error::Boxed(Box::new(e)))?;
+    let metadata = decoder.metadata();
+    let pixels = decoder
+        .read_image_hdr()
+        .map_err(|e| Error::Boxed(Box::new(e)))?;
+
+    let mut rgba = Vec::with_capacity(pixels.len() * 4);
+    for pixel in pixels {
+        rgba.extend_from_slice(&[pixel[0], pixel[1], pixel[2], 1.0]);
+    }
+
+    Ok(HdrImage::from_raw(metadata.width, metadata.height, rgba).unwrap())
+}
+
+#[derive(TypeUuid, Serialize, Deserialize, Default)]
+#[uuid = "58f5017d-07d9-47e6-a8b3-e5608f768c0e"]
+pub struct HdrImageImporterState(Option<AssetUuid>);
+
+// Imports OpenEXR (.exr) and Radiance (.hdr) images, keeping the full float range
+#[derive(TypeUuid)]
+#[uuid = "74972f2a-ee88-4cb1-b57c-9c68d426128d"]
+pub struct HdrImageImporter;
+impl Importer for HdrImageImporter {
+    fn version_static() -> u32
+    where
+        Self: Sized,
+    {
+        1
+    }
+
+    fn version(&self) -> u32 {
+        Self::version_static()
+    }
+
+    type Options = HdrImageImporterOptions;
+
+    type State = HdrImageImporterState;
+
+    /// Reads the given bytes and produces assets.
+    #[profiling::function]
+    fn import(
+        &self,
+        _op: &mut ImportOp,
+        source: &mut dyn Read,
+        options: &Self::Options,
+        state: &mut Self::State,
+    ) -> distill::importer::Result<ImporterValue> {
+        let id = state
+            .0
+            .unwrap_or_else(|| AssetUuid(*uuid::Uuid::new_v4().as_bytes()));
+        *state = HdrImageImporterState(Some(id));
+        let mut bytes = Vec::new();
+        source.read_to_end(&mut bytes)?;
+
+        let mut decoded_image = if bytes.starts_with(&EXR_MAGIC_NUMBER) {
+            decode_exr(&bytes)?
+        } else {
+            decode_radiance_hdr(&bytes)?
+        };
+
+        if let Some(max_resolution) = options.max_resolution {
+            // Lanczos can ring around very bright pixels (like the sun in an environment map)
+            decoded_image = downscale_to_max_resolution(
+                decoded_image,
+                max_resolution,
+                image::imageops::FilterType::Triangle,
+            );
+        }
+
+        let (width, height) = decoded_image.dimensions();
+        let asset_data = ImageAssetData::from_raw_rgba_f32(
+            width,
+            height,
+            options.format,
+            options.mip_generation,
+            RafxResourceType::TEXTURE,
+            &decoded_image.into_raw(),
+        )
+        .map_err(|e| Error::Boxed(Box::new(e)))?;
+
+        Ok(ImporterValue {
+            assets: vec![ImportedAsset {
+                id,
+                search_tags: vec![],
+                build_deps: vec![],
+                load_deps: vec![],
+                build_pipeline: None,
+                asset_data: Box::new(asset_data),
+            }],
+        })
+    }
+}
@@ -1,5 +1,7 @@
 pub mod assets;
 pub use assets::*;
 
+pub(crate) mod hdr;
+
 mod importer;
 pub use importer::*;
@@ -2,7 +2,7 @@ use crate::resource_loader::ResourceLoadResult;
 use crate::ResourceLoader;
 use crossbeam_channel::{Receiver, Sender};
 use distill::loader::storage::AssetLoadOp;
-use distill::loader::LoadHandle;
+use distill::loader::{AssetUuid, LoadHandle};
 use std::marker::PhantomData;
 use type_uuid::TypeUuid;
 
@@ -11,6 +11,7 @@ use type_uuid::TypeUuid;
 //
 pub struct LoadRequest<AssetDataT, AssetT> {
     pub load_handle: LoadHandle,
+    pub asset_uuid: AssetUuid,
     pub load_op: AssetLoadOp,
     pub result_tx: Sender<AssetT>,
     pub asset: AssetDataT,
@@ -120,6 +121,7 @@ where
     fn update_asset(
         &mut self,
         load_handle: LoadHandle,
+        asset_uuid: AssetUuid,
         load_op: AssetLoadOp,
         asset: AssetDataT,
     ) -> ResourceLoadResult<AssetT> {
@@ -133,6 +135,7 @@ where
 
         let request = LoadRequest {
             load_handle,
+            asset_uuid,
             load_op,
             result_tx,
             asset,
@@ -2,21 +2,25 @@ use rafx_framework::ResourceArc;
 use rafx_framework::{DescriptorSetAllocator, RafxResult};
 use rafx_framework::{DescriptorSetBindings, ImageViewResource};
 use rafx_framework::{DynDescriptorSet, SlotNameLookup};
+use rafx_framework::{MaterialParameterValue, UniformBufferLayoutLookup};
 use std::sync::Arc;
 
 pub struct DynPassMaterialInstance {
     descriptor_sets: Vec<DynDescriptorSet>,
     slot_name_lookup: Arc<SlotNameLookup>,
+    uniform_buffer_layout_lookup: Arc<UniformBufferLayoutLookup>,
 }
 
 impl DynPassMaterialInstance {
     pub fn new(
         descriptor_sets: Vec<DynDescriptorSet>,
         slot_name_lookup: Arc<SlotNameLookup>,
+        uniform_buffer_layout_lookup: Arc<UniformBufferLayoutLookup>,
     ) -> Self {
         DynPassMaterialInstance {
             descriptor_sets,
             slot_name_lookup,
+            uniform_buffer_layout_lookup,
         }
     }
 
@@ -72,6 +76,43 @@ impl DynPassMaterialInstance {
             }
         }
     }
+
+    // Sets a member of a uniform buffer by name, i.e. set_parameter("per_material_data",
+    // "base_color_factor", &[1.0, 0.0, 0.0, 1.0].into())
+    pub fn set_parameter(
+        &mut self,
+        slot_name: &str,
+        member_name: &str,
+        value: &MaterialParameterValue,
+    ) -> RafxResult<()> {
+        if let Some(slot_locations) = self.slot_name_lookup.get(slot_name) {
+            for slot_location in slot_locations {
+                let layout = self
+                    .uniform_buffer_layout_lookup
+                    .get(slot_location)
+                    .ok_or_else(|| {
+                        format!(
+                            "Slot {} is not a uniform buffer with an internal buffer",
+                            slot_name
+                        )
+                    })?;
+
+                if let Some(dyn_descriptor_set) = self
+                    .descriptor_sets
+                    .get_mut(slot_location.layout_index as usize)
+                {
+                    dyn_descriptor_set.set_buffer_member(
+                        slot_location.binding_index,
+                        layout,
+                        member_name,
+                        value,
+                    )?;
+                }
+            }
+        }
+
+        Ok(())
+    }
 }
 
 pub struct DynMaterialInstance {
@@ -121,4 +162,17 @@ impl DynMaterialInstance {
             pass.set_buffer_data(slot_name, data)
         }
     }
+
+    pub fn set_parameter(
+        &mut self,
+        slot_name: &str,
+        member_name: &str,
+        value: &MaterialParameterValue,
+    ) -> RafxResult<()> {
+        for pass in &mut self.passes {
+            pass.set_parameter(slot_name, member_name, value)?;
+        }
+
+        Ok(())
+    }
 }
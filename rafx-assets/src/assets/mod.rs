@@ -1,17 +1,23 @@
 mod image;
 pub use self::image::BasisImageImporter;
+pub use self::image::HdrImageImporter;
+pub use self::image::HdrImageImporterOptions;
 pub use self::image::ImageAsset;
 pub use self::image::ImageAssetBasisCompressionSettings;
 pub use self::image::ImageAssetBasisCompressionType;
 pub use self::image::ImageAssetColorSpace;
 pub use self::image::ImageAssetData;
 pub use self::image::ImageAssetDataFormat;
+pub use self::image::ImageAssetDataFormatConfig;
+pub use self::image::ImageAssetHdrDataFormatConfig;
 pub use self::image::ImageAssetMipGeneration;
 pub use self::image::ImageImporter;
+pub use self::image::ImageImporterOptions;
 
 mod shader;
 pub use shader::ShaderAsset;
 pub use shader::ShaderAssetData;
+pub use shader::ShaderAssetVariant;
 pub use shader::ShaderImporterCooked;
 pub use shader::ShaderImporterSpv;
 
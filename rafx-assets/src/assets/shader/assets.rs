@@ -2,7 +2,9 @@ use crate::{AssetManager, DefaultAssetTypeHandler, DefaultAssetTypeLoadHandler};
 use fnv::FnvHashMap;
 use rafx_api::{RafxResult, RafxShaderPackage};
 use rafx_framework::ResourceArc;
-use rafx_framework::{ReflectedEntryPoint, ShaderModuleHash, ShaderModuleResource};
+use rafx_framework::{
+    CookedShaderVariant, ReflectedEntryPoint, ShaderModuleHash, ShaderModuleResource,
+};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use type_uuid::*;
@@ -13,6 +15,9 @@ pub struct ShaderAssetData {
     pub shader_module_hash: ShaderModuleHash,
     pub shader_package: RafxShaderPackage,
     pub reflection_data: Option<Vec<ReflectedEntryPoint>>,
+    // Keywords the shader declares, and the permutations compiled with some of them defined
+    pub keywords: Vec<String>,
+    pub variants: Vec<CookedShaderVariant>,
 }
 
 //
@@ -24,8 +29,69 @@ pub struct ShaderAssetData {
 #[derive(TypeUuid, Clone)]
 #[uuid = "b6958faa-5769-4048-a507-f91a07f49af4"]
 pub struct ShaderAsset {
+    // The permutation with no keywords defined
     pub shader_module: ResourceArc<ShaderModuleResource>,
     pub reflection_data: Arc<FnvHashMap<String, ReflectedEntryPoint>>,
+    pub keywords: Arc<Vec<String>>,
+    // Keyed by sorted keyword list
+    pub variants: Arc<FnvHashMap<Vec<String>, ShaderAssetVariant>>,
+}
+
+// A permutation of a shader compiled with some of its keywords defined
+#[derive(Clone)]
+pub struct ShaderAssetVariant {
+    pub shader_module: ResourceArc<ShaderModuleResource>,
+    pub reflection_data: Arc<FnvHashMap<String, ReflectedEntryPoint>>,
+}
+
+impl ShaderAsset {
+    // Returns the shader module and reflection data compiled with the given keywords defined.
+    // Keywords that this shader does not declare are ignored. This allows a material pass to use a
+    // single keyword set for all of its stages.
+    pub fn find_variant(
+        &self,
+        keywords: &[String],
+    ) -> RafxResult<(
+        &ResourceArc<ShaderModuleResource>,
+        &Arc<FnvHashMap<String, ReflectedEntryPoint>>,
+    )> {
+        let mut used_keywords: Vec<String> = keywords
+            .iter()
+            .filter(|x| self.keywords.contains(x))
+            .cloned()
+            .collect();
+        used_keywords.sort();
+        used_keywords.dedup();
+
+        if used_keywords.is_empty() {
+            return Ok((&self.shader_module, &self.reflection_data));
+        }
+
+        let variant = self.variants.get(&used_keywords).ok_or_else(|| {
+            format!(
+                "Shader was not compiled with keywords {:?}, compiled permutations are {:?}",
+                used_keywords,
+                self.variants.keys().collect::<Vec<_>>()
+            )
+        })?;
+
+        Ok((&variant.shader_module, &variant.reflection_data))
+    }
+}
+
+fn create_reflection_data_lookup(
+    entry_points: &[ReflectedEntryPoint]
+) -> FnvHashMap<String, ReflectedEntryPoint> {
+    let mut reflection_data_lookup = FnvHashMap::default();
+    for entry_point in entry_points {
+        let old = reflection_data_lookup.insert(
+            entry_point.rafx_api_reflection.entry_point_name.clone(),
+            entry_point.clone(),
+        );
+        assert!(old.is_none());
+    }
+
+    reflection_data_lookup
 }
 
 pub struct ShaderLoadHandler;
@@ -36,25 +102,37 @@ impl DefaultAssetTypeLoadHandler<ShaderAssetData, ShaderAsset> for ShaderLoadHan
         asset_manager: &mut AssetManager,
         asset_data: ShaderAssetData,
     ) -> RafxResult<ShaderAsset> {
-        let mut reflection_data_lookup = FnvHashMap::default();
-        if let Some(reflection_data) = &asset_data.reflection_data {
-            for entry_point in reflection_data {
-                let old = reflection_data_lookup.insert(
-                    entry_point.rafx_api_reflection.entry_point_name.clone(),
-                    entry_point.clone(),
-                );
-                assert!(old.is_none());
-            }
-        }
+        let reflection_data_lookup = asset_data
+            .reflection_data
+            .as_ref()
+            .map(|x| create_reflection_data_lookup(x))
+            .unwrap_or_default();
 
         let shader_module = asset_manager.resources().get_or_create_shader_module(
             &asset_data.shader_package,
             Some(asset_data.shader_module_hash),
         )?;
 
+        let mut variants = FnvHashMap::default();
+        for variant in &asset_data.variants {
+            let shader_module = asset_manager
+                .resources()
+                .get_or_create_shader_module(&variant.shader_package, Some(variant.hash))?;
+
+            variants.insert(
+                variant.keywords.clone(),
+                ShaderAssetVariant {
+                    shader_module,
+                    reflection_data: Arc::new(create_reflection_data_lookup(&variant.entry_points)),
+                },
+            );
+        }
+
         Ok(ShaderAsset {
             shader_module,
             reflection_data: Arc::new(reflection_data_lookup),
+            keywords: Arc::new(asset_data.keywords),
+            variants: Arc::new(variants),
         })
     }
 }
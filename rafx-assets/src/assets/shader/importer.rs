@@ -32,7 +32,7 @@ impl Importer for ShaderImporterSpv {
     where
         Self: Sized,
     {
-        5
+        6
     }
 
     fn version(&self) -> u32 {
@@ -81,6 +81,8 @@ impl Importer for ShaderImporterSpv {
             shader_module_hash,
             shader_package,
             reflection_data: None,
+            keywords: Vec::default(),
+            variants: Vec::default(),
         };
 
         Ok(ImporterValue {
@@ -108,7 +110,7 @@ impl Importer for ShaderImporterCooked {
     where
         Self: Sized,
     {
-        5
+        6
     }
 
     fn version(&self) -> u32 {
@@ -152,6 +154,8 @@ impl Importer for ShaderImporterCooked {
             shader_module_hash: cooked_shader.hash,
             shader_package: cooked_shader.shader_package,
             reflection_data: Some(cooked_shader.entry_points),
+            keywords: cooked_shader.keywords,
+            variants: cooked_shader.variants,
         };
 
         Ok(ImporterValue {
@@ -12,8 +12,8 @@ use basis_universal::{TranscodeParameters, TranscoderTextureFormat};
 use crossbeam_channel::{Receiver, Sender};
 use distill::loader::{storage::AssetLoadOp, LoadHandle};
 use rafx_api::{
-    extra::upload::*, RafxBuffer, RafxDeviceContext, RafxError, RafxQueue, RafxResourceType,
-    RafxResult, RafxTexture,
+    extra::upload::*, RafxBuffer, RafxDeviceContext, RafxError, RafxFormat, RafxQueue,
+    RafxResourceType, RafxResult, RafxTexture,
 };
 
 //
@@ -617,6 +617,7 @@ pub struct UploadManager {
 
     pub astc4x4_supported: bool,
     pub bc7_supported: bool,
+    pub bc6h_supported: bool,
 }
 
 impl UploadManager {
@@ -629,6 +630,11 @@ impl UploadManager {
         let (image_upload_result_tx, image_upload_result_rx) = crossbeam_channel::unbounded();
         let (buffer_upload_result_tx, buffer_upload_result_rx) = crossbeam_channel::unbounded();
 
+        // If BC6H can't be sampled, Bc6hCompressed images are decompressed to RGBA16F on load
+        let bc6h_supported = device_context
+            .find_supported_format(&[RafxFormat::BC6H_UFLOAT_BLOCK], RafxResourceType::TEXTURE)
+            .is_some();
+
         UploadManager {
             upload_queue: UploadQueue::new(
                 device_context,
@@ -642,6 +648,7 @@ impl UploadManager {
             buffer_upload_result_tx,
             astc4x4_supported: false,
             bc7_supported: true,
+            bc6h_supported,
         }
     }
 
@@ -753,6 +760,42 @@ impl UploadManager {
 
                 GpuImageData::new(layers, rafx_format)
             }
+            ImageAssetDataFormat::RawRGBA16Float => GpuImageData::new_simple(
+                request.asset.width,
+                request.asset.height,
+                RafxFormat::R16G16B16A16_SFLOAT,
+                request.asset.data,
+            ),
+            ImageAssetDataFormat::RawRGBA32Float => GpuImageData::new_simple(
+                request.asset.width,
+                request.asset.height,
+                RafxFormat::R32G32B32A32_SFLOAT,
+                request.asset.data,
+            ),
+            ImageAssetDataFormat::Bc6hCompressed => {
+                if self.bc6h_supported && !generate_mips {
+                    GpuImageData::new_simple(
+                        request.asset.width,
+                        request.asset.height,
+                        RafxFormat::BC6H_UFLOAT_BLOCK,
+                        request.asset.data,
+                    )
+                } else {
+                    // We can't do runtime mip generation with compresed formats, fall back to uncompressed data
+                    let data = crate::assets::image::hdr::decompress_bc6h_to_rgba_f16_bytes(
+                        request.asset.width,
+                        request.asset.height,
+                        &request.asset.data,
+                    )?;
+
+                    GpuImageData::new_simple(
+                        request.asset.width,
+                        request.asset.height,
+                        RafxFormat::R16G16B16A16_SFLOAT,
+                        data,
+                    )
+                }
+            }
         };
         let t1 = std::time::Instant::now();
 
@@ -22,4 +22,6 @@ pub fn default_daemon() -> distill::daemon::AssetDaemon {
         .with_importer("tga", ImageImporter)
         .with_importer("bmp", ImageImporter)
         .with_importer("basis", BasisImageImporter)
+        .with_importer("hdr", HdrImageImporter)
+        .with_importer("exr", HdrImageImporter)
 }
@@ -1,4 +1,4 @@
-use distill::loader::{storage::AssetLoadOp, LoadHandle};
+use distill::loader::{storage::AssetLoadOp, AssetUuid, LoadHandle};
 
 use crossbeam_channel::Receiver;
 
@@ -27,6 +27,7 @@ where
     fn update_asset(
         &mut self,
         load_handle: LoadHandle,
+        asset_uuid: AssetUuid,
         load_op: AssetLoadOp,
         asset: AssetDataT,
     ) -> ResourceLoadResult<AssetT>;
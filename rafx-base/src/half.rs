@@ -0,0 +1,167 @@
+// Conversions between f32 and IEEE 754 half float bits
+
+// Rounds to nearest, ties to even. Values too large for a half become infinity, and NaN stays NaN.
+pub fn f32_to_f16(value: f32) -> u16 {
+    let bits = value.to_bits();
+    let sign = (bits >> 16) & 0x8000;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7fffff;
+
+    if exponent == 0xff {
+        // Inf or NaN
+        let nan_bit = if mantissa != 0 { 0x200 } else { 0 };
+        return (sign | 0x7c00 | nan_bit) as u16;
+    }
+
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        // Too large, becomes inf
+        return (sign | 0x7c00) as u16;
+    }
+
+    let (half_bits, remainder, halfway) = if half_exponent <= 0 {
+        if half_exponent < -10 {
+            // Too small, becomes zero
+            return sign as u16;
+        }
+
+        // Subnormal half
+        let mantissa = mantissa | 0x800000;
+        let shift = (14 - half_exponent) as u32;
+        (
+            mantissa >> shift,
+            mantissa & ((1 << shift) - 1),
+            1 << (shift - 1),
+        )
+    } else {
+        (
+            ((half_exponent as u32) << 10) | (mantissa >> 13),
+            mantissa & 0x1fff,
+            0x1000,
+        )
+    };
+
+    // If rounding carries into the exponent, the result is still correct
+    let round_up = remainder > halfway || (remainder == halfway && half_bits & 1 != 0);
+    (sign | (half_bits + round_up as u32)) as u16
+}
+
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+    let value = if exponent == 0 {
+        // Zero or subnormal
+        mantissa as f32 * (1.0 / (1 << 24) as f32)
+    } else if exponent == 0x1f {
+        if mantissa == 0 {
+            f32::INFINITY
+        } else {
+            f32::NAN
+        }
+    } else {
+        f32::from_bits(((exponent + 127 - 15) << 23) | (mantissa << 13))
+    };
+
+    if bits & 0x8000 != 0 {
+        -value
+    } else {
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SMALLEST_SUBNORMAL: f32 = 1.0 / (1 << 24) as f32;
+
+    #[test]
+    fn test_f32_to_f16_exact_values() {
+        assert_eq!(f32_to_f16(0.0), 0x0000);
+        assert_eq!(f32_to_f16(-0.0), 0x8000);
+        assert_eq!(f32_to_f16(1.0), 0x3c00);
+        assert_eq!(f32_to_f16(-2.0), 0xc000);
+        assert_eq!(f32_to_f16(65504.0), 0x7bff);
+        assert_eq!(f32_to_f16(6.103_515_6e-5), 0x0400);
+    }
+
+    #[test]
+    fn test_f32_to_f16_subnormals() {
+        assert_eq!(f32_to_f16(SMALLEST_SUBNORMAL), 0x0001);
+        assert_eq!(f32_to_f16(SMALLEST_SUBNORMAL * 3.0), 0x0003);
+        assert_eq!(f32_to_f16(SMALLEST_SUBNORMAL * 1023.0), 0x03ff);
+
+        // Half of the smallest subnormal is a tie and rounds to even (zero), anything above rounds up
+        assert_eq!(f32_to_f16(SMALLEST_SUBNORMAL * 0.5), 0x0000);
+        assert_eq!(f32_to_f16(SMALLEST_SUBNORMAL * 0.51), 0x0001);
+        assert_eq!(f32_to_f16(SMALLEST_SUBNORMAL * 0.25), 0x0000);
+        assert_eq!(f32_to_f16(-SMALLEST_SUBNORMAL * 0.25), 0x8000);
+
+        // Rounding up out of the subnormal range produces the smallest normal
+        assert_eq!(f32_to_f16(SMALLEST_SUBNORMAL * 1023.75), 0x0400);
+    }
+
+    #[test]
+    fn test_f32_to_f16_overflow() {
+        assert_eq!(f32_to_f16(65536.0), 0x7c00);
+        assert_eq!(f32_to_f16(-1.0e10), 0xfc00);
+        assert_eq!(f32_to_f16(f32::MAX), 0x7c00);
+        assert_eq!(f32_to_f16(f32::INFINITY), 0x7c00);
+        assert_eq!(f32_to_f16(f32::NEG_INFINITY), 0xfc00);
+
+        // Rounding up past the largest finite half produces infinity
+        assert_eq!(f32_to_f16(65520.0), 0x7c00);
+        assert_eq!(f32_to_f16(65519.0), 0x7bff);
+    }
+
+    #[test]
+    fn test_f32_to_f16_nan() {
+        let half = f32_to_f16(f32::NAN);
+        assert_eq!(half & 0x7c00, 0x7c00);
+        assert_ne!(half & 0x3ff, 0);
+        assert!(f16_to_f32(half).is_nan());
+    }
+
+    #[test]
+    fn test_f32_to_f16_rounding() {
+        // The spacing between halfs near 1.0 is 2^-10
+        let ulp = 1.0 / 1024.0;
+
+        // Ties round to even
+        assert_eq!(f32_to_f16(1.0 + ulp * 0.5), 0x3c00);
+        assert_eq!(f32_to_f16(1.0 + ulp * 1.5), 0x3c02);
+
+        // Otherwise round to nearest
+        assert_eq!(f32_to_f16(1.0 + ulp * 0.49), 0x3c00);
+        assert_eq!(f32_to_f16(1.0 + ulp * 0.51), 0x3c01);
+
+        // Rounding can carry into the exponent
+        assert_eq!(f32_to_f16(2.0 - ulp * 0.25), 0x4000);
+    }
+
+    #[test]
+    fn test_f16_to_f32() {
+        assert_eq!(f16_to_f32(0x0000), 0.0);
+        assert!(f16_to_f32(0x8000).is_sign_negative());
+        assert_eq!(f16_to_f32(0x3c00), 1.0);
+        assert_eq!(f16_to_f32(0xc000), -2.0);
+        assert_eq!(f16_to_f32(0x7bff), 65504.0);
+        assert_eq!(f16_to_f32(0x0001), SMALLEST_SUBNORMAL);
+        assert_eq!(f16_to_f32(0x83ff), -SMALLEST_SUBNORMAL * 1023.0);
+        assert_eq!(f16_to_f32(0x7c00), f32::INFINITY);
+        assert_eq!(f16_to_f32(0xfc00), f32::NEG_INFINITY);
+        assert!(f16_to_f32(0x7e00).is_nan());
+    }
+
+    #[test]
+    fn test_f16_round_trip() {
+        // Every finite half converts to an f32 that converts back to the same bits
+        for bits in 0..=0xffffu16 {
+            if bits & 0x7c00 == 0x7c00 {
+                continue;
+            }
+
+            assert_eq!(f32_to_f16(f16_to_f32(bits)), bits, "bits {:#06x}", bits);
+        }
+    }
+}
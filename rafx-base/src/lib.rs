@@ -8,6 +8,8 @@ pub mod slab;
 
 pub mod memory;
 
+pub mod half;
+
 pub mod offsetof;
 
 pub mod resource_map;
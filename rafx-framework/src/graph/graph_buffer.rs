@@ -13,6 +13,10 @@ pub struct VirtualBufferId(pub(super) usize);
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PhysicalBufferId(pub(super) usize);
 
+/// Unique ID provided for any buffer registered as an input buffer
+#[derive(Debug, Copy, Clone)]
+pub struct RenderGraphInputBufferId(pub(super) usize);
+
 /// Unique ID provided for any buffer registered as an output buffer
 #[derive(Debug, Copy, Clone)]
 pub struct RenderGraphOutputBufferId(pub(super) usize);
@@ -48,6 +52,7 @@ impl RenderGraphBufferResource {
 #[derive(Debug)]
 pub enum RenderGraphBufferUser {
     Node(RenderGraphNodeId),
+    Input(RenderGraphInputBufferId),
     Output(RenderGraphOutputBufferId),
 }
 
@@ -201,7 +206,7 @@ impl RenderGraphBufferConstraint {
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum RenderGraphBufferUsageType {
     Create,
-    //Input,
+    Input,
     Read,
     ModifyRead,
     ModifyWrite,
@@ -216,7 +221,7 @@ impl RenderGraphBufferUsageType {
             RenderGraphBufferUsageType::Output => true,
             RenderGraphBufferUsageType::ModifyRead => false,
             RenderGraphBufferUsageType::Create => false,
-            // RenderGraphBufferUsageType::Input => false,
+            RenderGraphBufferUsageType::Input => false,
             RenderGraphBufferUsageType::ModifyWrite => false,
         }
     }
@@ -225,9 +230,9 @@ impl RenderGraphBufferUsageType {
 /// Information about a specific version of the buffer.
 #[derive(Debug)]
 pub struct RenderGraphBufferResourceVersionInfo {
-    /// What node created the buffer (keep in mind these are virtual buffers, not buffers provided
-    /// from outside the graph. So every buffer will have a creator node)
-    pub(super) creator_node: RenderGraphNodeId,
+    /// What node created the buffer. This is None if the buffer was provided from outside the
+    /// graph (i.e. read_external_buffer)
+    pub(super) creator_node: Option<RenderGraphNodeId>,
 
     pub(super) create_usage: RenderGraphBufferUsageId,
     pub(super) read_usages: Vec<RenderGraphBufferUsageId>,
@@ -235,7 +240,7 @@ pub struct RenderGraphBufferResourceVersionInfo {
 
 impl RenderGraphBufferResourceVersionInfo {
     pub(super) fn new(
-        creator: RenderGraphNodeId,
+        creator: Option<RenderGraphNodeId>,
         create_usage: RenderGraphBufferUsageId,
     ) -> Self {
         RenderGraphBufferResourceVersionInfo {
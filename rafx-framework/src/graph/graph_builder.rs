@@ -14,12 +14,17 @@ pub enum RenderGraphQueue {
     Index(u32),
 }
 
-// /// An image that is being provided to the render graph that can be read from
-// #[derive(Debug)]
-// pub struct RenderGraphInputImage {
-//     pub usage: RenderGraphImageUsageId,
-//     pub specification: RenderGraphImageSpecification,
-// }
+/// An image that is being provided to the render graph that can be read from
+#[derive(Debug)]
+pub struct RenderGraphInputImage {
+    pub input_image_id: RenderGraphInputImageId,
+    pub usage: RenderGraphImageUsageId,
+    pub specification: RenderGraphImageSpecification,
+    pub src_image: ResourceArc<ImageViewResource>,
+
+    pub(super) initial_state: RafxResourceState,
+    pub(super) final_state: RafxResourceState,
+}
 
 /// An image that is being provided to the render graph that can be written to
 #[derive(Debug)]
@@ -32,12 +37,17 @@ pub struct RenderGraphOutputImage {
     pub(super) final_state: RafxResourceState,
 }
 
-// /// A buffer that is being provided to the render graph that can be read from
-// #[derive(Debug)]
-// pub struct RenderGraphInputBuffer {
-//     pub usage: RenderGraphBufferUsageId,
-//     pub specification: RenderGraphBufferSpecification,
-// }
+/// A buffer that is being provided to the render graph that can be read from
+#[derive(Debug)]
+pub struct RenderGraphInputBuffer {
+    pub input_buffer_id: RenderGraphInputBufferId,
+    pub usage: RenderGraphBufferUsageId,
+    pub specification: RenderGraphBufferSpecification,
+    pub src_buffer: ResourceArc<BufferResource>,
+
+    pub(super) initial_state: RafxResourceState,
+    pub(super) final_state: RafxResourceState,
+}
 
 /// A buffer that is being provided to the render graph that can be written to
 #[derive(Debug)]
@@ -70,8 +80,9 @@ pub struct RenderGraphBuilder {
     pub(super) image_usages: Vec<RenderGraphImageUsage>,
     pub(super) buffer_usages: Vec<RenderGraphBufferUsage>,
 
-    /// Images that are passed into the graph that can be read from
-    //pub(super) input_images: Vec<RenderGraphInputImage>,
+    /// Images/buffers that are passed into the graph that can be read from
+    pub(super) input_images: Vec<RenderGraphInputImage>,
+    pub(super) input_buffers: Vec<RenderGraphInputBuffer>,
 
     /// Images that are passed into the graph to be written to.
     pub(super) output_images: Vec<RenderGraphOutputImage>,
@@ -128,7 +139,7 @@ impl RenderGraphBuilder {
 
         let mut resource = RenderGraphImageResource::new();
 
-        let version_info = RenderGraphImageResourceVersionInfo::new(create_node, usage_id);
+        let version_info = RenderGraphImageResourceVersionInfo::new(Some(create_node), usage_id);
         resource.versions.push(version_info);
 
         // Add it to the graph
@@ -206,7 +217,8 @@ impl RenderGraphBuilder {
             view_options,
         );
 
-        let version_info = RenderGraphImageResourceVersionInfo::new(modify_node, write_usage_id);
+        let version_info =
+            RenderGraphImageResourceVersionInfo::new(Some(modify_node), write_usage_id);
         self.image_resources[read_version_id.index]
             .versions
             .push(version_info);
@@ -555,6 +567,53 @@ impl RenderGraphBuilder {
         usage
     }
 
+    /// Registers an image that was created outside the graph (for example, a previous frame's
+    /// color buffer or a shadow atlas that persists across frames) so that nodes can read or
+    /// modify it. initial_state must be the state the image is in when the graph begins executing.
+    /// The graph will insert barriers to transition it to the state required by each usage, and
+    /// to final_state after its last usage.
+    pub fn read_external_image(
+        &mut self,
+        src_image: ResourceArc<ImageViewResource>,
+        specification: RenderGraphImageSpecification,
+        view_options: RenderGraphImageViewOptions,
+        initial_state: RafxResourceState,
+        final_state: RafxResourceState,
+    ) -> RenderGraphImageUsageId {
+        let input_image_id = RenderGraphInputImageId(self.input_images.len());
+
+        let version_id = RenderGraphImageVersionId {
+            index: self.image_resources.len(),
+            version: 0,
+        };
+        let usage_id = self.add_image_usage(
+            RenderGraphImageUser::Input(input_image_id),
+            version_id,
+            RenderGraphImageUsageType::Input,
+            view_options,
+        );
+
+        let mut resource = RenderGraphImageResource::new();
+
+        let version_info = RenderGraphImageResourceVersionInfo::new(None, usage_id);
+        resource.versions.push(version_info);
+
+        // Add it to the graph
+        self.image_resources.push(resource);
+
+        let input_image = RenderGraphInputImage {
+            input_image_id,
+            usage: usage_id,
+            specification,
+            src_image,
+            initial_state,
+            final_state,
+        };
+
+        self.input_images.push(input_image);
+        usage_id
+    }
+
     pub fn set_output_image(
         &mut self,
         image_id: RenderGraphImageUsageId,
@@ -624,7 +683,7 @@ impl RenderGraphBuilder {
 
         let mut resource = RenderGraphBufferResource::new();
 
-        let version_info = RenderGraphBufferResourceVersionInfo::new(create_node, usage_id);
+        let version_info = RenderGraphBufferResourceVersionInfo::new(Some(create_node), usage_id);
         resource.versions.push(version_info);
 
         // Add it to the graph
@@ -696,7 +755,8 @@ impl RenderGraphBuilder {
             RenderGraphBufferUsageType::ModifyWrite,
         );
 
-        let version_info = RenderGraphBufferResourceVersionInfo::new(modify_node, write_usage_id);
+        let version_info =
+            RenderGraphBufferResourceVersionInfo::new(Some(modify_node), write_usage_id);
         self.buffer_resources[read_version_id.index]
             .versions
             .push(version_info);
@@ -806,6 +866,54 @@ impl RenderGraphBuilder {
         write_buffer
     }
 
+    /// Registers a buffer that was created outside the graph so that nodes can read or modify it.
+    /// initial_state must be the state the buffer is in when the graph begins executing. The graph
+    /// will insert barriers to transition it to the state required by each usage, and to
+    /// final_state after its last usage.
+    pub fn read_external_buffer(
+        &mut self,
+        src_buffer: ResourceArc<BufferResource>,
+        specification: RenderGraphBufferSpecification,
+        initial_state: RafxResourceState,
+        final_state: RafxResourceState,
+    ) -> RenderGraphBufferUsageId {
+        if specification.resource_type == RafxResourceType::UNDEFINED {
+            panic!("An input buffer with empty resource_type in the specification is almost certainly a mistake.");
+        }
+
+        let input_buffer_id = RenderGraphInputBufferId(self.input_buffers.len());
+
+        let version_id = RenderGraphBufferVersionId {
+            index: self.buffer_resources.len(),
+            version: 0,
+        };
+        let usage_id = self.add_buffer_usage(
+            RenderGraphBufferUser::Input(input_buffer_id),
+            version_id,
+            RenderGraphBufferUsageType::Input,
+        );
+
+        let mut resource = RenderGraphBufferResource::new();
+
+        let version_info = RenderGraphBufferResourceVersionInfo::new(None, usage_id);
+        resource.versions.push(version_info);
+
+        // Add it to the graph
+        self.buffer_resources.push(resource);
+
+        let input_buffer = RenderGraphInputBuffer {
+            input_buffer_id,
+            usage: usage_id,
+            specification,
+            src_buffer,
+            initial_state,
+            final_state,
+        };
+
+        self.input_buffers.push(input_buffer);
+        usage_id
+    }
+
     pub fn set_output_buffer(
         &mut self,
         buffer_id: RenderGraphBufferUsageId,
@@ -868,6 +976,18 @@ impl RenderGraphBuilder {
         self.node_mut(node_id).name = Some(name);
     }
 
+    /// Record the renderpass for this node with secondary command buffers. This allows
+    /// `PreparedRenderData::write_view_phase` to write large phases from multiple threads. All
+    /// drawing in the renderpass must go through `write_view_phase`, the command buffer passed to
+    /// the callback may only be used to execute secondary command buffers. It has no effect if the
+    /// device does not support multithreaded command recording.
+    pub fn set_renderpass_uses_secondary_command_buffers(
+        &mut self,
+        node_id: RenderGraphNodeId,
+    ) {
+        self.node_mut(node_id).uses_secondary_command_buffers = true;
+    }
+
     pub fn set_image_name(
         &mut self,
         image_id: RenderGraphImageUsageId,
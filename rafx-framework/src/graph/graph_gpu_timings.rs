@@ -0,0 +1,300 @@
+use crate::graph::graph_node::RenderGraphNodeName;
+use crate::graph::RenderGraphNodeId;
+use crate::resources::{DynCommandBuffer, DynCommandPool};
+use rafx_api::{
+    RafxCommandBuffer, RafxCommandBufferDef, RafxDeviceContext, RafxFence, RafxFenceStatus,
+    RafxQueryPool, RafxQueryPoolDef, RafxQueryType, RafxQueue, RafxResult,
+};
+use std::sync::Arc;
+
+/// GPU time spent executing a single node of the render graph. Times are in milliseconds and
+/// relative to the beginning of the first node in the graph.
+#[derive(Debug, Clone)]
+pub struct RenderGraphNodeGpuTiming {
+    pub node_id: RenderGraphNodeId,
+    pub name: Option<RenderGraphNodeName>,
+    pub begin_ms: f64,
+    pub end_ms: f64,
+}
+
+impl RenderGraphNodeGpuTiming {
+    pub fn duration_ms(&self) -> f64 {
+        self.end_ms - self.begin_ms
+    }
+}
+
+/// Resolved GPU timings for all nodes of a previously executed render graph. These are read back
+/// without blocking, so they are a few frames behind the frame currently being rendered. When
+/// several graphs are executed in a frame, only the first one is timed.
+#[derive(Debug, Clone, Default)]
+pub struct RenderGraphGpuTimings {
+    /// The frame index (as counted by the render graph cache) that produced these timings
+    pub frame_index: u64,
+    pub nodes: Vec<RenderGraphNodeGpuTiming>,
+}
+
+impl RenderGraphGpuTimings {
+    pub fn total_ms(&self) -> f64 {
+        self.nodes.last().map(|x| x.end_ms).unwrap_or(0.0)
+    }
+}
+
+// Queries written by one execution of the graph. The pool is only reset or dropped once the GPU
+// is done with it, which is known from the fence if one was submitted after the frame (see
+// RenderGraphCache::submit_gpu_timings_fence) or else from all of the queries being available.
+struct RenderGraphGpuTimingsFrame {
+    query_pool: Arc<RafxQueryPool>,
+    fence: RafxFence,
+    // An empty command buffer submitted after the frame to signal the fence. Submissions to a
+    // queue complete in order, so once it is done the queries have been written.
+    fence_command_buffer: Option<DynCommandBuffer>,
+    fence_submitted: bool,
+    frame_index: u64,
+    nodes: Vec<(RenderGraphNodeId, Option<RenderGraphNodeName>)>,
+    pending: bool,
+}
+
+// A ring of query pools, one per frame that may be in flight. Each frame takes the oldest slot,
+// harvesting its results first. If the GPU is still using that slot, the frame is not timed.
+pub(super) struct RenderGraphGpuTimingsState {
+    frames: Vec<Option<RenderGraphGpuTimingsFrame>>,
+    next_frame: usize,
+    last_written_frame: Option<usize>,
+    latest: Option<RenderGraphGpuTimings>,
+}
+
+impl RenderGraphGpuTimingsState {
+    pub fn new(frames_to_persist: u64) -> Self {
+        RenderGraphGpuTimingsState {
+            frames: (0..frames_to_persist).map(|_| None).collect(),
+            next_frame: 0,
+            last_written_frame: None,
+            latest: None,
+        }
+    }
+
+    pub fn latest(&self) -> Option<&RenderGraphGpuTimings> {
+        self.latest.as_ref()
+    }
+
+    pub fn clear(&mut self) {
+        for frame in &mut self.frames {
+            if let Some(frame) = frame {
+                if frame.fence_submitted {
+                    if let Err(e) = frame.fence.wait() {
+                        log::warn!("Failed to wait for render graph GPU timings fence: {:?}", e);
+                    }
+                }
+            }
+
+            *frame = None;
+        }
+        self.last_written_frame = None;
+        self.latest = None;
+    }
+
+    fn read_results(
+        device_context: &RafxDeviceContext,
+        frame: &RenderGraphGpuTimingsFrame,
+    ) -> RafxResult<Option<RenderGraphGpuTimings>> {
+        if frame.nodes.is_empty() {
+            return Ok(None);
+        }
+
+        let query_count = frame.nodes.len() as u32 * 2;
+        let mut results = vec![0; query_count as usize];
+        if !frame.query_pool.get_results(0, query_count, &mut results)? {
+            return Ok(None);
+        }
+
+        let ns_per_tick = device_context.device_info().timestamp_period_ns as f64;
+        let to_ms =
+            |ticks: u64| ticks.saturating_sub(results[0]) as f64 * ns_per_tick / (1000.0 * 1000.0);
+
+        let nodes = frame
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, (node_id, name))| RenderGraphNodeGpuTiming {
+                node_id: *node_id,
+                name: *name,
+                begin_ms: to_ms(results[i * 2]),
+                end_ms: to_ms(results[i * 2 + 1]),
+            })
+            .collect();
+
+        Ok(Some(RenderGraphGpuTimings {
+            frame_index: frame.frame_index,
+            nodes,
+        }))
+    }
+
+    // Returns false if the GPU may still be using the frame's query pool. Otherwise the frame's
+    // results are harvested if they haven't been already.
+    fn try_retire_frame(
+        device_context: &RafxDeviceContext,
+        frame: &mut RenderGraphGpuTimingsFrame,
+        latest: &mut Option<RenderGraphGpuTimings>,
+    ) -> RafxResult<bool> {
+        if frame.fence_submitted {
+            if frame.fence.get_fence_status()? == RafxFenceStatus::Incomplete {
+                return Ok(false);
+            }
+
+            frame.fence_submitted = false;
+        }
+
+        if frame.pending {
+            match Self::read_results(device_context, frame)? {
+                Some(timings) => *latest = Some(timings),
+                // Without a fence, the queries not being available means the frame is in flight
+                None => return Ok(false),
+            }
+
+            frame.pending = false;
+        }
+
+        Ok(true)
+    }
+
+    // Picks the query pool for the graph about to be executed and resets it. Returns None if the
+    // device can't write timestamps or the GPU is still using the oldest query pool.
+    pub fn begin_frame(
+        &mut self,
+        device_context: &RafxDeviceContext,
+        command_pool: &mut DynCommandPool,
+        command_buffer: &RafxCommandBuffer,
+        frame_index: u64,
+        node_count: usize,
+    ) -> RafxResult<Option<RenderGraphGpuTimingsWriter>> {
+        if !device_context.device_info().supports_timestamp_queries || node_count == 0 {
+            return Ok(None);
+        }
+
+        let slot = self.next_frame;
+        self.next_frame = (self.next_frame + 1) % self.frames.len();
+        self.last_written_frame = None;
+
+        if let Some(frame) = &mut self.frames[slot] {
+            match Self::try_retire_frame(device_context, frame, &mut self.latest) {
+                Ok(true) => {}
+                Ok(false) => {
+                    log::trace!("Render graph GPU timings query pool is in use, skipping frame");
+                    return Ok(None);
+                }
+                Err(e) => {
+                    log::warn!("Failed to read render graph GPU timings: {:?}", e);
+                    return Ok(None);
+                }
+            }
+        }
+
+        let query_count = node_count as u32 * 2;
+        let needs_new_pool = self.frames[slot]
+            .as_ref()
+            .map(|x| x.query_pool.query_pool_def().query_count < query_count)
+            .unwrap_or(true);
+
+        if needs_new_pool {
+            let query_pool = device_context.create_query_pool(&RafxQueryPoolDef {
+                query_type: RafxQueryType::Timestamp,
+                query_count,
+            })?;
+
+            self.frames[slot] = Some(RenderGraphGpuTimingsFrame {
+                query_pool: Arc::new(query_pool),
+                fence: device_context.create_fence()?,
+                fence_command_buffer: None,
+                fence_submitted: false,
+                frame_index,
+                nodes: Vec::default(),
+                pending: false,
+            });
+        }
+
+        let fence_command_buffer =
+            command_pool.allocate_dyn_command_buffer(&RafxCommandBufferDef {
+                is_secondary: false,
+            })?;
+        fence_command_buffer.begin()?;
+        fence_command_buffer.end()?;
+
+        let frame = self.frames[slot].as_mut().unwrap();
+        frame.frame_index = frame_index;
+        frame.fence_command_buffer = Some(fence_command_buffer);
+        frame.nodes.clear();
+
+        let query_pool = frame.query_pool.clone();
+        command_buffer.cmd_reset_queries(
+            &query_pool,
+            0,
+            query_pool.query_pool_def().query_count,
+        )?;
+
+        Ok(Some(RenderGraphGpuTimingsWriter {
+            slot,
+            query_pool,
+            nodes: Vec::with_capacity(node_count),
+        }))
+    }
+
+    pub fn end_frame(
+        &mut self,
+        writer: RenderGraphGpuTimingsWriter,
+    ) {
+        if let Some(frame) = &mut self.frames[writer.slot] {
+            frame.nodes = writer.nodes;
+            frame.pending = true;
+            self.last_written_frame = Some(writer.slot);
+        }
+    }
+
+    // Signals the fence of the most recently written frame once the GPU is done with everything
+    // submitted to the queue so far. Must be called after the frame's command buffers are submitted.
+    pub fn submit_fence(
+        &mut self,
+        queue: &RafxQueue,
+    ) -> RafxResult<()> {
+        let slot = match self.last_written_frame.take() {
+            Some(slot) => slot,
+            None => return Ok(()),
+        };
+
+        if let Some(frame) = &mut self.frames[slot] {
+            if let Some(fence_command_buffer) = frame.fence_command_buffer.take() {
+                queue.submit(&[&*fence_command_buffer], &[], &[], Some(&frame.fence))?;
+                frame.fence_submitted = true;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Records the timestamps around each pass while the graph is being written
+pub(super) struct RenderGraphGpuTimingsWriter {
+    slot: usize,
+    query_pool: Arc<RafxQueryPool>,
+    nodes: Vec<(RenderGraphNodeId, Option<RenderGraphNodeName>)>,
+}
+
+impl RenderGraphGpuTimingsWriter {
+    pub fn begin_node(
+        &mut self,
+        command_buffer: &RafxCommandBuffer,
+        node_id: RenderGraphNodeId,
+        name: Option<RenderGraphNodeName>,
+    ) -> RafxResult<()> {
+        let query_index = self.nodes.len() as u32 * 2;
+        self.nodes.push((node_id, name));
+        command_buffer.cmd_write_timestamp(&self.query_pool, query_index)
+    }
+
+    pub fn end_node(
+        &mut self,
+        command_buffer: &RafxCommandBuffer,
+    ) -> RafxResult<()> {
+        let query_index = self.nodes.len() as u32 * 2 - 1;
+        command_buffer.cmd_write_timestamp(&self.query_pool, query_index)
+    }
+}
@@ -17,6 +17,10 @@ pub struct PhysicalImageId(pub(super) usize);
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct PhysicalImageViewId(pub(super) usize);
 
+/// Unique ID provided for any image registered as an input image
+#[derive(Debug, Copy, Clone)]
+pub struct RenderGraphInputImageId(pub(super) usize);
+
 /// Unique ID provided for any image registered as an output image
 #[derive(Debug, Copy, Clone)]
 pub struct RenderGraphOutputImageId(pub(super) usize);
@@ -59,6 +63,7 @@ pub struct RenderGraphImageView {
 #[derive(Debug)]
 pub enum RenderGraphImageUser {
     Node(RenderGraphNodeId),
+    Input(RenderGraphInputImageId),
     Output(RenderGraphOutputImageId),
 }
 
@@ -355,7 +360,7 @@ impl RenderGraphImageConstraint {
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum RenderGraphImageUsageType {
     Create,
-    //Input,
+    Input,
     Read,
     ModifyRead,
     ModifyWrite,
@@ -370,7 +375,7 @@ impl RenderGraphImageUsageType {
             RenderGraphImageUsageType::Output => true,
             RenderGraphImageUsageType::ModifyRead => false,
             RenderGraphImageUsageType::Create => false,
-            //RenderGraphImageUsageType::Input => false,
+            RenderGraphImageUsageType::Input => false,
             RenderGraphImageUsageType::ModifyWrite => false,
         }
     }
@@ -379,9 +384,9 @@ impl RenderGraphImageUsageType {
 /// Information about a specific version of the image.
 #[derive(Debug)]
 pub struct RenderGraphImageResourceVersionInfo {
-    /// What node created the image (keep in mind these are virtual images, not images provided
-    /// from outside the graph. So every image will have a creator node)
-    pub(super) creator_node: RenderGraphNodeId,
+    /// What node created the image. This is None if the image was provided from outside the graph
+    /// (i.e. read_external_image)
+    pub(super) creator_node: Option<RenderGraphNodeId>,
 
     pub(super) create_usage: RenderGraphImageUsageId,
     pub(super) read_usages: Vec<RenderGraphImageUsageId>,
@@ -389,7 +394,7 @@ pub struct RenderGraphImageResourceVersionInfo {
 
 impl RenderGraphImageResourceVersionInfo {
     pub(super) fn new(
-        creator: RenderGraphNodeId,
+        creator: Option<RenderGraphNodeId>,
         create_usage: RenderGraphImageUsageId,
     ) -> Self {
         RenderGraphImageResourceVersionInfo {
@@ -140,6 +140,8 @@ pub struct RenderGraphNode {
     pub(super) resolve_attachments: Vec<Option<RenderGraphPassResolveAttachmentInfo>>,
 
     pub(super) sampled_images: Vec<RenderGraphImageUsageId>,
+
+    pub(super) uses_secondary_command_buffers: bool,
 }
 
 impl std::fmt::Debug for RenderGraphNode {
@@ -185,6 +187,7 @@ impl RenderGraphNode {
             depth_attachment: Default::default(),
             resolve_attachments: Default::default(),
             sampled_images: Default::default(),
+            uses_secondary_command_buffers: false,
         }
     }
 
@@ -161,6 +161,7 @@ pub struct RenderGraphRenderPass {
 pub struct RenderGraphComputePass {
     pub(super) node: RenderGraphNodeId,
     pub(super) pre_pass_barrier: Option<PrepassBarrier>,
+    pub(super) post_pass_barrier: Option<PostpassBarrier>,
 }
 
 #[derive(Debug)]
@@ -188,6 +189,29 @@ impl RenderGraphPass {
             }
         }
     }
+
+    // Appends to the barriers after the pass, if there are any already
+    pub fn add_post_pass_barrier(
+        &mut self,
+        barrier: PostpassBarrier,
+    ) {
+        let post_pass_barrier = match self {
+            RenderGraphPass::Renderpass(renderpass) => &mut renderpass.post_pass_barrier,
+            RenderGraphPass::Compute(compute_pass) => &mut compute_pass.post_pass_barrier,
+        };
+
+        match post_pass_barrier {
+            Some(post_pass_barrier) => {
+                post_pass_barrier
+                    .image_barriers
+                    .extend(barrier.image_barriers);
+                post_pass_barrier
+                    .buffer_barriers
+                    .extend(barrier.buffer_barriers);
+            }
+            None => *post_pass_barrier = Some(barrier),
+        }
+    }
 }
 
 pub struct RenderGraphColorRenderTarget {
@@ -223,6 +247,7 @@ pub struct RenderGraphOutputRenderPass {
     pub(super) color_render_targets: Vec<RenderGraphColorRenderTarget>,
     pub(super) depth_stencil_render_target: Option<RenderGraphDepthStencilRenderTarget>,
     pub(super) render_target_meta: GraphicsPipelineRenderTargetMeta,
+    pub(super) uses_secondary_command_buffers: bool,
 }
 
 impl std::fmt::Debug for RenderGraphOutputRenderPass {
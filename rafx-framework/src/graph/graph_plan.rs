@@ -51,63 +51,70 @@ fn visit_node(
     // Visit all the nodes we aren't delaying
     //
     for read in &node.image_reads {
-        let upstream_node = graph.image_version_info(read.image).creator_node;
-        visit_node(
-            graph,
-            upstream_node,
-            visited,
-            visiting,
-            visiting_stack,
-            ordered_list,
-        );
+        // Images/buffers provided from outside the graph (read_external_image/buffer) have no
+        // upstream node to visit
+        if let Some(upstream_node) = graph.image_version_info(read.image).creator_node {
+            visit_node(
+                graph,
+                upstream_node,
+                visited,
+                visiting,
+                visiting_stack,
+                ordered_list,
+            );
+        }
     }
 
     for modify in &node.image_modifies {
-        let upstream_node = graph.image_version_info(modify.input).creator_node;
-        visit_node(
-            graph,
-            upstream_node,
-            visited,
-            visiting,
-            visiting_stack,
-            ordered_list,
-        );
+        if let Some(upstream_node) = graph.image_version_info(modify.input).creator_node {
+            visit_node(
+                graph,
+                upstream_node,
+                visited,
+                visiting,
+                visiting_stack,
+                ordered_list,
+            );
+        }
     }
 
     for sampled_image in &node.sampled_images {
-        let upstream_node = graph.image_version_info(*sampled_image).creator_node;
-        visit_node(
-            graph,
-            upstream_node,
-            visited,
-            visiting,
-            visiting_stack,
-            ordered_list,
-        );
+        if let Some(upstream_node) = graph.image_version_info(*sampled_image).creator_node {
+            visit_node(
+                graph,
+                upstream_node,
+                visited,
+                visiting,
+                visiting_stack,
+                ordered_list,
+            );
+        }
     }
 
     for read in &node.buffer_reads {
-        let upstream_node = graph.buffer_version_info(read.buffer).creator_node;
-        visit_node(
-            graph,
-            upstream_node,
-            visited,
-            visiting,
-            visiting_stack,
-            ordered_list,
-        );
+        if let Some(upstream_node) = graph.buffer_version_info(read.buffer).creator_node {
+            visit_node(
+                graph,
+                upstream_node,
+                visited,
+                visiting,
+                visiting_stack,
+                ordered_list,
+            );
+        }
     }
 
     for modify in &node.buffer_modifies {
-        let upstream_node = graph.buffer_version_info(modify.input).creator_node;
-        visit_node(
-            graph,
-            upstream_node,
-            visited,
-            visiting,
-            visiting_stack,
-            ordered_list,
-        );
+        if let Some(upstream_node) = graph.buffer_version_info(modify.input).creator_node {
+            visit_node(
+                graph,
+                upstream_node,
+                visited,
+                visiting,
+                visiting_stack,
+                ordered_list,
+            );
+        }
     }
 
     // All our pre-requisites were visited, so it's now safe to push this node onto the
@@ -140,8 +147,12 @@ fn determine_node_order(graph: &RenderGraphBuilder) -> Vec<RenderGraphNodeId> {
     // Iterate all the images we need to output. This will visit all the nodes we need to execute,
     // potentially leaving out nodes we can cull.
     for output_image_id in &graph.output_images {
-        // Find the node that creates the output image
-        let output_node = graph.image_version_info(output_image_id.usage).creator_node;
+        // Find the node that creates the output image. If the output image was passed in from
+        // outside the graph, there is nothing to traverse
+        let output_node = match graph.image_version_info(output_image_id.usage).creator_node {
+            Some(output_node) => output_node,
+            None => continue,
+        };
         log::trace!(
             "Traversing dependencies of output image created by node {:?} {:?}",
             output_node,
@@ -161,10 +172,15 @@ fn determine_node_order(graph: &RenderGraphBuilder) -> Vec<RenderGraphNodeId> {
     // Iterate all the buffers we need to output. This will visit all the nodes we need to execute,
     // potentially leaving out nodes we can cull.
     for output_buffer_id in &graph.output_buffers {
-        // Find the node that creates the output buffer
-        let output_node = graph
+        // Find the node that creates the output buffer. If the output buffer was passed in from
+        // outside the graph, there is nothing to traverse
+        let output_node = match graph
             .buffer_version_info(output_buffer_id.usage)
-            .creator_node;
+            .creator_node
+        {
+            Some(output_node) => output_node,
+            None => continue,
+        };
         log::trace!(
             "Traversing dependencies of output buffer created by node {:?} {:?}",
             output_node,
@@ -236,19 +252,19 @@ fn determine_constraints(
     // Propagate input image state specifications into images. Inputs are fully specified and
     // their constraints will never be overwritten
     //
-    // for input_image in &graph.input_images {
-    //     log::trace!(
-    //         "    Image {:?} {:?}",
-    //         input_image,
-    //         graph.image_resource(input_image.usage).name
-    //     );
-    //     image_version_states
-    //         .entry(graph.get_create_usage(input_image.usage))
-    //         .or_default()
-    //         .set(&input_image.specification);
-    //
-    //     // Don't bother setting usage constraint for 0
-    // }
+    for input_image in &graph.input_images {
+        log::trace!(
+            "    Image {:?} {:?}",
+            input_image,
+            graph.image_resource(input_image.usage).name
+        );
+        image_version_states
+            .entry(graph.image_version_create_usage(input_image.usage))
+            .or_default()
+            .set(&input_image.specification);
+
+        // Don't bother setting usage constraint for 0
+    }
 
     log::trace!("  Set up input buffers");
 
@@ -256,19 +272,19 @@ fn determine_constraints(
     // Propagate input buffer state specifications into buffers. Inputs are fully specified and
     // their constraints will never be overwritten
     //
-    // for input_buffer in &graph.input_buffers {
-    //     log::trace!(
-    //         "    Buffer {:?} {:?}",
-    //         input_buffer,
-    //         graph.buffer_resource(input_buffer.usage).name
-    //     );
-    //     buffer_version_states
-    //         .entry(graph.get_create_usage(input_buffer.usage))
-    //         .or_default()
-    //         .set(&input_buffer.specification);
-    //
-    //     // Don't bother setting usage constraint for 0
-    // }
+    for input_buffer in &graph.input_buffers {
+        log::trace!(
+            "    Buffer {:?} {:?}",
+            input_buffer,
+            graph.buffer_resource(input_buffer.usage).name
+        );
+        buffer_version_states
+            .entry(graph.buffer_version_create_usage(input_buffer.usage))
+            .or_default()
+            .set(&input_buffer.specification);
+
+        // Don't bother setting usage constraint for 0
+    }
 
     log::trace!("  Propagate constraints FORWARD");
 
@@ -287,12 +303,11 @@ fn determine_constraints(
         //
         for image_create in &node.image_creates {
             // An image cannot be created within the graph and imported externally at the same
-            // time. (The code here assumes no input and will not produce correct results if there
-            // is an input image)
-            //TODO: Input images are broken, we don't properly represent an image being created
-            // vs. receiving an input. We probably need to make creator in
-            // RenderGraphImageResourceVersionInfo Option or an enum with input/create options
-            //assert!(graph.image_version_info(image_create.image).input_image.is_none());
+            // time. Input images are never created by a node.
+            debug_assert!(graph
+                .image_version_info(image_create.image)
+                .creator_node
+                .is_some());
 
             log::trace!(
                 "      Create image {:?} {:?}",
@@ -777,6 +792,220 @@ fn assign_virtual_resources(
         }
     }
 
+    //
+    // Try to share a written image forward into all the downstream usages of it. This can chain
+    // such that the same image is shared by many nodes
+    //
+    fn share_written_image(
+        graph: &RenderGraphBuilder,
+        constraint_results: &DetermineConstraintsResult,
+        written_image: RenderGraphImageUsageId,
+        image_usage_to_virtual: &mut FnvHashMap<RenderGraphImageUsageId, VirtualImageId>,
+        virtual_image_id_allocator: &mut VirtualImageIdAllocator,
+    ) {
+        // Count the downstream users of this image based on if they need read-only access
+        // or write access. We need this information to determine which usages we can share
+        // the output data with.
+        //
+        // I'm not sure if this works as written. I was thinking we might have trouble with
+        // multiple readers, and then they pass to a writer, but now that I think of it, readers
+        // don't "output" anything.
+        //
+        // That said, this doesn't understand multiple writers of different subresources right
+        // now.
+        //
+        //TODO: This could be smarter to handle the case of a resource being read and then
+        // later written
+        //TODO: Could handle non-overlapping subresource ranges being written
+        let written_image_version_info = graph.image_version_info(written_image);
+        let mut read_count = 0;
+        //let mut read_ranges = vec![];
+        let mut write_count = 0;
+        //let mut write_ranges = vec![];
+        for usage in &written_image_version_info.read_usages {
+            if graph.image_usages[usage.0].usage_type.is_read_only() {
+                read_count += 1;
+            //read_ranges.push(graph.image_usages[usage.0].subresource_range.clone());
+            } else {
+                write_count += 1;
+                //write_ranges.push(graph.image_usages[usage.0].subresource_range.clone());
+            }
+        }
+
+        // let mut has_overlapping_write = false;
+        // for i in 0..write_ranges.len() {
+        //     for j in 0..i {
+        //
+        //     }
+        // }
+
+        let write_virtual_image = *image_usage_to_virtual.get(&written_image).unwrap();
+        let write_type = graph.image_usages[written_image.0].usage_type;
+
+        let written_spec = constraint_results
+            .image_specification(written_image)
+            .unwrap();
+
+        for usage_resource_id in &written_image_version_info.read_usages {
+            let usage_spec = match constraint_results.image_specification(*usage_resource_id) {
+                Some(usage_spec) => usage_spec,
+                // If the reader of this image was culled, we may not have determined a spec.
+                // If so, skip this usage
+                None => continue,
+            };
+
+            // We can't share images if they aren't the same format
+            let specifications_match = *written_spec == *usage_spec;
+
+            // We can't share images unless it's a read or it's an exclusive write
+            let is_read_or_exclusive_write = (read_count > 0
+                && graph.image_usages[usage_resource_id.0]
+                    .usage_type
+                    .is_read_only())
+                || write_count <= 1;
+
+            let read_type = graph.image_usages[usage_resource_id.0].usage_type;
+            if specifications_match && is_read_or_exclusive_write {
+                // it's a shared read or an exclusive write
+                log::trace!(
+                    "    Usage {:?} will share an image with {:?} ({:?} -> {:?})",
+                    written_image,
+                    usage_resource_id,
+                    write_type,
+                    read_type
+                );
+                let overwritten_image =
+                    image_usage_to_virtual.insert(*usage_resource_id, write_virtual_image);
+
+                assert!(overwritten_image.is_none());
+            } else {
+                // allocate new image
+                let virtual_image = virtual_image_id_allocator.allocate();
+                log::trace!(
+                    "    Allocate image {:?} for {:?} ({:?} -> {:?})  (specifications_match match: {} is_read_or_exclusive_write: {})",
+                    virtual_image,
+                    usage_resource_id,
+                    write_type,
+                    read_type,
+                    specifications_match,
+                    is_read_or_exclusive_write
+                );
+                if !specifications_match {
+                    log::trace!("      written: {:?}", written_spec);
+                    log::trace!("      usage  : {:?}", usage_spec);
+                }
+                let overwritten_image =
+                    image_usage_to_virtual.insert(*usage_resource_id, virtual_image);
+
+                assert!(overwritten_image.is_none());
+
+                //TODO: One issue (aside from not doing any blits right now) is that images created in this way
+                // aren't included in the assign_physical_images logic
+                println!("      written: {:?}", written_spec);
+                println!("      usage  : {:?}", usage_spec);
+                panic!("Render graph does not currently support blit from one image to another to fix image compatibility");
+            }
+        }
+    }
+
+    //
+    // Try to share a written buffer forward into all the downstream usages of it. This can chain
+    // such that the same buffer is shared by many nodes
+    //
+    fn share_written_buffer(
+        graph: &RenderGraphBuilder,
+        constraint_results: &DetermineConstraintsResult,
+        written_buffer: RenderGraphBufferUsageId,
+        buffer_usage_to_virtual: &mut FnvHashMap<RenderGraphBufferUsageId, VirtualBufferId>,
+        virtual_buffer_id_allocator: &mut VirtualBufferIdAllocator,
+    ) {
+        // Count the downstream users of this image based on if they need read-only access
+        // or write access. We need this information to determine which usages we can share
+        // the output data with.
+        //
+        // I'm not sure if this works as written. I was thinking we might have trouble with
+        // multiple readers, and then they pass to a writer, but now that I think of it, readers
+        // don't "output" anything.
+        //TODO: This could be smarter to handle the case of a resource being read and then
+        // later written
+        let written_buffer_version_info = graph.buffer_version_info(written_buffer);
+        let mut read_count = 0;
+        let mut write_count = 0;
+        for usage in &written_buffer_version_info.read_usages {
+            if graph.buffer_usages[usage.0].usage_type.is_read_only() {
+                read_count += 1;
+            } else {
+                write_count += 1;
+            }
+        }
+
+        let write_virtual_buffer = *buffer_usage_to_virtual.get(&written_buffer).unwrap();
+        let write_type = graph.buffer_usages[written_buffer.0].usage_type;
+
+        let written_spec = constraint_results
+            .buffer_specification(written_buffer)
+            .unwrap();
+
+        for usage_resource_id in &written_buffer_version_info.read_usages {
+            let usage_spec = match constraint_results.buffer_specification(*usage_resource_id) {
+                Some(usage_spec) => usage_spec,
+                // If the reader of this buffer was culled, we may not have determined a spec.
+                // If so, skip this usage
+                None => continue,
+            };
+
+            // We can't share buffers if they aren't the same format
+            let specifications_match = *written_spec == *usage_spec;
+
+            // We can't share buffers unless it's a read or it's an exclusive write
+            let is_read_or_exclusive_write = (read_count > 0
+                && graph.buffer_usages[usage_resource_id.0]
+                    .usage_type
+                    .is_read_only())
+                || write_count <= 1;
+
+            let read_type = graph.buffer_usages[usage_resource_id.0].usage_type;
+            if specifications_match && is_read_or_exclusive_write {
+                // it's a shared read or an exclusive write
+                log::trace!(
+                    "    Usage {:?} will share a buffer with {:?} ({:?} -> {:?})",
+                    written_buffer,
+                    usage_resource_id,
+                    write_type,
+                    read_type
+                );
+                let overwritten_buffer =
+                    buffer_usage_to_virtual.insert(*usage_resource_id, write_virtual_buffer);
+
+                assert!(overwritten_buffer.is_none());
+            } else {
+                // allocate new buffer
+                let virtual_buffer = virtual_buffer_id_allocator.allocate();
+                log::trace!(
+                    "    Allocate buffer {:?} for {:?} ({:?} -> {:?})  (specifications_match match: {} is_read_or_exclusive_write: {})",
+                    virtual_buffer,
+                    usage_resource_id,
+                    write_type,
+                    read_type,
+                    specifications_match,
+                    is_read_or_exclusive_write
+                );
+                if !specifications_match {
+                    log::trace!("      written: {:?}", written_spec);
+                    log::trace!("      usage  : {:?}", usage_spec);
+                }
+                let overwritten_buffer =
+                    buffer_usage_to_virtual.insert(*usage_resource_id, virtual_buffer);
+
+                assert!(overwritten_buffer.is_none());
+
+                //TODO: One issue (aside from not doing any copies right now) is that buffers created in this way
+                // aren't included in the assign_physical_buffers logic
+                panic!("Render graph does not currently support blit from one buffer to another to fix buffer compatibility");
+            }
+        }
+    }
+
     let mut image_usage_to_virtual: FnvHashMap<RenderGraphImageUsageId, VirtualImageId> =
         FnvHashMap::default();
     let mut buffer_usage_to_virtual: FnvHashMap<RenderGraphBufferUsageId, VirtualBufferId> =
@@ -785,7 +1014,45 @@ fn assign_virtual_resources(
     let mut virtual_image_id_allocator = VirtualImageIdAllocator::default();
     let mut virtual_buffer_id_allocator = VirtualBufferIdAllocator::default();
 
-    //TODO: Associate input images here? We can wait until we decide which images are shared
+    //
+    // Images/buffers passed into the graph always get their own virtual resource. Share them forward
+    // into the nodes that read them just like an image/buffer created by a node
+    //
+    log::trace!("Associate input images/buffers with virtual images/buffers");
+    for input_image in &graph.input_images {
+        let virtual_image = virtual_image_id_allocator.allocate();
+        log::trace!(
+            "  Input {:?} will use image {:?}",
+            input_image.usage,
+            virtual_image
+        );
+        image_usage_to_virtual.insert(input_image.usage, virtual_image);
+        share_written_image(
+            graph,
+            constraint_results,
+            input_image.usage,
+            &mut image_usage_to_virtual,
+            &mut virtual_image_id_allocator,
+        );
+    }
+
+    for input_buffer in &graph.input_buffers {
+        let virtual_buffer = virtual_buffer_id_allocator.allocate();
+        log::trace!(
+            "  Input {:?} will use buffer {:?}",
+            input_buffer.usage,
+            virtual_buffer
+        );
+        buffer_usage_to_virtual.insert(input_buffer.usage, virtual_buffer);
+        share_written_buffer(
+            graph,
+            constraint_results,
+            input_buffer.usage,
+            &mut buffer_usage_to_virtual,
+            &mut virtual_buffer_id_allocator,
+        );
+    }
+
     log::trace!("Associate images written by nodes with virtual images");
     for node in node_execution_order.iter() {
         let node = graph.node(*node);
@@ -880,197 +1147,23 @@ fn assign_virtual_resources(
         }
 
         for written_image in written_images {
-            // Count the downstream users of this image based on if they need read-only access
-            // or write access. We need this information to determine which usages we can share
-            // the output data with.
-            //
-            // I'm not sure if this works as written. I was thinking we might have trouble with
-            // multiple readers, and then they pass to a writer, but now that I think of it, readers
-            // don't "output" anything.
-            //
-            // That said, this doesn't understand multiple writers of different subresources right
-            // now.
-            //
-            //TODO: This could be smarter to handle the case of a resource being read and then
-            // later written
-            //TODO: Could handle non-overlapping subresource ranges being written
-            let written_image_version_info = graph.image_version_info(written_image);
-            let mut read_count = 0;
-            //let mut read_ranges = vec![];
-            let mut write_count = 0;
-            //let mut write_ranges = vec![];
-            for usage in &written_image_version_info.read_usages {
-                if graph.image_usages[usage.0].usage_type.is_read_only() {
-                    read_count += 1;
-                //read_ranges.push(graph.image_usages[usage.0].subresource_range.clone());
-                } else {
-                    write_count += 1;
-                    //write_ranges.push(graph.image_usages[usage.0].subresource_range.clone());
-                }
-            }
-
-            // let mut has_overlapping_write = false;
-            // for i in 0..write_ranges.len() {
-            //     for j in 0..i {
-            //
-            //     }
-            // }
-
-            let write_virtual_image = *image_usage_to_virtual.get(&written_image).unwrap();
-            let write_type = graph.image_usages[written_image.0].usage_type;
-
-            let written_spec = constraint_results
-                .image_specification(written_image)
-                .unwrap();
-
-            for usage_resource_id in &written_image_version_info.read_usages {
-                let usage_spec = match constraint_results.image_specification(*usage_resource_id) {
-                    Some(usage_spec) => usage_spec,
-                    // If the reader of this image was culled, we may not have determined a spec.
-                    // If so, skip this usage
-                    None => continue,
-                };
-
-                // We can't share images if they aren't the same format
-                let specifications_match = *written_spec == *usage_spec;
-
-                // We can't share images unless it's a read or it's an exclusive write
-                let is_read_or_exclusive_write = (read_count > 0
-                    && graph.image_usages[usage_resource_id.0]
-                        .usage_type
-                        .is_read_only())
-                    || write_count <= 1;
-
-                let read_type = graph.image_usages[usage_resource_id.0].usage_type;
-                if specifications_match && is_read_or_exclusive_write {
-                    // it's a shared read or an exclusive write
-                    log::trace!(
-                        "    Usage {:?} will share an image with {:?} ({:?} -> {:?})",
-                        written_image,
-                        usage_resource_id,
-                        write_type,
-                        read_type
-                    );
-                    let overwritten_image =
-                        image_usage_to_virtual.insert(*usage_resource_id, write_virtual_image);
-
-                    assert!(overwritten_image.is_none());
-                } else {
-                    // allocate new image
-                    let virtual_image = virtual_image_id_allocator.allocate();
-                    log::trace!(
-                        "    Allocate image {:?} for {:?} ({:?} -> {:?})  (specifications_match match: {} is_read_or_exclusive_write: {})",
-                        virtual_image,
-                        usage_resource_id,
-                        write_type,
-                        read_type,
-                        specifications_match,
-                        is_read_or_exclusive_write
-                    );
-                    if !specifications_match {
-                        log::trace!("      written: {:?}", written_spec);
-                        log::trace!("      usage  : {:?}", usage_spec);
-                    }
-                    let overwritten_image =
-                        image_usage_to_virtual.insert(*usage_resource_id, virtual_image);
-
-                    assert!(overwritten_image.is_none());
-
-                    //TODO: One issue (aside from not doing any blits right now) is that images created in this way
-                    // aren't included in the assign_physical_images logic
-                    println!("      written: {:?}", written_spec);
-                    println!("      usage  : {:?}", usage_spec);
-                    panic!("Render graph does not currently support blit from one image to another to fix image compatibility");
-                }
-            }
+            share_written_image(
+                graph,
+                constraint_results,
+                written_image,
+                &mut image_usage_to_virtual,
+                &mut virtual_image_id_allocator,
+            );
         }
 
         for written_buffer in written_buffers {
-            // Count the downstream users of this image based on if they need read-only access
-            // or write access. We need this information to determine which usages we can share
-            // the output data with.
-            //
-            // I'm not sure if this works as written. I was thinking we might have trouble with
-            // multiple readers, and then they pass to a writer, but now that I think of it, readers
-            // don't "output" anything.
-            //TODO: This could be smarter to handle the case of a resource being read and then
-            // later written
-            let written_buffer_version_info = graph.buffer_version_info(written_buffer);
-            let mut read_count = 0;
-            let mut write_count = 0;
-            for usage in &written_buffer_version_info.read_usages {
-                if graph.buffer_usages[usage.0].usage_type.is_read_only() {
-                    read_count += 1;
-                } else {
-                    write_count += 1;
-                }
-            }
-
-            let write_virtual_buffer = *buffer_usage_to_virtual.get(&written_buffer).unwrap();
-            let write_type = graph.buffer_usages[written_buffer.0].usage_type;
-
-            let written_spec = constraint_results
-                .buffer_specification(written_buffer)
-                .unwrap();
-
-            for usage_resource_id in &written_buffer_version_info.read_usages {
-                let usage_spec = match constraint_results.buffer_specification(*usage_resource_id) {
-                    Some(usage_spec) => usage_spec,
-                    // If the reader of this buffer was culled, we may not have determined a spec.
-                    // If so, skip this usage
-                    None => continue,
-                };
-
-                // We can't share buffers if they aren't the same format
-                let specifications_match = *written_spec == *usage_spec;
-
-                // We can't share buffers unless it's a read or it's an exclusive write
-                let is_read_or_exclusive_write = (read_count > 0
-                    && graph.buffer_usages[usage_resource_id.0]
-                        .usage_type
-                        .is_read_only())
-                    || write_count <= 1;
-
-                let read_type = graph.buffer_usages[usage_resource_id.0].usage_type;
-                if specifications_match && is_read_or_exclusive_write {
-                    // it's a shared read or an exclusive write
-                    log::trace!(
-                        "    Usage {:?} will share a buffer with {:?} ({:?} -> {:?})",
-                        written_buffer,
-                        usage_resource_id,
-                        write_type,
-                        read_type
-                    );
-                    let overwritten_buffer =
-                        buffer_usage_to_virtual.insert(*usage_resource_id, write_virtual_buffer);
-
-                    assert!(overwritten_buffer.is_none());
-                } else {
-                    // allocate new buffer
-                    let virtual_buffer = virtual_buffer_id_allocator.allocate();
-                    log::trace!(
-                        "    Allocate buffer {:?} for {:?} ({:?} -> {:?})  (specifications_match match: {} is_read_or_exclusive_write: {})",
-                        virtual_buffer,
-                        usage_resource_id,
-                        write_type,
-                        read_type,
-                        specifications_match,
-                        is_read_or_exclusive_write
-                    );
-                    if !specifications_match {
-                        log::trace!("      written: {:?}", written_spec);
-                        log::trace!("      usage  : {:?}", usage_spec);
-                    }
-                    let overwritten_buffer =
-                        buffer_usage_to_virtual.insert(*usage_resource_id, virtual_buffer);
-
-                    assert!(overwritten_buffer.is_none());
-
-                    //TODO: One issue (aside from not doing any copies right now) is that buffers created in this way
-                    // aren't included in the assign_physical_buffers logic
-                    panic!("Render graph does not currently support blit from one buffer to another to fix buffer compatibility");
-                }
-            }
+            share_written_buffer(
+                graph,
+                constraint_results,
+                written_buffer,
+                &mut buffer_usage_to_virtual,
+                &mut virtual_buffer_id_allocator,
+            );
         }
     }
 
@@ -1162,6 +1255,7 @@ fn build_physical_passes(
                 passes.push(RenderGraphPass::Compute(RenderGraphComputePass {
                     node: compute_node,
                     pre_pass_barrier: Default::default(),
+                    post_pass_barrier: Default::default(),
                 }));
             }
             PassNode::RenderNode(renderpass_node) => {
@@ -1582,7 +1676,6 @@ fn assign_physical_resources(
     }
 
     //TODO: Find transients
-    //TODO: Mark input images as non-reuse?
     //TODO: Stay in same queue?
 
     struct PhysicalImage {
@@ -1648,6 +1741,58 @@ fn assign_physical_resources(
         );
     }
 
+    //
+    // Allocate physical IDs for all input images
+    //
+    for input_image in &graph.input_images {
+        let physical_image_id = PhysicalImageId(physical_images.len());
+        physical_images.push(PhysicalImage {
+            specification: input_image.specification.clone(),
+            last_node_pass_index: passes.len() - 1,
+            can_be_reused: false, // The image is owned outside the graph, so never alias it
+        });
+
+        let virtual_id = virtual_resources.image_usage_to_virtual[&input_image.usage];
+        let old = image_virtual_to_physical.insert(virtual_id, physical_image_id);
+        assert!(
+            old.is_none(),
+            "An image passed into the graph with read_external_image cannot also be an output image"
+        );
+        log::trace!(
+            "  Input Image {:?} -> {:?} Used in passes [{}:{}]",
+            virtual_id,
+            physical_image_id,
+            0,
+            passes.len() - 1
+        );
+    }
+
+    //
+    // Allocate physical IDs for all input buffers
+    //
+    for input_buffer in &graph.input_buffers {
+        let physical_buffer_id = PhysicalBufferId(physical_buffers.len());
+        physical_buffers.push(PhysicalBuffer {
+            specification: input_buffer.specification.clone(),
+            last_node_pass_index: passes.len() - 1,
+            can_be_reused: false, // The buffer is owned outside the graph, so never alias it
+        });
+
+        let virtual_id = virtual_resources.buffer_usage_to_virtual[&input_buffer.usage];
+        let old = buffer_virtual_to_physical.insert(virtual_id, physical_buffer_id);
+        assert!(
+            old.is_none(),
+            "A buffer passed into the graph with read_external_buffer cannot also be an output buffer"
+        );
+        log::trace!(
+            "  Input Buffer {:?} -> {:?} Used in passes [{}:{}]",
+            virtual_id,
+            physical_buffer_id,
+            0,
+            passes.len() - 1
+        );
+    }
+
     //
     // Determine the minimal set of physical images needed to represent all our virtual images,
     // given that virtual images can use the same physical image if their lifetimes don't overlap
@@ -1657,7 +1802,7 @@ fn assign_physical_resources(
     //
     for reuse_requirements in &image_reuse_requirements {
         if image_virtual_to_physical.contains_key(&reuse_requirements.virtual_id) {
-            // May already have been registered by output image
+            // May already have been registered by input/output image
             continue;
         }
 
@@ -1709,7 +1854,7 @@ fn assign_physical_resources(
 
     for reuse_requirements in &buffer_reuse_requirements {
         if buffer_virtual_to_physical.contains_key(&reuse_requirements.virtual_id) {
-            // May already have been registered by output buffer
+            // May already have been registered by input/output buffer
             continue;
         }
 
@@ -2044,6 +2189,17 @@ fn build_pass_barriers(
         Default::default()
     });
 
+    // Images/buffers passed into the graph start in whatever state the caller left them in
+    for input_image in &graph.input_images {
+        let physical_image = physical_resources.image_usage_to_physical[&input_image.usage];
+        image_states[physical_image.0].resource_state = input_image.initial_state;
+    }
+
+    for input_buffer in &graph.input_buffers {
+        let physical_buffer = physical_resources.buffer_usage_to_physical[&input_buffer.usage];
+        buffer_states[physical_buffer.0].resource_state = input_buffer.initial_state;
+    }
+
     for (pass_index, pass) in passes.iter_mut().enumerate() {
         log::trace!("pass {}", pass_index);
 
@@ -2182,7 +2338,7 @@ fn build_pass_barriers(
         // TODO: This only works if no one else reads it?
         log::trace!("Check for output images");
         for (output_image_index, output_image) in graph.output_images.iter().enumerate() {
-            if graph.image_version_info(output_image.usage).creator_node == subpass_node_id {
+            if graph.image_version_info(output_image.usage).creator_node == Some(subpass_node_id) {
                 let output_physical_image =
                     physical_resources.image_usage_to_physical[&output_image.usage];
                 log::trace!(
@@ -2225,6 +2381,43 @@ fn build_pass_barriers(
 
         //TODO: Need to do a dependency? Maybe by adding a flush?
     }
+
+    // Images/buffers passed into the graph are transitioned to the state the caller asked for
+    // after the last pass
+    if let Some(last_pass) = passes.last_mut() {
+        let mut image_barriers = vec![];
+        for input_image in &graph.input_images {
+            let physical_image = physical_resources.image_usage_to_physical[&input_image.usage];
+            let image_state = &image_states[physical_image.0];
+            if image_state.resource_state != input_image.final_state {
+                image_barriers.push(PrepassImageBarrier {
+                    image: physical_image,
+                    old_state: image_state.resource_state,
+                    new_state: input_image.final_state,
+                });
+            }
+        }
+
+        let mut buffer_barriers = vec![];
+        for input_buffer in &graph.input_buffers {
+            let physical_buffer = physical_resources.buffer_usage_to_physical[&input_buffer.usage];
+            let buffer_state = &buffer_states[physical_buffer.0];
+            if buffer_state.resource_state != input_buffer.final_state {
+                buffer_barriers.push(PrepassBufferBarrier {
+                    buffer: physical_buffer,
+                    old_state: buffer_state.resource_state,
+                    new_state: input_buffer.final_state,
+                });
+            }
+        }
+
+        if !image_barriers.is_empty() || !buffer_barriers.is_empty() {
+            last_pass.add_post_pass_barrier(PostpassBarrier {
+                image_barriers,
+                buffer_barriers,
+            });
+        }
+    }
 }
 
 #[profiling::function]
@@ -2349,6 +2542,9 @@ fn create_output_passes(
                     color_render_targets,
                     depth_stencil_render_target,
                     render_target_meta,
+                    uses_secondary_command_buffers: graph
+                        .node(pass.node_id)
+                        .uses_secondary_command_buffers,
                 };
 
                 renderpasses.push(RenderGraphOutputPass::Renderpass(output_pass));
@@ -2357,7 +2553,7 @@ fn create_output_passes(
                 let output_pass = RenderGraphOutputComputePass {
                     node: pass.node,
                     pre_pass_barrier: pass.pre_pass_barrier,
-                    post_pass_barrier: None,
+                    post_pass_barrier: pass.post_pass_barrier,
                     debug_name: graph.node(pass.node).name,
                 };
 
@@ -2474,7 +2670,12 @@ fn verify_unculled_image_usages_specifications_exist(
         //log::trace!("  Image {:?} {:?}", image_index, image_resource.name);
         for (_version_index, version) in image_resource.versions.iter().enumerate() {
             // Check the write usage for this version
-            if node_execution_order.contains(&version.creator_node)
+            let is_scheduled = match version.creator_node {
+                Some(creator_node) => node_execution_order.contains(&creator_node),
+                None => true,
+            };
+
+            if is_scheduled
                 && constraint_results
                     .images
                     .get(&version.create_usage)
@@ -2492,6 +2693,7 @@ fn verify_unculled_image_usages_specifications_exist(
                 let usage_info = &graph.image_usages[usage.0];
                 let is_scheduled = match &usage_info.user {
                     RenderGraphImageUser::Node(node_id) => node_execution_order.contains(node_id),
+                    RenderGraphImageUser::Input(_) => true,
                     RenderGraphImageUser::Output(_) => true,
                 };
 
@@ -2622,10 +2824,27 @@ fn print_final_image_usage(
     }
 }
 
+#[derive(Debug)]
+pub struct RenderGraphPlanInputImage {
+    pub input_id: RenderGraphInputImageId,
+    pub name: Option<RenderGraphResourceName>,
+    pub src_image: ResourceArc<ImageViewResource>,
+    pub initial_state: RafxResourceState,
+}
+
+#[derive(Debug)]
+pub struct RenderGraphPlanInputBuffer {
+    pub input_id: RenderGraphInputBufferId,
+    pub src_buffer: ResourceArc<BufferResource>,
+    pub initial_state: RafxResourceState,
+}
+
 #[derive(Debug)]
 pub struct RenderGraphPlanOutputImage {
     pub output_id: RenderGraphOutputImageId,
+    pub name: Option<RenderGraphResourceName>,
     pub dst_image: ResourceArc<ImageViewResource>,
+    pub final_state: RafxResourceState,
 }
 
 #[derive(Debug)]
@@ -2638,6 +2857,8 @@ pub struct RenderGraphPlanOutputBuffer {
 /// includes the computed metadata and does not allocate resources.
 pub struct RenderGraphPlan {
     pub(super) passes: Vec<RenderGraphOutputPass>,
+    pub(super) input_images: FnvHashMap<PhysicalImageViewId, RenderGraphPlanInputImage>,
+    pub(super) input_buffers: FnvHashMap<PhysicalBufferId, RenderGraphPlanInputBuffer>,
     pub(super) output_images: FnvHashMap<PhysicalImageViewId, RenderGraphPlanOutputImage>,
     pub(super) output_buffers: FnvHashMap<PhysicalBufferId, RenderGraphPlanOutputBuffer>,
     pub(super) intermediate_images: FnvHashMap<PhysicalImageId, RenderGraphImageSpecification>,
@@ -2807,9 +3028,50 @@ impl RenderGraphPlan {
         let output_passes = create_output_passes(&graph, passes);
 
         //
-        // Separate the output images from the intermediate images (the rendergraph will be
+        // Separate the input/output images from the intermediate images (the rendergraph will be
         // responsible for allocating the intermediate images)
         //
+        let mut input_images: FnvHashMap<PhysicalImageViewId, RenderGraphPlanInputImage> =
+            Default::default();
+        let mut input_image_physical_ids = FnvHashSet::default();
+        for input_image in &graph.input_images {
+            let input_image_view =
+                assign_physical_resources_result.image_usage_to_image_view[&input_image.usage];
+
+            input_images.insert(
+                input_image_view,
+                RenderGraphPlanInputImage {
+                    input_id: input_image.input_image_id,
+                    name: graph.image_resource(input_image.usage).name,
+                    src_image: input_image.src_image.clone(),
+                    initial_state: input_image.initial_state,
+                },
+            );
+
+            input_image_physical_ids.insert(
+                assign_physical_resources_result.image_views[input_image_view.0].physical_image,
+            );
+        }
+
+        let mut input_buffers: FnvHashMap<PhysicalBufferId, RenderGraphPlanInputBuffer> =
+            Default::default();
+        let mut input_buffer_physical_ids = FnvHashSet::default();
+        for input_buffer in &graph.input_buffers {
+            let input_buffer_id =
+                assign_physical_resources_result.buffer_usage_to_physical[&input_buffer.usage];
+
+            input_buffers.insert(
+                input_buffer_id,
+                RenderGraphPlanInputBuffer {
+                    input_id: input_buffer.input_buffer_id,
+                    src_buffer: input_buffer.src_buffer.clone(),
+                    initial_state: input_buffer.initial_state,
+                },
+            );
+
+            input_buffer_physical_ids.insert(input_buffer_id);
+        }
+
         let mut output_images: FnvHashMap<PhysicalImageViewId, RenderGraphPlanOutputImage> =
             Default::default();
         let mut output_image_physical_ids = FnvHashSet::default();
@@ -2821,7 +3083,9 @@ impl RenderGraphPlan {
                 output_image_view,
                 RenderGraphPlanOutputImage {
                     output_id: output_image.output_image_id,
+                    name: graph.image_resource(output_image.usage).name,
                     dst_image: output_image.dst_image.clone(),
+                    final_state: output_image.final_state,
                 },
             );
 
@@ -2856,7 +3120,9 @@ impl RenderGraphPlan {
             .enumerate()
         {
             let physical_image = PhysicalImageId(index);
-            if output_image_physical_ids.contains(&physical_image) {
+            if output_image_physical_ids.contains(&physical_image)
+                || input_image_physical_ids.contains(&physical_image)
+            {
                 continue;
             }
 
@@ -2871,7 +3137,9 @@ impl RenderGraphPlan {
             .enumerate()
         {
             let physical_buffer = PhysicalBufferId(index);
-            if output_buffer_physical_ids.contains(&physical_buffer) {
+            if output_buffer_physical_ids.contains(&physical_buffer)
+                || input_buffer_physical_ids.contains(&physical_buffer)
+            {
                 continue;
             }
 
@@ -2904,6 +3172,8 @@ impl RenderGraphPlan {
 
         RenderGraphPlan {
             passes: output_passes,
+            input_images,
+            input_buffers,
             output_images,
             output_buffers,
             intermediate_images,
@@ -2919,3 +3189,144 @@ impl RenderGraphPlan {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::resources::ResourceId;
+    use crate::ImageResource;
+    use rafx_api::empty::RafxTextureEmpty;
+    use rafx_api::{RafxResourceType, RafxTexture};
+
+    const EXTENTS: RenderGraphImageExtents = RenderGraphImageExtents::Custom(64, 64, 1);
+
+    fn specification() -> RenderGraphImageSpecification {
+        RenderGraphImageSpecification {
+            samples: RafxSampleCount::SampleCount1,
+            format: RafxFormat::R8G8B8A8_UNORM,
+            resource_type: RafxResourceType::TEXTURE | RafxResourceType::RENDER_TARGET_COLOR,
+            extents: EXTENTS,
+            layer_count: 1,
+            mip_count: 1,
+        }
+    }
+
+    fn color_constraint() -> RenderGraphImageConstraint {
+        RenderGraphImageConstraint {
+            samples: Some(RafxSampleCount::SampleCount1),
+            format: Some(RafxFormat::R8G8B8A8_UNORM),
+            extents: Some(EXTENTS),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_input_image_is_not_aliased() {
+        // The resources send themselves here when dropped, so these must outlive the graph
+        let (image_tx, _image_rx) = crossbeam_channel::unbounded();
+        let (image_view_tx, _image_view_rx) = crossbeam_channel::unbounded();
+
+        let image = ResourceArc::new(
+            ImageResource {
+                image: RafxTexture::Empty(RafxTextureEmpty),
+                image_key: None,
+            },
+            ResourceId(0),
+            image_tx,
+        );
+        let image_view = ResourceArc::new(
+            ImageViewResource {
+                image,
+                image_view_key: None,
+                texture_bind_type: None,
+            },
+            ResourceId(1),
+            image_view_tx,
+        );
+
+        // input -> first -> second -> third -> output. The image written by second starts after
+        // the last read of the input image and has the same specification, so it could reuse the
+        // input image if the input were treated like an intermediate image.
+        let mut graph = RenderGraphBuilder::default();
+        let input = graph.read_external_image(
+            image_view.clone(),
+            specification(),
+            Default::default(),
+            RafxResourceState::COPY_DST,
+            RafxResourceState::COPY_SRC,
+        );
+
+        let first = graph.add_node("first", RenderGraphQueue::DefaultGraphics);
+        graph.sample_image(first, input, Default::default(), Default::default());
+        let first_color =
+            graph.create_color_attachment(first, 0, None, color_constraint(), Default::default());
+
+        let second = graph.add_node("second", RenderGraphQueue::DefaultGraphics);
+        let sampled_first_color =
+            graph.sample_image(second, first_color, Default::default(), Default::default());
+        let second_color =
+            graph.create_color_attachment(second, 0, None, color_constraint(), Default::default());
+
+        let third = graph.add_node("third", RenderGraphQueue::DefaultGraphics);
+        graph.sample_image(third, second_color, Default::default(), Default::default());
+        let third_color =
+            graph.create_color_attachment(third, 0, None, color_constraint(), Default::default());
+
+        graph.set_output_image(
+            third_color,
+            image_view,
+            specification(),
+            Default::default(),
+            RafxResourceState::SHADER_RESOURCE,
+        );
+
+        let plan = graph.build_plan();
+        let physical_image = |usage: RenderGraphImageUsageId| {
+            plan.image_views[plan.image_usage_to_view[&usage].0].physical_image
+        };
+
+        // The input image gets its own physical image that is not allocated by the graph
+        let input_view = plan.image_usage_to_view[&input];
+        let input_physical_image = physical_image(input);
+        assert!(plan.input_images.contains_key(&input_view));
+        assert!(!plan.intermediate_images.contains_key(&input_physical_image));
+
+        // None of the images created by the graph may alias it
+        for created in &[first_color, sampled_first_color, second_color, third_color] {
+            assert_ne!(physical_image(*created), input_physical_image);
+        }
+
+        // The first pass transitions the input image out of the state it was passed in with
+        let first_pass = &plan.passes[plan.node_to_pass_index[&first]];
+        let barrier = first_pass
+            .pre_pass_barrier()
+            .expect("first pass should have a barrier for the input image");
+        let input_barrier = barrier
+            .image_barriers
+            .iter()
+            .find(|image_barrier| image_barrier.image == input_physical_image)
+            .expect("first pass should have a barrier for the input image");
+        assert_eq!(input_barrier.old_state, RafxResourceState::COPY_DST);
+        assert_eq!(
+            input_barrier.new_state,
+            RafxResourceState::PIXEL_SHADER_RESOURCE
+        );
+
+        // The last pass transitions the input image to the final state it was passed in with
+        let last_pass = plan.passes.last().unwrap();
+        let input_barrier = last_pass
+            .post_pass_barrier()
+            .and_then(|barrier| {
+                barrier
+                    .image_barriers
+                    .iter()
+                    .find(|image_barrier| image_barrier.image == input_physical_image)
+            })
+            .expect("last pass should have a barrier for the input image");
+        assert_eq!(
+            input_barrier.old_state,
+            RafxResourceState::PIXEL_SHADER_RESOURCE
+        );
+        assert_eq!(input_barrier.new_state, RafxResourceState::COPY_SRC);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::graph::graph_buffer::PhysicalBufferId;
+use crate::graph::graph_gpu_timings::{RenderGraphGpuTimings, RenderGraphGpuTimingsState};
 use crate::graph::graph_image::{PhysicalImageId, PhysicalImageViewId};
 use crate::graph::{
     RenderGraphBufferSpecification, RenderGraphImageSpecification, RenderGraphPlan,
@@ -6,7 +7,9 @@ use crate::graph::{
 };
 use crate::{BufferResource, ImageResource, ImageViewResource, ResourceArc, ResourceLookupSet};
 use fnv::FnvHashMap;
-use rafx_api::{RafxBufferDef, RafxDeviceContext, RafxMemoryUsage, RafxResult, RafxTextureDef};
+use rafx_api::{
+    RafxBufferDef, RafxDeviceContext, RafxMemoryUsage, RafxQueue, RafxResult, RafxTextureDef,
+};
 use std::sync::{Arc, Mutex};
 
 #[derive(Clone, Hash, PartialEq, Eq, Debug)]
@@ -33,7 +36,16 @@ struct RenderGraphCachedImage {
 pub struct RenderGraphCacheInner {
     buffers: FnvHashMap<RenderGraphCachedBufferKey, Vec<RenderGraphCachedBuffer>>,
     images: FnvHashMap<RenderGraphCachedImageKey, Vec<RenderGraphCachedImage>>,
-    current_frame_index: u64,
+
+    // Keeps track of what index in the cache we will use next. This starts at 0 for each key every
+    // frame and increments every time we use a buffer/image. If the next index is >= length of the
+    // cached list, we allocate one and push it into that key's list. This persists across all the
+    // graphs allocated in a frame so that they never share intermediate resources.
+    next_buffer_to_use: FnvHashMap<RenderGraphCachedBufferKey, usize>,
+    next_image_to_use: FnvHashMap<RenderGraphCachedImageKey, usize>,
+
+    pub(super) gpu_timings: RenderGraphGpuTimingsState,
+    pub(super) current_frame_index: u64,
     frames_to_persist: u64,
 }
 
@@ -42,6 +54,9 @@ impl RenderGraphCacheInner {
         RenderGraphCacheInner {
             buffers: Default::default(),
             images: Default::default(),
+            next_buffer_to_use: Default::default(),
+            next_image_to_use: Default::default(),
+            gpu_timings: RenderGraphGpuTimingsState::new(max_frames_in_flight as u64 + 1),
             current_frame_index: 0,
             frames_to_persist: max_frames_in_flight as u64 + 1,
         }
@@ -63,12 +78,18 @@ impl RenderGraphCacheInner {
 
         self.images.retain(|_k, v| !v.is_empty());
 
+        self.next_buffer_to_use.clear();
+        self.next_image_to_use.clear();
+
         self.current_frame_index += 1;
     }
 
     pub fn clear(&mut self) {
         self.buffers.clear();
         self.images.clear();
+        self.next_buffer_to_use.clear();
+        self.next_image_to_use.clear();
+        self.gpu_timings.clear();
     }
 
     pub(super) fn allocate_buffers(
@@ -81,14 +102,13 @@ impl RenderGraphCacheInner {
         let mut buffer_resources: FnvHashMap<PhysicalBufferId, ResourceArc<BufferResource>> =
             Default::default();
 
-        // Keeps track of what index in the cache we will use next. This starts at 0 for each key
-        // and increments every time we use an image. If the next image is >= length of buffers, we
-        // allocate one and push it into that key's list of cached buffers
-        let mut next_buffer_to_use = FnvHashMap::<RenderGraphCachedBufferKey, usize>::default();
-
         // Using a buffer will bump the keep_until_frame for that buffer
         let keep_until_frame = self.current_frame_index + self.frames_to_persist;
 
+        for (&physical_id, buffer) in &graph.input_buffers {
+            buffer_resources.insert(physical_id, buffer.src_buffer.clone());
+        }
+
         for (&physical_id, buffer) in &graph.output_buffers {
             buffer_resources.insert(physical_id, buffer.dst_buffer.clone());
         }
@@ -100,7 +120,7 @@ impl RenderGraphCacheInner {
                 specification: specification.clone(),
             };
 
-            let next_buffer_index = next_buffer_to_use.entry(key.clone()).or_insert(0);
+            let next_buffer_index = self.next_buffer_to_use.entry(key.clone()).or_insert(0);
             let matching_cached_buffers = self
                 .buffers
                 .entry(key.clone())
@@ -130,7 +150,10 @@ impl RenderGraphCacheInner {
                     //initial_state: key.specification.initial_state,
                     ..Default::default()
                 })?;
-                let buffer = resources.insert_buffer(buffer);
+                let buffer = resources.insert_buffer_with_debug_name(
+                    buffer,
+                    &format!("render graph buffer {:?}", id),
+                );
 
                 log::trace!(
                     "  Buffer {:?} - CREATE {:?}  (key: {:?}, index: {})",
@@ -167,14 +190,14 @@ impl RenderGraphCacheInner {
         let mut image_resources: FnvHashMap<PhysicalImageId, ResourceArc<ImageResource>> =
             Default::default();
 
-        // Keeps track of what index in the cache we will use next. This starts at 0 for each key
-        // and increments every time we use an image. If the next image is >= length of images, we
-        // allocate one and push it into that key's list of cached images
-        let mut next_image_to_use = FnvHashMap::<RenderGraphCachedImageKey, usize>::default();
-
         // Using an image will bump the keep_until_frame for that image
         let keep_until_frame = self.current_frame_index + self.frames_to_persist;
 
+        for (id, image) in &graph.input_images {
+            let physical_id = graph.image_views[id.0].physical_image;
+            image_resources.insert(physical_id, image.src_image.get_raw().image);
+        }
+
         for (id, image) in &graph.output_images {
             let physical_id = graph.image_views[id.0].physical_image;
             image_resources.insert(physical_id, image.dst_image.get_raw().image);
@@ -188,7 +211,7 @@ impl RenderGraphCacheInner {
                 swapchain_surface_info: swapchain_surface_info.clone(),
             };
 
-            let next_image_index = next_image_to_use.entry(key.clone()).or_insert(0);
+            let next_image_index = self.next_image_to_use.entry(key.clone()).or_insert(0);
             let matching_cached_images = self
                 .images
                 .entry(key.clone())
@@ -224,7 +247,8 @@ impl RenderGraphCacheInner {
                     resource_type: specification.resource_type,
                     dimensions: Default::default(),
                 })?;
-                let image = resources.insert_image(image);
+                let image = resources
+                    .insert_image_with_debug_name(image, &format!("render graph image {:?}", id));
 
                 log::trace!(
                     "  Image {:?} - CREATE {:?}  (key: {:?}, index: {})",
@@ -261,8 +285,12 @@ impl RenderGraphCacheInner {
             ResourceArc<ImageViewResource>,
         > = Default::default();
 
-        // For output images, the physical id just needs to be associated with the image provided by
-        // the user
+        // For input/output images, the physical id just needs to be associated with the image
+        // provided by the user
+        for (id, image) in &graph.input_images {
+            image_view_resources.insert(*id, image.src_image.clone());
+        }
+
         for (id, image) in &graph.output_images {
             image_view_resources.insert(*id, image.dst_image.clone());
         }
@@ -270,7 +298,7 @@ impl RenderGraphCacheInner {
         for (id, view) in graph.image_views.iter().enumerate() {
             let id = PhysicalImageViewId(id);
 
-            // Skip output images (handled above). They already have ImageViewResources
+            // Skip input/output images (handled above). They already have ImageViewResources
             if image_view_resources.contains_key(&id) {
                 continue;
             }
@@ -313,4 +341,22 @@ impl RenderGraphCache {
     pub fn clear(&self) {
         self.inner.lock().unwrap().clear();
     }
+
+    /// The most recent GPU timings for each node of the render graph that have been read back.
+    /// Only the first graph executed in a frame is timed. Returns None if the device does not
+    /// support timestamp queries or no results are ready yet.
+    pub fn gpu_timings(&self) -> Option<RenderGraphGpuTimings> {
+        self.inner.lock().unwrap().gpu_timings.latest().cloned()
+    }
+
+    /// Should be called after the command buffers of the frame's first executed graph have been
+    /// submitted to `queue`. This lets the query pool used for GPU timings be recycled as soon as
+    /// the GPU finishes the frame. If it isn't called, a query pool is only recycled once all of
+    /// its results are available.
+    pub fn submit_gpu_timings_fence(
+        &self,
+        queue: &RafxQueue,
+    ) -> RafxResult<()> {
+        self.inner.lock().unwrap().gpu_timings.submit_fence(queue)
+    }
 }
@@ -26,6 +26,10 @@ use graph_pass::*;
 mod graph_plan;
 pub use graph_plan::RenderGraphPlan;
 
+mod graph_gpu_timings;
+pub use graph_gpu_timings::RenderGraphGpuTimings;
+pub use graph_gpu_timings::RenderGraphNodeGpuTiming;
+
 mod graph_resource_cache;
 pub use graph_resource_cache::RenderGraphCache;
 
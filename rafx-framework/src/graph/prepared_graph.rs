@@ -6,7 +6,7 @@ use crate::graph::graph_pass::{PrepassBufferBarrier, PrepassImageBarrier, Render
 use crate::graph::graph_plan::RenderGraphPlan;
 use crate::graph::{
     RenderGraphBufferUsageId, RenderGraphBuilder, RenderGraphImageUsageId,
-    RenderGraphNodeVisitNodeCallback,
+    RenderGraphNodeVisitNodeCallback, RenderGraphResourceName,
 };
 use crate::render_features::{PreparedRenderData, RenderJobBeginExecuteGraphContext};
 use crate::resources::DynCommandBuffer;
@@ -16,7 +16,8 @@ use fnv::FnvHashMap;
 use rafx_api::{
     RafxBarrierQueueTransition, RafxBufferBarrier, RafxColorRenderTargetBinding, RafxCommandBuffer,
     RafxCommandBufferDef, RafxCommandPoolDef, RafxDepthStencilRenderTargetBinding,
-    RafxDeviceContext, RafxExtents2D, RafxFormat, RafxQueue, RafxResult, RafxTextureBarrier,
+    RafxDeviceContext, RafxExtents2D, RafxFormat, RafxQueue, RafxResourceState, RafxResult,
+    RafxTextureBarrier,
 };
 use std::hash::Hash;
 
@@ -74,6 +75,9 @@ pub struct VisitRenderpassNodeArgs<'graph, 'write> {
     pub command_buffer: DynCommandBuffer,
     pub render_target_meta: GraphicsPipelineRenderTargetMeta,
     pub graph_context: RenderGraphContext<'graph, 'write>,
+    // If set, the renderpass was begun for secondary command buffers and all drawing must be
+    // recorded into secondary command buffers allocated for this queue
+    pub secondary_command_buffer_queue: Option<RafxQueue>,
 }
 
 /// Encapsulates a render graph plan and all resources required to execute it
@@ -95,6 +99,19 @@ impl PreparedRenderGraph {
         self.graph_plan.passes[pass_index].debug_name()
     }
 
+    /// Finds an output image by the name given to it with `set_image_name()`. Returns the image
+    /// and the state it will be left in once the graph has executed.
+    pub fn output_image_by_name(
+        &self,
+        name: RenderGraphResourceName,
+    ) -> Option<(ResourceArc<ImageViewResource>, RafxResourceState)> {
+        self.graph_plan
+            .output_images
+            .values()
+            .find(|x| x.name == Some(name))
+            .map(|x| (x.dst_image.clone(), x.final_state))
+    }
+
     pub fn new(
         device_context: &RafxDeviceContext,
         resource_context: &ResourceContext,
@@ -267,7 +284,7 @@ impl PreparedRenderGraph {
 
     pub fn execute_graph<'write>(
         &'write self,
-        prepared_render_data: PreparedRenderData<'write>,
+        prepared_render_data: &PreparedRenderData<'write>,
         queue: &RafxQueue,
     ) -> RafxResult<Vec<DynCommandBuffer>> {
         profiling::scope!("Execute Graph");
@@ -285,21 +302,50 @@ impl PreparedRenderGraph {
 
         command_buffer.begin()?;
 
-        let render_graph_context = RenderGraphContext {
-            prepared_render_graph: &self,
-            prepared_render_data: &prepared_render_data,
+        // Several graphs may be executed with the same render data in a frame (i.e. one per
+        // window). Work that should only happen once per frame is written by the first one.
+        let is_first_graph_in_frame = prepared_render_data.mark_graph_executed();
+
+        //
+        // Every pass is wrapped with timestamps so that GPU time can be reported per node. The
+        // results are read back a few frames later via RenderGraphCache::gpu_timings()
+        //
+        let mut gpu_timings_writer = if is_first_graph_in_frame {
+            let mut cache = self
+                .resource_context
+                .render_graph_cache()
+                .inner
+                .lock()
+                .unwrap();
+            let frame_index = cache.current_frame_index;
+            cache.gpu_timings.begin_frame(
+                &self.device_context,
+                &mut command_writer,
+                &command_buffer,
+                frame_index,
+                self.graph_plan.passes.len(),
+            )?
+        } else {
+            None
         };
 
-        let args = OnBeginExecuteGraphArgs {
-            graph_context: render_graph_context,
-            command_buffer: command_buffer.clone(),
+        let render_graph_context = RenderGraphContext {
+            prepared_render_graph: &self,
+            prepared_render_data,
         };
 
-        let mut write_context =
-            RenderJobBeginExecuteGraphContext::from_on_begin_execute_graph_args(&args);
-        args.graph_context
-            .prepared_render_data()
-            .on_begin_execute_graph(&mut write_context)?;
+        if is_first_graph_in_frame {
+            let args = OnBeginExecuteGraphArgs {
+                graph_context: render_graph_context,
+                command_buffer: command_buffer.clone(),
+            };
+
+            let mut write_context =
+                RenderJobBeginExecuteGraphContext::from_on_begin_execute_graph_args(&args);
+            args.graph_context
+                .prepared_render_data()
+                .on_begin_execute_graph(&mut write_context)?;
+        }
 
         //
         // Iterate through all passes
@@ -313,6 +359,14 @@ impl PreparedRenderGraph {
 
             let node_id = pass.node();
 
+            if let Some(gpu_timings_writer) = &mut gpu_timings_writer {
+                gpu_timings_writer.begin_node(&command_buffer, node_id, pass.debug_name())?;
+            }
+
+            // Label the pass so it can be found easily in RenderDoc/Xcode/etc.
+            command_buffer
+                .cmd_push_group_debug_name(pass.debug_name().unwrap_or("unnamed pass"))?;
+
             if let Some(pre_pass_barrier) = pass.pre_pass_barrier() {
                 log::trace!(
                     "prepass barriers for pass {} {:?}",
@@ -383,13 +437,31 @@ impl PreparedRenderGraph {
                     //println!("color bindings:\n{:#?}", color_target_bindings);
                     //println!("depth binding:\n{:#?}", depth_target_binding);
 
-                    command_buffer
-                        .cmd_begin_render_pass(&color_target_bindings, depth_target_binding)?;
+                    let use_secondary_command_buffers = pass.uses_secondary_command_buffers
+                        && self
+                            .device_context
+                            .device_info()
+                            .supports_multithreaded_command_recording;
+
+                    if use_secondary_command_buffers {
+                        command_buffer.cmd_begin_render_pass_with_secondary_command_buffers(
+                            &color_target_bindings,
+                            depth_target_binding,
+                        )?;
+                    } else {
+                        command_buffer
+                            .cmd_begin_render_pass(&color_target_bindings, depth_target_binding)?;
+                    }
 
                     let args = VisitRenderpassNodeArgs {
                         render_target_meta: pass.render_target_meta.clone(),
                         graph_context: render_graph_context,
                         command_buffer: command_buffer.clone(),
+                        secondary_command_buffer_queue: if use_secondary_command_buffers {
+                            Some(queue.clone())
+                        } else {
+                            None
+                        },
                     };
 
                     self.visit_renderpass_node(node_id, args)?;
@@ -418,6 +490,22 @@ impl PreparedRenderGraph {
                     &post_pass_barrier.image_barriers,
                 )?;
             }
+
+            command_buffer.cmd_pop_group_debug_name()?;
+
+            if let Some(gpu_timings_writer) = &mut gpu_timings_writer {
+                gpu_timings_writer.end_node(&command_buffer)?;
+            }
+        }
+
+        if let Some(gpu_timings_writer) = gpu_timings_writer {
+            self.resource_context
+                .render_graph_cache()
+                .inner
+                .lock()
+                .unwrap()
+                .gpu_timings
+                .end_frame(gpu_timings_writer);
         }
 
         command_buffer.end()?;
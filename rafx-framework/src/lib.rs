@@ -12,4 +12,7 @@ pub mod visibility;
 
 pub use rafx_api::RafxResult;
 
+// Used by generated shader code to set push constants
+pub use rafx_api::{RafxCommandBuffer, RafxRootSignature};
+
 pub const MAX_FRAMES_IN_FLIGHT: usize = 2;
@@ -64,17 +64,37 @@ impl<'a> ViewVisibilityJob<'a> {
         extract_context: &RenderJobExtractContext<'extract>,
     ) -> RenderViewVisibilityQuery {
         let mut view_frustum = self.view.view_frustum();
-        let visibility_query = view_frustum
-            .query_visibility(extract_context.visibility_config)
-            .unwrap();
-
-        let visibility_object_lookup = self.visibility_region.object_lookup();
-        let render_feature_mask = self.view.render_feature_mask();
-
+        let shadowed_view_frustums = self.view.shadowed_view_frustums();
         let mut render_objects = extract_context
             .allocation_context
             .query_visibility_vecs(&self.view);
 
+        let visibility_query = if shadowed_view_frustums.is_empty() {
+            view_frustum.query_visibility(extract_context.visibility_config)
+        } else {
+            view_frustum
+                .query_shadow_casters(shadowed_view_frustums, extract_context.visibility_config)
+        };
+
+        // A view that can't be queried (e.g. its projection was never set) is skipped for this frame
+        let visibility_query = match visibility_query {
+            Ok(visibility_query) => visibility_query,
+            Err(err) => {
+                log::warn!(
+                    "Skipping visibility for view {}: {:?}",
+                    self.view.debug_name(),
+                    err
+                );
+                return RenderViewVisibilityQuery {
+                    view: self.view().clone(),
+                    per_view_render_objects: render_objects,
+                };
+            }
+        };
+
+        let visibility_object_lookup = self.visibility_region.object_lookup();
+        let render_feature_mask = self.view.render_feature_mask();
+
         let visible_objects = &visibility_query.objects;
         for visibility_object in visible_objects.iter().map(|visibility_result| {
             visibility_object_lookup.object_ref(self.visibility_object_id(visibility_result))
@@ -3,9 +3,11 @@ mod render_feature_write_job;
 mod render_job_begin_execute_graph_context;
 mod render_job_command_buffer_context;
 mod render_job_write_context;
+mod render_job_write_thread_pool;
 
 pub use prepared_render_data::*;
 pub use render_feature_write_job::*;
 pub use render_job_begin_execute_graph_context::*;
 pub use render_job_command_buffer_context::*;
 pub use render_job_write_context::*;
+pub use render_job_write_thread_pool::*;
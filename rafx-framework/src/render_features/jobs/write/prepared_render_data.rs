@@ -1,6 +1,10 @@
 use crate::render_features::render_features_prelude::*;
+use crate::DynCommandBuffer;
 use fnv::FnvHashMap;
-use rafx_api::RafxResult;
+use rafx_api::{
+    RafxCommandBuffer, RafxCommandBufferDef, RafxCommandPoolDef, RafxQueue, RafxResult,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 /// The total number of submit nodes needed by each `RenderView`. The `RenderView` is represented by
@@ -19,6 +23,8 @@ pub type SubmitNodeBlocks = FnvHashMap<ViewPhase, ViewPhaseSubmitNodeBlock>;
 pub struct PreparedRenderData<'write> {
     submit_node_blocks: &'write SubmitNodeBlocks,
     write_jobs: Vec<Option<Arc<dyn RenderFeatureWriteJob<'write> + 'write>>>,
+    write_thread_pool: Option<&'write dyn RenderJobWriteThreadPool>,
+    has_executed_graph: AtomicBool,
     _write_context: RenderJobWriteContext<'write>,
 }
 
@@ -26,15 +32,23 @@ impl<'write> PreparedRenderData<'write> {
     pub fn new(
         submit_node_blocks: &'write SubmitNodeBlocks,
         write_jobs: Vec<Option<Arc<dyn RenderFeatureWriteJob<'write> + 'write>>>,
+        write_thread_pool: Option<&'write dyn RenderJobWriteThreadPool>,
         _write_context: RenderJobWriteContext<'write>,
     ) -> Self {
         Self {
             submit_node_blocks,
             write_jobs,
+            write_thread_pool,
+            has_executed_graph: AtomicBool::new(false),
             _write_context,
         }
     }
 
+    // Returns true if this is the first graph to be executed with this data
+    pub(crate) fn mark_graph_executed(&self) -> bool {
+        !self.has_executed_graph.swap(true, Ordering::Relaxed)
+    }
+
     pub fn on_begin_execute_graph(
         &self,
         write_context: &mut RenderJobBeginExecuteGraphContext,
@@ -73,6 +87,124 @@ impl<'write> PreparedRenderData<'write> {
                 &[]
             };
 
+        if let Some(queue) = write_context.secondary_command_buffer_queue.clone() {
+            self.write_submit_nodes_with_secondary_command_buffers(
+                view,
+                render_phase_index,
+                submit_nodes,
+                write_context,
+                &queue,
+            )
+        } else {
+            Self::write_submit_nodes(
+                &self.write_jobs,
+                view,
+                render_phase_index,
+                submit_nodes,
+                write_context,
+            )
+        }
+    }
+
+    // Splits the submit nodes into chunks that are each written into a secondary command buffer,
+    // possibly in parallel, and then executes them in order on the primary command buffer
+    fn write_submit_nodes_with_secondary_command_buffers(
+        &self,
+        view: &RenderView,
+        render_phase_index: RenderPhaseIndex,
+        submit_nodes: &[RenderFeatureSubmitNode],
+        write_context: &mut RenderJobCommandBufferContext,
+        queue: &RafxQueue,
+    ) -> RafxResult<()> {
+        if submit_nodes.is_empty() {
+            return Ok(());
+        }
+
+        let chunk_size = self
+            .write_thread_pool
+            .map(|write_thread_pool| write_thread_pool.submit_node_chunk_size().max(1))
+            .unwrap_or_else(|| submit_nodes.len());
+        let chunks: Vec<&[RenderFeatureSubmitNode]> = submit_nodes.chunks(chunk_size).collect();
+
+        // Command pools can't be used from multiple threads, so each chunk gets its own. The
+        // secondary command buffers are begun here in the order they will execute because metal
+        // orders them by when they were created.
+        let dyn_command_pool_allocator = write_context
+            .resource_context
+            .create_dyn_command_pool_allocator();
+        let mut command_pools = Vec::with_capacity(chunks.len());
+        let mut chunk_write_contexts = Vec::with_capacity(chunks.len());
+        for _ in 0..chunks.len() {
+            let mut command_pool = dyn_command_pool_allocator.allocate_dyn_pool(
+                queue,
+                &RafxCommandPoolDef { transient: true },
+                0,
+            )?;
+            let command_buffer = command_pool
+                .allocate_dyn_command_buffer(&RafxCommandBufferDef { is_secondary: true })?;
+            command_buffer.begin_secondary(&write_context.command_buffer)?;
+
+            command_pools.push(command_pool);
+            chunk_write_contexts.push(Mutex::new(RenderJobCommandBufferContext::new(
+                write_context.resource_context.clone(),
+                command_buffer,
+                write_context.render_target_meta.clone(),
+            )));
+        }
+
+        let write_jobs = &self.write_jobs;
+        let write_chunk = |chunk_index: usize| -> RafxResult<()> {
+            let mut chunk_write_context = chunk_write_contexts[chunk_index].lock();
+            Self::write_submit_nodes(
+                write_jobs,
+                view,
+                render_phase_index,
+                chunks[chunk_index],
+                &mut *chunk_write_context,
+            )
+        };
+
+        match self.write_thread_pool {
+            Some(write_thread_pool) if chunks.len() > 1 => {
+                write_thread_pool.run_write_chunks(chunks.len(), &write_chunk)?
+            }
+            _ => {
+                for chunk_index in 0..chunks.len() {
+                    write_chunk(chunk_index)?;
+                }
+            }
+        }
+
+        let secondary_command_buffers: Vec<DynCommandBuffer> = chunk_write_contexts
+            .into_iter()
+            .map(|chunk_write_context| chunk_write_context.into_inner().command_buffer)
+            .collect();
+
+        for secondary_command_buffer in &secondary_command_buffers {
+            secondary_command_buffer.end()?;
+        }
+
+        let secondary_command_buffer_refs: Vec<&RafxCommandBuffer> = secondary_command_buffers
+            .iter()
+            .map(|secondary_command_buffer| &**secondary_command_buffer)
+            .collect();
+        write_context
+            .command_buffer
+            .cmd_execute_secondary(&secondary_command_buffer_refs)?;
+
+        // The pools are returned to the allocator and will be reset once the frame is no longer in
+        // flight
+        drop(command_pools);
+        Ok(())
+    }
+
+    fn write_submit_nodes(
+        write_jobs: &[Option<Arc<dyn RenderFeatureWriteJob<'write> + 'write>>],
+        view: &RenderView,
+        render_phase_index: RenderPhaseIndex,
+        submit_nodes: &[RenderFeatureSubmitNode],
+        write_context: &mut RenderJobCommandBufferContext,
+    ) -> RafxResult<()> {
         let mut previous_node_feature_index: i32 = -1;
         let mut previous_view_frame_index: Option<ViewFrameIndex> = None;
 
@@ -81,7 +213,7 @@ impl<'write> PreparedRenderData<'write> {
                 if previous_node_feature_index != -1 {
                     // call revert setup
                     log::trace!("revert setup for feature {}", previous_node_feature_index);
-                    self.write_jobs[previous_node_feature_index as usize]
+                    write_jobs[previous_node_feature_index as usize]
                         .as_ref()
                         .unwrap()
                         .revert_setup(
@@ -93,7 +225,7 @@ impl<'write> PreparedRenderData<'write> {
 
                 previous_node_feature_index = submit_node.feature_index() as i32;
                 previous_view_frame_index = Some(
-                    self.write_jobs[submit_node.feature_index() as usize]
+                    write_jobs[submit_node.feature_index() as usize]
                         .as_ref()
                         .unwrap()
                         .view_frame_index(view),
@@ -101,7 +233,7 @@ impl<'write> PreparedRenderData<'write> {
 
                 // call apply setup
                 log::trace!("apply setup for feature {}", submit_node.feature_index());
-                self.write_jobs[submit_node.feature_index() as usize]
+                write_jobs[submit_node.feature_index() as usize]
                     .as_ref()
                     .unwrap()
                     .apply_setup(
@@ -117,7 +249,7 @@ impl<'write> PreparedRenderData<'write> {
                 submit_node.submit_node_id(),
             );
 
-            self.write_jobs[submit_node.feature_index() as usize]
+            write_jobs[submit_node.feature_index() as usize]
                 .as_ref()
                 .unwrap()
                 .render_submit_node(
@@ -131,7 +263,7 @@ impl<'write> PreparedRenderData<'write> {
         if previous_node_feature_index != -1 {
             // call revert setup
             log::trace!("revert setup for feature: {}", previous_node_feature_index);
-            self.write_jobs[previous_node_feature_index as usize]
+            write_jobs[previous_node_feature_index as usize]
                 .as_ref()
                 .unwrap()
                 .revert_setup(
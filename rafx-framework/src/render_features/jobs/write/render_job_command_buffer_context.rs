@@ -1,12 +1,15 @@
 use crate::graph::VisitRenderpassNodeArgs;
 use crate::{DynCommandBuffer, GraphicsPipelineRenderTargetMeta, ResourceContext};
-use rafx_api::RafxDeviceContext;
+use rafx_api::{RafxDeviceContext, RafxQueue};
 
 pub struct RenderJobCommandBufferContext {
     pub device_context: RafxDeviceContext,
     pub resource_context: ResourceContext,
     pub command_buffer: DynCommandBuffer,
     pub render_target_meta: GraphicsPipelineRenderTargetMeta,
+    /// Set if `command_buffer` is a primary command buffer in a renderpass that must be written
+    /// with secondary command buffers allocated for this queue
+    pub secondary_command_buffer_queue: Option<RafxQueue>,
 }
 
 impl RenderJobCommandBufferContext {
@@ -20,16 +23,19 @@ impl RenderJobCommandBufferContext {
             resource_context,
             command_buffer,
             render_target_meta,
+            secondary_command_buffer_queue: None,
         }
     }
 
     pub fn from_graph_visit_render_pass_args(
         args: &VisitRenderpassNodeArgs
     ) -> RenderJobCommandBufferContext {
-        RenderJobCommandBufferContext::new(
+        let mut context = RenderJobCommandBufferContext::new(
             args.graph_context.resource_context().clone(),
             args.command_buffer.clone(),
             args.render_target_meta.clone(),
-        )
+        );
+        context.secondary_command_buffer_queue = args.secondary_command_buffer_queue.clone();
+        context
     }
 }
@@ -0,0 +1,17 @@
+use rafx_api::RafxResult;
+
+/// Allows `PreparedRenderData` to split a large `ViewPhaseSubmitNodeBlock` into chunks that are
+/// written into separate secondary command buffers in parallel. This is only used for renderpasses
+/// that were set up with `RenderGraphBuilder::set_renderpass_uses_secondary_command_buffers`.
+pub trait RenderJobWriteThreadPool: Sync + Send {
+    /// The maximum number of `SubmitNode`s written into a single secondary command buffer.
+    fn submit_node_chunk_size(&self) -> usize;
+
+    /// Calls `write_chunk` for each chunk index in `0..num_chunks`. The calls may happen in any
+    /// order and on any thread. Returns an error if any of the calls returned an error.
+    fn run_write_chunks(
+        &self,
+        num_chunks: usize,
+        write_chunk: &(dyn Fn(usize) -> RafxResult<()> + Sync),
+    ) -> RafxResult<()>;
+}
@@ -55,13 +55,13 @@ pub mod render_features_prelude {
         RenderFeatureDebugConstants, RenderFeatureExtractJob, RenderFeatureFramePacket,
         RenderFeatureIndex, RenderFeaturePrepareJob, RenderFeatureSubmitNode,
         RenderFeatureSubmitNodeBlock, RenderFeatureSubmitPacket, RenderFeatureViewPacket,
-        RenderFeatureViewSubmitPacket, RenderFeatureWriteJob, RenderObjectHandle, RenderObjectId,
-        RenderObjectInstanceId, RenderObjectInstanceObjectIds, RenderObjectInstancePerViewId,
-        RenderObjectsMap, RenderPhase, RenderPhaseIndex, RenderRegistry, RenderRegistryBuilder,
-        RenderView, RenderViewIndex, RenderViewSet, RenderViewSubmitNodeCount,
-        RenderViewVisibilityQuery, SubmitNode, SubmitNodeBlocks, SubmitNodeId, SubmitNodeSortKey,
-        SubmitPacketData, ViewFrameIndex, ViewPacketSize, ViewPhase, ViewPhaseSubmitNodeBlock,
-        ViewVisibilityJob, VisibleRenderObjects,
+        RenderFeatureViewSubmitPacket, RenderFeatureWriteJob, RenderJobWriteThreadPool,
+        RenderObjectHandle, RenderObjectId, RenderObjectInstanceId, RenderObjectInstanceObjectIds,
+        RenderObjectInstancePerViewId, RenderObjectsMap, RenderPhase, RenderPhaseIndex,
+        RenderRegistry, RenderRegistryBuilder, RenderView, RenderViewIndex, RenderViewSet,
+        RenderViewSubmitNodeCount, RenderViewVisibilityQuery, SubmitNode, SubmitNodeBlocks,
+        SubmitNodeId, SubmitNodeSortKey, SubmitPacketData, ViewFrameIndex, ViewPacketSize,
+        ViewPhase, ViewPhaseSubmitNodeBlock, ViewVisibilityJob, VisibleRenderObjects,
     };
 
     pub use crate::visibility::{ObjectId, VisibilityRegion};
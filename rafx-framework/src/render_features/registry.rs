@@ -168,6 +168,17 @@ impl RenderRegistry {
         self.inner.phase_name_to_index.get(name).copied()
     }
 
+    pub fn render_phase_name(
+        &self,
+        render_phase_index: RenderPhaseIndex,
+    ) -> Option<&str> {
+        self.inner
+            .phase_name_to_index
+            .iter()
+            .find(|(_, &index)| index == render_phase_index)
+            .map(|(name, _)| name.as_str())
+    }
+
     pub fn submit_node_sort_function(
         &self,
         render_phase_index: RenderPhaseIndex,
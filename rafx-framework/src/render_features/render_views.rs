@@ -34,6 +34,42 @@ impl RenderViewSet {
         let view_index = self.view_count.fetch_add(1, Ordering::Release);
         RenderView::new(
             view_frustum,
+            Vec::default(),
+            view_index,
+            eye_position,
+            view,
+            proj,
+            extents,
+            depth_range,
+            render_phase_mask,
+            render_feature_mask,
+            render_feature_flag_mask,
+            debug_name,
+        )
+    }
+
+    /// Creates a view that renders a light's shadow map. Instead of the objects inside the view's
+    /// frustum, the visibility query returns the objects that can cast a shadow into any of the
+    /// `shadowed_view_frustums`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_shadow_map_view(
+        &self,
+        view_frustum: ViewFrustumArc,
+        shadowed_view_frustums: Vec<ViewFrustumArc>,
+        eye_position: Vec3,
+        view: Mat4,
+        proj: Mat4,
+        extents: (u32, u32),
+        depth_range: RenderViewDepthRange,
+        render_phase_mask: RenderPhaseMask,
+        render_feature_mask: RenderFeatureMask,
+        render_feature_flag_mask: RenderFeatureFlagMask,
+        debug_name: String,
+    ) -> RenderView {
+        let view_index = self.view_count.fetch_add(1, Ordering::Release);
+        RenderView::new(
+            view_frustum,
+            shadowed_view_frustums,
             view_index,
             eye_position,
             view,
@@ -55,6 +91,8 @@ impl RenderViewSet {
 ////////////////// Views //////////////////
 pub struct RenderViewInner {
     view_frustum: ViewFrustumArc,
+    // If not empty, the view is a shadow map and only needs the objects casting shadows into these
+    shadowed_view_frustums: Vec<ViewFrustumArc>,
     eye_position: Vec3,
     view: Mat4,
     proj: Mat4,
@@ -161,8 +199,10 @@ pub struct RenderView {
 }
 
 impl RenderView {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         view_frustum: ViewFrustumArc,
+        shadowed_view_frustums: Vec<ViewFrustumArc>,
         view_index: RenderViewIndex,
         eye_position: Vec3,
         view: Mat4,
@@ -179,6 +219,7 @@ impl RenderView {
         log::trace!("Allocate view {} {}", debug_name, view_index);
         let inner = RenderViewInner {
             view_frustum,
+            shadowed_view_frustums,
             eye_position,
             view,
             proj,
@@ -202,6 +243,10 @@ impl RenderView {
         self.inner.view_frustum.clone()
     }
 
+    pub fn shadowed_view_frustums(&self) -> &[ViewFrustumArc] {
+        &self.inner.shadowed_view_frustums
+    }
+
     pub fn eye_position(&self) -> Vec3 {
         self.inner.eye_position
     }
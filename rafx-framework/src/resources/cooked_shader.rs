@@ -1,5 +1,6 @@
 use crate::{
     DescriptorSetLayout, DescriptorSetLayoutBinding, MaterialPassVertexInput, ShaderModuleHash,
+    UniformBufferLayout, UniformBufferLayoutLookup,
 };
 use fnv::{FnvHashMap, FnvHashSet};
 use rafx_api::{
@@ -17,6 +18,17 @@ pub struct SlotLocation {
 
 pub type SlotNameLookup = FnvHashMap<String, FnvHashSet<SlotLocation>>;
 
+// A member of a uniform buffer. Members of nested structs and arrays are flattened, i.e.
+// "lights[1].color". type_name is the GLSL type (one of the builtin types the shader processor
+// supports, like float or vec4)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub struct ReflectedUniformMember {
+    pub name: String,
+    pub type_name: String,
+    pub offset: u32,
+    pub size: u32,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
 pub struct ReflectedDescriptorSetLayoutBinding {
     // Basic info required to create the RafxRootSignature
@@ -29,6 +41,10 @@ pub struct ReflectedDescriptorSetLayoutBinding {
     // and automatically bind it - this makes binding data easy to do without having to manage
     // buffers.
     pub internal_buffer_per_descriptor_size: Option<u32>,
+
+    // Only populated for uniform buffers. Allows setting members of the buffer by name.
+    #[serde(default)]
+    pub uniform_members: Vec<ReflectedUniformMember>,
 }
 
 impl Into<DescriptorSetLayoutBinding> for ReflectedDescriptorSetLayoutBinding {
@@ -66,12 +82,28 @@ pub struct ReflectedEntryPoint {
     pub vertex_inputs: Vec<ReflectedVertexInput>,
 }
 
+// A permutation of a shader compiled with a set of keywords #define'd
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CookedShaderVariant {
+    // Sorted, and always a subset of the keywords declared by the shader
+    pub keywords: Vec<String>,
+    pub hash: ShaderModuleHash,
+    pub shader_package: RafxShaderPackage,
+    pub entry_points: Vec<ReflectedEntryPoint>,
+}
+
 // An import format that will get turned into ShaderAssetData
 #[derive(Serialize, Deserialize)]
 pub struct CookedShaderPackage {
+    // The shader compiled with no keywords defined
     pub hash: ShaderModuleHash,
     pub shader_package: RafxShaderPackage,
     pub entry_points: Vec<ReflectedEntryPoint>,
+
+    // All keywords the shader declares, and the permutations that were compiled with at least one
+    // of them defined
+    pub keywords: Vec<String>,
+    pub variants: Vec<CookedShaderVariant>,
 }
 
 impl CookedShaderPackage {
@@ -83,11 +115,19 @@ impl CookedShaderPackage {
             .iter()
             .find(|x| x.rafx_api_reflection.entry_point_name == entry_point_name)
     }
+
+    pub fn find_variant(
+        &self,
+        keywords: &[String],
+    ) -> Option<&CookedShaderVariant> {
+        self.variants.iter().find(|x| x.keywords == keywords)
+    }
 }
 
 pub struct ReflectedShader {
     pub descriptor_set_layout_defs: Vec<DescriptorSetLayout>,
     pub slot_name_lookup: SlotNameLookup,
+    pub uniform_buffer_layout_lookup: UniformBufferLayoutLookup,
     pub vertex_inputs: Option<Arc<Vec<MaterialPassVertexInput>>>,
 }
 
@@ -95,6 +135,7 @@ impl ReflectedShader {
     pub fn new(entry_points: &[&ReflectedEntryPoint]) -> RafxResult<ReflectedShader> {
         let mut descriptor_set_layout_defs = Vec::default();
         let mut slot_name_lookup: SlotNameLookup = Default::default();
+        let mut uniform_buffer_layout_lookup: UniformBufferLayoutLookup = Default::default();
         let mut vertex_inputs = None;
 
         // We iterate through the entry points we will hit for each stage. Each stage may define
@@ -221,6 +262,39 @@ impl ReflectedShader {
                             descriptor_set_layout_defs[set_index].bindings.push(def);
                         }
 
+                        // Uniform buffers that are bound by value can have their members set by
+                        // name (i.e. from a material instance)
+                        if let (Some(size), false) = (
+                            binding.internal_buffer_per_descriptor_size,
+                            binding.uniform_members.is_empty(),
+                        ) {
+                            let location = SlotLocation {
+                                layout_index: set_index as u32,
+                                binding_index: binding.resource.binding,
+                            };
+
+                            let layout = UniformBufferLayout {
+                                size,
+                                members: binding.uniform_members.clone(),
+                            };
+
+                            if let Some(existing_layout) =
+                                uniform_buffer_layout_lookup.get(&location)
+                            {
+                                if **existing_layout != layout {
+                                    let error = format!(
+                                        "Load Material Failed - Pass is using shaders in different stages with different uniform buffer members for set={} binding={}",
+                                        set_index,
+                                        binding.resource.binding
+                                    );
+                                    log::error!("{}", error);
+                                    return Err(error)?;
+                                }
+                            } else {
+                                uniform_buffer_layout_lookup.insert(location, Arc::new(layout));
+                            }
+                        }
+
                         if let Some(slot_name) = &binding.resource.name {
                             log::trace!(
                                 "  Assign slot name '{}' to binding set={} binding={}",
@@ -245,6 +319,7 @@ impl ReflectedShader {
             vertex_inputs,
             descriptor_set_layout_defs,
             slot_name_lookup,
+            uniform_buffer_layout_lookup,
         })
     }
 }
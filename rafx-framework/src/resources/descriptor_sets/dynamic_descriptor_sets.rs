@@ -9,7 +9,7 @@ use crate::resources::descriptor_sets::{
 };
 use crate::resources::resource_lookup::{DescriptorSetLayoutResource, ImageViewResource};
 use crate::resources::ResourceArc;
-use crate::BufferResource;
+use crate::{BufferResource, MaterialParameterValue, UniformBufferLayout};
 use rafx_api::{RafxCommandBuffer, RafxResult};
 use std::fmt::Formatter;
 
@@ -246,6 +246,39 @@ impl DynDescriptorSet {
             log::warn!("Tried to set buffer data on a binding index that does not exist");
         }
     }
+
+    // Writes a single named member of a uniform buffer that is bound by value. Other members keep
+    // their current values (or zero if the buffer has never been written)
+    pub fn set_buffer_member(
+        &mut self,
+        binding_index: u32,
+        layout: &UniformBufferLayout,
+        member_name: &str,
+        value: &MaterialParameterValue,
+    ) -> RafxResult<()> {
+        let key = DescriptorSetElementKey {
+            dst_binding: binding_index,
+            array_index: 0,
+        };
+
+        let element = self.write_set.elements.get_mut(&key).ok_or_else(|| {
+            format!(
+                "Tried to set buffer member {} on a binding index that does not exist",
+                member_name
+            )
+        })?;
+
+        let mut data = match &element.buffer_info.buffer {
+            Some(DescriptorSetWriteElementBufferData::Data(data)) => data.clone(),
+            _ => Vec::default(),
+        };
+
+        layout.write_member(member_name, value, &mut data)?;
+
+        element.buffer_info.buffer = Some(DescriptorSetWriteElementBufferData::Data(data));
+        self.pending_write_set.elements.insert(key, element.clone());
+        Ok(())
+    }
 }
 
 impl DescriptorSetBindings for DynDescriptorSet {
@@ -1,7 +1,7 @@
 use crate::{
     DescriptorSetWriteSet, FixedFunctionState, MaterialPassResource, MaterialPassVertexInput,
     RafxResult, ReflectedEntryPoint, ReflectedShader, ResourceArc, ResourceContext,
-    ShaderModuleResource, SlotNameLookup,
+    ShaderModuleResource, SlotNameLookup, UniformBufferLayoutLookup,
 };
 use fnv::FnvHashSet;
 use rafx_api::{RafxImmutableSamplerKey, RafxShaderStageFlags};
@@ -46,6 +46,8 @@ pub struct MaterialPassInner {
     //TODO: Use hash instead of string. Probably want to have a "hashed string" type that keeps the
     // string around only in debug mode. Maybe this could be generalized to a HashOfThing<T>.
     pub pass_slot_name_lookup: Arc<SlotNameLookup>,
+    // Layouts of the uniform buffers in the pass that can be written by member name
+    pub pass_uniform_buffer_layout_lookup: Arc<UniformBufferLayoutLookup>,
     // This is a hint of what render phase we should register a material with in the pipeline cache
     // It is optional and the pipeline cache can handle materials used in any render phase
     //pub render_phase_index: Option<RenderPhaseIndex>,
@@ -156,6 +158,9 @@ impl MaterialPass {
             shader_modules,
             material_pass_resource: material_pass.clone(),
             pass_slot_name_lookup: Arc::new(reflected_shader.slot_name_lookup),
+            pass_uniform_buffer_layout_lookup: Arc::new(
+                reflected_shader.uniform_buffer_layout_lookup,
+            ),
             vertex_inputs,
         };
 
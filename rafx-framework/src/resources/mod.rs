@@ -13,6 +13,7 @@ pub use resource_lookup::GraphicsPipelineResource;
 pub use resource_lookup::ImageKey;
 pub use resource_lookup::ImageResource;
 pub use resource_lookup::ImageViewResource;
+pub use resource_lookup::MaterialPassHash;
 pub use resource_lookup::MaterialPassResource;
 pub use resource_lookup::MaterialPassVertexInput;
 pub use resource_lookup::ResourceHash;
@@ -52,11 +53,22 @@ mod material;
 pub use material::MaterialPass;
 pub use material::MaterialShaderStage;
 
+mod uniform_buffer_layout;
+pub use uniform_buffer_layout::MaterialParameterValue;
+pub use uniform_buffer_layout::UniformBufferLayout;
+pub use uniform_buffer_layout::UniformBufferLayoutLookup;
+
 mod pipeline_cache;
 pub use pipeline_cache::GraphicsPipelineCache;
 pub use pipeline_cache::GraphicsPipelineRenderTargetMeta;
 pub use pipeline_cache::GraphicsPipelineRenderTargetMetaHash;
 
+mod pipeline_manifest;
+pub use pipeline_manifest::GraphicsPipelineManifest;
+pub use pipeline_manifest::GraphicsPipelineManifestEntry;
+pub use pipeline_manifest::GraphicsPipelineManifestVertexLayout;
+pub use pipeline_manifest::MaterialPassAssetId;
+
 mod vertex_data;
 pub use vertex_data::VertexCopyError;
 pub use vertex_data::VertexData;
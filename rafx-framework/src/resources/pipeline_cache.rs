@@ -3,19 +3,22 @@ use crate::render_features::{
 };
 use crate::resources::resource_arc::{ResourceId, WeakResourceArc};
 use crate::resources::vertex_data::{VertexDataSetLayout, VertexDataSetLayoutHash};
-use crate::{GraphicsPipelineResource, MaterialPassResource, ResourceArc, ResourceLookupSet};
+use crate::{
+    GraphicsPipelineManifest, GraphicsPipelineManifestEntry, GraphicsPipelineResource,
+    MaterialPassAssetId, MaterialPassResource, ResourceArc, ResourceLookupSet,
+};
 use fnv::{FnvHashMap, FnvHashSet, FnvHasher};
 use rafx_api::{
-    RafxFormat, RafxResult, RafxSampleCount, RafxVertexLayout, RafxVertexLayoutAttribute,
-    RafxVertexLayoutBuffer,
+    RafxFormat, RafxPipelineCacheDef, RafxResult, RafxSampleCount, RafxVertexLayout,
+    RafxVertexLayoutAttribute, RafxVertexLayoutBuffer,
 };
 use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 //TODO: Allow caching for N frames
 //TODO: Return a kind of ResourceArc for a cached pipeline. Allow dropping after N frames pass with
 // nothing request/using it
-//TODO: vulkan pipeline cache object
 
 //TODO: Remove Serialize/Deserialize
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -86,10 +89,21 @@ struct CachedGraphicsPipelineKey {
     vertex_data_set_layout: VertexDataSetLayoutHash,
 }
 
+// Unlike CachedGraphicsPipelineKey, this only contains values that are stable between runs
+#[derive(PartialEq, Eq, Hash)]
+struct GraphicsPipelineManifestKey {
+    render_phase_index: RenderPhaseIndex,
+    material_pass_asset: MaterialPassAssetId,
+    render_target_meta_hash: GraphicsPipelineRenderTargetMetaHash,
+    vertex_data_set_layout: VertexDataSetLayoutHash,
+}
+
 #[derive(PartialEq, Eq)]
 struct CachedGraphicsPipeline {
     material_pass_resource: WeakResourceArc<MaterialPassResource>,
     graphics_pipeline: ResourceArc<GraphicsPipelineResource>,
+    // Set for precompiled pipelines so that they are not dropped before anything draws with them
+    pinned_until_used: bool,
 }
 
 #[derive(Debug)]
@@ -108,9 +122,20 @@ pub struct GraphicsPipelineCacheInner {
 
     cached_pipelines: FnvHashMap<CachedGraphicsPipelineKey, CachedGraphicsPipeline>,
 
+    // The assets that material passes were loaded from. More than one asset may produce the same
+    // material pass
+    material_pass_assets: FnvHashMap<MaterialPassAssetId, WeakResourceArc<MaterialPassResource>>,
+    material_pass_asset_ids: FnvHashMap<ResourceId, Vec<MaterialPassAssetId>>,
+
+    // Every pipeline that has been created, even if it was later dropped from cached_pipelines
+    manifest_entries: FnvHashMap<GraphicsPipelineManifestKey, GraphicsPipelineManifestEntry>,
+
     current_frame_index: u64,
     frames_to_persist: u64,
 
+    // Where the API pipeline cache is loaded from/saved to, if persisting it was requested
+    pipeline_cache_path: Option<PathBuf>,
+
     #[cfg(debug_assertions)]
     vertex_data_set_layouts: FnvHashMap<VertexDataSetLayoutHash, VertexDataSetLayout>,
     #[cfg(debug_assertions)]
@@ -166,8 +191,12 @@ impl GraphicsPipelineCache {
             render_target_meta_assignments,
             material_pass_assignments,
             cached_pipelines: Default::default(),
+            material_pass_assets: Default::default(),
+            material_pass_asset_ids: Default::default(),
+            manifest_entries: Default::default(),
             current_frame_index: 0,
             frames_to_persist: DEFAULT_FRAMES_TO_PERSIST,
+            pipeline_cache_path: None,
             #[cfg(debug_assertions)]
             vertex_data_set_layouts: Default::default(),
             #[cfg(debug_assertions)]
@@ -361,6 +390,34 @@ impl GraphicsPipelineCache {
         // pipelines?
     }
 
+    // Associates a material pass with the asset it was loaded from so that pipelines created for
+    // it can be recorded in the pipeline manifest. Registering the same asset id again (i.e. the
+    // material was reloaded) replaces the previous material pass.
+    pub fn register_material_pass_asset(
+        &self,
+        material_pass_asset: MaterialPassAssetId,
+        material_pass: &ResourceArc<MaterialPassResource>,
+    ) {
+        let mut guard = self.inner.lock().unwrap();
+        let inner = &mut *guard;
+        #[cfg(debug_assertions)]
+        {
+            inner.lock_call_count += 1;
+        }
+
+        inner
+            .material_pass_assets
+            .insert(material_pass_asset, material_pass.downgrade());
+
+        let asset_ids = inner
+            .material_pass_asset_ids
+            .entry(material_pass.get_hash())
+            .or_default();
+        if !asset_ids.contains(&material_pass_asset) {
+            asset_ids.push(material_pass_asset);
+        }
+    }
+
     pub fn try_get_graphics_pipeline(
         &self,
         render_phase_index: RenderPhaseIndex,
@@ -429,9 +486,10 @@ impl GraphicsPipelineCache {
 
         inner
             .cached_pipelines
-            .get(&key)
+            .get_mut(&key)
             .map(|x| {
                 debug_assert!(x.material_pass_resource.upgrade().is_some());
+                x.pinned_until_used = false;
                 Ok(x.graphics_pipeline.clone())
             })
             .or_else(|| {
@@ -511,12 +569,22 @@ impl GraphicsPipelineCache {
                     );
 
                     if let Ok(pipeline) = pipeline {
+                        Self::record_manifest_entry(
+                            inner,
+                            &self.render_registry,
+                            render_phase_index,
+                            material_pass,
+                            render_target_meta,
+                            vertex_data_set_layout,
+                        );
+
                         inner.cached_pipelines.insert(
                             key,
                             CachedGraphicsPipeline {
                                 graphics_pipeline: pipeline.clone(),
                                 //render_target_meta: render_target_meta.clone(),
                                 material_pass_resource: material_pass.downgrade(),
+                                pinned_until_used: false,
                             },
                         );
 
@@ -530,6 +598,157 @@ impl GraphicsPipelineCache {
             })
     }
 
+    fn record_manifest_entry(
+        inner: &mut GraphicsPipelineCacheInner,
+        render_registry: &RenderRegistry,
+        render_phase_index: RenderPhaseIndex,
+        material_pass: &ResourceArc<MaterialPassResource>,
+        render_target_meta: &GraphicsPipelineRenderTargetMeta,
+        vertex_data_set_layout: &VertexDataSetLayout,
+    ) {
+        let render_phase_name = match render_registry.render_phase_name(render_phase_index) {
+            Some(render_phase_name) => render_phase_name,
+            None => return,
+        };
+
+        let asset_ids = match inner.material_pass_asset_ids.get(&material_pass.get_hash()) {
+            Some(asset_ids) => asset_ids,
+            // Material passes that weren't loaded from an asset can't be found on a later run
+            None => return,
+        };
+
+        let material_pass_hash = material_pass.get_raw().material_pass_key.hash();
+        for &material_pass_asset in asset_ids {
+            // Skip assets that have been reloaded with a different material pass since this one was
+            // registered
+            let is_current_pass = inner
+                .material_pass_assets
+                .get(&material_pass_asset)
+                .and_then(|x| x.upgrade())
+                .map(|x| x.get_hash() == material_pass.get_hash())
+                .unwrap_or(false);
+            if !is_current_pass {
+                continue;
+            }
+
+            let key = GraphicsPipelineManifestKey {
+                render_phase_index,
+                material_pass_asset,
+                render_target_meta_hash: render_target_meta.render_target_meta_hash(),
+                vertex_data_set_layout: vertex_data_set_layout.hash(),
+            };
+
+            // Replaces the entry recorded for an older version of the asset
+            inner.manifest_entries.insert(
+                key,
+                GraphicsPipelineManifestEntry::new(
+                    render_phase_name.to_string(),
+                    material_pass_asset,
+                    material_pass_hash,
+                    render_target_meta,
+                    vertex_data_set_layout,
+                ),
+            );
+        }
+    }
+
+    // Returns every pipeline this cache has created so far. Save this at shutdown and pass it to
+    // precompile_pipelines() on the next run.
+    pub fn pipeline_manifest(&self) -> GraphicsPipelineManifest {
+        let mut guard = self.inner.lock().unwrap();
+        #[cfg(debug_assertions)]
+        {
+            guard.lock_call_count += 1;
+        }
+
+        GraphicsPipelineManifest {
+            entries: guard.manifest_entries.values().cloned().collect(),
+        }
+    }
+
+    // Creates all the pipelines in the manifest so that they don't need to be compiled the first
+    // time they are used. The material assets must already be loaded. If a material was modified
+    // since the manifest was saved, the pipeline is built for its current version. Entries for
+    // render phases or material assets that don't exist are skipped, and since only pipelines that
+    // are created get recorded, they are pruned from the next pipeline_manifest(). progress_callback
+    // is called with (completed, total) after each entry. Precompiled pipelines stay in the cache
+    // until they are used for the first time, after that they are dropped under the same rules as
+    // pipelines created while drawing.
+    pub fn precompile_pipelines<F: FnMut(usize, usize)>(
+        &self,
+        manifest: &GraphicsPipelineManifest,
+        mut progress_callback: F,
+    ) -> RafxResult<()> {
+        let total = manifest.entries.len();
+
+        for (index, entry) in manifest.entries.iter().enumerate() {
+            let render_phase_index = self
+                .render_registry
+                .render_phase_index_from_name(&entry.render_phase);
+            let material_pass = self
+                .inner
+                .lock()
+                .unwrap()
+                .material_pass_assets
+                .get(&entry.material_pass_asset)
+                .and_then(|x| x.upgrade());
+
+            if let (Some(render_phase_index), Some(material_pass)) =
+                (render_phase_index, material_pass)
+            {
+                let render_target_meta = entry.render_target_meta();
+                let vertex_data_set_layout = entry.vertex_data_set_layout();
+                self.get_or_create_graphics_pipeline(
+                    render_phase_index,
+                    &material_pass,
+                    &render_target_meta,
+                    &vertex_data_set_layout,
+                )?;
+
+                self.pin_graphics_pipeline(
+                    &material_pass,
+                    &render_target_meta,
+                    &vertex_data_set_layout,
+                );
+            } else {
+                log::debug!(
+                    "Skipping pipeline manifest entry, the render phase {} or material pass {:?} is not loaded",
+                    entry.render_phase,
+                    entry.material_pass_asset
+                );
+            }
+
+            progress_callback(index + 1, total);
+        }
+
+        Ok(())
+    }
+
+    // Nothing may draw with a precompiled pipeline for many frames (for example if they are
+    // compiled during a loading screen), so keep it cached until it is used
+    fn pin_graphics_pipeline(
+        &self,
+        material_pass: &ResourceArc<MaterialPassResource>,
+        render_target_meta: &GraphicsPipelineRenderTargetMeta,
+        vertex_data_set_layout: &VertexDataSetLayout,
+    ) {
+        let key = CachedGraphicsPipelineKey {
+            material_pass: material_pass.get_hash(),
+            render_target_meta_hash: render_target_meta.render_target_meta_hash(),
+            vertex_data_set_layout: vertex_data_set_layout.hash(),
+        };
+
+        let mut guard = self.inner.lock().unwrap();
+        #[cfg(debug_assertions)]
+        {
+            guard.lock_call_count += 1;
+        }
+
+        if let Some(cached_pipeline) = guard.cached_pipelines.get_mut(&key) {
+            cached_pipeline.pinned_until_used = true;
+        }
+    }
+
     pub fn precache_pipelines_for_all_phases(&self) -> RafxResult<()> {
         let mut guard = self.inner.lock().unwrap();
         let _inner = &mut *guard;
@@ -596,6 +815,23 @@ impl GraphicsPipelineCache {
             phase.retain(|_k, v| v.upgrade().is_some());
         }
 
+        inner
+            .material_pass_assets
+            .retain(|_k, v| v.upgrade().is_some());
+        let material_pass_assets = &inner.material_pass_assets;
+        inner
+            .material_pass_asset_ids
+            .retain(|material_pass, asset_ids| {
+                asset_ids.retain(|asset_id| {
+                    material_pass_assets
+                        .get(asset_id)
+                        .and_then(|x| x.upgrade())
+                        .map(|x| x.get_hash() == *material_pass)
+                        .unwrap_or(false)
+                });
+                !asset_ids.is_empty()
+            });
+
         //TODO: Could do something smarter than this to track when the last one is dropped
         let mut all_render_target_meta = FnvHashSet::default();
         for phase in &inner.render_target_meta_assignments {
@@ -605,7 +841,7 @@ impl GraphicsPipelineCache {
         }
 
         inner.cached_pipelines.retain(|k, v| {
-            let render_target_meta_still_exists = all_render_target_meta.contains(&k.render_target_meta_hash);
+            let render_target_meta_still_exists = v.pinned_until_used || all_render_target_meta.contains(&k.render_target_meta_hash);
             let material_pass_still_exists = v.material_pass_resource.upgrade().is_some();
 
             if !render_target_meta_still_exists || !material_pass_still_exists {
@@ -629,4 +865,242 @@ impl GraphicsPipelineCache {
 
         guard.cached_pipelines.clear();
     }
+
+    // Creates the API pipeline cache that all pipelines will be compiled with, seeded with data
+    // from a previous run if the file exists. The path is remembered so that save_pipeline_cache()
+    // can write the cache back to it. Missing or incompatible data is not an error, the cache will
+    // just start out empty.
+    pub fn load_pipeline_cache(
+        &self,
+        path: impl Into<PathBuf>,
+    ) -> RafxResult<()> {
+        let path = path.into();
+        let data = match std::fs::read(&path) {
+            Ok(data) => Some(data),
+            Err(e) => {
+                log::info!(
+                    "Could not read pipeline cache {:?}, starting with an empty cache: {}",
+                    path,
+                    e
+                );
+                None
+            }
+        };
+
+        let mut guard = self.inner.lock().unwrap();
+        #[cfg(debug_assertions)]
+        {
+            guard.lock_call_count += 1;
+        }
+
+        let pipeline_cache = guard
+            .resource_lookup_set
+            .device_context()
+            .create_pipeline_cache(&RafxPipelineCacheDef {
+                data: data.as_deref(),
+            })?;
+        guard
+            .resource_lookup_set
+            .set_pipeline_cache(Some(Arc::new(pipeline_cache)));
+        guard.pipeline_cache_path = Some(path);
+        Ok(())
+    }
+
+    // Writes the API pipeline cache to the path passed to load_pipeline_cache(). Does nothing if
+    // load_pipeline_cache() was never called.
+    pub fn save_pipeline_cache(&self) -> RafxResult<()> {
+        let mut guard = self.inner.lock().unwrap();
+        #[cfg(debug_assertions)]
+        {
+            guard.lock_call_count += 1;
+        }
+
+        if let Some(path) = &guard.pipeline_cache_path {
+            if let Some(pipeline_cache) = guard.resource_lookup_set.pipeline_cache() {
+                let data = pipeline_cache.get_data()?;
+                log::info!("Saving pipeline cache ({} bytes) to {:?}", data.len(), path);
+                std::fs::write(path, data)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::render_features::{RenderFeatureSubmitNode, RenderRegistryBuilder};
+    use crate::MaterialPassHash;
+    use rafx_api::empty::RafxDeviceContextEmpty;
+    use rafx_api::{RafxDeviceContext, RafxPrimitiveTopology};
+
+    // The test never registers a render phase with the registry, any valid index works
+    const RENDER_PHASE_INDEX: RenderPhaseIndex = 0;
+
+    fn sort_submit_nodes(_submit_nodes: &mut Vec<RenderFeatureSubmitNode>) {}
+
+    crate::declare_render_phase!(
+        PipelineManifestTestRenderPhase,
+        PIPELINE_MANIFEST_TEST_RENDER_PHASE_INDEX,
+        sort_submit_nodes
+    );
+
+    #[test]
+    fn test_manifest_keyed_by_material_pass_asset() {
+        let render_registry = RenderRegistryBuilder::default()
+            .register_render_phase::<PipelineManifestTestRenderPhase>("PipelineManifestTest")
+            .build();
+        let render_phase_index = PipelineManifestTestRenderPhase::render_phase_index();
+
+        let resource_lookup_set =
+            ResourceLookupSet::new(&RafxDeviceContext::Empty(RafxDeviceContextEmpty), 2);
+        let cache = GraphicsPipelineCache::new(&render_registry, resource_lookup_set.clone());
+
+        let render_target_meta = GraphicsPipelineRenderTargetMeta::new(
+            vec![RafxFormat::R8G8B8A8_UNORM],
+            None,
+            RafxSampleCount::SampleCount1,
+        );
+        let vertex_data_set_layout =
+            VertexDataSetLayout::new(vec![], RafxPrimitiveTopology::TriangleList);
+        let (material_pass, pipeline) = resource_lookup_set
+            .create_empty_graphics_pipeline(
+                &render_target_meta,
+                RafxPrimitiveTopology::TriangleList,
+            )
+            .unwrap();
+
+        let record_manifest_entry = || {
+            GraphicsPipelineCache::record_manifest_entry(
+                &mut cache.inner.lock().unwrap(),
+                &render_registry,
+                render_phase_index,
+                &material_pass,
+                &render_target_meta,
+                &vertex_data_set_layout,
+            )
+        };
+
+        // Material passes that were not loaded from an asset are not recorded
+        record_manifest_entry();
+        assert!(cache.pipeline_manifest().entries.is_empty());
+
+        // Two assets that produce the same material pass each get an entry
+        let asset_a = MaterialPassAssetId {
+            material_asset: [1; 16],
+            pass_index: 0,
+        };
+        let asset_b = MaterialPassAssetId {
+            material_asset: [2; 16],
+            pass_index: 3,
+        };
+        cache.register_material_pass_asset(asset_a, &material_pass);
+        cache.register_material_pass_asset(asset_b, &material_pass);
+        record_manifest_entry();
+        record_manifest_entry();
+
+        let mut entries = cache.pipeline_manifest().entries;
+        entries.sort_by_key(|x| x.material_pass_asset.material_asset);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].material_pass_asset, asset_a);
+        assert_eq!(entries[1].material_pass_asset, asset_b);
+        for entry in &entries {
+            assert_eq!(entry.render_phase, "PipelineManifestTest");
+            assert_eq!(
+                entry.material_pass,
+                material_pass.get_raw().material_pass_key.hash()
+            );
+        }
+
+        // Entries for assets that don't exist any more are skipped when precompiling, so they are
+        // not carried into the next manifest
+        let mut stale_entry = entries[0].clone();
+        stale_entry.material_pass_asset = MaterialPassAssetId {
+            material_asset: [3; 16],
+            pass_index: 0,
+        };
+        stale_entry.material_pass = MaterialPassHash::default();
+        let mut progress = vec![];
+        cache
+            .precompile_pipelines(
+                &GraphicsPipelineManifest {
+                    entries: vec![stale_entry],
+                },
+                |completed, total| progress.push((completed, total)),
+            )
+            .unwrap();
+        assert_eq!(progress, vec![(1, 1)]);
+        assert_eq!(cache.pipeline_manifest().entries.len(), 2);
+        assert_eq!(cache.metrics().pipeline_count, 0);
+
+        // Resources must be released before the lookups are destroyed
+        std::mem::drop(material_pass);
+        std::mem::drop(pipeline);
+        resource_lookup_set.destroy().unwrap();
+    }
+
+    #[test]
+    fn test_precompiled_pipeline_kept_until_used() {
+        let resource_lookup_set =
+            ResourceLookupSet::new(&RafxDeviceContext::Empty(RafxDeviceContextEmpty), 2);
+        let cache = GraphicsPipelineCache::new(
+            &RenderRegistryBuilder::default().build(),
+            resource_lookup_set.clone(),
+        );
+
+        let render_target_meta = GraphicsPipelineRenderTargetMeta::new(
+            vec![RafxFormat::R8G8B8A8_UNORM],
+            Some(RafxFormat::D32_SFLOAT),
+            RafxSampleCount::SampleCount1,
+        );
+        let vertex_data_set_layout =
+            VertexDataSetLayout::new(vec![], RafxPrimitiveTopology::TriangleList);
+        let (material_pass, pipeline) = resource_lookup_set
+            .create_empty_graphics_pipeline(
+                &render_target_meta,
+                RafxPrimitiveTopology::TriangleList,
+            )
+            .unwrap();
+
+        // Creating a pipeline requires a device, so put it into the cache the same way
+        // precompile_pipelines() leaves it
+        cache.inner.lock().unwrap().cached_pipelines.insert(
+            CachedGraphicsPipelineKey {
+                material_pass: material_pass.get_hash(),
+                render_target_meta_hash: render_target_meta.render_target_meta_hash(),
+                vertex_data_set_layout: vertex_data_set_layout.hash(),
+            },
+            CachedGraphicsPipeline {
+                material_pass_resource: material_pass.downgrade(),
+                graphics_pipeline: pipeline.clone(),
+                pinned_until_used: false,
+            },
+        );
+        cache.pin_graphics_pipeline(&material_pass, &render_target_meta, &vertex_data_set_layout);
+
+        // Nothing has registered the render target meta, but the pipeline is kept
+        for _ in 0..5 {
+            cache.on_frame_complete();
+        }
+        assert_eq!(cache.metrics().pipeline_count, 1);
+
+        // Drawing with it unpins it, so it is dropped once it goes unused
+        let cached_pipeline = cache.try_get_graphics_pipeline(
+            RENDER_PHASE_INDEX,
+            &material_pass,
+            &render_target_meta,
+            &vertex_data_set_layout,
+        );
+        assert!(cached_pipeline == Some(pipeline));
+
+        cache.on_frame_complete();
+        assert_eq!(cache.metrics().pipeline_count, 1);
+        cache.on_frame_complete();
+        assert_eq!(cache.metrics().pipeline_count, 0);
+
+        // Resources must be released before the lookups are destroyed
+        std::mem::drop(material_pass);
+        resource_lookup_set.destroy().unwrap();
+    }
 }
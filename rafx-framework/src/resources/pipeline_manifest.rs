@@ -0,0 +1,210 @@
+use crate::{
+    GraphicsPipelineRenderTargetMeta, MaterialPassHash, VertexDataLayout, VertexDataSetLayout,
+    VertexMember,
+};
+use rafx_api::{RafxFormat, RafxPrimitiveTopology, RafxSampleCount, RafxVertexAttributeRate};
+use serde::{Deserialize, Serialize};
+
+/// Serializable form of a `VertexDataLayout`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphicsPipelineManifestVertexLayout {
+    pub vertex_stride: usize,
+    pub vertex_rate: RafxVertexAttributeRate,
+    pub members: Vec<VertexMember>,
+}
+
+impl GraphicsPipelineManifestVertexLayout {
+    pub fn new(layout: &VertexDataLayout) -> Self {
+        let mut members: Vec<_> = layout
+            .members()
+            .iter()
+            .map(|(semantic, meta)| VertexMember {
+                semantic: semantic.clone(),
+                format: meta.format,
+                byte_offset: meta.byte_offset,
+            })
+            .collect();
+
+        // The members are stored in a hash map, sort them so that the manifest is deterministic
+        members.sort_by(|lhs, rhs| {
+            lhs.byte_offset
+                .cmp(&rhs.byte_offset)
+                .then_with(|| lhs.semantic.cmp(&rhs.semantic))
+        });
+
+        GraphicsPipelineManifestVertexLayout {
+            vertex_stride: layout.vertex_stride(),
+            vertex_rate: layout.vertex_rate(),
+            members,
+        }
+    }
+
+    pub fn vertex_data_layout(&self) -> VertexDataLayout {
+        VertexDataLayout::new(self.vertex_stride, self.vertex_rate, &self.members)
+    }
+}
+
+/// Identifies a material pass by the asset it was loaded from, which (unlike the material pass
+/// hash) stays the same when the material asset is edited. Registered with
+/// `GraphicsPipelineCache::register_material_pass_asset` when the material is loaded.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MaterialPassAssetId {
+    // UUID of the material asset
+    pub material_asset: [u8; 16],
+    // Index of the pass within the material
+    pub pass_index: u32,
+}
+
+/// Everything needed to build a graphics pipeline with `GraphicsPipelineCache` ahead of time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphicsPipelineManifestEntry {
+    // Render phase indices are assigned at runtime, so phases are stored by name
+    pub render_phase: String,
+    pub material_pass_asset: MaterialPassAssetId,
+    // The hash of the material pass when the entry was recorded. If the material asset has been
+    // edited since then, the pipeline is built for the current version of the pass instead
+    pub material_pass: MaterialPassHash,
+    pub vertex_layouts: Vec<GraphicsPipelineManifestVertexLayout>,
+    pub primitive_topology: RafxPrimitiveTopology,
+    pub color_formats: Vec<RafxFormat>,
+    pub depth_stencil_format: Option<RafxFormat>,
+    pub sample_count: RafxSampleCount,
+}
+
+impl GraphicsPipelineManifestEntry {
+    pub fn new(
+        render_phase: String,
+        material_pass_asset: MaterialPassAssetId,
+        material_pass: MaterialPassHash,
+        render_target_meta: &GraphicsPipelineRenderTargetMeta,
+        vertex_data_set_layout: &VertexDataSetLayout,
+    ) -> Self {
+        GraphicsPipelineManifestEntry {
+            render_phase,
+            material_pass_asset,
+            material_pass,
+            vertex_layouts: vertex_data_set_layout
+                .bindings()
+                .iter()
+                .map(GraphicsPipelineManifestVertexLayout::new)
+                .collect(),
+            primitive_topology: vertex_data_set_layout.primitive_topology(),
+            color_formats: render_target_meta.color_formats().to_vec(),
+            depth_stencil_format: render_target_meta.depth_stencil_format(),
+            sample_count: render_target_meta.sample_count(),
+        }
+    }
+
+    pub fn vertex_data_set_layout(&self) -> VertexDataSetLayout {
+        VertexDataSetLayout::new(
+            self.vertex_layouts
+                .iter()
+                .map(|x| x.vertex_data_layout())
+                .collect(),
+            self.primitive_topology,
+        )
+    }
+
+    pub fn render_target_meta(&self) -> GraphicsPipelineRenderTargetMeta {
+        GraphicsPipelineRenderTargetMeta::new(
+            self.color_formats.clone(),
+            self.depth_stencil_format,
+            self.sample_count,
+        )
+    }
+}
+
+/// A list of the graphics pipelines a `GraphicsPipelineCache` has created. It can be saved with
+/// any serde format and passed to `GraphicsPipelineCache::precompile_pipelines` on a later run
+/// (for example during a loading screen) so that pipelines are not compiled the first time
+/// something is drawn. Only material passes registered with an asset id are recorded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphicsPipelineManifest {
+    pub entries: Vec<GraphicsPipelineManifestEntry>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_manifest_serde_round_trip() {
+        let vertex_layout = VertexDataLayout::new(
+            20,
+            RafxVertexAttributeRate::Vertex,
+            &[
+                VertexMember {
+                    semantic: "TEXCOORD".to_string(),
+                    format: RafxFormat::R32G32_SFLOAT,
+                    byte_offset: 12,
+                },
+                VertexMember {
+                    semantic: "POSITION".to_string(),
+                    format: RafxFormat::R32G32B32_SFLOAT,
+                    byte_offset: 0,
+                },
+            ],
+        );
+        let instance_layout = VertexDataLayout::new(
+            64,
+            RafxVertexAttributeRate::Instance,
+            &[VertexMember {
+                semantic: "MODEL_MATRIX".to_string(),
+                format: RafxFormat::R32G32B32A32_SFLOAT,
+                byte_offset: 0,
+            }],
+        );
+        let vertex_data_set_layout = VertexDataSetLayout::new(
+            vec![vertex_layout, instance_layout],
+            RafxPrimitiveTopology::TriangleList,
+        );
+        let render_target_meta = GraphicsPipelineRenderTargetMeta::new(
+            vec![RafxFormat::R16G16B16A16_SFLOAT, RafxFormat::R8G8B8A8_UNORM],
+            Some(RafxFormat::D32_SFLOAT),
+            RafxSampleCount::SampleCount4,
+        );
+
+        let manifest = GraphicsPipelineManifest {
+            entries: vec![
+                GraphicsPipelineManifestEntry::new(
+                    "Opaque".to_string(),
+                    MaterialPassAssetId {
+                        material_asset: [7; 16],
+                        pass_index: 0,
+                    },
+                    MaterialPassHash::default(),
+                    &render_target_meta,
+                    &vertex_data_set_layout,
+                ),
+                GraphicsPipelineManifestEntry::new(
+                    "DepthPrepass".to_string(),
+                    MaterialPassAssetId {
+                        material_asset: [7; 16],
+                        pass_index: 1,
+                    },
+                    MaterialPassHash::default(),
+                    &GraphicsPipelineRenderTargetMeta::new(
+                        vec![],
+                        Some(RafxFormat::D32_SFLOAT),
+                        RafxSampleCount::SampleCount1,
+                    ),
+                    &VertexDataSetLayout::new(vec![], RafxPrimitiveTopology::LineList),
+                ),
+            ],
+        };
+
+        let serialized = ron::ser::to_string(&manifest).unwrap();
+        let deserialized: GraphicsPipelineManifest = ron::de::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, manifest);
+
+        // The entry rebuilds the same layout and render target meta, so the rebuilt pipeline
+        // will use the same cache keys as the original one
+        let entry = &deserialized.entries[0];
+        assert_eq!(entry.vertex_data_set_layout(), vertex_data_set_layout);
+        assert_eq!(
+            entry.vertex_data_set_layout().hash(),
+            vertex_data_set_layout.hash()
+        );
+        assert_eq!(entry.render_target_meta(), render_target_meta);
+    }
+}
@@ -163,7 +163,6 @@ where
         Ok(arc)
     }
 
-    #[allow(dead_code)]
     pub fn get(
         &self,
         key: &KeyT,
@@ -470,6 +469,12 @@ pub struct MaterialPassKey {
     hash: MaterialPassHash,
 }
 
+impl MaterialPassKey {
+    pub fn hash(&self) -> MaterialPassHash {
+        self.hash
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct GraphicsPipelineKey {
     hash: GraphicsPipelineHash,
@@ -634,6 +639,9 @@ pub struct ResourceLookupSetInner {
     samplers: ResourceLookup<SamplerKey, SamplerResource>,
     buffers: ResourceLookup<BufferKey, BufferResource>,
 
+    // Passed to the API when creating pipelines, if one has been set
+    pipeline_cache: Mutex<Option<Arc<RafxPipelineCache>>>,
+
     // Used to generate keys for images/buffers
     next_image_id: AtomicU64,
     next_buffer_id: AtomicU64,
@@ -662,6 +670,7 @@ impl ResourceLookupSet {
             image_views: ResourceLookup::new(max_frames_in_flight),
             samplers: ResourceLookup::new(max_frames_in_flight),
             buffers: ResourceLookup::new(max_frames_in_flight),
+            pipeline_cache: Default::default(),
             next_image_id: AtomicU64::new(0),
             next_buffer_id: AtomicU64::new(0),
         };
@@ -675,6 +684,18 @@ impl ResourceLookupSet {
         &self.inner.device_context
     }
 
+    pub fn pipeline_cache(&self) -> Option<Arc<RafxPipelineCache>> {
+        self.inner.pipeline_cache.lock().unwrap().clone()
+    }
+
+    // Only affects pipelines created after this is called
+    pub fn set_pipeline_cache(
+        &self,
+        pipeline_cache: Option<Arc<RafxPipelineCache>>,
+    ) {
+        *self.inner.pipeline_cache.lock().unwrap() = pipeline_cache;
+    }
+
     #[profiling::function]
     pub fn on_frame_complete(&self) -> RafxResult<()> {
         self.inner.images.on_frame_complete()?;
@@ -707,6 +728,7 @@ impl ResourceLookupSet {
         self.inner.buffers.destroy()?;
         self.inner.image_views.destroy()?;
         self.inner.images.destroy()?;
+        self.set_pipeline_cache(None);
         Ok(())
     }
 
@@ -996,6 +1018,7 @@ impl ResourceLookupSet {
                 log::trace!("Creating graphics pipeline\n{:#?}", pipeline_key);
 
                 let fixed_function_state = &material_pass.get_raw().fixed_function_state;
+                let pipeline_cache = self.pipeline_cache();
                 let pipeline = self.inner.device_context.create_graphics_pipeline(
                     &RafxGraphicsPipelineDef {
                         root_signature: &material_pass
@@ -1016,6 +1039,7 @@ impl ResourceLookupSet {
                         color_formats: &render_target_meta.color_formats(),
                         depth_stencil_format: render_target_meta.depth_stencil_format(),
                         sample_count: render_target_meta.sample_count(),
+                        pipeline_cache: pipeline_cache.as_deref(),
                     },
                 )?;
 
@@ -1049,12 +1073,14 @@ impl ResourceLookupSet {
             .compute_pipelines
             .get_or_create(&pipeline_key, || {
                 log::trace!("Creating compute pipeline\n{:#?}", pipeline_key);
+                let pipeline_cache = self.pipeline_cache();
                 let rafx_pipeline =
                     self.inner
                         .device_context
                         .create_compute_pipeline(&RafxComputePipelineDef {
                             root_signature: &root_signature.get_raw().root_signature,
                             shader: &shader.get_raw().shader,
+                            pipeline_cache: pipeline_cache.as_deref(),
                         })?;
                 log::trace!("Created compute pipeline {:?}", rafx_pipeline);
 
@@ -1086,6 +1112,17 @@ impl ResourceLookupSet {
             .unwrap()
     }
 
+    // Same as insert_image, but forwards the name to the API object so that it shows up in
+    // debugging tools (RenderDoc, Xcode, etc.) when debug names are enabled
+    pub fn insert_image_with_debug_name(
+        &self,
+        image: RafxTexture,
+        debug_name: &str,
+    ) -> ResourceArc<ImageResource> {
+        image.set_debug_name(debug_name);
+        self.insert_image(image)
+    }
+
     //TODO: Support direct removal of raw images with verification that no references remain
 
     // A key difference between this insert_buffer and the insert_buffer in a DynResourceAllocator
@@ -1109,6 +1146,17 @@ impl ResourceLookupSet {
             .unwrap()
     }
 
+    // Same as insert_buffer, but forwards the name to the API object so that it shows up in
+    // debugging tools (RenderDoc, Xcode, etc.) when debug names are enabled
+    pub fn insert_buffer_with_debug_name(
+        &self,
+        buffer: RafxBuffer,
+        debug_name: &str,
+    ) -> ResourceArc<BufferResource> {
+        buffer.set_debug_name(debug_name);
+        self.insert_buffer(buffer)
+    }
+
     pub fn get_or_create_image_view(
         &self,
         image: &ResourceArc<ImageResource>,
@@ -1137,3 +1185,81 @@ impl ResourceLookupSet {
         })
     }
 }
+
+#[cfg(test)]
+impl ResourceLookupSet {
+    // Creates a material pass and a graphics pipeline that uses it without creating any API
+    // objects. This only works with the empty backend and is for testing code that tracks the
+    // lifetime of these resources.
+    pub(crate) fn create_empty_graphics_pipeline(
+        &self,
+        render_target_meta: &GraphicsPipelineRenderTargetMeta,
+        primitive_topology: RafxPrimitiveTopology,
+    ) -> RafxResult<(
+        ResourceArc<MaterialPassResource>,
+        ResourceArc<GraphicsPipelineResource>,
+    )> {
+        let shader_key = ShaderKey {
+            hash: ShaderHash(0),
+        };
+        let shader = self.inner.shaders.get_or_create(&shader_key, || {
+            Ok(ShaderResource {
+                key: shader_key,
+                shader_modules: vec![],
+                shader: RafxShader::Empty(empty::RafxShaderEmpty),
+            })
+        })?;
+
+        let root_signature_key = RootSignatureKey {
+            hash: RootSignatureHash(0),
+        };
+        let root_signature =
+            self.inner
+                .root_signatures
+                .get_or_create(&root_signature_key, || {
+                    Ok(RootSignatureResource {
+                        key: root_signature_key,
+                        shaders: vec![shader.clone()],
+                        immutable_samplers: vec![],
+                        root_signature: RafxRootSignature::Empty(empty::RafxRootSignatureEmpty),
+                    })
+                })?;
+
+        let material_pass = self.get_or_create_material_pass(
+            shader,
+            root_signature,
+            vec![],
+            Arc::new(FixedFunctionState {
+                blend_state: Default::default(),
+                depth_state: Default::default(),
+                rasterizer_state: Default::default(),
+            }),
+            Arc::new(vec![]),
+        )?;
+
+        let vertex_layout = RafxVertexLayout {
+            attributes: vec![],
+            buffers: vec![],
+        };
+        let pipeline_key = GraphicsPipelineKey {
+            hash: GraphicsPipelineHash::new(
+                material_pass.get_raw().material_pass_key.hash,
+                render_target_meta,
+                primitive_topology,
+                &vertex_layout,
+            ),
+        };
+        let pipeline = self
+            .inner
+            .graphics_pipelines
+            .get_or_create(&pipeline_key, || {
+                Ok(GraphicsPipelineResource {
+                    render_target_meta: render_target_meta.clone(),
+                    pipeline: Arc::new(RafxPipeline::Empty(empty::RafxPipelineEmpty)),
+                    descriptor_set_layouts: material_pass.get_raw().descriptor_set_layouts.clone(),
+                })
+            })?;
+
+        Ok((material_pass, pipeline))
+    }
+}
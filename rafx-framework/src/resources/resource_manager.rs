@@ -187,6 +187,10 @@ impl Drop for ResourceManager {
         log::info!("Cleaning up resource manager");
         log::trace!("Resource Manager Metrics:\n{:#?}", self.metrics());
 
+        if let Err(e) = self.graphics_pipeline_cache.save_pipeline_cache() {
+            log::warn!("Failed to save pipeline cache: {:?}", e);
+        }
+
         // Wipe caches to ensure we don't keep anything alive
         self.render_graph_cache.clear();
         self.graphics_pipeline_cache.clear_all_pipelines();
@@ -0,0 +1,513 @@
+use crate::{RafxResult, ReflectedUniformMember, SlotLocation};
+use fnv::FnvHashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+pub type UniformBufferLayoutLookup = FnvHashMap<SlotLocation, Arc<UniformBufferLayout>>;
+
+// A value that can be written into a named member of a uniform buffer. The list variants are used
+// for vector and matrix types (matrices are in column-major order). Integer values may be written
+// to float members, so that hand-written values like [1, 0, 0, 1] can be used for a vec4.
+//
+// In human-readable formats (i.e. RON material instances) the value is written without the variant
+// name (1.0, [1, 0, 0, 1], true). Binary formats don't support that, so the variant is kept.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(remote = "Self")]
+pub enum MaterialParameterValue {
+    Float(f32),
+    Int(i32),
+    Uint(u32),
+    Bool(bool),
+    Floats(Vec<f32>),
+    Ints(Vec<i32>),
+    Uints(Vec<u32>),
+}
+
+// Variants are tried in order, so integers are read as Int (or Uint if they are too large) and
+// lists of integers as Ints
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum UntaggedMaterialParameterValue {
+    Bool(bool),
+    Int(i32),
+    Uint(u32),
+    Float(f32),
+    Ints(Vec<i32>),
+    Uints(Vec<u32>),
+    Floats(Vec<f32>),
+}
+
+impl Serialize for MaterialParameterValue {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return MaterialParameterValue::serialize(self, serializer);
+        }
+
+        match self {
+            MaterialParameterValue::Float(x) => x.serialize(serializer),
+            MaterialParameterValue::Int(x) => x.serialize(serializer),
+            MaterialParameterValue::Uint(x) => x.serialize(serializer),
+            MaterialParameterValue::Bool(x) => x.serialize(serializer),
+            MaterialParameterValue::Floats(x) => x.serialize(serializer),
+            MaterialParameterValue::Ints(x) => x.serialize(serializer),
+            MaterialParameterValue::Uints(x) => x.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MaterialParameterValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return MaterialParameterValue::deserialize(deserializer);
+        }
+
+        Ok(
+            match UntaggedMaterialParameterValue::deserialize(deserializer)? {
+                UntaggedMaterialParameterValue::Bool(x) => MaterialParameterValue::Bool(x),
+                UntaggedMaterialParameterValue::Int(x) => MaterialParameterValue::Int(x),
+                UntaggedMaterialParameterValue::Uint(x) => MaterialParameterValue::Uint(x),
+                UntaggedMaterialParameterValue::Float(x) => MaterialParameterValue::Float(x),
+                UntaggedMaterialParameterValue::Ints(x) => MaterialParameterValue::Ints(x),
+                UntaggedMaterialParameterValue::Uints(x) => MaterialParameterValue::Uints(x),
+                UntaggedMaterialParameterValue::Floats(x) => MaterialParameterValue::Floats(x),
+            },
+        )
+    }
+}
+
+impl From<f32> for MaterialParameterValue {
+    fn from(value: f32) -> Self {
+        MaterialParameterValue::Float(value)
+    }
+}
+
+impl From<i32> for MaterialParameterValue {
+    fn from(value: i32) -> Self {
+        MaterialParameterValue::Int(value)
+    }
+}
+
+impl From<u32> for MaterialParameterValue {
+    fn from(value: u32) -> Self {
+        MaterialParameterValue::Uint(value)
+    }
+}
+
+impl From<bool> for MaterialParameterValue {
+    fn from(value: bool) -> Self {
+        MaterialParameterValue::Bool(value)
+    }
+}
+
+impl From<&[f32]> for MaterialParameterValue {
+    fn from(value: &[f32]) -> Self {
+        MaterialParameterValue::Floats(value.to_vec())
+    }
+}
+
+impl From<[f32; 2]> for MaterialParameterValue {
+    fn from(value: [f32; 2]) -> Self {
+        MaterialParameterValue::Floats(value.to_vec())
+    }
+}
+
+impl From<[f32; 3]> for MaterialParameterValue {
+    fn from(value: [f32; 3]) -> Self {
+        MaterialParameterValue::Floats(value.to_vec())
+    }
+}
+
+impl From<[f32; 4]> for MaterialParameterValue {
+    fn from(value: [f32; 4]) -> Self {
+        MaterialParameterValue::Floats(value.to_vec())
+    }
+}
+
+impl From<&[i32]> for MaterialParameterValue {
+    fn from(value: &[i32]) -> Self {
+        MaterialParameterValue::Ints(value.to_vec())
+    }
+}
+
+impl From<&[u32]> for MaterialParameterValue {
+    fn from(value: &[u32]) -> Self {
+        MaterialParameterValue::Uints(value.to_vec())
+    }
+}
+
+// The type of each component of a uniform buffer member
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum ComponentType {
+    Float,
+    Int,
+    Uint,
+    Bool,
+}
+
+// A single component of a MaterialParameterValue
+#[derive(Copy, Clone)]
+enum ComponentValue {
+    Float(f32),
+    Int(i32),
+    Uint(u32),
+    Bool(bool),
+}
+
+// The reflected layout of a uniform buffer that is bound by value. This allows writing members of
+// the buffer by name rather than having to build the buffer as raw bytes with a matching struct.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformBufferLayout {
+    pub size: u32,
+    pub members: Vec<ReflectedUniformMember>,
+}
+
+impl UniformBufferLayout {
+    pub fn find_member(
+        &self,
+        member_name: &str,
+    ) -> Option<&ReflectedUniformMember> {
+        self.members.iter().find(|x| x.name == member_name)
+    }
+
+    // Writes the value into buffer_data at the member's offset. buffer_data is zero-extended to
+    // the size of the buffer if it is shorter.
+    pub fn write_member(
+        &self,
+        member_name: &str,
+        value: &MaterialParameterValue,
+        buffer_data: &mut Vec<u8>,
+    ) -> RafxResult<()> {
+        let member = self.find_member(member_name).ok_or_else(|| {
+            format!(
+                "Uniform buffer has no member named {}, valid members are {:?}",
+                member_name,
+                self.members.iter().map(|x| &x.name).collect::<Vec<_>>()
+            )
+        })?;
+
+        let bytes = Self::value_to_bytes(member, value)?;
+        let begin = member.offset as usize;
+        let end = begin + bytes.len();
+        if end > self.size as usize {
+            Err(format!(
+                "Uniform buffer member {} (offset {}, {} bytes) does not fit in a buffer of size {}",
+                member_name,
+                member.offset,
+                bytes.len(),
+                self.size
+            ))?;
+        }
+
+        if buffer_data.len() < self.size as usize {
+            buffer_data.resize(self.size as usize, 0);
+        }
+
+        buffer_data[begin..end].copy_from_slice(&bytes);
+        Ok(())
+    }
+
+    // Returns the component type and count of the GLSL type, and for matrices the number of rows
+    // (columns are padded to 16 bytes)
+    fn member_components(type_name: &str) -> Option<(ComponentType, usize, Option<usize>)> {
+        Some(match type_name {
+            "float" => (ComponentType::Float, 1, None),
+            "vec2" => (ComponentType::Float, 2, None),
+            "vec3" => (ComponentType::Float, 3, None),
+            "vec4" => (ComponentType::Float, 4, None),
+            "mat3" => (ComponentType::Float, 9, Some(3)),
+            "mat4" => (ComponentType::Float, 16, Some(4)),
+            "int" => (ComponentType::Int, 1, None),
+            "ivec2" => (ComponentType::Int, 2, None),
+            "ivec3" => (ComponentType::Int, 3, None),
+            "ivec4" => (ComponentType::Int, 4, None),
+            "uint" => (ComponentType::Uint, 1, None),
+            "uvec2" => (ComponentType::Uint, 2, None),
+            "uvec3" => (ComponentType::Uint, 3, None),
+            "uvec4" => (ComponentType::Uint, 4, None),
+            "bool" => (ComponentType::Bool, 1, None),
+            _ => return None,
+        })
+    }
+
+    fn value_to_bytes(
+        member: &ReflectedUniformMember,
+        value: &MaterialParameterValue,
+    ) -> RafxResult<Vec<u8>> {
+        let type_error = || {
+            format!(
+                "Value {:?} cannot be written to uniform buffer member {} of type {}",
+                value, member.name, member.type_name
+            )
+        };
+
+        let (component_type, component_count, matrix_rows) =
+            Self::member_components(&member.type_name).ok_or_else(type_error)?;
+
+        // Scalar members take a single value, vectors and matrices take a list
+        let components: Vec<ComponentValue> = match (component_count, value) {
+            (1, MaterialParameterValue::Float(x)) => vec![ComponentValue::Float(*x)],
+            (1, MaterialParameterValue::Int(x)) => vec![ComponentValue::Int(*x)],
+            (1, MaterialParameterValue::Uint(x)) => vec![ComponentValue::Uint(*x)],
+            (1, MaterialParameterValue::Bool(x)) => vec![ComponentValue::Bool(*x)],
+            (_, MaterialParameterValue::Floats(x)) if x.len() == component_count => {
+                x.iter().map(|x| ComponentValue::Float(*x)).collect()
+            }
+            (_, MaterialParameterValue::Ints(x)) if x.len() == component_count => {
+                x.iter().map(|x| ComponentValue::Int(*x)).collect()
+            }
+            (_, MaterialParameterValue::Uints(x)) if x.len() == component_count => {
+                x.iter().map(|x| ComponentValue::Uint(*x)).collect()
+            }
+            _ => Err(type_error())?,
+        };
+
+        let mut bytes = Vec::with_capacity(member.size as usize);
+        for (i, component) in components.into_iter().enumerate() {
+            // Integers may be written to floats, but floats are never truncated to integers
+            let component_bytes = match (component_type, component) {
+                (ComponentType::Float, ComponentValue::Float(x)) => x.to_ne_bytes(),
+                (ComponentType::Float, ComponentValue::Int(x)) => (x as f32).to_ne_bytes(),
+                (ComponentType::Float, ComponentValue::Uint(x)) => (x as f32).to_ne_bytes(),
+                (ComponentType::Int, ComponentValue::Int(x)) => x.to_ne_bytes(),
+                (ComponentType::Int, ComponentValue::Uint(x)) => {
+                    i32::try_from(x).map_err(|_| type_error())?.to_ne_bytes()
+                }
+                (ComponentType::Uint, ComponentValue::Uint(x)) => x.to_ne_bytes(),
+                (ComponentType::Uint, ComponentValue::Int(x)) => {
+                    u32::try_from(x).map_err(|_| type_error())?.to_ne_bytes()
+                }
+                // GLSL bools are 4 bytes
+                (ComponentType::Bool, ComponentValue::Bool(x)) => (x as u32).to_ne_bytes(),
+                _ => Err(type_error())?,
+            };
+
+            // In std140 and std430, matrix columns are aligned like a vec4
+            if let Some(matrix_rows) = matrix_rows {
+                if i % matrix_rows == 0 {
+                    bytes.resize(i / matrix_rows * 16, 0);
+                }
+            }
+
+            bytes.extend_from_slice(&component_bytes);
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn member(
+        name: &str,
+        type_name: &str,
+        offset: u32,
+        size: u32,
+    ) -> ReflectedUniformMember {
+        ReflectedUniformMember {
+            name: name.to_string(),
+            type_name: type_name.to_string(),
+            offset,
+            size,
+        }
+    }
+
+    // Members as the shader processor reflects them for:
+    //
+    // struct Light { vec3 position; float intensity; mat3 basis; };
+    // uniform LightData { vec4 color; Light lights[2]; ivec2 sizes[2]; uint light_count; bool enabled; };
+    fn light_data_layout() -> UniformBufferLayout {
+        UniformBufferLayout {
+            size: 192,
+            members: vec![
+                member("color", "vec4", 0, 16),
+                member("lights[0].position", "vec3", 16, 12),
+                member("lights[0].intensity", "float", 28, 4),
+                member("lights[0].basis", "mat3", 32, 48),
+                member("lights[1].position", "vec3", 80, 12),
+                member("lights[1].intensity", "float", 92, 4),
+                member("lights[1].basis", "mat3", 96, 48),
+                member("sizes[0]", "ivec2", 144, 8),
+                member("sizes[1]", "ivec2", 160, 8),
+                member("light_count", "uint", 176, 4),
+                member("enabled", "bool", 180, 4),
+            ],
+        }
+    }
+
+    fn read_f32s(
+        buffer_data: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> Vec<f32> {
+        buffer_data[offset..offset + count * 4]
+            .chunks(4)
+            .map(|x| f32::from_ne_bytes([x[0], x[1], x[2], x[3]]))
+            .collect()
+    }
+
+    fn read_u32s(
+        buffer_data: &[u8],
+        offset: usize,
+        count: usize,
+    ) -> Vec<u32> {
+        buffer_data[offset..offset + count * 4]
+            .chunks(4)
+            .map(|x| u32::from_ne_bytes([x[0], x[1], x[2], x[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_member_offsets() {
+        let layout = light_data_layout();
+
+        // Existing data is kept and the buffer is extended to the size of the layout
+        let mut buffer_data = vec![0xFF; 4];
+        layout
+            .write_member(
+                "lights[1].intensity",
+                &MaterialParameterValue::Float(2.5),
+                &mut buffer_data,
+            )
+            .unwrap();
+        assert_eq!(buffer_data.len(), 192);
+        assert_eq!(&buffer_data[0..4], &[0xFF; 4]);
+        assert_eq!(read_f32s(&buffer_data, 92, 1), vec![2.5]);
+
+        layout
+            .write_member(
+                "lights[1].position",
+                &[1.0, 2.0, 3.0].into(),
+                &mut buffer_data,
+            )
+            .unwrap();
+        assert_eq!(read_f32s(&buffer_data, 80, 3), vec![1.0, 2.0, 3.0]);
+
+        // Array elements are written at their std140 stride
+        layout
+            .write_member(
+                "sizes[1]",
+                &MaterialParameterValue::Ints(vec![-4, 8]),
+                &mut buffer_data,
+            )
+            .unwrap();
+        assert_eq!(read_u32s(&buffer_data, 160, 2), vec![-4i32 as u32, 8]);
+        assert_eq!(read_u32s(&buffer_data, 144, 2), vec![0, 0]);
+
+        layout
+            .write_member(
+                "enabled",
+                &MaterialParameterValue::Bool(true),
+                &mut buffer_data,
+            )
+            .unwrap();
+        assert_eq!(read_u32s(&buffer_data, 180, 1), vec![1]);
+
+        // Members that don't exist are rejected
+        assert!(layout
+            .write_member(
+                "lights[2].intensity",
+                &MaterialParameterValue::Float(1.0),
+                &mut buffer_data
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_write_member_mat3_columns_are_padded() {
+        let layout = light_data_layout();
+        let mut buffer_data = Vec::default();
+        let basis: Vec<f32> = (1..=9).map(|x| x as f32).collect();
+        layout
+            .write_member(
+                "lights[0].basis",
+                &MaterialParameterValue::Floats(basis),
+                &mut buffer_data,
+            )
+            .unwrap();
+
+        assert_eq!(read_f32s(&buffer_data, 32, 3), vec![1.0, 2.0, 3.0]);
+        assert_eq!(read_f32s(&buffer_data, 48, 3), vec![4.0, 5.0, 6.0]);
+        assert_eq!(read_f32s(&buffer_data, 64, 3), vec![7.0, 8.0, 9.0]);
+        assert_eq!(read_f32s(&buffer_data, 44, 1), vec![0.0]);
+        // The next light is untouched
+        assert_eq!(read_f32s(&buffer_data, 80, 1), vec![0.0]);
+    }
+
+    #[test]
+    fn test_write_member_type_mismatch() {
+        let layout = light_data_layout();
+        let mut buffer_data = Vec::default();
+
+        let mismatches = [
+            // Wrong component count
+            ("color", MaterialParameterValue::Floats(vec![1.0, 0.0, 0.0])),
+            (
+                "lights[0].basis",
+                MaterialParameterValue::Floats(vec![0.0; 16]),
+            ),
+            // Scalar written to a vector
+            ("color", MaterialParameterValue::Float(1.0)),
+            // Floats are not truncated to integers
+            ("sizes[0]", MaterialParameterValue::Floats(vec![1.0, 2.0])),
+            ("light_count", MaterialParameterValue::Float(1.0)),
+            // Out of range for the member's type
+            ("light_count", MaterialParameterValue::Int(-1)),
+            ("sizes[0]", MaterialParameterValue::Uints(vec![0, u32::MAX])),
+            // Bools are only written to bools
+            ("enabled", MaterialParameterValue::Int(1)),
+            ("lights[0].intensity", MaterialParameterValue::Bool(true)),
+        ];
+
+        for (member_name, value) in &mismatches {
+            assert!(
+                layout
+                    .write_member(member_name, value, &mut buffer_data)
+                    .is_err(),
+                "{} = {:?} should be rejected",
+                member_name,
+                value
+            );
+        }
+
+        // Nothing was written
+        assert!(buffer_data.iter().all(|x| *x == 0));
+    }
+
+    #[test]
+    fn test_untagged_values_in_ron() {
+        let values: BTreeMap<String, MaterialParameterValue> = ron::de::from_str(
+            "{
+                \"color\": [1, 0, 0.5, 1],
+                \"lights[0].intensity\": 3,
+                \"sizes[0]\": [-1, 2],
+                \"light_count\": 2,
+                \"enabled\": true,
+            }",
+        )
+        .unwrap();
+
+        let layout = light_data_layout();
+        let mut buffer_data = Vec::default();
+        for (member_name, value) in &values {
+            layout
+                .write_member(member_name, value, &mut buffer_data)
+                .unwrap();
+        }
+
+        assert_eq!(read_f32s(&buffer_data, 0, 4), vec![1.0, 0.0, 0.5, 1.0]);
+        assert_eq!(read_f32s(&buffer_data, 28, 1), vec![3.0]);
+        assert_eq!(read_u32s(&buffer_data, 144, 2), vec![-1i32 as u32, 2]);
+        assert_eq!(read_u32s(&buffer_data, 176, 2), vec![2, 1]);
+
+        // Values are written back without a tag
+        let serialized = ron::ser::to_string(&MaterialParameterValue::Ints(vec![1, 2])).unwrap();
+        assert_eq!(serialized, "[1,2]");
+    }
+}
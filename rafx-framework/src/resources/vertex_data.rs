@@ -1,5 +1,6 @@
 use fnv::FnvHashMap;
 use rafx_api::{RafxFormat, RafxPrimitiveTopology, RafxVertexAttributeRate};
+use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
@@ -59,7 +60,7 @@ pub enum VertexCopyError {
     CantReinitializeFrom,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VertexMember {
     pub semantic: String,
     pub format: RafxFormat,
@@ -140,6 +140,52 @@ impl ViewFrustumArc {
         Ok(self.inner.visibility_query.read())
     }
 
+    /// Treats this view frustum as a light and returns the objects that can cast a shadow into any
+    /// of the `shadowed` view frustums, rather than the objects inside this view frustum. The
+    /// `shadowed` view frustums must be registered in the same `VisibilityRegion`.
+    pub fn query_shadow_casters(
+        &mut self,
+        shadowed: &[ViewFrustumArc],
+        visibility_config: &VisibilityConfig,
+    ) -> RafxResult<RwLockReadGuard<VisibilityQuery>> {
+        self.inner.visibility_world.update();
+
+        if visibility_config.enable_visibility_update {
+            let mut results = self.inner.visibility_query.write();
+
+            results.objects.clear();
+            results.volumes.clear();
+
+            let storage = self.inner.storage.read();
+
+            // Only the planes of the shadowed view frustums are used, so either handle will do
+            let shadowed_handles = shadowed
+                .iter()
+                .filter_map(|view_frustum| {
+                    self.view_frustum(&view_frustum.inner.static_view_frustum, &storage)
+                        .or_else(|| {
+                            self.view_frustum(&view_frustum.inner.dynamic_view_frustum, &storage)
+                        })
+                        .map(|view_frustum| view_frustum.handle)
+                })
+                .collect::<Vec<_>>();
+
+            if let Some(static_view_frustum) =
+                self.view_frustum(&self.inner.static_view_frustum, &storage)
+            {
+                static_view_frustum.query_shadow_casters(&shadowed_handles, &mut results)?;
+            }
+
+            if let Some(dynamic_view_frustum) =
+                self.view_frustum(&self.inner.dynamic_view_frustum, &storage)
+            {
+                dynamic_view_frustum.query_shadow_casters(&shadowed_handles, &mut results)?;
+            }
+        }
+
+        Ok(self.inner.visibility_query.read())
+    }
+
     fn view_frustum<'a>(
         &self,
         view_frustum: &Option<ViewFrustumObjectId>,
@@ -238,7 +284,22 @@ impl ViewFrustumObject {
     ) -> RafxResult<()> {
         self.visibility_world
             .query_visibility(self.handle, results)
-            .map_err(|_err| RafxError::StringError("Unable to query visibility.".to_string()))?;
+            .map_err(|err| {
+                RafxError::StringError(format!("Unable to query visibility: {:?}", err))
+            })?;
+        Ok(())
+    }
+
+    pub fn query_shadow_casters(
+        &self,
+        shadowed: &[ViewFrustumHandle],
+        results: &mut VisibilityQuery,
+    ) -> RafxResult<()> {
+        self.visibility_world
+            .query_shadow_casters(self.handle, shadowed, results)
+            .map_err(|err| {
+                RafxError::StringError(format!("Unable to query shadow casters: {:?}", err))
+            })?;
         Ok(())
     }
 }
@@ -0,0 +1,90 @@
+use crate::SwapchainResources;
+use rafx_api::{
+    RafxExtents2D, RafxExtents3D, RafxFence, RafxFenceStatus, RafxFormat, RafxResourceState,
+    RafxResourceType, RafxResult, RafxSampleCount, RafxTextureDef,
+};
+use rafx_assets::AssetManager;
+use rafx_framework::graph::SwapchainSurfaceInfo;
+use rafx_framework::{ImageViewResource, ResourceArc};
+use std::sync::Arc;
+
+/// An offscreen image that `Renderer::render_headless` draws into in place of a swapchain image.
+/// This allows rendering without a window (thumbnails, offline rendering, automated image tests).
+/// The image is left in the SHADER_RESOURCE state after each frame.
+pub struct HeadlessRenderTarget {
+    image: ResourceArc<ImageViewResource>,
+    swapchain_resources: SwapchainResources,
+}
+
+impl HeadlessRenderTarget {
+    pub fn new(
+        asset_manager: &AssetManager,
+        extents: RafxExtents2D,
+        format: RafxFormat,
+    ) -> RafxResult<Self> {
+        let device_context = asset_manager.device_context();
+        let texture = device_context.create_texture(&RafxTextureDef {
+            extents: RafxExtents3D {
+                width: extents.width,
+                height: extents.height,
+                depth: 1,
+            },
+            format,
+            sample_count: RafxSampleCount::SampleCount1,
+            resource_type: RafxResourceType::TEXTURE | RafxResourceType::RENDER_TARGET_COLOR,
+            ..Default::default()
+        })?;
+
+        let resources = asset_manager.resources();
+        let image = resources.insert_image_with_debug_name(texture, "headless render target");
+        let image = resources.get_or_create_image_view(&image, None)?;
+
+        // The graph generator sees this as if it were a swapchain of the same size and format
+        let mut swapchain_resources =
+            SwapchainResources::new(device_context, SwapchainSurfaceInfo { extents, format })?;
+        swapchain_resources.swapchain_image_final_state = RafxResourceState::SHADER_RESOURCE;
+
+        Ok(HeadlessRenderTarget {
+            image,
+            swapchain_resources,
+        })
+    }
+
+    pub fn image(&self) -> &ResourceArc<ImageViewResource> {
+        &self.image
+    }
+
+    pub fn extents(&self) -> RafxExtents2D {
+        self.swapchain_resources.swapchain_surface_info.extents
+    }
+
+    pub fn format(&self) -> RafxFormat {
+        self.swapchain_resources.swapchain_surface_info.format
+    }
+
+    pub fn swapchain_resources(&self) -> &SwapchainResources {
+        &self.swapchain_resources
+    }
+}
+
+/// Returned by `Renderer::render_headless`. The fence is signaled when the GPU finishes the frame.
+/// Fences are reused after `MAX_FRAMES_IN_FLIGHT` headless frames, so waiting on an old
+/// `HeadlessFrame` may also wait for a more recent frame.
+pub struct HeadlessFrame {
+    pub(crate) fence: Arc<RafxFence>,
+}
+
+impl HeadlessFrame {
+    pub fn fence(&self) -> &RafxFence {
+        &self.fence
+    }
+
+    /// Blocks until the frame has finished rendering, after which the target's image can be read
+    pub fn wait(&self) -> RafxResult<()> {
+        self.fence.wait()
+    }
+
+    pub fn is_complete(&self) -> RafxResult<bool> {
+        Ok(self.fence.get_fence_status()? != RafxFenceStatus::Incomplete)
+    }
+}
@@ -6,15 +6,23 @@ pub use renderer_asset_plugin::RendererAssetPlugin;
 
 mod render_graph_generator;
 pub use render_graph_generator::RenderGraphGenerator;
+pub use render_graph_generator::RenderTargetView;
 
 mod viewports_resource;
 pub use viewports_resource::RenderViewMeta;
+pub use viewports_resource::SurfaceId;
+pub use viewports_resource::SurfaceViewport;
 pub use viewports_resource::ViewportsResource;
 
+mod headless;
+pub use headless::HeadlessFrame;
+pub use headless::HeadlessRenderTarget;
+
 mod render_thread;
 use render_thread::RenderThread;
 
 mod swapchain_resources;
+pub use swapchain_resources::SurfaceSwapchainResources;
 pub use swapchain_resources::SwapchainResources;
 
 mod render_frame_job;
@@ -29,11 +37,19 @@ mod renderer_thread_pool_none;
 
 //TODO: Find a way to not expose this
 mod swapchain_handling;
+pub use swapchain_handling::SurfaceSwapchains;
 pub use swapchain_handling::SwapchainHandler;
 
 mod renderer;
 pub use renderer::*;
 
+mod screenshot;
+pub use screenshot::ScreenshotFormat;
+pub use screenshot::ScreenshotHandle;
+pub use screenshot::ScreenshotRequest;
+pub use screenshot::ScreenshotResult;
+pub use screenshot::ScreenshotSource;
+
 mod renderer_thread_pool;
 pub use renderer_thread_pool::*;
 
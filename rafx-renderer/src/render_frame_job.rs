@@ -1,7 +1,8 @@
 use super::Renderer;
-use crate::{RenderFeaturePlugin, RendererThreadPool};
+use crate::screenshot::{self, ScreenshotCapture};
+use crate::{RenderFeaturePlugin, RendererThreadPool, SurfaceId};
 use fnv::FnvBuildHasher;
-use rafx_api::{RafxCommandBuffer, RafxDeviceContext, RafxQueue};
+use rafx_api::{RafxCommandBuffer, RafxDeviceContext, RafxFence, RafxQueue};
 use rafx_api::{RafxPresentableFrame, RafxResult};
 use rafx_framework::graph::PreparedRenderGraph;
 use rafx_framework::render_features::render_features_prelude::*;
@@ -15,6 +16,9 @@ pub struct RenderFrameJobResult;
 pub struct RenderFrameJob {
     pub renderer: Renderer,
     pub prepared_render_graph: PreparedRenderGraph,
+    // One graph per surface in ViewportsResource::surfaces, in the same order as the presentable
+    // frames passed to render_async
+    pub surface_render_graphs: Vec<(SurfaceId, PreparedRenderGraph)>,
     pub resource_context: ResourceContext,
     pub frame_packets: Vec<Box<dyn RenderFeatureFramePacket>>,
     pub render_registry: RenderRegistry,
@@ -22,12 +26,14 @@ pub struct RenderFrameJob {
     pub graphics_queue: RafxQueue,
     pub render_views: Vec<RenderView>,
     pub feature_plugins: Arc<Vec<Arc<dyn RenderFeaturePlugin>>>,
+    pub(crate) screenshot_captures: Vec<ScreenshotCapture>,
 }
 
 impl RenderFrameJob {
     pub fn render_async(
         self,
         presentable_frame: RafxPresentableFrame,
+        surface_presentable_frames: Vec<(SurfaceId, RafxPresentableFrame)>,
         render_resources: &RenderResources,
     ) -> RenderFrameJobResult {
         let t0 = std::time::Instant::now();
@@ -37,8 +43,10 @@ impl RenderFrameJob {
             renderer_inner.thread_pool.clone_to_box()
         };
 
+        let resource_context = self.resource_context.clone();
         let result = Self::do_render_async(
             self.prepared_render_graph,
+            self.surface_render_graphs,
             self.resource_context,
             self.frame_packets,
             self.render_registry,
@@ -56,17 +64,74 @@ impl RenderFrameJob {
         );
 
         match result {
-            Ok(command_buffers) => {
+            Ok((mut command_buffers, surface_command_buffers)) => {
                 // ignore the error, we will receive it when we try to acquire the next image
                 let graphics_queue = self.renderer.graphics_queue();
 
+                let mut screenshot_frame_readback = None;
+                if !self.screenshot_captures.is_empty() {
+                    profiling::scope!("Record Screenshot Captures");
+                    if let Some((command_buffer, frame_readback)) =
+                        screenshot::record_screenshot_captures(
+                            &resource_context,
+                            graphics_queue,
+                            self.screenshot_captures,
+                        )
+                    {
+                        command_buffers.push(command_buffer);
+                        screenshot_frame_readback = Some(frame_readback);
+                    }
+                }
+
                 let refs: Vec<&RafxCommandBuffer> = command_buffers.iter().map(|x| &**x).collect();
-                let _ = presentable_frame.present(graphics_queue, &refs);
+                let present_result = presentable_frame.present(graphics_queue, &refs);
+
+                if let Err(e) = resource_context
+                    .render_graph_cache()
+                    .submit_gpu_timings_fence(graphics_queue)
+                {
+                    log::warn!("Failed to submit render graph GPU timings fence: {:?}", e);
+                }
+
+                if let Some(frame_readback) = screenshot_frame_readback {
+                    if let Err(e) = present_result {
+                        frame_readback.fail(format!("Failed to present frame: {:?}", e));
+                    }
+
+                    // The readback is kept until the fence signals even if it failed, since the
+                    // copy may have been submitted
+                    match frame_readback.submit_fence(graphics_queue) {
+                        Ok(()) => self
+                            .renderer
+                            .inner
+                            .lock()
+                            .unwrap()
+                            .screenshots
+                            .add_frame_readback(frame_readback),
+                        Err(e) => frame_readback.fail(format!("Failed to submit fence: {:?}", e)),
+                    }
+                }
+
+                for ((_, surface_presentable_frame), command_buffers) in surface_presentable_frames
+                    .into_iter()
+                    .zip(surface_command_buffers)
+                {
+                    let refs: Vec<&RafxCommandBuffer> =
+                        command_buffers.iter().map(|x| &**x).collect();
+                    let _ = surface_presentable_frame.present(graphics_queue, &refs);
+                }
             }
             Err(err) => {
                 log::error!("Render thread failed with error {:?}", err);
+                for capture in self.screenshot_captures {
+                    capture.fail(format!("Frame failed to render: {:?}", err));
+                }
+
                 // Pass error on to the next swapchain image acquire call
                 let graphics_queue = self.renderer.graphics_queue();
+                for (_, surface_presentable_frame) in surface_presentable_frames {
+                    surface_presentable_frame.present_with_error(graphics_queue, err.clone());
+                }
                 presentable_frame.present_with_error(graphics_queue, err);
             }
         }
@@ -80,9 +145,86 @@ impl RenderFrameJob {
         RenderFrameJobResult {}
     }
 
+    /// Used by `Renderer::render_headless`. Renders on the calling thread and submits the command
+    /// buffers with `fence` instead of presenting.
+    pub fn render_headless(
+        self,
+        fence: &RafxFence,
+        render_resources: &RenderResources,
+    ) -> RafxResult<()> {
+        let mut thread_pool = {
+            let mut renderer_inner = self.renderer.inner.lock().unwrap();
+            renderer_inner.thread_pool.clone_to_box()
+        };
+
+        let resource_context = self.resource_context.clone();
+        let result = Self::do_render_async(
+            self.prepared_render_graph,
+            self.surface_render_graphs,
+            self.resource_context,
+            self.frame_packets,
+            self.render_registry,
+            render_resources,
+            self.graphics_queue.clone(),
+            self.render_views,
+            self.feature_plugins,
+            &mut *thread_pool,
+        );
+
+        let mut command_buffers = match result {
+            Ok((command_buffers, _)) => command_buffers,
+            Err(err) => {
+                for capture in self.screenshot_captures {
+                    capture.fail(format!("Frame failed to render: {:?}", err));
+                }
+                return Err(err);
+            }
+        };
+
+        let mut screenshot_frame_readback = None;
+        if !self.screenshot_captures.is_empty() {
+            profiling::scope!("Record Screenshot Captures");
+            if let Some((command_buffer, frame_readback)) = screenshot::record_screenshot_captures(
+                &resource_context,
+                &self.graphics_queue,
+                self.screenshot_captures,
+            ) {
+                command_buffers.push(command_buffer);
+                screenshot_frame_readback = Some(frame_readback);
+            }
+        }
+
+        let refs: Vec<&RafxCommandBuffer> = command_buffers.iter().map(|x| &**x).collect();
+        let submit_result = self.graphics_queue.submit(&refs, &[], &[], Some(fence));
+
+        if let Some(frame_readback) = screenshot_frame_readback {
+            if let Err(e) = &submit_result {
+                frame_readback.fail(format!("Failed to submit frame: {:?}", e));
+            }
+
+            match frame_readback.submit_fence(&self.graphics_queue) {
+                Ok(()) => self
+                    .renderer
+                    .inner
+                    .lock()
+                    .unwrap()
+                    .screenshots
+                    .add_frame_readback(frame_readback),
+                Err(e) => frame_readback.fail(format!("Failed to submit fence: {:?}", e)),
+            }
+        }
+
+        submit_result?;
+
+        resource_context
+            .render_graph_cache()
+            .submit_gpu_timings_fence(&self.graphics_queue)
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn do_render_async(
         prepared_render_graph: PreparedRenderGraph,
+        surface_render_graphs: Vec<(SurfaceId, PreparedRenderGraph)>,
         resource_context: ResourceContext,
         frame_packets: Vec<Box<dyn RenderFeatureFramePacket>>,
         render_registry: RenderRegistry,
@@ -91,7 +233,7 @@ impl RenderFrameJob {
         render_views: Vec<RenderView>,
         feature_plugins: Arc<Vec<Arc<dyn RenderFeaturePlugin>>>,
         thread_pool: &mut dyn RendererThreadPool,
-    ) -> RafxResult<Vec<DynCommandBuffer>> {
+    ) -> RafxResult<(Vec<DynCommandBuffer>, Vec<Vec<DynCommandBuffer>>)> {
         let t0 = std::time::Instant::now();
 
         //
@@ -159,13 +301,28 @@ impl RenderFrameJob {
                 )
             };
 
-            let prepared_render_data =
-                PreparedRenderData::new(&submit_node_blocks, write_jobs, write_context);
+            let prepared_render_data = PreparedRenderData::new(
+                &submit_node_blocks,
+                write_jobs,
+                thread_pool.write_thread_pool(),
+                write_context,
+            );
 
-            {
+            let command_buffers = {
                 profiling::scope!("Execute Render Graph");
-                prepared_render_graph.execute_graph(prepared_render_data, &graphics_queue)?
+                prepared_render_graph.execute_graph(&prepared_render_data, &graphics_queue)?
+            };
+
+            let mut surface_command_buffers = Vec::with_capacity(surface_render_graphs.len());
+            for (surface_id, surface_render_graph) in &surface_render_graphs {
+                profiling::scope!("Execute Surface Render Graph");
+                log::trace!("Execute render graph for {:?}", surface_id);
+                surface_command_buffers.push(
+                    surface_render_graph.execute_graph(&prepared_render_data, &graphics_queue)?,
+                );
             }
+
+            (command_buffers, surface_command_buffers)
         };
 
         let t2 = std::time::Instant::now();
@@ -1,14 +1,34 @@
+use crate::SwapchainResources;
+use rafx_api::RafxFormat;
 use rafx_assets::AssetManager;
 use rafx_framework::graph::PreparedRenderGraph;
 use rafx_framework::render_features::{ExtractResources, RenderView};
 use rafx_framework::{ImageViewResource, RafxResult, RenderResources, ResourceArc};
 
+/// A view in `ViewportsResource::render_targets`. The view should be drawn into `image`, the
+/// persistent image created by `AssetManager::create_render_target` with the same name.
+#[derive(Clone)]
+pub struct RenderTargetView {
+    pub name: String,
+    pub view: RenderView,
+    pub image: ResourceArc<ImageViewResource>,
+    pub format: RafxFormat,
+}
+
+/// Called once per frame for the main window and once for each surface in
+/// `ViewportsResource::surfaces`. `swapchain_resources` and `main_view` belong to the window that
+/// the graph is being generated for.
+///
+/// `render_target_views` are only provided for the main window's graph. They must be drawn before
+/// any node that might sample their images (i.e. materials that reference a render target by name)
 pub trait RenderGraphGenerator: 'static + Send {
     fn generate_render_graph(
         &self,
         asset_manager: &AssetManager,
         swapchain_image: ResourceArc<ImageViewResource>,
+        swapchain_resources: &SwapchainResources,
         main_view: RenderView,
+        render_target_views: &[RenderTargetView],
         extract_resources: &ExtractResources,
         render_resources: &RenderResources,
     ) -> RafxResult<PreparedRenderGraph>;
@@ -1,5 +1,5 @@
 use super::render_frame_job::RenderFrameJobResult;
-use super::RenderFrameJob;
+use super::{RenderFrameJob, SurfaceId};
 use crossbeam_channel::{Receiver, RecvTimeoutError, Sender};
 use rafx_api::RafxPresentableFrame;
 use rafx_framework::RenderResources;
@@ -7,7 +7,11 @@ use std::sync::{Arc, Mutex};
 use std::thread::JoinHandle;
 
 enum RenderThreadMessage {
-    Render(RenderFrameJob, RafxPresentableFrame),
+    Render(
+        RenderFrameJob,
+        RafxPresentableFrame,
+        Vec<(SurfaceId, RafxPresentableFrame)>,
+    ),
     Finish,
 }
 
@@ -56,11 +60,13 @@ impl RenderThread {
         &mut self,
         prepared_frame: RenderFrameJob,
         presentable_frame: RafxPresentableFrame,
+        surface_presentable_frames: Vec<(SurfaceId, RafxPresentableFrame)>,
     ) {
         self.job_tx
             .send(RenderThreadMessage::Render(
                 prepared_frame,
                 presentable_frame,
+                surface_presentable_frames,
             ))
             .unwrap();
 
@@ -94,12 +100,20 @@ impl RenderThread {
             profiling::register_thread!();
 
             match job_rx.recv()? {
-                RenderThreadMessage::Render(prepared_frame, frame_in_flight) => {
+                RenderThreadMessage::Render(
+                    prepared_frame,
+                    frame_in_flight,
+                    surface_frames_in_flight,
+                ) => {
                     profiling::scope!("Render Frame");
 
                     log::trace!("kick off render");
                     let resource_lock = render_resources.lock().unwrap();
-                    let result = prepared_frame.render_async(frame_in_flight, &*resource_lock);
+                    let result = prepared_frame.render_async(
+                        frame_in_flight,
+                        surface_frames_in_flight,
+                        &*resource_lock,
+                    );
                     result_tx.send(result).unwrap();
                 }
                 RenderThreadMessage::Finish => {
@@ -1,9 +1,11 @@
+use crate::screenshot::ScreenshotManager;
 use rafx_assets::distill_impl::AssetResource;
 use rafx_assets::{image_upload, AssetManagerRenderResource, GpuImageDataColorSpace};
 use rafx_assets::{AssetManager, GpuImageData};
+use rafx_framework::graph::RenderGraphGpuTimings;
 use rafx_framework::render_features::render_features_prelude::*;
 use rafx_framework::visibility::{VisibilityConfig, VisibilityRegion};
-use rafx_framework::{DynResourceAllocatorSet, RenderResources};
+use rafx_framework::{DynResourceAllocatorSet, RenderResources, MAX_FRAMES_IN_FLIGHT};
 use rafx_framework::{ImageViewResource, ResourceArc};
 use std::sync::{Arc, Mutex};
 
@@ -12,11 +14,21 @@ use super::*;
 use super::{RenderFeaturePlugin, RenderGraphGenerator, ViewportsResource};
 use rafx_api::extra::upload::{RafxTransferUpload, RafxUploadError};
 use rafx_api::{
-    RafxDeviceContext, RafxError, RafxPresentableFrame, RafxQueue, RafxResourceType, RafxResult,
-    RafxSwapchainHelper,
+    RafxDeviceContext, RafxError, RafxFence, RafxPresentableFrame, RafxQueue, RafxResourceState,
+    RafxResourceType, RafxResult, RafxSwapchainHelper,
 };
 use rafx_assets::image_upload::ImageUploadParams;
 
+// What the main view of a frame is drawn into
+#[derive(Copy, Clone)]
+enum RenderFrameTarget<'a> {
+    Swapchain {
+        presentable_frame: &'a RafxPresentableFrame,
+        surface_presentable_frames: &'a [(SurfaceId, RafxPresentableFrame)],
+    },
+    Headless(&'a HeadlessRenderTarget),
+}
+
 #[derive(Default, Copy, Clone, Debug)]
 pub struct RendererConfigResource {
     pub visibility_config: VisibilityConfig,
@@ -36,6 +48,10 @@ pub struct RendererInner {
     pub(super) feature_plugins: Arc<Vec<Arc<dyn RenderFeaturePlugin>>>,
     pub(super) temporary_work: RenderJobExtractAllocationContext,
     pub(super) thread_pool: Box<dyn RendererThreadPool>,
+    pub(super) gpu_timings: Option<RenderGraphGpuTimings>,
+    pub(super) screenshots: ScreenshotManager,
+    pub(super) headless_fences: Vec<Arc<RafxFence>>,
+    pub(super) next_headless_fence_index: usize,
 }
 
 #[derive(Clone)]
@@ -148,6 +164,11 @@ impl Renderer {
 
         let render_thread = RenderThread::start(render_resources);
 
+        let mut headless_fences = Vec::with_capacity(MAX_FRAMES_IN_FLIGHT);
+        for _ in 0..MAX_FRAMES_IN_FLIGHT {
+            headless_fences.push(Arc::new(device_context.create_fence()?));
+        }
+
         let num_features = RenderRegistry::registered_feature_count() as usize;
         let renderer = RendererInner {
             feature_plugins,
@@ -155,6 +176,10 @@ impl Renderer {
             render_graph_generator,
             thread_pool,
             temporary_work: RenderJobExtractAllocationContext::new(num_features),
+            gpu_timings: None,
+            screenshots: ScreenshotManager::new(),
+            headless_fences,
+            next_headless_fence_index: 0,
         };
 
         Ok(Renderer {
@@ -178,6 +203,74 @@ impl Renderer {
         &self.transfer_queue
     }
 
+    /// GPU time spent in each node of the render graph. These are read back without stalling, so
+    /// they are from a frame that finished a few frames ago. Returns None if the device does not
+    /// support timestamp queries.
+    pub fn gpu_timings(&self) -> Option<RenderGraphGpuTimings> {
+        self.inner.lock().unwrap().gpu_timings.clone()
+    }
+
+    /// Captures one or more upcoming frames to disk. The copy is appended to the end of each
+    /// captured frame and read back once the GPU has finished with it, so the files are written a
+    /// few frames later. Poll the returned handle to find out when they are done.
+    pub fn request_screenshot(
+        &self,
+        request: ScreenshotRequest,
+    ) -> ScreenshotHandle {
+        self.inner
+            .lock()
+            .unwrap()
+            .screenshots
+            .request_screenshot(request)
+    }
+
+    /// Renders a frame into `target` instead of the main window's swapchain. No window,
+    /// `RafxSwapchainHelper` or `SwapchainHandler` is required, but
+    /// `ViewportsResource::main_view_meta` must be set. The frame is recorded and submitted on the
+    /// calling thread. Rather than being presented, the returned frame's fence is signaled when the
+    /// GPU is done with it.
+    #[profiling::function]
+    pub fn render_headless(
+        &self,
+        extract_resources: &mut ExtractResources,
+        target: &HeadlessRenderTarget,
+    ) -> RafxResult<HeadlessFrame> {
+        let (fence, render_resources) = {
+            let mut guard = self.inner.lock().unwrap();
+            let renderer_inner = &mut *guard;
+
+            // A windowed frame may still be using the render resources on the render thread
+            renderer_inner
+                .render_thread
+                .wait_for_render_finish(std::time::Duration::from_secs(30));
+
+            let fence =
+                renderer_inner.headless_fences[renderer_inner.next_headless_fence_index].clone();
+            renderer_inner.next_headless_fence_index = (renderer_inner.next_headless_fence_index
+                + 1)
+                % renderer_inner.headless_fences.len();
+
+            (
+                fence,
+                renderer_inner.render_thread.render_resources().clone(),
+            )
+        };
+
+        // Limit the number of headless frames in flight, same as the swapchain does. Resources
+        // that are released by the previous frames are only kept for MAX_FRAMES_IN_FLIGHT frames
+        fence.wait()?;
+
+        let render_frame_job = Self::try_create_render_job(
+            self,
+            extract_resources,
+            RenderFrameTarget::Headless(target),
+        )?;
+
+        render_frame_job.render_headless(&fence, &*render_resources.lock().unwrap())?;
+
+        Ok(HeadlessFrame { fence })
+    }
+
     fn upload_image_data(
         device_context: &RafxDeviceContext,
         upload: &mut RafxTransferUpload,
@@ -204,18 +297,58 @@ impl Renderer {
         //
         let t0 = std::time::Instant::now();
 
-        let presentable_frame = {
+        let (presentable_frame, surface_presentable_frames) = {
             let viewports_resource = extract_resources.fetch::<ViewportsResource>();
             let mut swapchain_helper = extract_resources.fetch_mut::<RafxSwapchainHelper>();
             let mut asset_manager = extract_resources.fetch_mut::<AssetManager>();
-            SwapchainHandler::acquire_next_image(
+            let presentable_frame = SwapchainHandler::acquire_next_image(
                 &mut *swapchain_helper,
                 &mut *asset_manager,
                 self,
                 viewports_resource.main_window_size.width,
                 viewports_resource.main_window_size.height,
-            )
-        }?;
+            )?;
+
+            let mut surface_presentable_frames = Vec::default();
+            if let Some(mut surface_swapchains) =
+                extract_resources.try_fetch_mut::<SurfaceSwapchains>()
+            {
+                for (&surface_id, surface_viewport) in &viewports_resource.surfaces {
+                    if !surface_swapchains.contains_surface(surface_id)
+                        || surface_viewport.view_meta.is_none()
+                    {
+                        continue;
+                    }
+
+                    let result = SwapchainHandler::acquire_next_surface_image(
+                        &mut *surface_swapchains,
+                        surface_id,
+                        &mut *asset_manager,
+                        self,
+                        surface_viewport.window_size.width,
+                        surface_viewport.window_size.height,
+                    );
+
+                    match result {
+                        Ok(surface_presentable_frame) => {
+                            surface_presentable_frames.push((surface_id, surface_presentable_frame))
+                        }
+                        Err(e) => {
+                            // The frames that were already acquired must still be presented. The
+                            // error will be returned when acquiring the main window's next image.
+                            for (_, surface_presentable_frame) in surface_presentable_frames {
+                                surface_presentable_frame
+                                    .present_with_error(&self.graphics_queue, e.clone());
+                            }
+                            presentable_frame.present_with_error(&self.graphics_queue, e);
+                            return Ok(());
+                        }
+                    }
+                }
+            }
+
+            (presentable_frame, surface_presentable_frames)
+        };
 
         self.inner
             .lock()
@@ -229,7 +362,12 @@ impl Renderer {
             (t1 - t0).as_secs_f32() * 1000.0
         );
 
-        Self::create_and_start_render_job(self, extract_resources, presentable_frame);
+        Self::create_and_start_render_job(
+            self,
+            extract_resources,
+            presentable_frame,
+            surface_presentable_frames,
+        );
 
         Ok(())
     }
@@ -238,8 +376,16 @@ impl Renderer {
         renderer: &Renderer,
         extract_resources: &mut ExtractResources,
         presentable_frame: RafxPresentableFrame,
+        surface_presentable_frames: Vec<(SurfaceId, RafxPresentableFrame)>,
     ) {
-        let result = Self::try_create_render_job(&renderer, extract_resources, &presentable_frame);
+        let result = Self::try_create_render_job(
+            &renderer,
+            extract_resources,
+            RenderFrameTarget::Swapchain {
+                presentable_frame: &presentable_frame,
+                surface_presentable_frames: &surface_presentable_frames,
+            },
+        );
 
         let mut guard = renderer.inner.lock().unwrap();
         let renderer_inner = &mut *guard;
@@ -249,16 +395,22 @@ impl Renderer {
                     // NOTE(dvd): Run single threaded. Useful when trying to track # of global memory allocations.
                     let _ = prepared_frame.render_async(
                         presentable_frame,
+                        surface_presentable_frames,
                         &*guard.render_thread.render_resources().lock().unwrap(),
                     );
                 } else {
-                    renderer_inner
-                        .render_thread
-                        .render(prepared_frame, presentable_frame);
+                    renderer_inner.render_thread.render(
+                        prepared_frame,
+                        presentable_frame,
+                        surface_presentable_frames,
+                    );
                 }
             }
             Err(e) => {
                 let graphics_queue = renderer.graphics_queue();
+                for (_, surface_presentable_frame) in surface_presentable_frames {
+                    surface_presentable_frame.present_with_error(graphics_queue, e.clone());
+                }
                 presentable_frame.present_with_error(graphics_queue, e)
             }
         };
@@ -267,7 +419,7 @@ impl Renderer {
     fn try_create_render_job(
         renderer: &Renderer,
         extract_resources: &mut ExtractResources,
-        presentable_frame: &RafxPresentableFrame,
+        target: RenderFrameTarget,
     ) -> RafxResult<RenderFrameJob> {
         //
         // Fetch resources
@@ -287,6 +439,8 @@ impl Renderer {
 
         let mut guard = renderer.inner.lock().unwrap();
         let renderer_inner = &mut *guard;
+        renderer_inner.gpu_timings = resource_context.render_graph_cache().gpu_timings();
+
         let render_resources = &mut renderer_inner
             .render_thread
             .render_resources()
@@ -301,23 +455,40 @@ impl Renderer {
         //
         // Swapchain Status
         //
-        let swapchain_image = {
-            // Temporary hack to jam a swapchain image into the existing resource lookups.. may want
-            // to reconsider this later since the ResourceArc can be held past the lifetime of the
-            // swapchain image
+        // Temporary hack to jam a swapchain image into the existing resource lookups.. may want
+        // to reconsider this later since the ResourceArc can be held past the lifetime of the
+        // swapchain image
+        let insert_swapchain_image = |presentable_frame: &RafxPresentableFrame| {
             let swapchain_image = presentable_frame.swapchain_texture().clone();
 
             let swapchain_image = resource_context.resources().insert_image(swapchain_image);
 
             resource_context
                 .resources()
-                .get_or_create_image_view(&swapchain_image, None)?
+                .get_or_create_image_view(&swapchain_image, None)
         };
 
-        let swapchain_surface_info = render_resources
-            .fetch::<SwapchainResources>()
-            .swapchain_surface_info
-            .clone();
+        let (swapchain_image, swapchain_surface_info, surface_presentable_frames) = match target {
+            RenderFrameTarget::Swapchain {
+                presentable_frame,
+                surface_presentable_frames,
+            } => (
+                insert_swapchain_image(presentable_frame)?,
+                render_resources
+                    .fetch::<SwapchainResources>()
+                    .swapchain_surface_info
+                    .clone(),
+                surface_presentable_frames,
+            ),
+            RenderFrameTarget::Headless(headless_target) => (
+                headless_target.image().clone(),
+                headless_target
+                    .swapchain_resources()
+                    .swapchain_surface_info
+                    .clone(),
+                &[][..],
+            ),
+        };
 
         let render_view_set = RenderViewSet::default();
 
@@ -328,21 +499,57 @@ impl Renderer {
         let viewports_resource = extract_resources.fetch::<ViewportsResource>();
         let view_meta = viewports_resource.main_view_meta.clone().unwrap();
 
-        let main_window_size = viewports_resource.main_window_size;
+        let main_window_size = match target {
+            RenderFrameTarget::Swapchain { .. } => viewports_resource.main_window_size,
+            RenderFrameTarget::Headless(headless_target) => headless_target.extents(),
+        };
 
-        let main_view = render_view_set.create_view(
-            view_meta.view_frustum,
-            view_meta.eye_position,
-            view_meta.view,
-            view_meta.proj,
+        let main_view = Renderer::create_view_from_meta(
+            &render_view_set,
+            view_meta,
             (main_window_size.width, main_window_size.height),
-            view_meta.depth_range,
-            view_meta.render_phase_mask,
-            view_meta.render_feature_mask,
-            view_meta.render_feature_flag_mask,
-            view_meta.debug_name,
         );
 
+        let mut surface_views = Vec::with_capacity(surface_presentable_frames.len());
+        for (surface_id, surface_presentable_frame) in surface_presentable_frames {
+            let surface_viewport = &viewports_resource.surfaces[surface_id];
+            let view_meta = surface_viewport.view_meta.clone().unwrap();
+            let window_size = surface_viewport.window_size;
+
+            let surface_view = Renderer::create_view_from_meta(
+                &render_view_set,
+                view_meta,
+                (window_size.width, window_size.height),
+            );
+
+            let surface_swapchain_image = insert_swapchain_image(surface_presentable_frame)?;
+            surface_views.push((*surface_id, surface_swapchain_image, surface_view));
+        }
+
+        let mut render_target_views = Vec::with_capacity(viewports_resource.render_targets.len());
+        for (name, view_meta) in &viewports_resource.render_targets {
+            let image = asset_manager.render_target(name).ok_or_else(|| {
+                format!(
+                    "ViewportsResource has a view for render target {} but the render target has not been created",
+                    name
+                )
+            })?;
+
+            let texture_def = image.get_raw().image.get_raw().image.texture_def().clone();
+            let view = Renderer::create_view_from_meta(
+                &render_view_set,
+                view_meta.clone(),
+                (texture_def.extents.width, texture_def.extents.height),
+            );
+
+            render_target_views.push(RenderTargetView {
+                name: name.clone(),
+                view,
+                image: image.clone(),
+                format: texture_def.format,
+            });
+        }
+
         //
         // Compute Views
         //
@@ -352,6 +559,12 @@ impl Renderer {
         {
             profiling::scope!("Compute Views");
             render_views.push(main_view.clone());
+            for render_target_view in &render_target_views {
+                render_views.push(render_target_view.view.clone());
+            }
+            for (_, _, surface_view) in &surface_views {
+                render_views.push(surface_view.clone());
+            }
             for plugin in &*renderer_inner.feature_plugins {
                 plugin.add_render_views(
                     extract_resources,
@@ -438,15 +651,74 @@ impl Renderer {
         render_resources.remove::<AssetManagerRenderResource>();
 
         //TODO: This is now possible to run on the render thread
-        let prepared_render_graph = renderer_inner
-            .render_graph_generator
-            .generate_render_graph(
-                asset_manager,
-                swapchain_image,
-                main_view.clone(),
-                extract_resources,
-                render_resources,
-            )?;
+        let prepared_render_graph = {
+            let main_swapchain_resources;
+            let swapchain_resources = match target {
+                RenderFrameTarget::Swapchain { .. } => {
+                    main_swapchain_resources = render_resources.fetch::<SwapchainResources>();
+                    &*main_swapchain_resources
+                }
+                RenderFrameTarget::Headless(headless_target) => {
+                    headless_target.swapchain_resources()
+                }
+            };
+
+            renderer_inner
+                .render_graph_generator
+                .generate_render_graph(
+                    asset_manager,
+                    swapchain_image,
+                    swapchain_resources,
+                    main_view.clone(),
+                    &render_target_views,
+                    extract_resources,
+                    render_resources,
+                )?
+        };
+
+        let mut surface_render_graphs = Vec::with_capacity(surface_views.len());
+        for (surface_id, surface_swapchain_image, surface_view) in surface_views {
+            let surface_swapchain_resources = render_resources.fetch::<SurfaceSwapchainResources>();
+            let swapchain_resources = surface_swapchain_resources
+                .surfaces
+                .get(&surface_id)
+                .ok_or_else(|| format!("No swapchain resources exist for {:?}", surface_id))?;
+
+            let surface_render_graph = renderer_inner
+                .render_graph_generator
+                .generate_render_graph(
+                    asset_manager,
+                    surface_swapchain_image,
+                    swapchain_resources,
+                    surface_view,
+                    &[],
+                    extract_resources,
+                    render_resources,
+                )?;
+
+            surface_render_graphs.push((surface_id, surface_render_graph));
+        }
+
+        let screenshot_captures = match target {
+            RenderFrameTarget::Swapchain {
+                presentable_frame, ..
+            } => renderer_inner.screenshots.begin_frame(
+                &prepared_render_graph,
+                (
+                    presentable_frame.swapchain_texture(),
+                    RafxResourceState::PRESENT,
+                ),
+            ),
+            RenderFrameTarget::Headless(headless_target) => renderer_inner.screenshots.begin_frame(
+                &prepared_render_graph,
+                (
+                    &headless_target.image().get_raw().image.get_raw().image,
+                    headless_target
+                        .swapchain_resources()
+                        .swapchain_image_final_state,
+                ),
+            ),
+        };
 
         let renderer = renderer.clone();
         let graphics_queue = renderer.graphics_queue.clone();
@@ -455,6 +727,7 @@ impl Renderer {
         let prepared_frame = RenderFrameJob {
             renderer,
             prepared_render_graph,
+            surface_render_graphs,
             resource_context,
             frame_packets,
             render_registry,
@@ -462,11 +735,31 @@ impl Renderer {
             graphics_queue,
             feature_plugins,
             render_views,
+            screenshot_captures,
         };
 
         Ok(prepared_frame)
     }
 
+    fn create_view_from_meta(
+        render_view_set: &RenderViewSet,
+        view_meta: RenderViewMeta,
+        extents: (u32, u32),
+    ) -> RenderView {
+        render_view_set.create_view(
+            view_meta.view_frustum,
+            view_meta.eye_position,
+            view_meta.view,
+            view_meta.proj,
+            extents,
+            view_meta.depth_range,
+            view_meta.render_phase_mask,
+            view_meta.render_feature_mask,
+            view_meta.render_feature_flag_mask,
+            view_meta.debug_name,
+        )
+    }
+
     fn create_view_visibility_jobs<'visibility>(
         render_views: &[RenderView],
         visibility_region: &'visibility VisibilityRegion,
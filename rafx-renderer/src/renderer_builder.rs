@@ -7,6 +7,7 @@ use rafx_api::{RafxApi, RafxQueueType, RafxResult};
 use rafx_assets::distill_impl::AssetResource;
 use rafx_assets::{AssetManager, UploadQueueConfig};
 use rafx_framework::render_features::{ExtractResources, RenderRegistryBuilder};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 pub enum AssetSource {
@@ -27,6 +28,7 @@ pub struct RendererBuilderResult {
 pub struct RendererBuilder {
     feature_plugins: Vec<Arc<dyn RenderFeaturePlugin>>,
     asset_plugins: Vec<Arc<dyn RendererAssetPlugin>>,
+    pipeline_cache_path: Option<PathBuf>,
 }
 
 impl RendererBuilder {
@@ -46,6 +48,15 @@ impl RendererBuilder {
         self
     }
 
+    // Load compiled pipelines from this file on startup and write them back on shutdown
+    pub fn pipeline_cache_path(
+        mut self,
+        path: impl Into<PathBuf>,
+    ) -> Self {
+        self.pipeline_cache_path = Some(path.into());
+        self
+    }
+
     pub fn build(
         self,
         extract_resources: ExtractResources,
@@ -117,6 +128,13 @@ impl RendererBuilder {
             &transfer_queue,
         );
 
+        if let Some(pipeline_cache_path) = self.pipeline_cache_path {
+            asset_manager
+                .resource_manager()
+                .graphics_pipeline_cache()
+                .load_pipeline_cache(pipeline_cache_path)?;
+        }
+
         asset_manager.register_default_asset_types(&mut asset_resource);
 
         for plugin in &self.asset_plugins {
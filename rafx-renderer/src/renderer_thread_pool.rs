@@ -16,7 +16,8 @@ use std::sync::Arc;
 ///
 /// # Write
 ///
-/// The `write` step is not able to be parallelized in this release.
+/// The `write` step may use the `RenderJobWriteThreadPool` returned by `write_thread_pool` to write
+/// large `ViewPhaseSubmitNodeBlock`s into secondary command buffers in parallel.
 pub trait RendererThreadPool: Sync + Send {
     /// Each `RenderView` has an associated `ViewVisibilityJob` for calculating visible render objects
     /// from that `RenderView`.
@@ -71,5 +72,12 @@ pub trait RendererThreadPool: Sync + Send {
         finished_prepare_jobs: &Vec<Arc<dyn RenderFeaturePrepareJob<'prepare> + 'prepare>>,
     ) -> SubmitNodeBlocks;
 
+    /// Renderpasses that use secondary command buffers split each `ViewPhaseSubmitNodeBlock` into
+    /// chunks that are written using the returned `RenderJobWriteThreadPool`. If `None` is
+    /// returned, the chunks are written on the render thread.
+    fn write_thread_pool(&self) -> Option<&dyn RenderJobWriteThreadPool> {
+        None
+    }
+
     fn clone_to_box(&mut self) -> Box<dyn RendererThreadPool>;
 }
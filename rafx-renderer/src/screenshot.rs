@@ -0,0 +1,838 @@
+use crossbeam_channel::{Receiver, Sender};
+use rafx_api::extra::readback::RafxTextureReadbackBuffer;
+use rafx_api::{
+    RafxCommandBufferDef, RafxCommandPoolDef, RafxExtents3D, RafxFence, RafxFenceStatus,
+    RafxFormat, RafxQueue, RafxResourceState, RafxResult, RafxTexture,
+};
+use rafx_base::half::f16_to_f32;
+use rafx_framework::graph::{PreparedRenderGraph, RenderGraphResourceName};
+use rafx_framework::{DynCommandBuffer, ResourceContext};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// The image a screenshot is read from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScreenshotSource {
+    /// The swapchain image, as it is presented. For frames rendered with
+    /// `Renderer::render_headless()`, the headless target's image.
+    Swapchain,
+    /// An output image of the render graph, identified by the name given to it with
+    /// `RenderGraphBuilder::set_image_name()`
+    OutputImage(RenderGraphResourceName),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScreenshotFormat {
+    /// EXR for floating point images, PNG for everything else
+    Auto,
+    Png,
+    Exr,
+}
+
+/// Describes a screenshot to capture with `Renderer::request_screenshot()`
+#[derive(Debug, Clone)]
+pub struct ScreenshotRequest {
+    pub source: ScreenshotSource,
+    pub format: ScreenshotFormat,
+    /// When capturing a single frame, the file to write. The extension is replaced to match the
+    /// format that is written. When capturing multiple frames, the directory to write them to as
+    /// frame_00000.png, frame_00001.png, etc.
+    pub path: PathBuf,
+    /// Number of consecutive frames to capture
+    pub frame_count: u32,
+}
+
+impl ScreenshotRequest {
+    /// Capture the next frame presented to the swapchain
+    pub fn new<T: Into<PathBuf>>(path: T) -> Self {
+        ScreenshotRequest {
+            source: ScreenshotSource::Swapchain,
+            format: ScreenshotFormat::Auto,
+            path: path.into(),
+            frame_count: 1,
+        }
+    }
+
+    /// Capture `frame_count` consecutive frames into a directory
+    pub fn frame_sequence<T: Into<PathBuf>>(
+        directory: T,
+        frame_count: u32,
+    ) -> Self {
+        ScreenshotRequest {
+            frame_count,
+            ..ScreenshotRequest::new(directory)
+        }
+    }
+
+    pub fn with_source(
+        mut self,
+        source: ScreenshotSource,
+    ) -> Self {
+        self.source = source;
+        self
+    }
+
+    pub fn with_format(
+        mut self,
+        format: ScreenshotFormat,
+    ) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// The files that were written for a screenshot request, in frame order
+pub type ScreenshotResult = RafxResult<Vec<PathBuf>>;
+
+/// Returned by `Renderer::request_screenshot()`. Screenshots are read back without stalling the
+/// GPU, so the result is available a few frames after the last captured frame is rendered.
+pub struct ScreenshotHandle {
+    result_rx: Receiver<ScreenshotResult>,
+}
+
+impl ScreenshotHandle {
+    /// Returns the result once all frames have been written (or the capture failed), otherwise
+    /// returns None
+    pub fn try_take_result(&self) -> Option<ScreenshotResult> {
+        self.result_rx.try_recv().ok()
+    }
+}
+
+struct ScreenshotRequestState {
+    request: ScreenshotRequest,
+    result_tx: Sender<ScreenshotResult>,
+    // Number of frames that have been handed out for capture
+    frames_started: u32,
+    written_paths: Vec<PathBuf>,
+    finished: bool,
+}
+
+impl ScreenshotRequestState {
+    fn frame_path(
+        &self,
+        frame: u32,
+        extension: &str,
+    ) -> PathBuf {
+        if self.request.frame_count > 1 {
+            self.request
+                .path
+                .join(format!("frame_{:05}.{}", frame, extension))
+        } else {
+            self.request.path.with_extension(extension)
+        }
+    }
+
+    fn fail(
+        &mut self,
+        error: String,
+    ) {
+        log::error!("Screenshot {:?} failed: {}", self.request.path, error);
+        if !self.finished {
+            self.finished = true;
+            let _ = self.result_tx.send(Err(error.into()));
+        }
+    }
+
+    fn frame_written(
+        &mut self,
+        path: PathBuf,
+    ) {
+        self.written_paths.push(path);
+        if !self.finished && self.written_paths.len() as u32 == self.request.frame_count {
+            self.finished = true;
+            let _ = self
+                .result_tx
+                .send(Ok(std::mem::take(&mut self.written_paths)));
+        }
+    }
+}
+
+/// A frame of a screenshot request that will be copied at the end of the frame being rendered
+pub(crate) struct ScreenshotCapture {
+    state: Arc<Mutex<ScreenshotRequestState>>,
+    frame: u32,
+    texture: RafxTexture,
+    texture_state: RafxResourceState,
+}
+
+impl ScreenshotCapture {
+    pub fn fail(
+        self,
+        error: String,
+    ) {
+        self.state.lock().unwrap().fail(error);
+    }
+}
+
+// A copy that has been recorded into a frame's command buffers
+struct ScreenshotReadback {
+    state: Arc<Mutex<ScreenshotRequestState>>,
+    frame: u32,
+    readback_buffer: RafxTextureReadbackBuffer,
+}
+
+/// The copies recorded for a frame. The copies are submitted with the rest of the frame, and
+/// `submit_fence()` must be called after the frame is submitted so that the manager can tell when
+/// the GPU is done with them.
+pub(crate) struct ScreenshotFrameReadback {
+    readbacks: Vec<ScreenshotReadback>,
+    fence: RafxFence,
+    // An empty command buffer submitted after the frame to signal the fence. Submissions to a
+    // queue complete in order, so once it is done the copies are done too.
+    fence_command_buffer: DynCommandBuffer,
+}
+
+impl ScreenshotFrameReadback {
+    pub fn submit_fence(
+        &self,
+        graphics_queue: &RafxQueue,
+    ) -> RafxResult<()> {
+        graphics_queue.submit(&[&*self.fence_command_buffer], &[], &[], Some(&self.fence))
+    }
+
+    pub fn fail(
+        &self,
+        error: String,
+    ) {
+        for readback in &self.readbacks {
+            readback.state.lock().unwrap().fail(error.clone());
+        }
+    }
+}
+
+// Pixels that have been read back, waiting to be encoded and written by the writer thread
+struct ScreenshotWrite {
+    state: Arc<Mutex<ScreenshotRequestState>>,
+    frame: u32,
+    format: RafxFormat,
+    extents: RafxExtents3D,
+    data: Vec<u8>,
+}
+
+impl ScreenshotWrite {
+    fn write(&self) -> RafxResult<PathBuf> {
+        let pixels = decode_pixels(self.format, &self.data)?;
+        let is_srgb = matches!(
+            self.format,
+            RafxFormat::R8G8B8A8_SRGB | RafxFormat::B8G8R8A8_SRGB
+        );
+        let screenshot_format = match self.state.lock().unwrap().request.format {
+            ScreenshotFormat::Auto => {
+                if let DecodedPixels::Rgba32F(_) = pixels {
+                    ScreenshotFormat::Exr
+                } else {
+                    ScreenshotFormat::Png
+                }
+            }
+            format => format,
+        };
+
+        let extension = match screenshot_format {
+            ScreenshotFormat::Exr => "exr",
+            _ => "png",
+        };
+
+        let path = self.state.lock().unwrap().frame_path(self.frame, extension);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let width = self.extents.width;
+        let height = self.extents.height;
+        match screenshot_format {
+            ScreenshotFormat::Exr => {
+                let rgba = pixels.to_rgba32f(is_srgb);
+                exr::prelude::write_rgba_file(&path, width as usize, height as usize, |x, y| {
+                    let offset = (y * width as usize + x) * 4;
+                    (
+                        rgba[offset],
+                        rgba[offset + 1],
+                        rgba[offset + 2],
+                        rgba[offset + 3],
+                    )
+                })
+                .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+            }
+            _ => {
+                image::save_buffer_with_format(
+                    &path,
+                    &pixels.to_rgba8(),
+                    width,
+                    height,
+                    image::ColorType::Rgba8,
+                    image::ImageFormat::Png,
+                )
+                .map_err(|e| format!("Failed to write {:?}: {}", path, e))?;
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+// Encoding and writing images is slow, so it is done on a separate thread to avoid holding up
+// the frame (and the renderer lock)
+struct ScreenshotWriterThread {
+    write_tx: Option<Sender<ScreenshotWrite>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl ScreenshotWriterThread {
+    fn start() -> Self {
+        let (write_tx, write_rx) = crossbeam_channel::unbounded();
+
+        let thread_builder = std::thread::Builder::new().name("Screenshot Writer".to_string());
+        let join_handle = thread_builder
+            .spawn(move || Self::writer_thread(write_rx))
+            .unwrap();
+
+        ScreenshotWriterThread {
+            write_tx: Some(write_tx),
+            join_handle: Some(join_handle),
+        }
+    }
+
+    fn send(
+        &self,
+        write: ScreenshotWrite,
+    ) {
+        // The thread only exits once the sender is dropped
+        self.write_tx.as_ref().unwrap().send(write).unwrap();
+    }
+
+    fn writer_thread(write_rx: Receiver<ScreenshotWrite>) {
+        profiling::register_thread!();
+
+        for write in write_rx {
+            profiling::scope!("Write Screenshot");
+            if write.state.lock().unwrap().finished {
+                continue;
+            }
+
+            let result = write.write();
+            let mut state = write.state.lock().unwrap();
+            match result {
+                Ok(path) => state.frame_written(path),
+                Err(e) => state.fail(format!("{:?}", e)),
+            }
+        }
+    }
+}
+
+impl Drop for ScreenshotWriterThread {
+    fn drop(&mut self) {
+        // Finish writing any screenshots that were already read back
+        self.write_tx = None;
+        self.join_handle.take().unwrap().join().unwrap();
+    }
+}
+
+/// Tracks screenshot requests. Each frame, captures are handed to the render job, which appends
+/// the copies to the end of the frame. Once the frame's fence is signaled, the data is read back
+/// and handed to a writer thread that encodes it and writes it to disk.
+pub(crate) struct ScreenshotManager {
+    requests: Vec<Arc<Mutex<ScreenshotRequestState>>>,
+    frame_readbacks: Vec<ScreenshotFrameReadback>,
+    // Started when the first screenshot is read back
+    writer_thread: Option<ScreenshotWriterThread>,
+}
+
+impl ScreenshotManager {
+    pub fn new() -> Self {
+        ScreenshotManager {
+            requests: Default::default(),
+            frame_readbacks: Default::default(),
+            writer_thread: None,
+        }
+    }
+
+    pub fn request_screenshot(
+        &mut self,
+        request: ScreenshotRequest,
+    ) -> ScreenshotHandle {
+        let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+        let mut state = ScreenshotRequestState {
+            request,
+            result_tx,
+            frames_started: 0,
+            written_paths: Default::default(),
+            finished: false,
+        };
+
+        if state.request.frame_count == 0 {
+            state.fail("frame_count must be at least 1".to_string());
+        } else {
+            self.requests.push(Arc::new(Mutex::new(state)));
+        }
+
+        ScreenshotHandle { result_rx }
+    }
+
+    // Called once per frame on the main thread after the frame's target is known. Hands any
+    // frames the GPU has finished with to the writer thread and returns the captures for the new
+    // frame. `swapchain_image` is the image the frame is rendered to and the state the graph
+    // leaves it in.
+    #[profiling::function]
+    pub fn begin_frame(
+        &mut self,
+        prepared_render_graph: &PreparedRenderGraph,
+        swapchain_image: (&RafxTexture, RafxResourceState),
+    ) -> Vec<ScreenshotCapture> {
+        self.send_completed_readbacks();
+
+        let mut captures = Vec::with_capacity(self.requests.len());
+        for state_arc in &self.requests {
+            let mut state = state_arc.lock().unwrap();
+            if state.finished {
+                continue;
+            }
+
+            let source = match &state.request.source {
+                ScreenshotSource::Swapchain => Some((swapchain_image.0.clone(), swapchain_image.1)),
+                ScreenshotSource::OutputImage(name) => prepared_render_graph
+                    .output_image_by_name(name)
+                    .map(|(image_view, texture_state)| {
+                        (image_view.get_raw().image.get_raw().image, texture_state)
+                    }),
+            };
+
+            if let Some((texture, texture_state)) = source {
+                captures.push(ScreenshotCapture {
+                    state: state_arc.clone(),
+                    frame: state.frames_started,
+                    texture,
+                    texture_state,
+                });
+                state.frames_started += 1;
+            } else {
+                let error = format!(
+                    "{:?} is not an output image of the render graph",
+                    state.request.source
+                );
+                state.fail(error);
+            }
+        }
+
+        self.requests.retain(|x| {
+            let state = x.lock().unwrap();
+            !state.finished && state.frames_started < state.request.frame_count
+        });
+
+        captures
+    }
+
+    pub fn add_frame_readback(
+        &mut self,
+        frame_readback: ScreenshotFrameReadback,
+    ) {
+        self.frame_readbacks.push(frame_readback);
+    }
+
+    fn send_completed_readbacks(&mut self) {
+        let mut pending = Vec::with_capacity(self.frame_readbacks.len());
+        for frame_readback in std::mem::take(&mut self.frame_readbacks) {
+            match frame_readback.fence.get_fence_status() {
+                Ok(RafxFenceStatus::Incomplete) => {
+                    pending.push(frame_readback);
+                    continue;
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    // The buffers may still be in use, so wait before dropping them
+                    let _ = frame_readback.fence.wait();
+                    frame_readback.fail(format!("Failed to get fence status: {:?}", e));
+                    continue;
+                }
+            }
+
+            for readback in frame_readback.readbacks {
+                if readback.state.lock().unwrap().finished {
+                    continue;
+                }
+
+                match readback.readback_buffer.read_data() {
+                    Ok(data) => {
+                        let write = ScreenshotWrite {
+                            state: readback.state,
+                            frame: readback.frame,
+                            format: readback.readback_buffer.format(),
+                            extents: readback.readback_buffer.extents(),
+                            data,
+                        };
+
+                        self.writer_thread
+                            .get_or_insert_with(ScreenshotWriterThread::start)
+                            .send(write);
+                    }
+                    Err(e) => readback
+                        .state
+                        .lock()
+                        .unwrap()
+                        .fail(format!("Failed to read back image: {:?}", e)),
+                }
+            }
+        }
+
+        self.frame_readbacks = pending;
+    }
+}
+
+// Records the copies for all captures of a frame into a single command buffer that is submitted
+// at the end of the frame
+pub(crate) fn record_screenshot_captures(
+    resource_context: &ResourceContext,
+    graphics_queue: &RafxQueue,
+    captures: Vec<ScreenshotCapture>,
+) -> Option<(DynCommandBuffer, ScreenshotFrameReadback)> {
+    match try_record_screenshot_captures(resource_context, graphics_queue, &captures) {
+        Ok((command_buffer, fence_command_buffer, fence, readback_buffers)) => {
+            let readbacks = captures
+                .into_iter()
+                .zip(readback_buffers)
+                .map(|(capture, readback_buffer)| ScreenshotReadback {
+                    state: capture.state,
+                    frame: capture.frame,
+                    readback_buffer,
+                })
+                .collect();
+
+            Some((
+                command_buffer,
+                ScreenshotFrameReadback {
+                    readbacks,
+                    fence,
+                    fence_command_buffer,
+                },
+            ))
+        }
+        Err(e) => {
+            for capture in captures {
+                capture.fail(format!("Failed to record copy: {:?}", e));
+            }
+
+            None
+        }
+    }
+}
+
+fn try_record_screenshot_captures(
+    resource_context: &ResourceContext,
+    graphics_queue: &RafxQueue,
+    captures: &[ScreenshotCapture],
+) -> RafxResult<(
+    DynCommandBuffer,
+    DynCommandBuffer,
+    RafxFence,
+    Vec<RafxTextureReadbackBuffer>,
+)> {
+    let mut command_pool = resource_context
+        .create_dyn_command_pool_allocator()
+        .allocate_dyn_pool(graphics_queue, &RafxCommandPoolDef { transient: true }, 0)?;
+
+    let command_buffer = command_pool.allocate_dyn_command_buffer(&RafxCommandBufferDef {
+        is_secondary: false,
+    })?;
+
+    command_buffer.begin()?;
+
+    let mut readback_buffers = Vec::with_capacity(captures.len());
+    for capture in captures {
+        let readback_buffer = RafxTextureReadbackBuffer::new(
+            resource_context.device_context(),
+            graphics_queue.queue_type(),
+            &capture.texture,
+            0,
+            0,
+        )?;
+
+        readback_buffer.cmd_copy_from_texture(
+            &command_buffer,
+            &capture.texture,
+            capture.texture_state,
+        )?;
+
+        readback_buffers.push(readback_buffer);
+    }
+
+    command_buffer.end()?;
+
+    let fence_command_buffer = command_pool.allocate_dyn_command_buffer(&RafxCommandBufferDef {
+        is_secondary: false,
+    })?;
+    fence_command_buffer.begin()?;
+    fence_command_buffer.end()?;
+
+    let fence = resource_context.device_context().create_fence()?;
+
+    Ok((
+        command_buffer,
+        fence_command_buffer,
+        fence,
+        readback_buffers,
+    ))
+}
+
+enum DecodedPixels {
+    Rgba8(Vec<u8>),
+    Rgba32F(Vec<f32>),
+}
+
+impl DecodedPixels {
+    fn to_rgba8(&self) -> Vec<u8> {
+        match self {
+            DecodedPixels::Rgba8(data) => data.clone(),
+            // Floating point images are linear, PNG expects sRGB
+            DecodedPixels::Rgba32F(data) => data
+                .chunks_exact(4)
+                .flat_map(|x| {
+                    let to_u8 = |v: f32| (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+                    vec![
+                        to_u8(linear_to_srgb(x[0])),
+                        to_u8(linear_to_srgb(x[1])),
+                        to_u8(linear_to_srgb(x[2])),
+                        to_u8(x[3]),
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    fn to_rgba32f(
+        &self,
+        is_srgb: bool,
+    ) -> Vec<f32> {
+        match self {
+            DecodedPixels::Rgba8(data) => data
+                .chunks_exact(4)
+                .flat_map(|x| {
+                    let to_f32 = |v: u8| v as f32 / 255.0;
+                    let to_linear = |v: u8| {
+                        if is_srgb {
+                            srgb_to_linear(to_f32(v))
+                        } else {
+                            to_f32(v)
+                        }
+                    };
+                    vec![
+                        to_linear(x[0]),
+                        to_linear(x[1]),
+                        to_linear(x[2]),
+                        to_f32(x[3]),
+                    ]
+                })
+                .collect(),
+            DecodedPixels::Rgba32F(data) => data.clone(),
+        }
+    }
+}
+
+fn decode_pixels(
+    format: RafxFormat,
+    data: &[u8],
+) -> RafxResult<DecodedPixels> {
+    Ok(match format {
+        RafxFormat::R8G8B8A8_UNORM | RafxFormat::R8G8B8A8_SRGB => {
+            DecodedPixels::Rgba8(data.to_vec())
+        }
+        RafxFormat::B8G8R8A8_UNORM | RafxFormat::B8G8R8A8_SRGB => DecodedPixels::Rgba8(
+            data.chunks_exact(4)
+                .flat_map(|x| vec![x[2], x[1], x[0], x[3]])
+                .collect(),
+        ),
+        RafxFormat::R16G16B16A16_SFLOAT => DecodedPixels::Rgba32F(
+            data.chunks_exact(2)
+                .map(|x| f16_to_f32(u16::from_ne_bytes([x[0], x[1]])))
+                .collect(),
+        ),
+        RafxFormat::R32G32B32A32_SFLOAT => DecodedPixels::Rgba32F(
+            data.chunks_exact(4)
+                .map(|x| f32::from_ne_bytes([x[0], x[1], x[2], x[3]]))
+                .collect(),
+        ),
+        _ => Err(format!(
+            "Screenshots of {:?} images are not supported",
+            format
+        ))?,
+    })
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.0031308 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rafx_base::half::f32_to_f16;
+
+    fn assert_close(
+        actual: f32,
+        expected: f32,
+    ) {
+        assert!(
+            (actual - expected).abs() < 1e-5,
+            "expected {} but got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn test_srgb_to_linear() {
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert_close(srgb_to_linear(1.0), 1.0);
+
+        // Below the threshold the curve is linear
+        assert_close(srgb_to_linear(0.04), 0.04 / 12.92);
+
+        // Middle gray
+        assert_close(srgb_to_linear(0.5), 0.214_041_14);
+    }
+
+    #[test]
+    fn test_linear_to_srgb() {
+        assert_eq!(linear_to_srgb(0.0), 0.0);
+        assert_close(linear_to_srgb(1.0), 1.0);
+        assert_close(linear_to_srgb(0.003), 0.003 * 12.92);
+        assert_close(linear_to_srgb(0.214_041_14), 0.5);
+    }
+
+    #[test]
+    fn test_srgb_round_trip() {
+        // Every 8-bit sRGB value survives a round trip through linear
+        for value in 0..=255u8 {
+            let srgb = value as f32 / 255.0;
+            let round_trip = linear_to_srgb(srgb_to_linear(srgb));
+            assert_eq!((round_trip * 255.0 + 0.5) as u8, value);
+        }
+    }
+
+    #[test]
+    fn test_decode_bgra() {
+        let pixels = decode_pixels(RafxFormat::B8G8R8A8_UNORM, &[1, 2, 3, 4, 5, 6, 7, 8]).unwrap();
+        assert_eq!(pixels.to_rgba8(), vec![3, 2, 1, 4, 7, 6, 5, 8]);
+    }
+
+    #[test]
+    fn test_decode_half_float() {
+        let data: Vec<u8> = [0.0f32, 0.5, 1.0, -2.0]
+            .iter()
+            .flat_map(|x| f32_to_f16(*x).to_ne_bytes().to_vec())
+            .collect();
+
+        let pixels = decode_pixels(RafxFormat::R16G16B16A16_SFLOAT, &data).unwrap();
+        assert_eq!(pixels.to_rgba32f(false), vec![0.0, 0.5, 1.0, -2.0]);
+
+        // Color is converted to sRGB and clamped, alpha is only clamped
+        assert_eq!(pixels.to_rgba8(), vec![0, 188, 255, 0]);
+    }
+
+    #[test]
+    fn test_rgba8_to_rgba32f() {
+        let pixels = DecodedPixels::Rgba8(vec![0, 128, 255, 128]);
+
+        let unorm = pixels.to_rgba32f(false);
+        assert_close(unorm[1], 128.0 / 255.0);
+        assert_close(unorm[3], 128.0 / 255.0);
+
+        // Only color is converted to linear for sRGB formats
+        let srgb = pixels.to_rgba32f(true);
+        assert_eq!(srgb[0], 0.0);
+        assert_close(srgb[1], srgb_to_linear(128.0 / 255.0));
+        assert_close(srgb[2], 1.0);
+        assert_close(srgb[3], 128.0 / 255.0);
+    }
+
+    #[test]
+    fn test_write_png_and_exr() {
+        // Unique per process so that concurrent test runs don't write to the same files
+        let directory = std::env::temp_dir().join(format!(
+            "rafx_screenshot_test_write_png_and_exr_{}",
+            std::process::id()
+        ));
+        let (result_tx, result_rx) = crossbeam_channel::bounded(1);
+        let state = Arc::new(Mutex::new(ScreenshotRequestState {
+            request: ScreenshotRequest::frame_sequence(&directory, 2),
+            result_tx,
+            frames_started: 2,
+            written_paths: Default::default(),
+            finished: false,
+        }));
+
+        let half_data: Vec<u8> = [0.25f32, 0.5, 2.0, 1.0]
+            .iter()
+            .flat_map(|x| f32_to_f16(*x).to_ne_bytes().to_vec())
+            .collect();
+
+        let writes = vec![
+            ScreenshotWrite {
+                state: state.clone(),
+                frame: 0,
+                format: RafxFormat::R8G8B8A8_SRGB,
+                extents: RafxExtents3D {
+                    width: 1,
+                    height: 1,
+                    depth: 1,
+                },
+                data: vec![10, 20, 30, 255],
+            },
+            ScreenshotWrite {
+                state: state.clone(),
+                frame: 1,
+                format: RafxFormat::R16G16B16A16_SFLOAT,
+                extents: RafxExtents3D {
+                    width: 1,
+                    height: 1,
+                    depth: 1,
+                },
+                data: half_data,
+            },
+        ];
+
+        let writer_thread = ScreenshotWriterThread::start();
+        for write in writes {
+            writer_thread.send(write);
+        }
+        drop(writer_thread);
+
+        let paths = result_rx.try_recv().unwrap().unwrap();
+        assert_eq!(
+            paths,
+            vec![
+                directory.join("frame_00000.png"),
+                directory.join("frame_00001.exr")
+            ]
+        );
+
+        let png = image::open(&paths[0]).unwrap().to_rgba8();
+        assert_eq!(png.get_pixel(0, 0).0, [10, 20, 30, 255]);
+
+        let exr = exr::prelude::read_first_rgba_layer_from_file(
+            &paths[1],
+            |_, _| vec![(0.0f32, 0.0f32, 0.0f32, 0.0f32)],
+            |pixels, _, (r, g, b, a): (f32, f32, f32, f32)| pixels[0] = (r, g, b, a),
+        )
+        .unwrap();
+        assert_eq!(exr.layer_data.channel_data.pixels[0], (0.25, 0.5, 2.0, 1.0));
+
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_unsupported_format() {
+        assert!(decode_pixels(RafxFormat::R8_UNORM, &[0]).is_err());
+    }
+}
@@ -1,5 +1,5 @@
-use super::swapchain_resources::SwapchainResources;
-use super::Renderer;
+use super::swapchain_resources::{SurfaceSwapchainResources, SwapchainResources};
+use super::{Renderer, SurfaceId};
 use rafx_api::raw_window_handle::HasRawWindowHandle;
 use rafx_api::{
     RafxDeviceContext, RafxExtents2D, RafxPresentableFrame, RafxResult, RafxSwapchain,
@@ -7,10 +7,33 @@ use rafx_api::{
 };
 use rafx_assets::AssetManager;
 use rafx_framework::graph::SwapchainSurfaceInfo;
+use std::collections::BTreeMap;
+
+/// The swapchains of the windows in `ViewportsResource::surfaces`. Like the main window's
+/// `RafxSwapchainHelper`, this must be in the extract resources for the surfaces to be rendered.
+#[derive(Default)]
+pub struct SurfaceSwapchains {
+    swapchain_helpers: BTreeMap<SurfaceId, RafxSwapchainHelper>,
+}
+
+impl SurfaceSwapchains {
+    pub fn contains_surface(
+        &self,
+        surface_id: SurfaceId,
+    ) -> bool {
+        self.swapchain_helpers.contains_key(&surface_id)
+    }
+
+    pub fn surface_ids(&self) -> impl Iterator<Item = SurfaceId> + '_ {
+        self.swapchain_helpers.keys().copied()
+    }
+}
 
 pub struct SwapchainHandler<'a> {
     pub asset_manager: &'a mut AssetManager,
     pub renderer: &'a Renderer,
+    // None for the main window
+    pub surface_id: Option<SurfaceId>,
 }
 
 impl<'a> SwapchainHandler<'a> {
@@ -21,6 +44,48 @@ impl<'a> SwapchainHandler<'a> {
         window: &dyn HasRawWindowHandle,
         width: u32,
         height: u32,
+    ) -> RafxResult<RafxSwapchainHelper> {
+        Self::do_create_swapchain(asset_manager, renderer, None, window, width, height)
+    }
+
+    /// Creates the swapchain for a window other than the main window. The window is drawn using
+    /// the `SurfaceViewport` in `ViewportsResource::surfaces` with the same id.
+    #[profiling::function]
+    pub fn create_surface_swapchain(
+        surface_swapchains: &mut SurfaceSwapchains,
+        surface_id: SurfaceId,
+        asset_manager: &mut AssetManager,
+        renderer: &mut Renderer,
+        window: &dyn HasRawWindowHandle,
+        width: u32,
+        height: u32,
+    ) -> RafxResult<()> {
+        if surface_swapchains.contains_surface(surface_id) {
+            Err(format!("A swapchain already exists for {:?}", surface_id))?;
+        }
+
+        let swapchain_helper = Self::do_create_swapchain(
+            asset_manager,
+            renderer,
+            Some(surface_id),
+            window,
+            width,
+            height,
+        )?;
+
+        surface_swapchains
+            .swapchain_helpers
+            .insert(surface_id, swapchain_helper);
+        Ok(())
+    }
+
+    fn do_create_swapchain(
+        asset_manager: &mut AssetManager,
+        renderer: &mut Renderer,
+        surface_id: Option<SurfaceId>,
+        window: &dyn HasRawWindowHandle,
+        width: u32,
+        height: u32,
     ) -> RafxResult<RafxSwapchainHelper> {
         let swapchain_helper = {
             let device_context = asset_manager.device_context().clone();
@@ -36,6 +101,7 @@ impl<'a> SwapchainHandler<'a> {
             let mut lifetime_listener = SwapchainHandler {
                 asset_manager,
                 renderer,
+                surface_id,
             };
 
             RafxSwapchainHelper::new(&device_context, swapchain, Some(&mut lifetime_listener))?
@@ -55,6 +121,34 @@ impl<'a> SwapchainHandler<'a> {
         let mut lifetime_listener = SwapchainHandler {
             asset_manager,
             renderer,
+            surface_id: None,
+        };
+
+        swapchain_helper.acquire_next_image(
+            window_width,
+            window_height,
+            Some(&mut lifetime_listener),
+        )
+    }
+
+    #[profiling::function]
+    pub fn acquire_next_surface_image(
+        surface_swapchains: &mut SurfaceSwapchains,
+        surface_id: SurfaceId,
+        asset_manager: &mut AssetManager,
+        renderer: &Renderer,
+        window_width: u32,
+        window_height: u32,
+    ) -> RafxResult<RafxPresentableFrame> {
+        let swapchain_helper = surface_swapchains
+            .swapchain_helpers
+            .get_mut(&surface_id)
+            .ok_or_else(|| format!("No swapchain exists for {:?}", surface_id))?;
+
+        let mut lifetime_listener = SwapchainHandler {
+            asset_manager,
+            renderer,
+            surface_id: Some(surface_id),
         };
 
         swapchain_helper.acquire_next_image(
@@ -73,6 +167,30 @@ impl<'a> SwapchainHandler<'a> {
         let mut lifetime_listener = SwapchainHandler {
             asset_manager,
             renderer,
+            surface_id: None,
+        };
+
+        swapchain_helper.destroy(Some(&mut lifetime_listener))?;
+        std::mem::drop(swapchain_helper);
+        Ok(())
+    }
+
+    #[profiling::function]
+    pub fn destroy_surface_swapchain(
+        surface_swapchains: &mut SurfaceSwapchains,
+        surface_id: SurfaceId,
+        asset_manager: &mut AssetManager,
+        renderer: &Renderer,
+    ) -> RafxResult<()> {
+        let mut swapchain_helper = surface_swapchains
+            .swapchain_helpers
+            .remove(&surface_id)
+            .ok_or_else(|| format!("No swapchain exists for {:?}", surface_id))?;
+
+        let mut lifetime_listener = SwapchainHandler {
+            asset_manager,
+            renderer,
+            surface_id: Some(surface_id),
         };
 
         swapchain_helper.destroy(Some(&mut lifetime_listener))?;
@@ -114,7 +232,15 @@ impl<'a> RafxSwapchainEventListener for SwapchainHandler<'a> {
         let swapchain_resources = SwapchainResources::new(device_context, swapchain_surface_info)?;
 
         let mut render_resources = renderer.render_thread.render_resources().lock().unwrap();
-        render_resources.insert(swapchain_resources);
+        if let Some(surface_id) = self.surface_id {
+            render_resources.try_insert_default::<SurfaceSwapchainResources>();
+            render_resources
+                .fetch_mut::<SurfaceSwapchainResources>()
+                .surfaces
+                .insert(surface_id, swapchain_resources);
+        } else {
+            render_resources.insert(swapchain_resources);
+        }
 
         log::debug!("renderer swapchain_created finished");
 
@@ -133,7 +259,15 @@ impl<'a> RafxSwapchainEventListener for SwapchainHandler<'a> {
         log::debug!("renderer swapchain destroyed");
 
         let mut render_resources = renderer.render_thread.render_resources().lock().unwrap();
-        render_resources.remove::<SwapchainResources>();
+        if let Some(surface_id) = self.surface_id {
+            if let Some(mut surface_swapchain_resources) =
+                render_resources.try_fetch_mut::<SurfaceSwapchainResources>()
+            {
+                surface_swapchain_resources.surfaces.remove(&surface_id);
+            }
+        } else {
+            render_resources.remove::<SwapchainResources>();
+        }
 
         //TODO: Explicitly remove the images instead of just dropping them. This prevents anything
         // from accidentally using them after they've been freed
@@ -1,16 +1,25 @@
-use rafx_api::{RafxDeviceContext, RafxFormat, RafxResourceType, RafxResult};
+use crate::SurfaceId;
+use fnv::FnvHashMap;
+use rafx_api::{RafxDeviceContext, RafxFormat, RafxResourceState, RafxResourceType, RafxResult};
 use rafx_framework::graph::SwapchainSurfaceInfo;
 
+// The main window's SwapchainResources are in the render resources as-is, the resources of the
+// other surfaces in ViewportsResource::surfaces are kept here
+#[derive(Default)]
+pub struct SurfaceSwapchainResources {
+    pub surfaces: FnvHashMap<SurfaceId, SwapchainResources>,
+}
+
 pub struct SwapchainResources {
-    // The images presented by the swapchain
-    //TODO: We don't properly support multiple swapchains right now. This would ideally be a map
-    // of window/surface to info for the swapchain
-    //pub swapchain_images: Vec<ResourceArc<ImageViewResource>>,
     pub swapchain_surface_info: SwapchainSurfaceInfo,
 
     pub default_color_format_hdr: RafxFormat,
     pub default_color_format_sdr: RafxFormat,
     pub default_depth_format: RafxFormat,
+
+    // The state the graph should leave the swapchain image in. This is PRESENT for real swapchains
+    // and SHADER_RESOURCE for a HeadlessRenderTarget
+    pub swapchain_image_final_state: RafxResourceState,
 }
 
 impl SwapchainResources {
@@ -42,6 +51,7 @@ impl SwapchainResources {
             default_color_format_hdr,
             default_color_format_sdr,
             default_depth_format,
+            swapchain_image_final_state: RafxResourceState::PRESENT,
         })
     }
 }
@@ -4,10 +4,17 @@ use rafx_framework::render_features::{
     RenderFeatureFlagMask, RenderFeatureMask, RenderPhaseMask, RenderViewDepthRange,
 };
 use rafx_framework::visibility::ViewFrustumArc;
+use std::collections::BTreeMap;
 
-// Very bare-bones for now, in the future this could support multiple windows, multiple viewports
-// per window, and some method for configuring the graph that's being drawn (maybe the graph
-// is provided some metadata like a string)
+// Very bare-bones for now, in the future this could support multiple viewports per window, and
+// some method for configuring the graph that's being drawn (maybe the graph is provided some
+// metadata like a string)
+
+/// Identifies a window other than the main window. The id is chosen by the application and is used
+/// to match the `SurfaceViewport` in `ViewportsResource::surfaces` with a swapchain created by
+/// `SwapchainHandler::create_surface_swapchain`
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SurfaceId(pub u32);
 
 #[derive(Clone)]
 pub struct RenderViewMeta {
@@ -22,8 +29,22 @@ pub struct RenderViewMeta {
     pub debug_name: String,
 }
 
+#[derive(Clone, Default)]
+pub struct SurfaceViewport {
+    pub window_size: RafxExtents2D,
+    pub view_meta: Option<RenderViewMeta>,
+}
+
 #[derive(Default)]
 pub struct ViewportsResource {
     pub main_window_size: RafxExtents2D,
     pub main_view_meta: Option<RenderViewMeta>,
+    // Windows that are rendered and presented in the same frame as the main window. A surface is
+    // only drawn if it has a swapchain and a view_meta.
+    pub surfaces: BTreeMap<SurfaceId, SurfaceViewport>,
+    // Views that are drawn into a persistent image (i.e. a security camera, mirror or minimap)
+    // before the main view so that materials can sample the result in the same frame. The key is
+    // the name passed to AssetManager::create_render_target, the extents of the view match the
+    // image.
+    pub render_targets: BTreeMap<String, RenderViewMeta>,
 }
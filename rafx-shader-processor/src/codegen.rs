@@ -41,8 +41,8 @@ pub(crate) fn generate_rust_code(
     builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
     user_types: &mut FnvHashMap<String, UserType>,
     parsed_declarations: &ParseDeclarationsResult,
-    shader_module: &spirv_reflect::ShaderModule,
-    reflected_entry_point: &ReflectedEntryPoint,
+    shader_modules: &[spirv_reflect::ShaderModule],
+    reflected_entry_points: &[&ReflectedEntryPoint],
 ) -> Result<String, String> {
     //
     // Populate the user types map. Adding types in the map helps us detect duplicate type names
@@ -52,7 +52,10 @@ pub(crate) fn generate_rust_code(
     // Structs and bindings can both declare new types, so gather data from both sources
     //
 
-    verify_all_binding_layouts(&builtin_types, user_types, shader_module)?;
+    // Every permutation of the shader shares the generated code, so check all of them
+    for shader_module in shader_modules {
+        verify_all_binding_layouts(&builtin_types, user_types, shader_module)?;
+    }
 
     //
     // Any struct that's explicitly exported will produce all layouts
@@ -73,10 +76,11 @@ pub(crate) fn generate_rust_code(
 
     //
     // Bindings can either be std140 (uniform) or std430 (push constant/buffer). Depending on the
-    // binding, enable export for just the type that we need
+    // binding, enable export for just the type that we need. Push constant blocks are always
+    // exported so that a typed push_constants() function can be generated for them.
     //
     for b in &parsed_declarations.bindings {
-        if b.annotations.export.is_some() {
+        if b.annotations.export.is_some() || b.parsed.layout_parts.push_constant {
             match determine_binding_type(b)? {
                 StructBindingType::PushConstant => {
                     recursive_modify_user_type(user_types, &b.parsed.type_name, &|udt| {
@@ -107,7 +111,7 @@ pub(crate) fn generate_rust_code(
         &parsed_declarations,
         &builtin_types,
         &user_types,
-        reflected_entry_point,
+        reflected_entry_points,
     )
 }
 
@@ -115,7 +119,7 @@ fn generate_rust_file(
     parsed_declarations: &ParseDeclarationsResult,
     builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
     user_types: &FnvHashMap<String, UserType>,
-    reflected_entry_point: &ReflectedEntryPoint,
+    reflected_entry_points: &[&ReflectedEntryPoint],
 ) -> Result<String, String> {
     let mut rust_code = Vec::<String>::default();
 
@@ -130,10 +134,17 @@ fn generate_rust_file(
         builtin_types,
         user_types,
         &parsed_declarations,
-        reflected_entry_point,
+        reflected_entry_points,
+    )?;
+
+    let push_constant_type_name = rust_push_constants(
+        &mut rust_code,
+        builtin_types,
+        user_types,
+        &parsed_declarations,
     )?;
 
-    rust_tests(&mut rust_code, &structs);
+    rust_tests(&mut rust_code, &structs, push_constant_type_name.as_deref());
 
     let mut rust_code_str = String::default();
     for s in rust_code {
@@ -150,6 +161,8 @@ fn rust_header(rust_code: &mut Vec<String>) {
     rust_code.push("use rafx_framework::RafxResult;\n\n".to_string());
     rust_code.push("#[allow(unused_imports)]\n".to_string());
     rust_code.push("use rafx_framework::{ResourceArc, ImageViewResource, DynDescriptorSet, DescriptorSetAllocator, DescriptorSetInitializer, DescriptorSetArc, DescriptorSetWriter, DescriptorSetWriterContext, DescriptorSetBindings};\n\n".to_string());
+    rust_code.push("#[allow(unused_imports)]\n".to_string());
+    rust_code.push("use rafx_framework::{RafxCommandBuffer, RafxRootSignature};\n\n".to_string());
 }
 
 fn rust_structs(
@@ -252,7 +265,7 @@ fn rust_binding_wrappers(
     builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
     user_types: &FnvHashMap<String, UserType>,
     parsed_declarations: &ParseDeclarationsResult,
-    reflected_entry_point: &ReflectedEntryPoint,
+    reflected_entry_points: &[&ReflectedEntryPoint],
 ) -> Result<(), String> {
     let mut bindings_by_set =
         BTreeMap::<usize, BTreeMap<usize, &ParsedBindingWithAnnotations>>::default();
@@ -276,7 +289,7 @@ fn rust_binding_wrappers(
                 &mut binding_wrapper_struct_lifetimes,
                 user_types,
                 builtin_types,
-                reflected_entry_point,
+                reflected_entry_points,
                 set_index,
                 binding_index,
                 binding,
@@ -489,6 +502,44 @@ fn rust_binding_wrappers(
     Ok(())
 }
 
+// Returns the name of the push constant type, if the shader has push constants
+fn rust_push_constants(
+    rust_code: &mut Vec<String>,
+    builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
+    user_types: &FnvHashMap<String, UserType>,
+    parsed_declarations: &ParseDeclarationsResult,
+) -> Result<Option<String>, String> {
+    let push_constant = parsed_declarations
+        .bindings
+        .iter()
+        .find(|x| x.parsed.layout_parts.push_constant);
+
+    if let Some(push_constant) = push_constant {
+        let type_name = get_rust_type_name_alias(
+            builtin_types,
+            user_types,
+            &push_constant.parsed.type_name,
+            &[],
+            StructBindingType::PushConstant,
+        )?;
+
+        rust_code.push(format!(
+            "pub fn push_constants(command_buffer: &RafxCommandBuffer, root_signature: &RafxRootSignature, data: &{}) -> RafxResult<()> {{\n",
+            type_name
+        ));
+        rust_code.push("    let bytes = unsafe {\n".to_string());
+        rust_code.push("        std::slice::from_raw_parts(data as *const _ as *const u8, std::mem::size_of_val(data))\n".to_string());
+        rust_code.push("    };\n".to_string());
+        rust_code
+            .push("    command_buffer.cmd_push_constants(root_signature, 0, bytes)\n".to_string());
+        rust_code.push("}\n\n".to_string());
+
+        Ok(Some(type_name))
+    } else {
+        Ok(None)
+    }
+}
+
 struct BindingWrapperItem {
     binding_name: String,
     setter_fn_name_single: String,
@@ -505,7 +556,7 @@ fn create_binding_wrapper_binding_item(
     binding_wrapper_struct_lifetimes: &mut Vec<String>,
     user_types: &FnvHashMap<String, UserType>,
     builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
-    reflected_entry_point: &ReflectedEntryPoint,
+    reflected_entry_points: &[&ReflectedEntryPoint],
     set_index: usize,
     binding_index: usize,
     binding: &ParsedBindingWithAnnotations,
@@ -515,30 +566,24 @@ fn create_binding_wrapper_binding_item(
         return Ok(());
     }
 
-    // Find the binding in the reflection data
-    let e = reflected_entry_point
-        .descriptor_set_layouts
-        .get(set_index)
-        .ok_or_else(|| {
-            format!(
-                "Could not find descriptor set index {} in reflection data",
-                set_index
-            )
-        })?
-        .as_ref()
-        .ok_or_else(|| {
-            format!(
-                "Could not find descriptor set index {} in reflection data",
-                set_index
-            )
-        })?
-        .bindings
+    // Find the binding in the reflection data. Bindings inside an #ifdef may only exist in some
+    // permutations of the shader, so use the first permutation that has it
+    let e = reflected_entry_points
         .iter()
-        .find(|x| x.resource.binding == binding_index as u32)
+        .filter_map(|reflected_entry_point| {
+            reflected_entry_point
+                .descriptor_set_layouts
+                .get(set_index)?
+                .as_ref()?
+                .bindings
+                .iter()
+                .find(|x| x.resource.binding == binding_index as u32)
+        })
+        .next()
         .ok_or_else(|| {
             format!(
-                "Could not find descriptor binding index {} in reflection data",
-                binding_index
+                "Could not find descriptor set index {} binding index {} in reflection data",
+                set_index, binding_index
             )
         })?;
 
@@ -677,12 +722,16 @@ fn rust_binding_constants(
 fn rust_tests(
     rust_code: &mut Vec<String>,
     structs: &[GenerateStructResult],
+    push_constant_type_name: Option<&str>,
 ) {
-    if !structs.is_empty() {
+    if !structs.is_empty() || push_constant_type_name.is_some() {
         rust_code.push("#[cfg(test)]\nmod test {\n    use super::*;\n".to_string());
         for s in structs {
             rust_code.push(generate_struct_test_code(&s));
         }
+        if let Some(push_constant_type_name) = push_constant_type_name {
+            rust_code.push(generate_push_constants_test_code(push_constant_type_name));
+        }
         rust_code.push("}\n".to_string());
     }
 }
@@ -780,6 +829,23 @@ fn generate_struct_test_code(st: &GenerateStructResult) -> String {
     result_string
 }
 
+// Push constants are written in 4-byte words, so the generated push_constants() function can only
+// write types that are a multiple of 4 bytes
+fn generate_push_constants_test_code(type_name: &str) -> String {
+    let mut result_string = String::default();
+    result_string += "\n    #[test]\n    fn test_push_constants() {\n";
+    result_string += &format!(
+        "        assert_eq!(std::mem::size_of::<{}>() % 4, 0);\n",
+        type_name
+    );
+    result_string += &format!(
+        "        let _: fn(&RafxCommandBuffer, &RafxRootSignature, &{}) -> RafxResult<()> = push_constants;\n",
+        type_name
+    );
+    result_string += "    }\n";
+    result_string
+}
+
 fn format_member(
     name: &str,
     ty: &str,
@@ -792,3 +858,103 @@ fn format_member(
     str += &format!("// +{} (size: {})\n", offset, size);
     str
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_source::FileToProcess;
+
+    fn parse_declarations_for_test(shader_code: &str) -> ParseDeclarationsResult {
+        let file_to_process = FileToProcess {
+            path: "".into(),
+            include_type: crate::IncludeType::Relative,
+            requested_from: "".into(),
+            include_depth: 0,
+        };
+
+        let mut declarations = Vec::default();
+        let mut included_files = Default::default();
+        let code: Vec<char> = shader_code.chars().collect();
+        crate::parse_source::parse_shader_source_text(
+            &file_to_process,
+            &mut declarations,
+            &mut included_files,
+            &code,
+        )
+        .unwrap();
+        crate::parse_declarations::parse_declarations(&declarations).unwrap()
+    }
+
+    fn generate_push_constants_for_test(shader_code: &str) -> (Option<String>, String, String) {
+        let parsed_declarations = parse_declarations_for_test(shader_code);
+        let user_types = create_user_type_lookup(&parsed_declarations).unwrap();
+        let builtin_types = create_builtin_type_lookup();
+
+        let mut rust_code = Vec::default();
+        let type_name = rust_push_constants(
+            &mut rust_code,
+            &builtin_types,
+            &user_types,
+            &parsed_declarations,
+        )
+        .unwrap();
+
+        let mut test_code = Vec::default();
+        rust_tests(&mut test_code, &[], type_name.as_deref());
+
+        (type_name, rust_code.concat(), test_code.concat())
+    }
+
+    #[test]
+    fn test_push_constants() {
+        let shader_code = r#"
+            #version 450
+
+            // @[export]
+            layout (push_constant) uniform PushConstants {
+                mat4 model;
+                vec4 color;
+            } push_constants;
+
+            void main() {}
+        "#;
+
+        let (type_name, rust_code, test_code) = generate_push_constants_for_test(shader_code);
+        let type_name = type_name.unwrap();
+
+        assert!(rust_code.contains(&format!(
+            "pub fn push_constants(command_buffer: &RafxCommandBuffer, root_signature: &RafxRootSignature, data: &{}) -> RafxResult<()>",
+            type_name
+        )));
+        assert!(rust_code.contains("command_buffer.cmd_push_constants(root_signature, 0, bytes)"));
+
+        assert!(test_code.contains("fn test_push_constants()"));
+        assert!(test_code.contains(&format!(
+            "assert_eq!(std::mem::size_of::<{}>() % 4, 0);",
+            type_name
+        )));
+        assert!(test_code.contains(&format!(
+            "let _: fn(&RafxCommandBuffer, &RafxRootSignature, &{}) -> RafxResult<()> = push_constants;",
+            type_name
+        )));
+    }
+
+    #[test]
+    fn test_no_push_constants() {
+        let shader_code = r#"
+            #version 450
+
+            // @[export]
+            layout (set = 0, binding = 0) uniform PerViewData {
+                mat4 view_proj;
+            } per_view_data;
+
+            void main() {}
+        "#;
+
+        let (type_name, rust_code, test_code) = generate_push_constants_for_test(shader_code);
+        assert!(type_name.is_none());
+        assert!(rust_code.is_empty());
+        assert!(test_code.is_empty());
+    }
+}
@@ -2,16 +2,17 @@ use rafx_api::{
     RafxShaderPackage, RafxShaderPackageGles2, RafxShaderPackageGles3, RafxShaderPackageMetal,
     RafxShaderPackageVulkan,
 };
-use rafx_framework::CookedShaderPackage;
+use rafx_framework::{CookedShaderPackage, CookedShaderVariant};
 use rafx_framework::{ReflectedEntryPoint, ShaderModuleHash};
 
-pub(crate) fn cook_shader(
+pub(crate) fn cook_shader_variant(
+    keywords: &[String],
     reflected_data: &[ReflectedEntryPoint],
     vk_spv: Option<&Vec<u8>>,
     metal_source: Option<String>,
     gles2_source: Option<String>,
     gles3_source: Option<String>,
-) -> Result<Vec<u8>, String> {
+) -> CookedShaderVariant {
     let shader_package = RafxShaderPackage {
         vk: vk_spv.map(|x| RafxShaderPackageVulkan::SpvBytes(x.to_vec())),
 
@@ -25,10 +26,25 @@ pub(crate) fn cook_shader(
         gles3: gles3_source.map(|x| RafxShaderPackageGles3::Src(x)),
     };
 
-    let cooked_shader = CookedShaderPackage {
+    CookedShaderVariant {
+        keywords: keywords.to_vec(),
         entry_points: reflected_data.to_vec(),
         hash: ShaderModuleHash::new(&shader_package),
         shader_package,
+    }
+}
+
+pub(crate) fn cook_shader(
+    base_variant: CookedShaderVariant,
+    keywords: Vec<String>,
+    variants: Vec<CookedShaderVariant>,
+) -> Result<Vec<u8>, String> {
+    let cooked_shader = CookedShaderPackage {
+        entry_points: base_variant.entry_points,
+        hash: base_variant.hash,
+        shader_package: base_variant.shader_package,
+        keywords,
+        variants,
     };
 
     bincode::serialize(&cooked_shader)
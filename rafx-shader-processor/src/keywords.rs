@@ -0,0 +1,235 @@
+use std::collections::BTreeSet;
+
+const KEYWORDS_PRAGMA: &str = "rafx_keywords";
+const VARIANT_PRAGMA: &str = "rafx_variant";
+
+// Keywords are declared in the top-level shader file:
+//
+//   #pragma rafx_keywords TEXTURED SHADOWS
+//
+// Every combination of the declared keywords is compiled, with each keyword in the combination
+// #define'd. Alternatively, only the listed combinations are compiled if any are given:
+//
+//   #pragma rafx_variant TEXTURED
+//   #pragma rafx_variant TEXTURED SHADOWS
+//
+// The permutation with no keywords defined is always compiled.
+#[derive(Debug, Default)]
+pub(crate) struct ShaderKeywords {
+    // Sorted
+    pub(crate) keywords: Vec<String>,
+    // Each is sorted and non-empty. Does not include the permutation with no keywords
+    pub(crate) variants: Vec<Vec<String>>,
+}
+
+fn try_parse_pragma<'a>(
+    line: &'a str,
+    pragma_name: &str,
+) -> Option<Vec<&'a str>> {
+    let line = line.trim_start();
+    if !line.starts_with('#') {
+        return None;
+    }
+
+    let mut words = line[1..].split_whitespace();
+    if words.next() != Some("pragma") || words.next() != Some(pragma_name) {
+        return None;
+    }
+
+    Some(words.collect())
+}
+
+fn is_valid_identifier(word: &str) -> bool {
+    !word.starts_with(|c: char| c.is_ascii_digit())
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn is_keyword_pragma(line: &str) -> bool {
+    try_parse_pragma(line, KEYWORDS_PRAGMA).is_some()
+        || try_parse_pragma(line, VARIANT_PRAGMA).is_some()
+}
+
+pub(crate) fn parse_shader_keywords(code: &str) -> Result<ShaderKeywords, String> {
+    let mut keywords = BTreeSet::<String>::default();
+    let mut listed_variants = Vec::<Vec<&str>>::default();
+
+    for line in code.lines() {
+        if let Some(words) = try_parse_pragma(line, KEYWORDS_PRAGMA) {
+            for word in words {
+                if !is_valid_identifier(word) {
+                    Err(format!(
+                        "Shader keyword {} is not a valid preprocessor identifier",
+                        word
+                    ))?;
+                }
+
+                keywords.insert(word.to_string());
+            }
+        } else if let Some(words) = try_parse_pragma(line, VARIANT_PRAGMA) {
+            listed_variants.push(words);
+        }
+    }
+
+    let keywords: Vec<String> = keywords.into_iter().collect();
+
+    let mut variants = BTreeSet::<Vec<String>>::default();
+    if listed_variants.is_empty() {
+        // 2^n permutations, skipping the empty set
+        if keywords.len() > 16 {
+            Err(format!(
+                "Shader declares {} keywords, list the permutations to compile with #pragma {}",
+                keywords.len(),
+                VARIANT_PRAGMA
+            ))?;
+        }
+
+        for mask in 1..(1u32 << keywords.len()) {
+            let variant = keywords
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << i) != 0)
+                .map(|(_, keyword)| keyword.clone())
+                .collect();
+            variants.insert(variant);
+        }
+    } else {
+        for words in listed_variants {
+            let mut variant = BTreeSet::default();
+            for word in words {
+                if !keywords.iter().any(|x| x == word) {
+                    Err(format!(
+                        "Shader variant uses keyword {} which was not declared with #pragma {}",
+                        word, KEYWORDS_PRAGMA
+                    ))?;
+                }
+
+                variant.insert(word.to_string());
+            }
+
+            if !variant.is_empty() {
+                variants.insert(variant.into_iter().collect());
+            }
+        }
+    }
+
+    Ok(ShaderKeywords {
+        keywords,
+        variants: variants.into_iter().collect(),
+    })
+}
+
+// Blank out the keyword pragmas so the compiler does not see them. Lines are kept so that line
+// numbers in error messages still match the file
+pub(crate) fn strip_keyword_pragmas(code: &str) -> String {
+    let mut stripped = String::with_capacity(code.len());
+    for line in code.lines() {
+        if !is_keyword_pragma(line) {
+            stripped += line;
+        }
+        stripped += "\n";
+    }
+
+    stripped
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn to_strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn test_no_keywords() {
+        let keywords = parse_shader_keywords("void main() {}\n").unwrap();
+        assert!(keywords.keywords.is_empty());
+        assert!(keywords.variants.is_empty());
+    }
+
+    #[test]
+    fn test_declared_keywords_only() {
+        let code = "#pragma rafx_keywords TEXTURED SHADOWS\nvoid main() {}\n";
+        let keywords = parse_shader_keywords(code).unwrap();
+        assert_eq!(keywords.keywords, to_strings(&["SHADOWS", "TEXTURED"]));
+        assert_eq!(
+            keywords.variants,
+            vec![
+                to_strings(&["SHADOWS"]),
+                to_strings(&["SHADOWS", "TEXTURED"]),
+                to_strings(&["TEXTURED"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_explicit_variants() {
+        let code = "\
+            #pragma rafx_keywords TEXTURED SHADOWS SKINNED\n\
+            #pragma rafx_variant TEXTURED\n\
+            #pragma rafx_variant SKINNED TEXTURED\n\
+            void main() {}\n";
+        let keywords = parse_shader_keywords(code).unwrap();
+        assert_eq!(
+            keywords.keywords,
+            to_strings(&["SHADOWS", "SKINNED", "TEXTURED"])
+        );
+        assert_eq!(
+            keywords.variants,
+            vec![
+                to_strings(&["SKINNED", "TEXTURED"]),
+                to_strings(&["TEXTURED"])
+            ]
+        );
+    }
+
+    #[test]
+    fn test_undeclared_keyword_in_variant() {
+        let code = "\
+            #pragma rafx_keywords TEXTURED\n\
+            #pragma rafx_variant TEXTURED SHADOWS\n";
+        assert!(parse_shader_keywords(code).is_err());
+    }
+
+    #[test]
+    fn test_invalid_keywords() {
+        assert!(parse_shader_keywords("#pragma rafx_keywords 2SIDED\n").is_err());
+        assert!(parse_shader_keywords("#pragma rafx_keywords TWO-SIDED\n").is_err());
+        assert!(parse_shader_keywords("#pragma rafx_keywords SIDED_2 _SIDED\n").is_ok());
+    }
+
+    #[test]
+    fn test_dedup_and_sort() {
+        let code = "\
+            #pragma rafx_keywords ZEBRA APPLE\n\
+            #pragma rafx_keywords APPLE MANGO\n\
+            #pragma rafx_variant ZEBRA APPLE ZEBRA\n\
+            #pragma rafx_variant APPLE ZEBRA\n\
+            #pragma rafx_variant MANGO\n\
+            #pragma rafx_variant\n";
+        let keywords = parse_shader_keywords(code).unwrap();
+        assert_eq!(keywords.keywords, to_strings(&["APPLE", "MANGO", "ZEBRA"]));
+
+        // The empty variant is always compiled, so it is not listed
+        assert_eq!(
+            keywords.variants,
+            vec![to_strings(&["APPLE", "ZEBRA"]), to_strings(&["MANGO"])]
+        );
+    }
+
+    #[test]
+    fn test_strip_keyword_pragmas() {
+        let code = "\
+            #version 450\n\
+            #pragma rafx_keywords TEXTURED\n\
+              #  pragma   rafx_variant TEXTURED\n\
+            #pragma shader_stage(fragment)\n\
+            void main() {}";
+        let stripped = strip_keyword_pragmas(code);
+        assert_eq!(
+            stripped,
+            "#version 450\n\n\n#pragma shader_stage(fragment)\nvoid main() {}\n"
+        );
+        assert_eq!(stripped.lines().count(), code.lines().count());
+    }
+}
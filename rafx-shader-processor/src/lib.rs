@@ -9,8 +9,10 @@ use parse_source::DeclarationText;
 mod parse_declarations;
 
 mod include;
+use crate::parse_declarations::ParseDeclarationsResult;
 use crate::reflect::ShaderProcessorRefectionData;
-use fnv::FnvHashSet;
+use crate::shader_types::{TypeAlignmentInfo, UserType};
+use fnv::{FnvHashMap, FnvHashSet};
 use include::include_impl;
 use include::IncludeType;
 use shaderc::ShaderKind;
@@ -21,6 +23,8 @@ mod codegen;
 
 mod cook;
 
+mod keywords;
+
 mod reflect;
 
 mod shader_types;
@@ -269,41 +273,12 @@ fn process_glsl_shader(
     let entry_point_name = "main";
 
     //
-    // First, compile the code with shaderc. This will validate that it's well-formed. We will also
-    // use the produced spv to create reflection data. This first pass must be UNOPTIMIZED so that
-    // we don't drop reflection data for unused elements.
-    //
-    // We want to preserve unused fields so that the rust API we generate does not substantially
-    // change and cause spurious compile errors just because a line of code gets commented out in
-    // the shader. (In the future we may want to generate the API but make it a noop.)
-    //
-    let mut compiler = shaderc::Compiler::new().unwrap();
-
-    log::trace!("{:?}: compile unoptimized", glsl_file);
-    let unoptimized_compile_spirv_result = {
-        let mut compile_options = shaderc::CompileOptions::new().unwrap();
-        compile_options.set_include_callback(include::shaderc_include_callback);
-
-        compiler.compile_into_spirv(
-            &code,
-            shader_kind,
-            glsl_file.to_str().unwrap(),
-            entry_point_name,
-            Some(&compile_options),
-        )?
-    };
-
-    //
-    // Read the unoptimized spv into spirv_cross so that we can grab reflection data
+    // Find keywords declared by the shader. Each permutation of keywords is compiled separately
+    // with the keywords #define'd
     //
-    log::trace!("{:?}: read spirv_cross module", glsl_file);
-    let spirv_cross_module =
-        spirv_cross::spirv::Module::from_words(unoptimized_compile_spirv_result.as_binary());
-
-    //TEMP: Create this for now, planning to remove the dependency later
-    log::trace!("{:?}: read spirv_reflect module", glsl_file);
-    let spirv_reflect_module =
-        spirv_reflect::create_shader_module(unoptimized_compile_spirv_result.as_binary_u8())?;
+    let shader_keywords = keywords::parse_shader_keywords(&code)?;
+    log::trace!("{:?}: keywords {:?}", glsl_file, shader_keywords);
+    let code = keywords::strip_keyword_pragmas(&code);
 
     //
     // Parse the shader code to find all declared resources. This is a high-level parse of the file
@@ -318,50 +293,81 @@ fn process_glsl_shader(
     log::trace!("{:?}: parse declarations", glsl_file);
     let parsed_declarations = parse_declarations::parse_declarations(&parsed_source.declarations)?;
 
-    // example usage of spirv_cross. We can provide options here to modify the shader
-    // programmatically. This could use annotations to drive this
-    log::trace!("{:?}: generate spirv_cross ast", glsl_file);
-    let mut spirv_cross_glsl_options = spirv_cross::glsl::CompilerOptions::default();
-    spirv_cross_glsl_options.vulkan_semantics = true;
-    let mut ast = spirv_cross::spirv::Ast::<spirv_cross::glsl::Target>::parse(&spirv_cross_module)?;
-    ast.set_compiler_options(&spirv_cross_glsl_options)?;
-
     log::trace!("{:?}: generate shader types", glsl_file);
     let mut user_types = shader_types::create_user_type_lookup(&parsed_declarations)?;
     let builtin_types = shader_types::create_builtin_type_lookup();
 
-    let mut reflected_data = if rs_file.is_some()
+    let mut compiler = shaderc::Compiler::new().unwrap();
+
+    let generate_reflection = rs_file.is_some()
         || cooked_shader_file.is_some()
         || metal_generated_src_file.is_some()
-        || gles2_generated_src_file.is_some()
-    {
-        log::trace!("{:?}: generate reflection data", glsl_file);
-        let require_semantics = cooked_shader_file.is_some();
-        Some(reflect::reflect_data(
-            &builtin_types,
-            &user_types,
-            &ast,
-            &parsed_declarations,
-            require_semantics,
-        )?)
-    } else {
-        None
+        || gles2_generated_src_file.is_some();
+
+    let mut compile_args = CompileShaderVariantArgs {
+        glsl_file,
+        code: &code,
+        entry_point_name,
+        shader_kind,
+        builtin_types: &builtin_types,
+        user_types: &user_types,
+        parsed_declarations: &parsed_declarations,
+        generate_reflection,
+        require_semantics: cooked_shader_file.is_some(),
+        generate_metal: metal_generated_src_file.is_some() || package_metal,
+        generate_gles2: gles2_generated_src_file.is_some() || package_gles2,
+        generate_gles3: gles3_generated_src_file.is_some() || package_gles3,
+        optimize_shaders: args.optimize_shaders,
     };
 
+    // The permutation with no keywords defined is used for the spv and generated source outputs
+    let base_variant = compile_shader_variant(&mut compiler, &compile_args, &[])?;
+
+    // Other permutations are included in the cooked shader package, and the generated rust code
+    // must be able to bind resources for all of them
+    let mut keyword_variants = Vec::default();
+    if cooked_shader_file.is_some() || rs_file.is_some() {
+        compile_args.generate_metal = package_metal;
+        compile_args.generate_gles2 = package_gles2;
+        compile_args.generate_gles3 = package_gles3;
+
+        for variant_keywords in &shader_keywords.variants {
+            let variant = compile_shader_variant(&mut compiler, &compile_args, variant_keywords)
+                .map_err(|x| format!("keywords {:?}: {}", variant_keywords, x.to_string()))?;
+            keyword_variants.push((variant_keywords, variant));
+        }
+    }
+
     let rust_code = if rs_file.is_some() {
         log::trace!("{:?}: generate rust code", glsl_file);
-        let reflected_entry_point = reflected_data
-            .as_ref()
-            .unwrap()
-            .reflection
-            .iter()
-            .find(|x| x.rafx_api_reflection.entry_point_name == entry_point_name)
-            .ok_or_else(|| {
-                format!(
-                    "Could not find entry point {} in compiled shader file",
-                    entry_point_name
-                )
-            })?;
+        let all_variants: Vec<_> = std::iter::once(&base_variant)
+            .chain(keyword_variants.iter().map(|(_, variant)| variant))
+            .collect();
+
+        let mut reflected_entry_points = Vec::with_capacity(all_variants.len());
+        let mut spirv_reflect_modules = Vec::with_capacity(all_variants.len());
+        for variant in all_variants {
+            let reflected_entry_point = variant
+                .reflected_data
+                .as_ref()
+                .unwrap()
+                .reflection
+                .iter()
+                .find(|x| x.rafx_api_reflection.entry_point_name == entry_point_name)
+                .ok_or_else(|| {
+                    format!(
+                        "Could not find entry point {} in compiled shader file",
+                        entry_point_name
+                    )
+                })?;
+            reflected_entry_points.push(reflected_entry_point);
+
+            //TEMP: Create this for now, planning to remove the dependency later
+            log::trace!("{:?}: read spirv_reflect module", glsl_file);
+            spirv_reflect_modules.push(spirv_reflect::create_shader_module(
+                variant.unoptimized_compile_spirv_result.as_binary_u8(),
+            )?);
+        }
 
         //
         // Generate rust code that matches up with the shader
@@ -371,8 +377,182 @@ fn process_glsl_shader(
             &builtin_types,
             &mut user_types,
             &parsed_declarations,
-            &spirv_reflect_module,
-            &reflected_entry_point,
+            &spirv_reflect_modules,
+            &reflected_entry_points,
+        )?)
+    } else {
+        None
+    };
+
+    // Don't worry about the return value
+    log::trace!("{:?}: cook shader", glsl_file);
+    let cooked_shader = if cooked_shader_file.is_some() {
+        let cook_variant = |keywords: &[String], variant: &CompiledShaderVariant| {
+            let output_spv = if package_vk {
+                Some(&variant.output_spv)
+            } else {
+                None
+            };
+
+            let metal_src = if package_metal {
+                Some(variant.metal_src.as_ref().unwrap().clone())
+            } else {
+                None
+            };
+
+            let gles2_src = if package_gles2 {
+                Some(variant.gles2_src.as_ref().unwrap().clone())
+            } else {
+                None
+            };
+
+            let gles3_src = if package_gles3 {
+                Some(variant.gles3_src.as_ref().unwrap().clone())
+            } else {
+                None
+            };
+
+            cook::cook_shader_variant(
+                keywords,
+                &variant.reflected_data.as_ref().unwrap().reflection,
+                output_spv,
+                metal_src,
+                gles2_src,
+                gles3_src,
+            )
+        };
+
+        let base_variant = cook_variant(&[], &base_variant);
+        let keyword_variants = keyword_variants
+            .iter()
+            .map(|(keywords, variant)| cook_variant(keywords.as_slice(), variant))
+            .collect();
+
+        Some(cook::cook_shader(
+            base_variant,
+            shader_keywords.keywords.clone(),
+            keyword_variants,
+        )?)
+    } else {
+        None
+    };
+
+    //
+    // Write out the spv and rust files if desired
+    //
+    if let Some(spv_file) = &spv_file {
+        std::fs::write(spv_file, base_variant.output_spv)?;
+    }
+
+    if let Some(rs_file) = &rs_file {
+        std::fs::write(rs_file, rust_code.unwrap())?;
+    }
+
+    if let Some(metal_generated_src_file) = &metal_generated_src_file {
+        std::fs::write(metal_generated_src_file, base_variant.metal_src.unwrap())?;
+    }
+
+    if let Some(gles2_generated_src_file) = &gles2_generated_src_file {
+        std::fs::write(gles2_generated_src_file, base_variant.gles2_src.unwrap())?;
+    }
+
+    if let Some(gles3_generated_src_file) = &gles3_generated_src_file {
+        std::fs::write(gles3_generated_src_file, base_variant.gles3_src.unwrap())?;
+    }
+
+    if let Some(cooked_shader_file) = &cooked_shader_file {
+        std::fs::write(cooked_shader_file, cooked_shader.unwrap())?;
+    }
+
+    Ok(())
+}
+
+struct CompileShaderVariantArgs<'a> {
+    glsl_file: &'a Path,
+    code: &'a str,
+    entry_point_name: &'a str,
+    shader_kind: shaderc::ShaderKind,
+    builtin_types: &'a FnvHashMap<String, TypeAlignmentInfo>,
+    user_types: &'a FnvHashMap<String, UserType>,
+    parsed_declarations: &'a ParseDeclarationsResult,
+    generate_reflection: bool,
+    require_semantics: bool,
+    generate_metal: bool,
+    generate_gles2: bool,
+    generate_gles3: bool,
+    optimize_shaders: bool,
+}
+
+struct CompiledShaderVariant {
+    unoptimized_compile_spirv_result: shaderc::CompilationArtifact,
+    reflected_data: Option<ShaderProcessorRefectionData>,
+    output_spv: Vec<u8>,
+    metal_src: Option<String>,
+    gles2_src: Option<String>,
+    gles3_src: Option<String>,
+}
+
+fn compile_shader_variant(
+    compiler: &mut shaderc::Compiler,
+    args: &CompileShaderVariantArgs,
+    keywords: &[String],
+) -> Result<CompiledShaderVariant, Box<dyn Error>> {
+    let glsl_file = args.glsl_file;
+    let shader_kind = args.shader_kind;
+
+    let create_compile_options = || {
+        let mut compile_options = shaderc::CompileOptions::new().unwrap();
+        compile_options.set_include_callback(include::shaderc_include_callback);
+        for keyword in keywords {
+            compile_options.add_macro_definition(keyword, Some("1"));
+        }
+        compile_options
+    };
+
+    //
+    // First, compile the code with shaderc. This will validate that it's well-formed. We will also
+    // use the produced spv to create reflection data. This first pass must be UNOPTIMIZED so that
+    // we don't drop reflection data for unused elements.
+    //
+    // We want to preserve unused fields so that the rust API we generate does not substantially
+    // change and cause spurious compile errors just because a line of code gets commented out in
+    // the shader. (In the future we may want to generate the API but make it a noop.)
+    //
+    log::trace!("{:?} {:?}: compile unoptimized", glsl_file, keywords);
+    let unoptimized_compile_spirv_result = {
+        let compile_options = create_compile_options();
+        compiler.compile_into_spirv(
+            args.code,
+            shader_kind,
+            glsl_file.to_str().unwrap(),
+            args.entry_point_name,
+            Some(&compile_options),
+        )?
+    };
+
+    //
+    // Read the unoptimized spv into spirv_cross so that we can grab reflection data
+    //
+    log::trace!("{:?} {:?}: read spirv_cross module", glsl_file, keywords);
+    let spirv_cross_module =
+        spirv_cross::spirv::Module::from_words(unoptimized_compile_spirv_result.as_binary());
+
+    // example usage of spirv_cross. We can provide options here to modify the shader
+    // programmatically. This could use annotations to drive this
+    log::trace!("{:?} {:?}: generate spirv_cross ast", glsl_file, keywords);
+    let mut spirv_cross_glsl_options = spirv_cross::glsl::CompilerOptions::default();
+    spirv_cross_glsl_options.vulkan_semantics = true;
+    let mut ast = spirv_cross::spirv::Ast::<spirv_cross::glsl::Target>::parse(&spirv_cross_module)?;
+    ast.set_compiler_options(&spirv_cross_glsl_options)?;
+
+    let mut reflected_data = if args.generate_reflection {
+        log::trace!("{:?} {:?}: generate reflection data", glsl_file, keywords);
+        Some(reflect::reflect_data(
+            args.builtin_types,
+            args.user_types,
+            &ast,
+            args.parsed_declarations,
+            args.require_semantics,
         )?)
     } else {
         None
@@ -390,29 +570,28 @@ fn process_glsl_shader(
     //ast.build_combined_image_samplers();
     //let compiled = ast.compile()?;
     let output_spv = if args.optimize_shaders {
-        log::trace!("{:?}: compile optimized", glsl_file);
-        let mut compile_options = shaderc::CompileOptions::new().unwrap();
-        compile_options.set_include_callback(include::shaderc_include_callback);
+        log::trace!("{:?} {:?}: compile optimized", glsl_file, keywords);
+        let mut compile_options = create_compile_options();
         compile_options.set_optimization_level(shaderc::OptimizationLevel::Performance);
         //NOTE: Could also use shaderc::OptimizationLevel::Size
 
         compiler
             .compile_into_spirv(
-                &code,
+                args.code,
                 shader_kind,
                 glsl_file.to_str().unwrap(),
-                entry_point_name,
+                args.entry_point_name,
                 Some(&compile_options),
             )?
             .as_binary_u8()
             .to_vec()
     } else {
-        log::trace!("{:?}: do not recompile optimized", glsl_file);
+        log::trace!("{:?} {:?}: do not recompile optimized", glsl_file, keywords);
         unoptimized_compile_spirv_result.as_binary_u8().to_vec()
     };
 
-    let metal_src = if metal_generated_src_file.is_some() || package_metal {
-        log::trace!("{:?}: create msl", glsl_file);
+    let metal_src = if args.generate_metal {
+        log::trace!("{:?} {:?}: create msl", glsl_file, keywords);
         let mut msl_ast =
             spirv_cross::spirv::Ast::<spirv_cross::msl::Target>::parse(&spirv_cross_module)?;
         let mut spirv_cross_msl_options = spirv_cross::msl::CompilerOptions::default();
@@ -438,8 +617,8 @@ fn process_glsl_shader(
         None
     };
 
-    let gles2_src = if gles2_generated_src_file.is_some() || package_gles2 {
-        log::trace!("{:?}: create gles2", glsl_file);
+    let gles2_src = if args.generate_gles2 {
+        log::trace!("{:?} {:?}: create gles2", glsl_file, keywords);
         let mut gles2_ast =
             spirv_cross::spirv::Ast::<spirv_cross::glsl::Target>::parse(&spirv_cross_module)?;
         let mut spirv_cross_gles2_options = spirv_cross::glsl::CompilerOptions::default();
@@ -472,8 +651,8 @@ fn process_glsl_shader(
         None
     };
 
-    let gles3_src = if gles3_generated_src_file.is_some() || package_gles3 {
-        log::trace!("{:?}: create gles3", glsl_file);
+    let gles3_src = if args.generate_gles3 {
+        log::trace!("{:?} {:?}: create gles3", glsl_file, keywords);
         let mut gles3_ast =
             spirv_cross::spirv::Ast::<spirv_cross::glsl::Target>::parse(&spirv_cross_module)?;
         let mut spirv_cross_gles3_options = spirv_cross::glsl::CompilerOptions::default();
@@ -495,68 +674,14 @@ fn process_glsl_shader(
         None
     };
 
-    // Don't worry about the return value
-    log::trace!("{:?}: cook shader", glsl_file);
-    let cooked_shader = if cooked_shader_file.is_some() {
-        let output_spv = if package_vk { Some(&output_spv) } else { None };
-
-        let metal_src = if package_metal {
-            Some(metal_src.as_ref().unwrap().clone())
-        } else {
-            None
-        };
-
-        let gles2_src = if package_gles2 {
-            Some(gles2_src.as_ref().unwrap().clone())
-        } else {
-            None
-        };
-
-        let gles3_src = if package_gles3 {
-            Some(gles3_src.as_ref().unwrap().clone())
-        } else {
-            None
-        };
-
-        Some(cook::cook_shader(
-            &reflected_data.as_ref().unwrap().reflection,
-            output_spv,
-            metal_src,
-            gles2_src,
-            gles3_src,
-        )?)
-    } else {
-        None
-    };
-
-    //
-    // Write out the spv and rust files if desired
-    //
-    if let Some(spv_file) = &spv_file {
-        std::fs::write(spv_file, output_spv)?;
-    }
-
-    if let Some(rs_file) = &rs_file {
-        std::fs::write(rs_file, rust_code.unwrap())?;
-    }
-
-    if let Some(metal_generated_src_file) = &metal_generated_src_file {
-        std::fs::write(metal_generated_src_file, metal_src.unwrap())?;
-    }
-
-    if let Some(gles2_generated_src_file) = &gles2_generated_src_file {
-        std::fs::write(gles2_generated_src_file, gles2_src.unwrap())?;
-    }
-
-    if let Some(gles3_generated_src_file) = &gles3_generated_src_file {
-        std::fs::write(gles3_generated_src_file, gles3_src.unwrap())?;
-    }
-
-    if let Some(cooked_shader_file) = &cooked_shader_file {
-        std::fs::write(cooked_shader_file, cooked_shader.unwrap())?;
-    }
-
-    Ok(())
+    Ok(CompiledShaderVariant {
+        unoptimized_compile_spirv_result,
+        reflected_data,
+        output_spv,
+        metal_src,
+        gles2_src,
+        gles3_src,
+    })
 }
 
 fn rename_gl_samplers(
@@ -2,6 +2,7 @@ use serde::Deserialize;
 
 use super::AnnotationText;
 use super::DeclarationText;
+use fnv::FnvHashMap;
 use std::num::ParseIntError;
 use std::sync::Arc;
 
@@ -486,6 +487,13 @@ fn try_parse_binding(code: &[char]) -> Result<Option<ParseBindingResult>, String
         identifiers.push(instance_name);
     }
 
+    if identifiers.len() < 2 {
+        Err(format!(
+            "Expected type and instance name while parsing binding:\n{}",
+            crate::parse_source::characters_to_string(code)
+        ))?;
+    }
+
     let modifiers = &identifiers[0..(identifiers.len() - 2)];
     let type_name = identifiers[identifiers.len() - 2].clone();
     let instance_name = identifiers[identifiers.len() - 1].clone();
@@ -566,6 +574,38 @@ fn try_parse_const(code: &[char]) -> Result<Option<()>, String> {
     Ok(Some(()))
 }
 
+// Layout qualifiers that don't declare a variable, i.e. layout(local_size_x = 64) in;
+fn try_parse_layout_qualifier(code: &[char]) -> Result<Option<()>, String> {
+    let mut position = 0;
+
+    if crate::parse_source::try_consume_literal(code, &mut position, "layout").is_none() {
+        return Ok(None);
+    }
+
+    crate::parse_source::skip_whitespace(code, &mut position);
+    if crate::parse_source::try_consume_literal(code, &mut position, "(").is_none() {
+        return Ok(None);
+    }
+
+    crate::parse_source::skip_whitespace(code, &mut position);
+    parse_layout_parts(code, &mut position)?;
+    crate::parse_source::skip_whitespace(code, &mut position);
+
+    let storage_qualifier = crate::parse_source::try_consume_identifier(code, &mut position);
+    if !matches!(storage_qualifier.as_deref(), Some("in") | Some("out")) {
+        return Ok(None);
+    }
+
+    crate::parse_source::skip_whitespace(code, &mut position);
+    crate::parse_source::try_consume_literal(code, &mut position, ";");
+    crate::parse_source::skip_whitespace(code, &mut position);
+    if position < code.len() {
+        return Ok(None);
+    }
+
+    Ok(Some(()))
+}
+
 // fn generate_struct(result: &ParseStructResult, annotations: &StructAnnotations) -> Result<String, String> {
 //     if !annotations.export.is_some() {
 //         return Ok("".to_string())
@@ -618,6 +658,16 @@ pub(crate) fn parse_declarations(
                 parsed: struct_result,
                 annotations: struct_annotations,
             });
+        } else if try_parse_layout_qualifier(&declaration.text)?.is_some() {
+            //
+            // Layout qualifiers like the compute workgroup size are read from reflection data
+            //
+            if !declaration.annotations.is_empty() {
+                return Err(format!(
+                    "Annotations on layout qualifiers are not supported:\n{}",
+                    crate::parse_source::characters_to_string(&declaration.text)
+                ));
+            }
         } else if let Some(binding_result) = try_parse_binding(&declaration.text)? {
             //
             // Handle Binding
@@ -655,5 +705,167 @@ pub(crate) fn parse_declarations(
         }
     }
 
+    verify_unique_binding_locations(&bindings)?;
+
     Ok(ParseDeclarationsResult { structs, bindings })
 }
+
+// The source is parsed before it is preprocessed, so declarations in every #ifdef branch are
+// included. Reflection data for each shader permutation is matched up with the declarations by
+// set/binding (or location), so declaring two different resources at the same place would give
+// some permutations the wrong declaration.
+fn verify_unique_binding_locations(
+    bindings: &[ParsedBindingWithAnnotations]
+) -> Result<(), String> {
+    let mut descriptor_bindings = FnvHashMap::<(usize, usize), &str>::default();
+    let mut in_locations = FnvHashMap::<usize, &str>::default();
+    let mut out_locations = FnvHashMap::<usize, &str>::default();
+
+    for binding in bindings {
+        let layout_parts = &binding.parsed.layout_parts;
+        let instance_name = binding.parsed.instance_name.as_str();
+
+        if let (Some(set), Some(binding_index)) = (layout_parts.set, layout_parts.binding) {
+            if let Some(other) = descriptor_bindings.insert((set, binding_index), instance_name) {
+                return Err(format!(
+                    "{} and {} are both declared at set = {}, binding = {}. Each set/binding may only be declared once, even in different #ifdef branches",
+                    other, instance_name, set, binding_index
+                ));
+            }
+        }
+
+        if let Some(location) = layout_parts.location {
+            let locations = match binding.parsed.binding_type {
+                BindingType::In => &mut in_locations,
+                BindingType::Out => &mut out_locations,
+                _ => continue,
+            };
+
+            if let Some(other) = locations.insert(location, instance_name) {
+                return Err(format!(
+                    "{} and {} are both declared at location = {}. Each location may only be declared once, even in different #ifdef branches",
+                    other, instance_name, location
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::parse_source::FileToProcess;
+
+    #[test]
+    fn test_compute_workgroup_size_is_not_a_binding() {
+        let shader_code = r#"
+            layout(set = 0, binding = 0, std430) buffer Positions {
+                vec2 data[];
+            } positions;
+
+            layout(local_size_x = 64) in;
+
+            void main() {}
+        "#;
+
+        let file_to_process = FileToProcess {
+            path: "".into(),
+            include_type: crate::IncludeType::Relative,
+            requested_from: "".into(),
+            include_depth: 0,
+        };
+
+        let mut declarations = Vec::default();
+        let mut included_files = Default::default();
+        let code: Vec<char> = shader_code.chars().collect();
+        crate::parse_source::parse_shader_source_text(
+            &file_to_process,
+            &mut declarations,
+            &mut included_files,
+            &code,
+        )
+        .unwrap();
+
+        let parsed = super::parse_declarations(&declarations).unwrap();
+        assert_eq!(parsed.bindings.len(), 1);
+        assert_eq!(parsed.bindings[0].parsed.instance_name, "positions");
+    }
+
+    fn parse_declarations_for_test(
+        shader_code: &str
+    ) -> Result<super::ParseDeclarationsResult, String> {
+        let file_to_process = FileToProcess {
+            path: "".into(),
+            include_type: crate::IncludeType::Relative,
+            requested_from: "".into(),
+            include_depth: 0,
+        };
+
+        let mut declarations = Vec::default();
+        let mut included_files = Default::default();
+        let code: Vec<char> = shader_code.chars().collect();
+        crate::parse_source::parse_shader_source_text(
+            &file_to_process,
+            &mut declarations,
+            &mut included_files,
+            &code,
+        )
+        .unwrap();
+
+        super::parse_declarations(&declarations)
+    }
+
+    #[test]
+    fn test_duplicate_bindings_in_ifdef_branches_are_rejected() {
+        let shader_code = r#"
+            #ifdef TEXTURED
+            layout (set = 1, binding = 1) uniform texture2D base_color_texture;
+            #else
+            layout (set = 1, binding = 1) uniform MaterialData {
+                vec4 base_color;
+            } material_data;
+            #endif
+
+            void main() {}
+        "#;
+
+        let error = parse_declarations_for_test(shader_code).err().unwrap();
+        assert!(error.contains("set = 1, binding = 1"));
+
+        let shader_code = r#"
+            layout (location = 0) in vec3 in_pos;
+            #ifdef TEXTURED
+            layout (location = 1) in vec2 in_uv;
+            #else
+            layout (location = 1) in vec4 in_color;
+            #endif
+
+            void main() {}
+        "#;
+
+        let error = parse_declarations_for_test(shader_code).err().unwrap();
+        assert!(error.contains("location = 1"));
+    }
+
+    #[test]
+    fn test_ifdef_bindings_at_different_locations() {
+        // Inputs and outputs have separate locations
+        let shader_code = r#"
+            layout (location = 0) in vec3 in_pos;
+            layout (location = 0) out vec3 out_pos;
+            #ifdef TEXTURED
+            layout (set = 1, binding = 1) uniform texture2D base_color_texture;
+            layout (location = 1) in vec2 in_uv;
+            #endif
+            layout (set = 1, binding = 0) uniform MaterialData {
+                vec4 base_color;
+            } material_data;
+
+            void main() {}
+        "#;
+
+        let parsed = parse_declarations_for_test(shader_code).unwrap();
+        assert_eq!(parsed.bindings.len(), 5);
+    }
+}
@@ -1,16 +1,16 @@
 use rafx_framework::cooked_shader::{
     ReflectedDescriptorSetLayout, ReflectedDescriptorSetLayoutBinding, ReflectedEntryPoint,
-    ReflectedVertexInput,
+    ReflectedUniformMember, ReflectedVertexInput,
 };
 
 use crate::shader_types::{
-    element_count, generate_struct, MemoryLayout, TypeAlignmentInfo, UserType,
+    determine_size, element_count, generate_struct, MemoryLayout, TypeAlignmentInfo, UserType,
 };
 use fnv::FnvHashMap;
 use rafx_api::{
     RafxAddressMode, RafxCompareOp, RafxFilterType, RafxGlUniformMember, RafxMipMapMode,
     RafxResourceType, RafxResult, RafxSamplerDef, RafxShaderResource, RafxShaderStageFlags,
-    RafxShaderStageReflection, MAX_DESCRIPTOR_SET_LAYOUTS,
+    RafxShaderStageReflection, MAX_DESCRIPTOR_SET_LAYOUTS, METAL_PUSH_CONSTANT_BUFFER_INDEX,
 };
 use spirv_cross::msl::{ResourceBinding, ResourceBindingLocation, SamplerData, SamplerLocation};
 use spirv_cross::spirv::{ExecutionModel, Type};
@@ -134,6 +134,7 @@ where
             &parsed_binding.parsed.type_name,
             parsed_binding.parsed.type_name.clone(),
             0,
+            MemoryLayout::Std140,
             &mut gl_uniform_members,
         )?;
     }
@@ -162,6 +163,66 @@ where
     Ok(resource)
 }
 
+// Push constants are not part of a descriptor set. When cross-compiling to GL ES, spirv_cross emits
+// the block as a plain uniform struct named after the instance, so the members are set like GL ES
+// 2.0 uniform buffers are.
+fn get_push_constant_resource<TargetT>(
+    builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
+    user_types: &FnvHashMap<String, UserType>,
+    ast: &spirv_cross::spirv::Ast<TargetT>,
+    declarations: &super::parse_declarations::ParseDeclarationsResult,
+    resource: &spirv_cross::spirv::Resource,
+    stage_flags: RafxShaderStageFlags,
+) -> RafxResult<RafxShaderResource>
+where
+    TargetT: spirv_cross::spirv::Target,
+    spirv_cross::spirv::Ast<TargetT>: spirv_cross::spirv::Parse<TargetT>,
+    spirv_cross::spirv::Ast<TargetT>: spirv_cross::spirv::Compile<TargetT>,
+{
+    let parsed_binding = declarations
+        .bindings
+        .iter()
+        .find(|x| x.parsed.layout_parts.push_constant)
+        .ok_or_else(|| format!("A push constant block named {} in spirv reflection data was not matched up to a push constant block scanned in source code.", resource.name))?;
+
+    let size_in_bytes = ast
+        .get_declared_struct_size(resource.type_id)
+        .map_err(|_x| "could not get push constant size from reflection data")?;
+
+    let gles_name = parsed_binding.parsed.instance_name.clone();
+
+    let mut gl_uniform_members = Vec::<RafxGlUniformMember>::default();
+    generate_gl_uniform_members(
+        &builtin_types,
+        &user_types,
+        &parsed_binding.parsed.type_name,
+        gles_name.clone(),
+        0,
+        MemoryLayout::Std430,
+        &mut gl_uniform_members,
+    )?;
+
+    let slot_name = if let Some(annotation) = &parsed_binding.annotations.slot_name {
+        Some(annotation.0.clone())
+    } else {
+        None
+    };
+
+    let resource = RafxShaderResource {
+        resource_type: RafxResourceType::ROOT_CONSTANT,
+        size_in_bytes,
+        used_in_shader_stages: stage_flags,
+        name: Some(slot_name.unwrap_or_else(|| resource.name.clone())),
+        gles_name: Some(gles_name),
+        gles2_uniform_members: gl_uniform_members,
+        ..Default::default()
+    };
+
+    resource.validate()?;
+
+    Ok(resource)
+}
+
 fn get_reflected_binding<TargetT>(
     builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
     user_types: &FnvHashMap<String, UserType>,
@@ -210,10 +271,24 @@ where
             None
         };
 
+    let mut uniform_members = Vec::default();
+    if resource_type == RafxResourceType::UNIFORM_BUFFER {
+        generate_uniform_members(
+            builtin_types,
+            user_types,
+            &parsed_binding.parsed.type_name,
+            String::default(),
+            0,
+            MemoryLayout::Std140,
+            &mut uniform_members,
+        )?;
+    }
+
     Ok(ReflectedDescriptorSetLayoutBinding {
         resource: rafx_resource,
         internal_buffer_per_descriptor_size,
         immutable_samplers,
+        uniform_members,
     })
 }
 
@@ -330,8 +405,15 @@ pub(crate) fn msl_assign_argument_buffer_ids(
     entry_points: &[ReflectedEntryPoint]
 ) -> RafxResult<BTreeMap<ResourceBindingLocation, ResourceBinding>> {
     let mut all_resources_lookup = FnvHashMap::<(u32, u32), RafxShaderResource>::default();
+    let mut push_constant_stages = RafxShaderStageFlags::empty();
     for entry_point in entry_points {
         for resource in &entry_point.rafx_api_reflection.resources {
+            // Push constants are not in an argument buffer
+            if resource.resource_type == RafxResourceType::ROOT_CONSTANT {
+                push_constant_stages |= resource.used_in_shader_stages;
+                continue;
+            }
+
             let key = (resource.set_index, resource.binding);
             if let Some(old) = all_resources_lookup.get_mut(&key) {
                 if resource.resource_type != old.resource_type {
@@ -436,6 +518,32 @@ pub(crate) fn msl_assign_argument_buffer_ids(
             resource.element_count_normalized();
     }
 
+    // Push constants are bound with set_bytes to a fixed buffer index. spirv_cross identifies them
+    // with a special descriptor set/binding (kPushConstDescSet/kPushConstBinding)
+    let push_constant_binding = ResourceBinding {
+        buffer_id: METAL_PUSH_CONSTANT_BUFFER_INDEX,
+        texture_id: 0,
+        sampler_id: 0,
+        count: 1,
+    };
+
+    for &(stage_flag, execution_model) in &[
+        (RafxShaderStageFlags::VERTEX, ExecutionModel::Vertex),
+        (RafxShaderStageFlags::FRAGMENT, ExecutionModel::Fragment),
+        (RafxShaderStageFlags::COMPUTE, ExecutionModel::Kernel),
+    ] {
+        if push_constant_stages.intersects(stage_flag) {
+            argument_buffer_assignments.insert(
+                ResourceBindingLocation {
+                    stage: execution_model,
+                    desc_set: !0,
+                    binding: 0,
+                },
+                push_constant_binding.clone(),
+            );
+        }
+    }
+
     Ok(argument_buffer_assignments)
 }
 
@@ -572,6 +680,7 @@ fn generate_gl_uniform_members(
     type_name: &str,
     prefix: String,
     offset: usize,
+    memory_layout: MemoryLayout,
     gl_uniform_members: &mut Vec<RafxGlUniformMember>,
 ) -> RafxResult<()> {
     if builtin_types.contains_key(type_name) {
@@ -593,7 +702,7 @@ fn generate_gl_uniform_members(
             user_types,
             &user_type.type_name,
             user_type,
-            MemoryLayout::Std140,
+            memory_layout,
         )?;
 
         for field in &*user_type.fields {
@@ -617,6 +726,7 @@ fn generate_gl_uniform_members(
                     &field.type_name,
                     member_full_name,
                     field_offset,
+                    memory_layout,
                     gl_uniform_members,
                 )?;
             } else {
@@ -631,6 +741,7 @@ fn generate_gl_uniform_members(
                         &field.type_name,
                         member_full_name,
                         field_offset,
+                        memory_layout,
                         gl_uniform_members,
                     )?;
                 }
@@ -641,6 +752,103 @@ fn generate_gl_uniform_members(
     Ok(())
 }
 
+// Similar to generate_gl_uniform_members, but names are relative to the buffer (i.e.
+// "lights[1].color" rather than "PerViewData.lights[1].color") and the type of each member is
+// recorded so that values can be validated when written by name
+fn generate_uniform_members(
+    builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
+    user_types: &FnvHashMap<String, UserType>,
+    type_name: &str,
+    prefix: String,
+    offset: usize,
+    memory_layout: MemoryLayout,
+    uniform_members: &mut Vec<ReflectedUniformMember>,
+) -> RafxResult<()> {
+    if let Some(builtin_type) = builtin_types.get(type_name) {
+        uniform_members.push(ReflectedUniformMember {
+            name: prefix,
+            type_name: type_name.to_string(),
+            offset: offset as u32,
+            size: builtin_type.size as u32,
+        })
+    } else {
+        let user_type = user_types.get(type_name).ok_or_else(|| {
+            format!(
+                "Could not find type named {} in generate_uniform_members",
+                type_name
+            )
+        })?;
+
+        let generated_struct = generate_struct(
+            builtin_types,
+            user_types,
+            &user_type.type_name,
+            user_type,
+            memory_layout,
+        )?;
+
+        for field in &*user_type.fields {
+            let struct_member = generated_struct
+                .members
+                .iter()
+                .find(|x| x.name == field.field_name)
+                .ok_or_else(|| {
+                    format!(
+                        "Could not find member {} within generated struct {}",
+                        field.field_name, generated_struct.name
+                    )
+                })?;
+
+            let member_name = if prefix.is_empty() {
+                field.field_name.clone()
+            } else {
+                format!("{}.{}", prefix, field.field_name)
+            };
+
+            if field.array_sizes.is_empty() {
+                generate_uniform_members(
+                    builtin_types,
+                    user_types,
+                    &field.type_name,
+                    member_name,
+                    offset + struct_member.offset,
+                    memory_layout,
+                    uniform_members,
+                )?;
+            } else {
+                // The rust struct member may be smaller than the GPU array (i.e. elements that
+                // are padded to 16 bytes in std140), so use the GPU size to find the stride
+                let element_count = element_count(&field.array_sizes);
+                let array_size = determine_size(
+                    builtin_types,
+                    user_types,
+                    &field.type_name,
+                    &field.array_sizes,
+                    0,
+                    0,
+                    &field.field_name,
+                    memory_layout,
+                )?;
+                let element_stride = array_size / element_count;
+                for i in 0..element_count {
+                    let field_offset = offset + struct_member.offset + (i * element_stride);
+                    generate_uniform_members(
+                        builtin_types,
+                        user_types,
+                        &field.type_name,
+                        format!("{}[{}]", member_name, i),
+                        field_offset,
+                        memory_layout,
+                        uniform_members,
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub struct ShaderProcessorRefectionData {
     pub reflection: Vec<ReflectedEntryPoint>,
     pub msl_argument_buffer_assignments: BTreeMap<ResourceBindingLocation, ResourceBinding>,
@@ -741,24 +949,20 @@ where
             }
         }
 
-        //TODO: This is using a list of push constants but I don't think multiple are allowed within
-        // the same file
-        for push_constant in &shader_resources.push_constant_buffers {
-            let push_constant_ranges = ast
-                .get_active_buffer_ranges(push_constant.id)
-                .map_err(|_x| "could not get active buffer ranges")?;
-            for push_constant_range in &push_constant_ranges {
-                let resource = RafxShaderResource {
-                    resource_type: RafxResourceType::ROOT_CONSTANT,
-                    size_in_bytes: push_constant_range.range as u32,
-                    used_in_shader_stages: stage_flags,
-                    name: Some(push_constant.name.clone()),
-                    ..Default::default()
-                };
-                resource.validate()?;
+        // GLSL only allows a single push constant block per stage
+        if shader_resources.push_constant_buffers.len() > 1 {
+            Err("Only one push constant block is allowed per shader stage")?;
+        }
 
-                rafx_bindings.push(resource);
-            }
+        for push_constant in &shader_resources.push_constant_buffers {
+            rafx_bindings.push(get_push_constant_resource(
+                builtin_types,
+                user_types,
+                ast,
+                declarations,
+                push_constant,
+                stage_flags,
+            )?);
         }
 
         //TODO: Store the type and verify that the format associated in the game i.e. R32G32B32 is
@@ -848,3 +1052,88 @@ fn map_shader_stage_flags(
         ExecutionModel::Kernel => RafxShaderStageFlags::COMPUTE,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_source::FileToProcess;
+    use crate::shader_types::{create_builtin_type_lookup, create_user_type_lookup};
+
+    #[test]
+    fn test_generate_uniform_members() {
+        let shader_code = r#"
+            #version 450
+
+            struct Light {
+                vec3 position;
+                float intensity;
+                mat3 basis;
+            };
+
+            layout (set = 0, binding = 0) uniform LightData {
+                vec4 color;
+                Light lights[2];
+                ivec2 sizes[2];
+                uint light_count;
+            } light_data;
+        "#;
+
+        let file_to_process = FileToProcess {
+            path: "".into(),
+            include_type: crate::IncludeType::Relative,
+            requested_from: "".into(),
+            include_depth: 0,
+        };
+
+        let mut declarations = Vec::default();
+        let mut included_files = Default::default();
+        let code: Vec<char> = shader_code.chars().collect();
+        crate::parse_source::parse_shader_source_text(
+            &file_to_process,
+            &mut declarations,
+            &mut included_files,
+            &code,
+        )
+        .unwrap();
+        let parsed_declarations =
+            crate::parse_declarations::parse_declarations(&declarations).unwrap();
+
+        let builtin_types = create_builtin_type_lookup();
+        let user_types = create_user_type_lookup(&parsed_declarations).unwrap();
+
+        let mut uniform_members = Vec::default();
+        generate_uniform_members(
+            &builtin_types,
+            &user_types,
+            "LightData",
+            String::default(),
+            0,
+            MemoryLayout::Std140,
+            &mut uniform_members,
+        )
+        .unwrap();
+
+        let members: Vec<_> = uniform_members
+            .iter()
+            .map(|x| (x.name.as_str(), x.type_name.as_str(), x.offset, x.size))
+            .collect();
+
+        // Nested structs and arrays are flattened, array elements are offset by the std140 stride
+        // (Light is 64 bytes, ivec2 array elements are rounded up to 16 bytes)
+        assert_eq!(
+            members,
+            vec![
+                ("color", "vec4", 0, 16),
+                ("lights[0].position", "vec3", 16, 12),
+                ("lights[0].intensity", "float", 28, 4),
+                ("lights[0].basis", "mat3", 32, 48),
+                ("lights[1].position", "vec3", 80, 12),
+                ("lights[1].intensity", "float", 92, 4),
+                ("lights[1].basis", "mat3", 96, 48),
+                ("sizes[0]", "ivec2", 144, 8),
+                ("sizes[1]", "ivec2", 160, 8),
+                ("light_count", "uint", 176, 4),
+            ]
+        );
+    }
+}
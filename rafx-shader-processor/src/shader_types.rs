@@ -163,6 +163,14 @@ pub(crate) fn create_builtin_type_lookup() -> FnvHashMap<String, TypeAlignmentIn
     add_type_alignment_info::<[f32; 2]>(&mut builtin_types, "vec2", "[f32; 2]");
     add_type_alignment_info::<[f32; 3]>(&mut builtin_types, "vec3", "[f32; 3]");
     add_type_alignment_info::<[f32; 4]>(&mut builtin_types, "vec4", "[f32; 4]");
+    add_type_alignment_info::<[i32; 2]>(&mut builtin_types, "ivec2", "[i32; 2]");
+    add_type_alignment_info::<[i32; 3]>(&mut builtin_types, "ivec3", "[i32; 3]");
+    add_type_alignment_info::<[i32; 4]>(&mut builtin_types, "ivec4", "[i32; 4]");
+    add_type_alignment_info::<[u32; 2]>(&mut builtin_types, "uvec2", "[u32; 2]");
+    add_type_alignment_info::<[u32; 3]>(&mut builtin_types, "uvec3", "[u32; 3]");
+    add_type_alignment_info::<[u32; 4]>(&mut builtin_types, "uvec4", "[u32; 4]");
+    // Each column of a mat3 is aligned like a vec4
+    add_type_alignment_info::<[[f32; 4]; 3]>(&mut builtin_types, "mat3", "[[f32; 4]; 3]");
     add_type_alignment_info::<[[f32; 4]; 4]>(&mut builtin_types, "mat4", "[[f32; 4]; 4]");
     builtin_types
 }
@@ -482,7 +490,7 @@ fn determine_size_of_member_in_rust(
     )
 }
 
-fn determine_size(
+pub(crate) fn determine_size(
     builtin_types: &FnvHashMap<String, TypeAlignmentInfo>,
     user_types: &FnvHashMap<String, UserType>,
     query_type: &str,
@@ -743,6 +751,22 @@ fn verify_layout(
 
         let array_sizes: Vec<usize> = block.array.dims.iter().map(|x| *x as usize).collect();
 
+        // A storage buffer that ends in a runtime-sized array (i.e. float data[]) is as large as
+        // the buffer that is bound to it, so there is no size to check. Its members are still
+        // checked below.
+        let ends_in_runtime_array = block
+            .members
+            .last()
+            .and_then(|x| x.type_description.as_ref())
+            .map(|x| *x.op == spirv_headers::Op::TypeRuntimeArray)
+            .unwrap_or(false);
+        if ends_in_runtime_array {
+            log::trace!(
+                "skipping size check of {}, it ends in a runtime array",
+                type_name
+            );
+        }
+
         let size = determine_size(
             builtin_types,
             user_types,
@@ -754,7 +778,9 @@ fn verify_layout(
             layout,
         )?;
 
-        if block.padded_size != 0 {
+        if ends_in_runtime_array {
+            // Nothing to compare
+        } else if block.padded_size != 0 {
             // The easy check, but it's 0 on storage buffers for some reason
             if size != block.size as usize {
                 println!("{:?}", block);
@@ -985,6 +1011,46 @@ mod test {
         verify_all_binding_layouts_in_test(reflect_data, &parsed_declarations)
     }
 
+    #[test]
+    fn test_integer_vector_and_mat3_layout() {
+        let shader_code = r#"
+            #version 450
+
+            struct Tile {
+                ivec2 coordinate;
+                uvec3 flags;
+                mat3 uv_transform;
+                float weight;
+            };
+
+            // @[export]
+            layout (set = 0, binding = 0) uniform TileDataUbo {
+                ivec4 bounds;
+                uvec2 counts;
+                mat3 transform;
+                Tile tiles[4];
+                mat3 transforms[2];
+            } tile_data_uniform;
+
+            // @[export]
+            layout (set = 0, binding = 1) buffer TileDataSbo {
+                ivec4 bounds;
+                uvec2 counts;
+                mat3 transform;
+                Tile tiles[4];
+                mat3 transforms[2];
+            } tile_data_buffer;
+
+            layout (location = 0) out vec4 out_color;
+            void main() {
+                out_color = vec4(tile_data_uniform.bounds + tile_data_buffer.bounds);
+            }
+        "#;
+
+        let (reflect_data, parsed_declarations) = compile_code_for_test(&shader_code);
+        verify_all_binding_layouts_in_test(reflect_data, &parsed_declarations)
+    }
+
     // One reason for this test is to check that we can support the same structs used in different
     // layouts
     #[test]
@@ -1,3 +1,7 @@
 mod packed_bounding_sphere_chunk;
 pub(crate) use packed_bounding_sphere_chunk::collect_visible_objects;
 pub(crate) use packed_bounding_sphere_chunk::PackedBoundingSphereChunk;
+
+mod shadow_caster_culling;
+pub(crate) use shadow_caster_culling::collect_shadow_casters;
+pub(crate) use shadow_caster_culling::ShadowExtrusion;
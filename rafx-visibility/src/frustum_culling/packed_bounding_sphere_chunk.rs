@@ -104,7 +104,6 @@ impl PackedBoundingSphereChunk {
         &self.metadata[index.0]
     }
 
-    #[allow(dead_code)]
     pub fn get(
         &self,
         index: usize,
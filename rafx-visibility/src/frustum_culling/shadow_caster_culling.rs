@@ -0,0 +1,387 @@
+use crate::frustum_culling::PackedBoundingSphereChunk;
+use crate::geometry::{BoundingSphere, Frustum, Plane};
+use crate::{Projection, ViewFrustum, VisibilityResult, VisibleObjects};
+use glam::Vec3;
+
+/// Describes how a bounding sphere is extruded away from a light to form its shadow volume.
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum ShadowExtrusion {
+    /// An orthographic light casts every shadow along the same direction.
+    Directional { direction: Vec3 },
+    /// A perspective light casts shadows away from its position.
+    Point { position: Vec3 },
+}
+
+impl ShadowExtrusion {
+    /// Returns `None` if the light has an `Undefined` projection.
+    pub fn new(light: &ViewFrustum) -> Option<Self> {
+        match light.get_projection() {
+            Projection::Orthographic(_) => Some(ShadowExtrusion::Directional {
+                direction: (light.look_at() - light.eye_position()).normalize(),
+            }),
+            Projection::Perspective(_) => Some(ShadowExtrusion::Point {
+                position: light.eye_position(),
+            }),
+            Projection::Undefined => None,
+        }
+    }
+
+    #[inline(always)]
+    fn direction(
+        &self,
+        sphere: &BoundingSphere,
+    ) -> Option<Vec3> {
+        match self {
+            ShadowExtrusion::Directional { direction } => Some(*direction),
+            ShadowExtrusion::Point { position } => {
+                let offset = sphere.position - *position;
+                let length = offset.length();
+                if length > f32::EPSILON {
+                    Some(offset / length)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+pub(crate) fn collect_shadow_casters(
+    chunk: &PackedBoundingSphereChunk,
+    light_position: Vec3,
+    light_frustum: &Frustum,
+    light_far_distance: f32,
+    extrusion: &ShadowExtrusion,
+    shadowed_frustums: &[Frustum],
+    results: &mut VisibleObjects,
+) {
+    assert_eq!(light_frustum.planes.len(), 6);
+
+    // Objects between the light and its near plane still cast shadows into the light's frustum, so
+    // the shadow volume is only tested against the far and side planes.
+    let light_planes = &light_frustum.planes[ViewFrustum::FAR..];
+
+    for index in 0..chunk.len() {
+        let sphere = chunk.get(index);
+
+        let is_shadow_caster = match extrusion.direction(&sphere) {
+            Some(direction) => {
+                let length =
+                    extrusion_length(light_frustum, light_far_distance, &sphere, direction);
+                let end = sphere.position + direction * length;
+
+                // An object that the light does not reach cannot cast a shadow.
+                capsule_intersects_planes(light_planes, &sphere, end)
+                    && shadowed_frustums
+                        .iter()
+                        .any(|frustum| capsule_intersects_planes(&frustum.planes, &sphere, end))
+            }
+            // The light is inside the object, so the shadow volume is unbounded.
+            None => light_frustum.contains_sphere_fast(&sphere) && !shadowed_frustums.is_empty(),
+        };
+
+        if is_shadow_caster {
+            let object = chunk.metadata(index);
+            results.push(VisibilityResult::new(
+                object.handle,
+                object.id,
+                light_position,
+                sphere,
+            ));
+        }
+    }
+}
+
+/// Returns the distance along `direction` from the sphere's center to the light's far plane.
+#[inline(always)]
+fn extrusion_length(
+    light_frustum: &Frustum,
+    light_far_distance: f32,
+    sphere: &BoundingSphere,
+    direction: Vec3,
+) -> f32 {
+    let far_plane = &light_frustum.planes[ViewFrustum::FAR];
+
+    // The far plane's normal points back towards the light.
+    let cos_angle = -far_plane.get_normal().dot(direction);
+    let length = if cos_angle > f32::EPSILON {
+        (far_plane.distance(sphere.position) + sphere.radius) / cos_angle
+    } else {
+        light_far_distance
+    };
+
+    length.max(0.)
+}
+
+/// Tests the capsule swept from the sphere's center to `end` against a convex set of planes.
+/// Like the sphere tests, this is conservative and may report an intersection near the corners.
+#[inline(always)]
+fn capsule_intersects_planes(
+    planes: &[Plane],
+    sphere: &BoundingSphere,
+    end: Vec3,
+) -> bool {
+    let negative_radius = -sphere.radius;
+
+    for plane in planes {
+        if plane.distance(sphere.position) <= negative_radius
+            && plane.distance(end) <= negative_radius
+        {
+            return false;
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::geometry::Transform;
+    use crate::{
+        DepthRange, ObjectHandle, QueryError, ViewFrustumHandle, VisibilityQuery,
+        VisibilityWorldArc, ZoneHandle,
+    };
+    use glam::Vec3;
+
+    // Objects without a cull model get a bounding sphere with a radius of 1.42 * scale.
+    const CASTER_SCALE: f32 = 0.1;
+
+    fn add_caster(
+        world: &VisibilityWorldArc,
+        zone: ZoneHandle,
+        id: u64,
+        position: Vec3,
+    ) -> ObjectHandle {
+        let mut inner = world.inner.lock();
+        let object = inner.new_object();
+        inner.set_object_id(object, id);
+        inner.set_object_zone(object, Some(zone));
+        inner.set_object_position(
+            object,
+            Transform {
+                translation: position,
+                scale: Vec3::splat(CASTER_SCALE),
+                ..Default::default()
+            },
+        );
+        object
+    }
+
+    fn add_perspective_view_frustum(
+        world: &VisibilityWorldArc,
+        zone: ZoneHandle,
+        eye_position: Vec3,
+        look_at: Vec3,
+        fov_y_radians: f32,
+        near_distance: f32,
+        far_distance: f32,
+    ) -> ViewFrustumHandle {
+        let mut inner = world.inner.lock();
+        let view_frustum = inner.new_view_frustum();
+        inner.set_view_frustum_zone(view_frustum, Some(zone));
+        inner.set_view_frustum_perspective(
+            view_frustum,
+            fov_y_radians,
+            1.0,
+            near_distance,
+            far_distance,
+            DepthRange::Normal,
+        );
+        inner.set_view_frustum_transforms(view_frustum, eye_position, look_at, Vec3::Z);
+        view_frustum
+    }
+
+    // An orthographic light 10 units above the origin shining straight down on a 10x10 area
+    fn add_directional_light(
+        world: &VisibilityWorldArc,
+        zone: ZoneHandle,
+    ) -> ViewFrustumHandle {
+        let mut inner = world.inner.lock();
+        let light = inner.new_view_frustum();
+        inner.set_view_frustum_zone(light, Some(zone));
+        inner.set_view_frustum_orthographic(
+            light,
+            -5.0,
+            5.0,
+            -5.0,
+            5.0,
+            1.0,
+            20.0,
+            DepthRange::Normal,
+        );
+        inner.set_view_frustum_transforms(light, Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO, Vec3::Y);
+        light
+    }
+
+    // A perspective light at the origin shining down +X with a 90 degree cone
+    fn add_spot_light(
+        world: &VisibilityWorldArc,
+        zone: ZoneHandle,
+    ) -> ViewFrustumHandle {
+        add_perspective_view_frustum(
+            world,
+            zone,
+            Vec3::ZERO,
+            Vec3::X,
+            std::f32::consts::FRAC_PI_2,
+            1.0,
+            20.0,
+        )
+    }
+
+    fn new_world() -> (VisibilityWorldArc, ZoneHandle) {
+        let world = VisibilityWorldArc::new();
+        let zone = world.inner.lock().new_zone();
+        (world, zone)
+    }
+
+    fn query_ids(
+        world: &VisibilityWorldArc,
+        view_frustum: ViewFrustumHandle,
+    ) -> Vec<u64> {
+        let mut query = VisibilityQuery::default();
+        assert!(world.query_visibility(view_frustum, &mut query).is_ok());
+        query.objects.iter().map(|x| x.id).collect()
+    }
+
+    fn query_shadow_caster_ids(
+        world: &VisibilityWorldArc,
+        light: ViewFrustumHandle,
+        shadowed: &[ViewFrustumHandle],
+    ) -> Vec<u64> {
+        let mut query = VisibilityQuery::default();
+        assert!(world
+            .query_shadow_casters(light, shadowed, &mut query)
+            .is_ok());
+        query.objects.iter().map(|x| x.id).collect()
+    }
+
+    #[test]
+    fn orthographic_caster_outside_light_frustum_shadows_view() {
+        let (world, zone) = new_world();
+        let light = add_directional_light(&world, zone);
+
+        // Looks at the origin from the side. The caster is above the light's near plane and out
+        // of the view, but its shadow falls on the origin.
+        let view = add_perspective_view_frustum(
+            &world,
+            zone,
+            Vec3::new(0.0, -10.0, 0.0),
+            Vec3::ZERO,
+            std::f32::consts::FRAC_PI_4,
+            0.1,
+            100.0,
+        );
+        add_caster(&world, zone, 1, Vec3::new(0.0, 0.0, 12.0));
+
+        assert!(query_ids(&world, light).is_empty());
+        assert!(query_ids(&world, view).is_empty());
+        assert_eq!(query_shadow_caster_ids(&world, light, &[view]), vec![1]);
+    }
+
+    #[test]
+    fn orthographic_shadow_misses_view() {
+        let (world, zone) = new_world();
+        let light = add_directional_light(&world, zone);
+
+        // Looks along -X, away from the column below the caster
+        let view = add_perspective_view_frustum(
+            &world,
+            zone,
+            Vec3::new(-3.0, 0.0, 0.0),
+            Vec3::new(-10.0, 0.0, 0.0),
+            std::f32::consts::FRAC_PI_4,
+            0.1,
+            100.0,
+        );
+        add_caster(&world, zone, 1, Vec3::new(3.0, 3.0, 0.0));
+
+        assert_eq!(query_ids(&world, light), vec![1]);
+        assert!(query_shadow_caster_ids(&world, light, &[view]).is_empty());
+    }
+
+    #[test]
+    fn perspective_caster_outside_light_frustum_shadows_view() {
+        let (world, zone) = new_world();
+        let light = add_spot_light(&world, zone);
+
+        // Looks at (10, 0, 0) from the side. The caster is between the light and its near plane,
+        // and its shadow extends down the light's axis through the view.
+        let view = add_perspective_view_frustum(
+            &world,
+            zone,
+            Vec3::new(10.0, -5.0, 0.0),
+            Vec3::new(10.0, 0.0, 0.0),
+            std::f32::consts::FRAC_PI_4,
+            0.1,
+            100.0,
+        );
+        add_caster(&world, zone, 1, Vec3::new(0.5, 0.0, 0.0));
+
+        assert!(query_ids(&world, light).is_empty());
+        assert!(query_ids(&world, view).is_empty());
+        assert_eq!(query_shadow_caster_ids(&world, light, &[view]), vec![1]);
+    }
+
+    #[test]
+    fn perspective_shadow_misses_view() {
+        let (world, zone) = new_world();
+        let light = add_spot_light(&world, zone);
+
+        // Looks along -Y. The caster's shadow extends away from the light towards +Y.
+        let view = add_perspective_view_frustum(
+            &world,
+            zone,
+            Vec3::new(10.0, -2.0, 0.0),
+            Vec3::new(10.0, -10.0, 0.0),
+            std::f32::consts::FRAC_PI_4,
+            0.1,
+            100.0,
+        );
+        add_caster(&world, zone, 1, Vec3::new(5.0, 3.0, 0.0));
+
+        assert_eq!(query_ids(&world, light), vec![1]);
+        assert!(query_shadow_caster_ids(&world, light, &[view]).is_empty());
+    }
+
+    #[test]
+    fn stale_shadowed_view_frustum_is_an_error() {
+        let (world, zone) = new_world();
+        let light = add_spot_light(&world, zone);
+        let view = add_spot_light(&world, zone);
+        world.inner.lock().destroy_view_frustum(view);
+
+        let mut query = VisibilityQuery::default();
+        assert!(matches!(
+            world.query_shadow_casters(light, &[view], &mut query),
+            Err(QueryError::NoViewFrustum)
+        ));
+    }
+
+    #[test]
+    fn undefined_projection_is_an_error() {
+        let (world, zone) = new_world();
+        let light = {
+            let mut inner = world.inner.lock();
+            let light = inner.new_view_frustum();
+            inner.set_view_frustum_zone(light, Some(zone));
+            light
+        };
+        let view = add_spot_light(&world, zone);
+        add_caster(&world, zone, 1, Vec3::new(5.0, 0.0, 0.0));
+
+        let mut query = VisibilityQuery::default();
+        assert!(matches!(
+            world.query_shadow_casters(light, &[view], &mut query),
+            Err(QueryError::UndefinedProjection)
+        ));
+        assert!(matches!(
+            world.query_shadow_casters(view, &[light], &mut query),
+            Err(QueryError::UndefinedProjection)
+        ));
+        assert!(matches!(
+            world.query_visibility(light, &mut query),
+            Err(QueryError::UndefinedProjection)
+        ));
+    }
+}
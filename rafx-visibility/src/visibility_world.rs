@@ -1,4 +1,6 @@
-use crate::frustum_culling::{collect_visible_objects, PackedBoundingSphereChunk};
+use crate::frustum_culling::{
+    collect_shadow_casters, collect_visible_objects, PackedBoundingSphereChunk, ShadowExtrusion,
+};
 use crate::geometry::{BoundingSphere, Transform};
 use crate::internal::VisibilityWorld;
 use crate::{Projection, ViewFrustum};
@@ -77,8 +79,11 @@ pub struct VisibilityWorldArc {
     receiver: Receiver<AsyncCommand>,
 }
 
+#[derive(Debug)]
 pub enum QueryError {
     NoViewFrustumZone,
+    NoViewFrustum,
+    UndefinedProjection,
 }
 
 impl VisibilityWorldArc {
@@ -211,6 +216,10 @@ impl VisibilityWorldArc {
         // NOTE(dvd): Acquire exclusive lock on the view frustum.
 
         let active_view_frustum = work.active_view_frustum.write();
+        if let Projection::Undefined = active_view_frustum.get_projection() {
+            return Err(QueryError::UndefinedProjection);
+        }
+
         let view_frustum_position = active_view_frustum.eye_position();
         let frustum = active_view_frustum.acquire_frustum().clone();
 
@@ -226,14 +235,89 @@ impl VisibilityWorldArc {
 
     /// Queries shadow casters for a `ViewFrustum` representing a light. The `result` is a `VisibilityQuery`.
     /// The objects in `result` are able to cast shadows into at least one of the `shadowed` frustums.
-    /// This function is thread-safe.
+    /// An orthographic light extrudes shadows along its view direction and a perspective light extrudes
+    /// them away from its eye position. Objects in front of the light's near plane are included if their
+    /// shadow reaches into the light's frustum. Returns `QueryError::NoViewFrustum` if any of the
+    /// `shadowed` handles is stale and `QueryError::UndefinedProjection` if the light or any of the
+    /// `shadowed` frustums has no projection yet. This function is thread-safe.
+    #[profiling::function]
     pub fn query_shadow_casters(
         &self,
-        _light: ViewFrustumHandle,
-        _shadowed: &[ViewFrustumHandle],
-        _result: &mut VisibilityQuery,
-    ) {
-        unimplemented!();
+        light: ViewFrustumHandle,
+        shadowed: &[ViewFrustumHandle],
+        result: &mut VisibilityQuery,
+    ) -> Result<(), QueryError> {
+        let work = {
+            let inner = self.inner.lock();
+
+            let zone = {
+                let view_frustum_zone = inner.view_frustum_zones.get(light);
+                if let Some(zone) = view_frustum_zone {
+                    Ok(*zone)
+                } else {
+                    return Err(QueryError::NoViewFrustumZone);
+                }
+            }?;
+
+            let light_view_frustum = inner.view_frustums.get(light).unwrap().clone();
+            let shadowed_view_frustums = shadowed
+                .iter()
+                .map(|view_frustum| {
+                    inner
+                        .view_frustums
+                        .get(*view_frustum)
+                        .cloned()
+                        .ok_or(QueryError::NoViewFrustum)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let chunks = inner.zones.get(zone).unwrap().chunks.clone();
+
+            Ok(ShadowCasterQueryWork {
+                light_view_frustum,
+                shadowed_view_frustums,
+                chunks,
+            })
+        }?;
+
+        // NOTE: The shadowed frustums may be in other zones. Only their planes are needed, so each one is
+        // locked just long enough to copy them. The light itself may also be in `shadowed`.
+
+        let shadowed_frustums = work
+            .shadowed_view_frustums
+            .iter()
+            .map(|view_frustum| {
+                let view_frustum = view_frustum.read();
+                if let Projection::Undefined = view_frustum.get_projection() {
+                    Err(QueryError::UndefinedProjection)
+                } else {
+                    Ok(view_frustum.acquire_frustum().clone())
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let light_view_frustum = work.light_view_frustum.read();
+        let extrusion =
+            ShadowExtrusion::new(&light_view_frustum).ok_or(QueryError::UndefinedProjection)?;
+        let light_position = light_view_frustum.eye_position();
+        let light_far_distance = light_view_frustum.get_projection().far_distance();
+        let light_frustum = light_view_frustum.acquire_frustum().clone();
+
+        // NOTE: Iterate through a read-only view of the chunks in the light's zone.
+
+        let zone = work.chunks.read();
+        for chunk in zone.iter() {
+            collect_shadow_casters(
+                chunk,
+                light_position,
+                &light_frustum,
+                light_far_distance,
+                &extrusion,
+                &shadowed_frustums,
+                &mut result.objects,
+            );
+        }
+
+        Ok(())
     }
 }
 
@@ -241,3 +325,9 @@ struct QueryWork {
     pub active_view_frustum: Arc<RwLock<ViewFrustum>>,
     pub chunks: Arc<RwLock<Vec<PackedBoundingSphereChunk>>>,
 }
+
+struct ShadowCasterQueryWork {
+    pub light_view_frustum: Arc<RwLock<ViewFrustum>>,
+    pub shadowed_view_frustums: Vec<Arc<RwLock<ViewFrustum>>>,
+    pub chunks: Arc<RwLock<Vec<PackedBoundingSphereChunk>>>,
+}
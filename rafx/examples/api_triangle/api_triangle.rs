@@ -275,6 +275,7 @@ fn run() -> RafxResult<()> {
             sample_count: RafxSampleCount::SampleCount1,
             depth_stencil_format: None,
             primitive_topology: RafxPrimitiveTopology::TriangleList,
+            pipeline_cache: None,
         })?;
 
         let start_time = std::time::Instant::now();
@@ -418,12 +418,12 @@ fn run() -> RafxResult<()> {
                 RenderJobWriteContext::new(resource_context.clone(), &render_resources);
 
             let prepared_render_data =
-                PreparedRenderData::new(&submit_node_blocks, Vec::default(), write_context);
+                PreparedRenderData::new(&submit_node_blocks, Vec::default(), None, write_context);
 
             //
             // Execute the graph. This will write out command buffer(s)
             //
-            let command_buffers = executor.execute_graph(prepared_render_data, &graphics_queue)?;
+            let command_buffers = executor.execute_graph(&prepared_render_data, &graphics_queue)?;
 
             //
             // Submit the command buffers to the GPU
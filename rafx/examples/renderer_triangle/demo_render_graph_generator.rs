@@ -8,7 +8,7 @@ use rafx::graph::{
     RenderGraphImageSpecification, RenderGraphQueue,
 };
 use rafx::render_features::RenderJobCommandBufferContext;
-use rafx::renderer::{RenderGraphGenerator, SwapchainResources};
+use rafx::renderer::{RenderGraphGenerator, RenderTargetView, SwapchainResources};
 
 pub struct DemoRenderGraphGenerator;
 
@@ -17,15 +17,16 @@ impl RenderGraphGenerator for DemoRenderGraphGenerator {
         &self,
         asset_manager: &AssetManager,
         swapchain_image: ResourceArc<ImageViewResource>,
+        swapchain_resources: &SwapchainResources,
         main_view: RenderView,
+        _render_target_views: &[RenderTargetView],
         _extract_resources: &ExtractResources,
-        render_resources: &RenderResources,
+        _render_resources: &RenderResources,
     ) -> RafxResult<PreparedRenderGraph> {
         profiling::scope!("Build Render Graph");
 
         let device_context = asset_manager.device_context();
         let resource_context = asset_manager.resource_manager().resource_context();
-        let swapchain_resources = render_resources.fetch::<SwapchainResources>();
 
         //
         // Create a graph to describe how we will draw the frame. Here we just have a single
@@ -81,7 +82,7 @@ impl RenderGraphGenerator for DemoRenderGraphGenerator {
                 mip_count: 1,
             },
             Default::default(),
-            RafxResourceState::PRESENT,
+            swapchain_resources.swapchain_image_final_state,
         );
 
         let prepared_render_graph = PreparedRenderGraph::new(